@@ -11,6 +11,10 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::Write;
 use std::path::Path;
+use std::rc::Rc;
+
+use crate::clock::{Clock, SystemClock};
+use crate::storage_handler::K_JOURNAL_SUFFIX;
 
 const STATE_FILE: &str = "backup_state.bin";
 
@@ -25,6 +29,8 @@ pub struct BackupOrganizer {
     project_data_file: String,
     week_data_file: String,
     storage_dir: String,
+    /// Source of the current time, for the backup interval and file names
+    clock: Rc<dyn Clock>,
 }
 
 impl BackupOrganizer {
@@ -39,9 +45,15 @@ impl BackupOrganizer {
             project_data_file: project_data_file.to_string(),
             week_data_file: week_data_file.to_string(),
             storage_dir: storage_dir.to_string(),
+            clock: Rc::new(SystemClock),
         }
     }
 
+    /// Setter for `clock`
+    pub fn set_clock(&mut self, clock: Rc<dyn Clock>) {
+        self.clock = clock;
+    }
+
     /// Main API for backing up data
     pub fn backup_data(
         &self,
@@ -98,6 +110,30 @@ impl BackupOrganizer {
         Ok(())
     }
 
+    /// Getter for `backup_dir`
+    pub fn backup_dir(&self) -> &str {
+        &self.backup_dir
+    }
+
+    /// Time of the last backup, the newest of the backup files and the periodic backup state. Forced backups
+    /// don't update the state, so the files are checked as well. None if no backup is done
+    pub fn last_backup(&self) -> Option<DateTime<Utc>> {
+        let newest_file = fs::read_dir(&self.backup_dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.ok()?.metadata().ok()?.modified().ok())
+            .max()
+            .map(DateTime::<Utc>::from);
+
+        let state_file = format!("{}/{}", self.storage_dir, STATE_FILE);
+        let from_state = fs::read(state_file)
+            .ok()
+            .and_then(|data| bincode::deserialize::<BackupState>(&data).ok())
+            .map(|state| state.last_backup);
+
+        newest_file.max(from_state)
+    }
+
     /// Create the backup directory if it does not exist
     fn init_backup_directory(&self) -> Result<(), Box<dyn std::error::Error>> {
         let path = Path::new(&self.backup_dir);
@@ -116,6 +152,19 @@ impl BackupOrganizer {
         Path::new(path).is_file()
     }
 
+    /// Copy a data file and its journal, the changes not yet written to the data file, to the backup file
+    fn copy_data_file(data_file: &str, backup_file: &str) -> std::io::Result<()> {
+        fs::copy(data_file, backup_file)?;
+        let journal = format!("{}{}", data_file, K_JOURNAL_SUFFIX);
+        let backup_journal = format!("{}{}", backup_file, K_JOURNAL_SUFFIX);
+        if Path::new(&journal).is_file() {
+            fs::copy(&journal, &backup_journal)?;
+        } else if Path::new(&backup_journal).is_file() {
+            fs::remove_file(&backup_journal)?;
+        }
+        Ok(())
+    }
+
     /// Get existing backups, in order to remove if wanted
     fn get_existing_backup_files(&self, backup_dir: &str, backup_file: &str) -> Vec<String> {
         let mut matches = Vec::new();
@@ -146,7 +195,7 @@ impl BackupOrganizer {
         let backup_state: BackupState = bincode::deserialize(&data)?;
 
         // Calculate the duration since last backup
-        let now = Utc::now();
+        let now = self.clock.now().with_timezone(&Utc);
         let duration = now.signed_duration_since(backup_state.last_backup);
 
         if duration.num_days() >= duration_days as i64 {
@@ -160,7 +209,7 @@ impl BackupOrganizer {
     fn save_state(&self) -> Result<bool, Box<dyn std::error::Error>> {
         let state_file_path = format!("{}/{}", self.storage_dir, STATE_FILE);
         let backup_state = BackupState {
-            last_backup: Utc::now(),
+            last_backup: self.clock.now().with_timezone(&Utc),
         };
 
         // Serialize the BackupState struct to binary using bincode
@@ -175,7 +224,7 @@ impl BackupOrganizer {
 
     /// Do the actual backup
     fn do_backup(&self, override_existing: bool) -> Result<(), Box<dyn std::error::Error>> {
-        let current_date = Utc::now();
+        let current_date = self.clock.now().with_timezone(&Utc);
 
         let prj_data_backup_file = format!(
             "{}/prj_data_{}.bin",
@@ -199,7 +248,7 @@ impl BackupOrganizer {
             tracing::debug!("No existing backup file found.");
         }
 
-        fs::copy(&self.project_data_file, &prj_data_backup_file)?;
+        Self::copy_data_file(&self.project_data_file, &prj_data_backup_file)?;
         tracing::info!("Backed up project data to {}", prj_data_backup_file);
 
         let week_data_backup_file = format!(
@@ -224,7 +273,7 @@ impl BackupOrganizer {
             tracing::debug!("No existing backup file found.");
         }
 
-        fs::copy(&self.week_data_file, &week_data_backup_file)?;
+        Self::copy_data_file(&self.week_data_file, &week_data_backup_file)?;
         tracing::info!("Backed up week data to {}", week_data_backup_file);
 
         Ok(())
@@ -0,0 +1,82 @@
+/*
+ * File: badge.rs
+ * Description: Minimal SVG badge, a label and a message on a colored background, e.g. "week 12 | 38.5/40h • 96%". The
+ *              text width is estimated from the number of characters, so no font metrics are needed.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+/// Estimated width of a character in the badge font, in pixels
+const K_CHAR_WIDTH: usize = 7;
+/// Horizontal padding on each side of a text, in pixels
+const K_TEXT_PADDING: usize = 6;
+/// Height of the badge, in pixels
+const K_BADGE_HEIGHT: usize = 20;
+/// Background color of the label
+const K_LABEL_COLOR: &str = "#555";
+
+/// Attainment percentages with the message color used from that percentage
+const K_ATTAINMENT_COLORS: [(u32, &str); 4] = [
+    (100, "#4c1"),
+    (90, "#a4a61d"),
+    (75, "#dfb317"),
+    (0, "#e05d44"),
+];
+
+/// Color of the message for the target attainment in percent
+pub fn attainment_color(percentage: u32) -> &'static str {
+    K_ATTAINMENT_COLORS
+        .iter()
+        .find(|(from, _)| percentage >= *from)
+        .map(|(_, color)| *color)
+        .unwrap_or(K_ATTAINMENT_COLORS[K_ATTAINMENT_COLORS.len() - 1].1)
+}
+
+/// Internal function to escape text for the SVG
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Internal function to get the width of the part of the badge with the text
+fn text_width(text: &str) -> usize {
+    text.chars().count() * K_CHAR_WIDTH + 2 * K_TEXT_PADDING
+}
+
+/// Render the badge as an SVG document
+pub fn render(label: &str, message: &str, color: &str) -> String {
+    let label_width = text_width(label);
+    let message_width = text_width(message);
+    let width = label_width + message_width;
+    let (label, message) = (escape(label), escape(message));
+
+    format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" role="img" aria-label="{label}: {message}">
+  <title>{label}: {message}</title>
+  <linearGradient id="shine" x2="0" y2="100%">
+    <stop offset="0" stop-color="#bbb" stop-opacity=".1"/>
+    <stop offset="1" stop-opacity=".1"/>
+  </linearGradient>
+  <clipPath id="round">
+    <rect width="{width}" height="{height}" rx="3" fill="#fff"/>
+  </clipPath>
+  <g clip-path="url(#round)">
+    <rect width="{label_width}" height="{height}" fill="{label_color}"/>
+    <rect x="{label_width}" width="{message_width}" height="{height}" fill="{color}"/>
+    <rect width="{width}" height="{height}" fill="url(#shine)"/>
+  </g>
+  <g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="11">
+    <text x="{label_x}" y="14">{label}</text>
+    <text x="{message_x}" y="14">{message}</text>
+  </g>
+</svg>
+"##,
+        height = K_BADGE_HEIGHT,
+        label_color = K_LABEL_COLOR,
+        label_x = label_width / 2,
+        message_x = label_width + message_width / 2,
+    )
+}
@@ -0,0 +1,152 @@
+/*
+ * File: batch.rs
+ * Description: Parsing of batch input, the commands to run against a single loaded butler. Either one command per line
+ *              with shell like quoting, or a JSON array where each command is a string or an array of arguments.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use serde_json::Value;
+use std::error::Error;
+use std::fmt;
+
+/// Error for invalid batch input
+#[derive(Debug)]
+pub struct BatchParseError(String);
+
+/// Implement Display trait for BatchParseError
+impl fmt::Display for BatchParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid batch input: {}", self.0)
+    }
+}
+
+/// Implement Error trait for BatchParseError
+impl Error for BatchParseError {}
+
+/// Command of a batch, with the position in the input used in messages
+#[derive(Debug)]
+pub struct BatchCommand {
+    /// Line number, or index in the JSON array, starting at 1
+    position: usize,
+    /// Arguments of the command, without the binary name
+    arguments: Vec<String>,
+}
+
+impl BatchCommand {
+    /// Getter for `position`
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Getter for `arguments`
+    pub fn arguments(&self) -> &Vec<String> {
+        &self.arguments
+    }
+}
+
+/// Internal function to split a command line into arguments. Single and double quotes group words, a backslash
+/// escapes the next character outside single quotes
+fn split_arguments(line: &str) -> Result<Vec<String>, String> {
+    let mut arguments = Vec::new();
+    let mut current = String::new();
+    let mut in_argument = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('\''), c) => current.push(c),
+            (_, '\\') => match chars.next() {
+                Some(escaped) => {
+                    current.push(escaped);
+                    in_argument = true;
+                }
+                None => return Err("line ends with a backslash".to_string()),
+            },
+            (Some(_), c) => current.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_argument = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_argument {
+                    arguments.push(std::mem::take(&mut current));
+                    in_argument = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_argument = true;
+            }
+        }
+    }
+
+    if let Some(q) = quote {
+        return Err(format!("missing closing {}", q));
+    }
+    if in_argument {
+        arguments.push(current);
+    }
+    Ok(arguments)
+}
+
+/// Internal function to parse a JSON array of commands
+fn parse_json(input: &str) -> Result<Vec<BatchCommand>, BatchParseError> {
+    let commands: Vec<Value> =
+        serde_json::from_str(input).map_err(|e| BatchParseError(e.to_string()))?;
+
+    commands
+        .into_iter()
+        .enumerate()
+        .map(|(i, command)| {
+            let position = i + 1;
+            let arguments = match command {
+                Value::String(line) => split_arguments(&line)
+                    .map_err(|e| BatchParseError(format!("command {}: {}", position, e)))?,
+                // Numbers and booleans are given as written, e.g. ["--hours", 2]
+                Value::Array(arguments) => arguments
+                    .into_iter()
+                    .map(|a| match a {
+                        Value::String(a) => a,
+                        other => other.to_string(),
+                    })
+                    .collect(),
+                _ => {
+                    return Err(BatchParseError(format!(
+                        "command {} is not a string or an array of arguments",
+                        position
+                    )))
+                }
+            };
+            Ok(BatchCommand {
+                position,
+                arguments,
+            })
+        })
+        .collect()
+}
+
+/// Parse the batch input, a JSON array if it starts with "[", otherwise one command per line. Empty lines and lines
+/// starting with "#" are skipped
+pub fn parse_batch(input: &str) -> Result<Vec<BatchCommand>, BatchParseError> {
+    if input.trim_start().starts_with('[') {
+        return parse_json(input);
+    }
+
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(i, line)| {
+            split_arguments(line)
+                .map(|arguments| BatchCommand {
+                    position: i + 1,
+                    arguments,
+                })
+                .map_err(|e| BatchParseError(format!("line {}: {}", i + 1, e)))
+        })
+        .collect()
+}
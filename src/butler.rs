@@ -6,24 +6,124 @@
  * License: MIT
  */
 
-use chrono::{Datelike, LocalResult, NaiveDateTime, TimeZone};
+use chrono::{
+    DateTime, Datelike, Local, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Weekday,
+};
 use comfy_table::{Cell, ContentArrangement, Table};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::{self, Write};
+use std::process;
+use std::rc::Rc;
 use uuid::Uuid;
 
-use crate::config::AppConfiguration;
-use crate::day::Day;
-use crate::entry::Entry;
-use crate::project::Project;
-use crate::report::ReportFormat;
+use crate::caldav;
+use crate::clipboard;
+use crate::clock::{Clock, FixedClock, SystemClock};
+use crate::config::{AppConfiguration, EntryTemplate};
+use crate::config_watcher::ConfigWatcher;
+use crate::day::{self, Allocation, Day, OvernightDate, RoundingPolicy};
+use crate::dev;
+use crate::display::{self, Display};
+use crate::duration::{self, DurationFormat, TimePrecision};
+use crate::entry::{Entry, EntrySelector};
+use crate::error::ButlerError;
+use crate::hooks::{self, HookKind};
+use crate::i18n::{self, Locale};
+use crate::importer::{CommandImporter, DayImportRow, EntryImportRow, ImporterRegistry};
+use crate::jira;
+use crate::mailer;
+use crate::period::{Period, WeekFilter};
+use crate::period_lock::{LockedPeriod, PeriodMetadata};
+use crate::porcelain;
+use crate::project::{self, DuplicateProjectAction, Project};
+use crate::publisher;
+use crate::report::{
+    self, OverviewRow, ReportColumn, ReportFormat, ReportGenerationFailure, ReportOptions,
+    ReportOverwrite, ReportSummary, ReportTargetStatus, Timesheet, K_DAY_REPORT_COLUMNS,
+    K_PROJECT_REPORT_COLUMNS,
+};
 use crate::report_manager::ReportManager;
+use crate::report_renderer::{CommandRenderer, K_BUILTIN_FORMATS};
+use crate::schema::{self, K_SCHEMA_DOCUMENTS};
+use crate::snapshot;
+use crate::stats;
 use crate::storage_handler::StorageHandler;
-use crate::tables;
-use crate::target::{MonthlyTargetStatus, WeeklyTargetStatus};
+use crate::sync::{self, SyncChange, SyncDirectory, SyncState};
+use crate::tables::{self, ListOptions, ListRow, ListedRows, SortableTable};
+use crate::target::{
+    MonthlyForecast, MonthlyTargetStatus, ProjectBurnDown, WeekTargetEdit, WeeklyTargetStatus,
+};
+use crate::template::{expand_dates, RepeatMode};
+use crate::trash::{Trash, TrashedItem};
+use crate::validation;
 use crate::version_info;
 use crate::version_manager::{VersionCompatibility, VersionManager};
-use crate::week::Week;
+use crate::week::{MergeOutcome, MergeStrategy, Week};
+use crate::week_scheme::{self, WeekNumbering, WeekScheme};
+use crate::{out, outln};
+
+/// Maximum number of suggested project names when a project isn't found
+const K_MAX_PROJECT_SUGGESTIONS: usize = 3;
+/// Minimum length of an entry ID prefix, shorter prefixes are too likely to be ambiguous
+const K_MIN_ID_PREFIX_LENGTH: usize = 4;
+
+/// Counts of a merge of another data set, e.g. from another machine
+#[derive(Default)]
+struct MergeSummary {
+    /// Projects not stored before
+    added_projects: usize,
+    /// Entries not stored before
+    added_entries: usize,
+    /// Entries in locked periods
+    skipped_entries: usize,
+    /// Days not stored before
+    added_days: usize,
+    /// Stored days with merged values
+    merged_days: usize,
+    /// Days that couldn't be added or merged
+    skipped_days: usize,
+    /// Stored projects, weeks, entries and days replaced with the changes of the other machines, only when syncing
+    updated_items: usize,
+    /// Stored projects, entries and days removed by the other machines, only when syncing
+    removed_items: usize,
+}
+
+impl MergeSummary {
+    /// Check if anything was added, merged, updated or removed
+    fn changed(&self) -> bool {
+        self.added_projects
+            + self.added_entries
+            + self.added_days
+            + self.merged_days
+            + self.updated_items
+            + self.removed_items
+            > 0
+    }
+
+    /// Set the counts in the porcelain result
+    fn set_porcelain(&self) {
+        porcelain::set("added_projects", self.added_projects);
+        porcelain::set("added_entries", self.added_entries);
+        porcelain::set("skipped_entries", self.skipped_entries);
+        porcelain::set("added_days", self.added_days);
+        porcelain::set("merged_days", self.merged_days);
+        porcelain::set("skipped_days", self.skipped_days);
+        porcelain::set("updated_items", self.updated_items);
+        porcelain::set("removed_items", self.removed_items);
+    }
+}
+
+/// Summed hours of a period, used when comparing periods
+struct PeriodSummary {
+    /// Hours of the days in the period
+    day_hours: f32,
+    /// Hours of the project entries in the period
+    entry_hours: f32,
+    /// Target hours of the period
+    target_hours: f32,
+    /// Entry hours per project name
+    project_hours: BTreeMap<String, f32>,
+}
 
 /// Butler struct - Main star of the show
 pub struct Butler {
@@ -31,6 +131,10 @@ pub struct Butler {
     projects: Vec<Project>,
     // Week vector
     weeks: Vec<Week>,
+    /// Index of the projects by name and alias, position in `projects`
+    project_index: HashMap<String, usize>,
+    /// Index of the weeks by week number and year, position in `weeks`
+    week_index: HashMap<(u32, i32), usize>,
     // Report functionality
     report_mngr: ReportManager,
     /// Storage functionality
@@ -39,26 +143,340 @@ pub struct Butler {
     configuration: AppConfiguration,
     // Version information
     version_mgnr: VersionManager,
+    /// Table display theme
+    display: Display,
+    /// Watcher of the configuration file, for the long-running modes
+    config_watcher: Option<ConfigWatcher>,
+    /// Rounding of the worked hours, from the configuration
+    rounding: RoundingPolicy,
+    /// Time forgotten open days are closed at, from the configuration
+    end_of_day: NaiveTime,
+    /// Hours below which a closed day is short, per weekday from Monday. None if the warning is disabled
+    short_day_hours: Option<[f32; 7]>,
+    /// Date of the days crossing midnight, from the configuration
+    overnight_date: OvernightDate,
+    /// Metadata of the time periods, the locked weeks and months
+    period_metadata: PeriodMetadata,
+    /// Removed projects, entries and days that can be restored
+    trash: Trash,
+    /// State of the sync run by the command, saved with the data
+    sync_state: Option<SyncState>,
+    /// Allow changes to days and entries in locked periods
+    force_unlock: bool,
+    /// Publish the generated reports to the configured endpoint
+    publish_reports: bool,
+    /// Source of the current time
+    clock: Rc<dyn Clock>,
+    /// Importers of the import sources, the built-in importers and the importers of the configuration
+    importers: ImporterRegistry,
 }
 
 /// Implementation of the functionality for the Butler
 impl Butler {
     /// Create a new Butler
     pub fn new(storage_handler: StorageHandler, configuration: AppConfiguration) -> Self {
+        let clock: Rc<dyn Clock> = Rc::new(SystemClock);
+        let display = Display::new(&configuration.color_theme(), false, clock.today());
+        match configuration.locale().parse::<Locale>() {
+            Ok(locale) => i18n::set_locale(locale),
+            Err(e) => tracing::warn!("{}: '{}', using English", e, configuration.locale()),
+        }
         Self {
             projects: Vec::new(),
             weeks: Vec::new(),
-            report_mngr: ReportManager::new(),
+            project_index: HashMap::new(),
+            week_index: HashMap::new(),
+            report_mngr: ReportManager::new(clock.now()),
             storage_handler,
             configuration,
             version_mgnr: VersionManager::new(version_info::VersionInfo::new()),
+            display,
+            config_watcher: None,
+            rounding: RoundingPolicy::None,
+            end_of_day: NaiveTime::from_hms_opt(17, 0, 0).unwrap(), // safe, valid time
+            short_day_hours: None,
+            overnight_date: OvernightDate::Start,
+            period_metadata: PeriodMetadata::default(),
+            trash: Trash::default(),
+            sync_state: None,
+            force_unlock: false,
+            publish_reports: false,
+            clock,
+            importers: ImporterRegistry::new(),
+        }
+    }
+
+    /// Getter for `clock`
+    pub fn clock(&self) -> &dyn Clock {
+        self.clock.as_ref()
+    }
+
+    /// Set the source of the current time, also used by the backups, the tables and the reports
+    pub fn set_clock(&mut self, clock: Rc<dyn Clock>) {
+        self.storage_handler.set_clock(clock.clone());
+        self.display.set_today(clock.today());
+        self.report_mngr.set_generated(clock.now());
+        self.clock = clock;
+    }
+
+    /// Internal function to rebuild the project and week indexes, needed when projects or weeks are removed or
+    /// renamed. The first project with a name or alias is indexed, same as a search from the start of the list
+    fn rebuild_indexes(&mut self) {
+        self.project_index.clear();
+        for (i, p) in self.projects.iter().enumerate() {
+            self.project_index.entry(p.name().to_string()).or_insert(i);
+            for alias in p.aliases() {
+                self.project_index.entry(alias.clone()).or_insert(i);
+            }
+        }
+
+        self.week_index.clear();
+        for (i, w) in self.weeks.iter().enumerate() {
+            self.week_index.entry((w.number(), w.year())).or_insert(i);
+        }
+    }
+
+    /// Internal function to get the position of a project by name or alias
+    fn project_position(&self, name: &str) -> Option<usize> {
+        self.project_index.get(name).copied()
+    }
+
+    /// Internal function to get the error of a project not found by name or alias. The message suggests the stored
+    /// names closest to the given name, the context is appended before the suggestions
+    fn project_not_found(&self, name: &str, context: &str) -> ButlerError {
+        let mut message = format!("Project with name {}{}", name, context);
+        let suggestions = self.project_suggestions(name);
+        if !suggestions.is_empty() {
+            message.push_str(&format!(", did you mean '{}'?", suggestions.join("' or '")));
+        }
+        ButlerError::NotFound(message)
+    }
+
+    /// Internal function to get the stored project names and aliases closest to the name
+    fn project_suggestions(&self, name: &str) -> Vec<String> {
+        closest_names(name, self.project_index.keys())
+    }
+
+    /// Internal function to get the position of a week by week number and year
+    fn week_position(&self, number: u32, year: i32) -> Option<usize> {
+        self.week_index.get(&(number, year)).copied()
+    }
+
+    /// Internal function to add a project and index it, returns the position of the project
+    fn push_project(&mut self, project: Project) -> usize {
+        let index = self.projects.len();
+        self.project_index
+            .entry(project.name().to_string())
+            .or_insert(index);
+        self.projects.push(project);
+        index
+    }
+
+    /// Internal function to add a week and index it, returns the position of the week
+    fn push_week(&mut self, week: Week) -> usize {
+        let index = self.weeks.len();
+        self.week_index
+            .entry((week.number(), week.year()))
+            .or_insert(index);
+        self.weeks.push(week);
+        index
+    }
+
+    /// Subscribe to changes of the configuration file, applied by `reload_configuration`
+    pub fn watch_configuration(&mut self, watcher: ConfigWatcher) {
+        tracing::debug!("Watching configuration file {}", watcher.config_path());
+        self.config_watcher = Some(watcher);
+    }
+
+    /// Apply changed targets and backup settings from the watched configuration file. Called periodically by the
+    /// long-running modes, returns true if settings were changed
+    pub fn reload_configuration(&mut self) -> bool {
+        let new_config = match self.config_watcher.as_mut().and_then(|w| w.poll()) {
+            Some(config) => config,
+            None => return false,
+        };
+
+        if !self.configuration.apply_live_settings(&new_config) {
+            return false;
+        }
+        self.report_mngr
+            .set_week_target_hours(self.configuration.week_target_hours());
+        self.report_mngr
+            .set_month_target_hours(self.configuration.month_target_hours());
+        self.auto_reapply_week_targets();
+        tracing::info!("Configuration reloaded, targets and backup settings updated");
+        true
+    }
+
+    /// Allow changes to days and entries in locked periods
+    pub fn set_force_unlock(&mut self, force_unlock: bool) {
+        self.force_unlock = force_unlock;
+    }
+
+    /// Internal function to check that the date isn't in a locked period, the lock is only logged if overridden
+    fn check_unlocked(&self, date: NaiveDate, action: &str) -> Result<(), ButlerError> {
+        match self.period_metadata.lock_for(date) {
+            Some(lock) if !self.force_unlock => Err(ButlerError::InvalidInput(format!(
+                "{} is in locked {}, unable to {}. Use --force-unlock to override the lock",
+                date, lock, action
+            ))),
+            Some(lock) => {
+                tracing::warn!("{} is in locked {}, lock overridden", date, lock);
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Internal function to get the date of the stored day with the ID
+    fn day_date(&self, id: &Uuid) -> Option<NaiveDate> {
+        self.weeks
+            .iter()
+            .flat_map(|w| w.entries())
+            .find(|d| d.id() == id)
+            .map(|d| d.date())
+    }
+
+    /// Lock the period, the days and entries in it can't be changed until unlocked
+    pub fn lock_period(&mut self, period: LockedPeriod) -> Result<(), ButlerError> {
+        if !self.period_metadata.lock(period) {
+            return Err(ButlerError::Duplicate(format!(
+                "{} is already locked",
+                period
+            )));
+        }
+        tracing::info!("Locked {}", period);
+        Ok(())
+    }
+
+    /// Write a snapshot of the days and entries in the period to the report directory, with its SHA-256 digest and
+    /// optionally a gpg signature
+    pub fn snapshot_period(&self, period: LockedPeriod, sign: bool) -> Result<(), ButlerError> {
+        let days: Vec<&Day> = self
+            .weeks
+            .iter()
+            .flat_map(|w| w.entries())
+            .filter(|d| period.contains(d.date()))
+            .collect();
+        let mut entries = Vec::new();
+        let mut entry_hours = 0.0;
+        for p in &self.projects {
+            for e in p
+                .entries()
+                .iter()
+                .filter(|e| period.contains(e.work_date()))
+            {
+                entries.push(serde_json::json!({ "project": p.name(), "entry": e }));
+                entry_hours += self.rounding.round(e.hours());
+            }
         }
+
+        let now = self.clock.now();
+        let snapshot = serde_json::json!({
+            "period": period,
+            "created": now.to_rfc3339(),
+            "app-version": self.version_mgnr.get_version().get_app_version(),
+            "day-hours": days.iter().fold(0.0, |sum, d| sum + self.rounding.round(d.hours())),
+            "entry-hours": entry_hours,
+            "days": days,
+            "entries": entries,
+        });
+        let content = serde_json::to_string_pretty(&snapshot).map_err(|e| {
+            ButlerError::ReportFailure(format!("Failed to serialize snapshot: {}", e))
+        })?;
+
+        let name = format!(
+            "snapshot-{}-{}",
+            period.to_string().replace(' ', "-"),
+            now.format("%Y%m%dT%H%M%S")
+        );
+        let written = snapshot::write_snapshot(
+            &self.configuration.report_directory(),
+            &name,
+            &content,
+            sign,
+        )
+        .map_err(|e| ButlerError::ReportFailure(e.to_string()))?;
+
+        for path in &written {
+            outln!("Snapshot written: {}", path);
+        }
+        porcelain::set("snapshot", &written);
+        Ok(())
+    }
+
+    /// Unlock a locked period
+    pub fn unlock_period(&mut self, period: LockedPeriod) -> Result<(), ButlerError> {
+        if !self.period_metadata.unlock(&period) {
+            return Err(ButlerError::NotFound(format!("Lock of {}", period)));
+        }
+        tracing::info!("Unlocked {}", period);
+        Ok(())
+    }
+
+    /// List the locked periods
+    pub fn list_locked_periods(&self) {
+        if self.period_metadata.locks().is_empty() {
+            outln!("No locked periods");
+            return;
+        }
+
+        let mut table = tables::get_table_locked_periods();
+        for lock in self.period_metadata.locks() {
+            let (period, number, year) = match lock {
+                LockedPeriod::Week { number, year } => (i18n::tr("Week"), number, year),
+                LockedPeriod::Month { number, year } => (i18n::tr("Month"), number, year),
+            };
+            table.add_row(vec![
+                period.to_string(),
+                number.to_string(),
+                year.to_string(),
+            ]);
+        }
+        outln!("{}", table);
+    }
+
+    /// Disable colors in the printed tables
+    pub fn set_no_color(&mut self, no_color: bool) {
+        let duration = self.display.duration();
+        self.display = Display::new(
+            &self.configuration.color_theme(),
+            no_color,
+            self.clock.today(),
+        );
+        self.display.set_duration_format(duration);
+    }
+
+    /// Set what is done when a report file already exists, and if the reports are only previewed
+    pub fn set_report_write_policy(&mut self, overwrite: ReportOverwrite, dry_run: bool) {
+        self.report_mngr.set_write_policy(overwrite, dry_run);
+    }
+
+    /// Write the total and target status rows last in the CSV reports
+    pub fn set_report_csv_totals(&mut self, csv_totals: bool) {
+        self.report_mngr.set_csv_totals(csv_totals);
+    }
+
+    /// Write the report metadata as comment lines first in the CSV reports
+    pub fn set_report_csv_metadata(&mut self, csv_metadata: bool) {
+        self.report_mngr.set_csv_metadata(csv_metadata);
     }
 
-    /// Internal function for prompting user for confirmation.
+    /// Publish the generated reports to the endpoint of the configuration, also done for all reports when set there
+    pub fn set_publish_reports(&mut self, publish: bool) {
+        self.publish_reports = publish;
+    }
+
+    /// Set the format of the hours in the tables and reports
+    pub fn set_duration_format(&mut self, duration: DurationFormat) {
+        self.display.set_duration_format(duration);
+        self.report_mngr.set_duration_format(duration);
+    }
+
+    /// Internal function for prompting user for confirmation, the question is expected to be translated.
     fn prompt_user_confirmation(question: &str) -> bool {
-        let promt = format!("{} [y/N]: ", question);
-        print!("{}", promt);
+        let promt = format!("{} {}: ", question, i18n::tr("[y/N]"));
+        out!("{}", promt);
         io::stdout().flush().expect("Failed to flush stdout");
 
         // Read user input
@@ -67,14 +485,25 @@ impl Butler {
             .read_line(&mut input)
             .expect("Failed to read input");
 
-        // Normalize and match the response
-        match input.trim().to_lowercase().as_str() {
-            "yes" | "y" => true,
-            _ => {
-                tracing::warn!("Not confirmed by user");
-                false
-            }
+        if i18n::is_yes(&input) {
+            true
+        } else {
+            tracing::warn!("Not confirmed by user");
+            false
+        }
+    }
+
+    /// Internal function for prompting the user for a value, the question is expected to be translated. Returns the
+    /// answer without the line ending, empty if nothing could be read
+    fn prompt_user_input(question: &str) -> String {
+        out!("{}: ", question);
+        io::stdout().flush().expect("Failed to flush stdout");
+
+        let mut input = String::new();
+        if let Err(e) = io::stdin().read_line(&mut input) {
+            tracing::warn!("Failed to read input: {}", e);
         }
+        input.trim_end_matches(['\r', '\n']).to_string()
     }
 
     /// Internal function to get number of weeks currently stored
@@ -87,13 +516,48 @@ impl Butler {
         self.projects.len()
     }
 
+    /// Internal function to store the projects and weeks converted from the storage file version in the current layout,
+    /// with the current metadata. Nothing is stored if an existing file couldn't be converted
+    fn store_migrated_storage(&mut self, version: &str) -> Result<(), ButlerError> {
+        if self.storage_handler.load_failed() {
+            return Err(ButlerError::StorageFailure(format!(
+                "The storage files of version {} could not be converted, nothing is changed",
+                version
+            )));
+        }
+
+        self.storage_handler.set_legacy_file_version(None);
+        // All items are written in the new layout
+        self.storage_handler.reset_stored_checksums();
+        self.storage_handler
+            .store_projects(&self.projects)
+            .and_then(|_| self.storage_handler.store_weeks(&self.weeks))
+            .map_err(|e| {
+                ButlerError::StorageFailure(format!("Failed to store the converted storage: {}", e))
+            })?;
+        self.storage_handler
+            .write_metadata(&self.version_mgnr.metadata())
+            .map_err(|e| {
+                ButlerError::StorageFailure(format!("Failed to write the storage metadata: {}", e))
+            })?;
+        self.version_mgnr.set_migrated();
+        tracing::info!(
+            "Converted {} projects and {} weeks from storage file version {}",
+            self.projects.len(),
+            self.weeks.len(),
+            version
+        );
+        Ok(())
+    }
+
     /// Init the butler in order to get the saved data from the storage etc.
-    pub fn init(&mut self) {
+    pub fn init(&mut self) -> Result<(), ButlerError> {
         tracing::debug!("Initializing the Butler!");
 
         // Update the file paths based on configuratoin
         self.storage_handler
             .set_paths_from_config(&self.configuration);
+        self.storage_handler.set_clock(self.clock.clone());
 
         self.version_mgnr
             .set_loaded_storage_metadata(self.storage_handler.load_metadata());
@@ -108,6 +572,7 @@ impl Butler {
         // Check storage compatibility and decide how to proceed based on configuration and user confirmation.
         // The loading of files don't do so much if failed, but we can fail fast instead of waiting to the writing part.
 
+        let mut migrate_from = None;
         match storage_compatibility {
             VersionCompatibility::Compatible => {
                 tracing::debug!(
@@ -125,13 +590,14 @@ impl Butler {
                     .always_force_halt_on_version_incompatibility()
                 {
                     tracing::info!("Configuration is set to always force halt on version incompatibility, aborting initialization to prevent potential data loss or corruption");
-                    return;
+                    return Err(ButlerError::VersionIncompatible(msg));
                 }
 
                 // Ask confirmation to user
-                if !Self::prompt_user_confirmation("Storage file version is incompatible with current application version. Do you want to proceed? This may lead to data loss or corruption if the incompatibility is due to breaking changes in the storage file format.") {
+                if !Self::prompt_user_confirmation(i18n::tr("Storage file version is incompatible with current application version. Do you want to proceed? This may lead \
+                     to data loss or corruption if the incompatibility is due to breaking changes in the storage file format.")) {
                      tracing::info!("User chose not to proceed, aborting initialization to prevent potential data loss or corruption");
-                  return;
+                  return Err(ButlerError::VersionIncompatible(msg));
                   } else {
                     tracing::warn!("User chose to proceed despite version incompatibility. Forcing a backup of storage as a precautionary measure before continuing.");
                     self.version_mgnr.set_override_on_incompatibility(true);
@@ -142,6 +608,22 @@ impl Butler {
                   }
                 tracing::warn!("Proceeding with initialization despite version incompatibility.");
             }
+            VersionCompatibility::Migratable(version) => {
+                tracing::info!(
+                    "Storage files of version {} are converted to version {}, a backup is done first",
+                    version,
+                    self.version_mgnr.get_version().get_storage_file_version()
+                );
+                self.storage_handler.do_backup_now().map_err(|e| {
+                    ButlerError::StorageFailure(format!(
+                        "No backup done, the storage files of version {} are not converted: {}",
+                        version, e
+                    ))
+                })?;
+                self.storage_handler
+                    .set_legacy_file_version(Some(version.clone()));
+                migrate_from = Some(version);
+            }
             VersionCompatibility::Unknown => {
                 tracing::warn!(
                     "Storage file version compatibility is unknown, no metadata loaded."
@@ -180,10 +662,118 @@ impl Butler {
         }
         tracing::debug!("Loaded {} weeks", self.weeks.len());
 
+        if let Some(version) = migrate_from {
+            self.store_migrated_storage(&version)?;
+        }
+
+        self.period_metadata = self.storage_handler.load_period_metadata().map_err(|e| {
+            ButlerError::StorageFailure(format!("Failed to load period metadata: {}", e))
+        })?;
+        self.trash = self
+            .storage_handler
+            .load_trash()
+            .map_err(|e| ButlerError::StorageFailure(format!("Failed to load trash: {}", e)))?;
+        self.rebuild_indexes();
+
         self.report_mngr
             .set_report_storage_dir(self.configuration.report_directory().to_string());
+        self.report_mngr
+            .set_week_target_hours(self.configuration.week_target_hours());
+        self.report_mngr
+            .set_month_target_hours(self.configuration.month_target_hours());
+        self.report_mngr
+            .set_html_dark_mode(self.configuration.html_dark_mode());
+
+        self.rounding = self
+            .configuration
+            .hours_rounding()
+            .parse::<RoundingPolicy>()
+            .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+        self.report_mngr.set_rounding_policy(self.rounding);
+        day::set_rounding_policy(self.rounding);
+
+        let duration = self
+            .configuration
+            .hours_format()
+            .parse::<DurationFormat>()
+            .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+        self.set_duration_format(duration);
+
+        let precision = self
+            .configuration
+            .hours_precision()
+            .parse::<TimePrecision>()
+            .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+        duration::set_time_precision(precision);
+
+        let week_start = week_scheme::parse_week_start(&self.configuration.week_start())
+            .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+        let week_numbering = self
+            .configuration
+            .week_numbering()
+            .parse::<WeekNumbering>()
+            .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+        week_scheme::set_week_scheme(WeekScheme::new(week_start, week_numbering));
+
+        for importer in self.configuration.importers() {
+            self.importers
+                .register(Box::new(CommandImporter::new(importer)))
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+        }
+        for renderer in self.configuration.report_renderers() {
+            self.report_mngr
+                .register_renderer(Box::new(CommandRenderer::new(renderer)))
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+        }
+
+        let end_of_day = self.configuration.end_of_day();
+        self.end_of_day = NaiveTime::parse_from_str(&end_of_day, "%H:%M").map_err(|_| {
+            ButlerError::InvalidInput(format!(
+                "Invalid end of day '{}', expected \"HH:MM\"",
+                end_of_day
+            ))
+        })?;
+
+        if let Some(limit) = self.configuration.auto_close_open_days_after_hours() {
+            if !limit.is_finite() || limit <= 0.0 {
+                return Err(ButlerError::InvalidInput(format!(
+                    "Invalid auto-close limit of open days: {} hours, must be positive",
+                    limit
+                )));
+            }
+        }
+
+        let days_config = self.configuration.days();
+        self.overnight_date = days_config
+            .overnight_date
+            .parse::<OvernightDate>()
+            .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+        if !days_config.max_overnight_hours.is_finite() || days_config.max_overnight_hours < 0.0 {
+            return Err(ButlerError::InvalidInput(format!(
+                "Invalid max overnight hours: {}, can't be negative",
+                days_config.max_overnight_hours
+            )));
+        }
+        self.short_day_hours = if days_config.short_day_warning {
+            let mut hours = [days_config.short_day_warning_hours; 7];
+            for (name, weekday_hours) in &days_config.short_day_warning_weekdays {
+                let weekday = name.parse::<Weekday>().map_err(|_| {
+                    ButlerError::InvalidInput(format!(
+                        "Invalid weekday '{}' of the short day warning, expected e.g. \"friday\"",
+                        name
+                    ))
+                })?;
+                hours[weekday.num_days_from_monday() as usize] = *weekday_hours;
+            }
+            Some(hours)
+        } else {
+            None
+        };
+
+        self.auto_reapply_week_targets();
 
         tracing::debug!("Initialization complete!");
+        Ok(())
     }
 
     pub fn modify_project(
@@ -191,26 +781,16 @@ impl Butler {
         project_id: String,
         new_name: Option<String>,
         new_description: Option<String>,
-    ) -> bool {
+    ) -> Result<(), ButlerError> {
         // Convert project_id string to Uuid
-        let project_id = match Uuid::parse_str(&project_id) {
-            Ok(parsed_id) => {
-                tracing::debug!("Parsed project ID: {}", parsed_id);
-                parsed_id
-            }
-            Err(e) => {
-                tracing::error!("Failed to parse project ID: {}", e);
-                return false;
-            }
-        };
+        let project_id = Self::parse_id(&project_id)?;
         let project_index = match self.projects.iter().position(|p| p.id() == &project_id) {
             Some(index) => index,
             None => {
-                tracing::error!(
-                    "Project with ID {} not found, unable to modify project",
+                return Err(ButlerError::NotFound(format!(
+                    "Project with ID {}, unable to modify project",
                     project_id
-                );
-                return false;
+                )));
             }
         };
 
@@ -219,54 +799,327 @@ impl Butler {
         if let Some(ref requested_new_name) = new_name {
             // Check if new name already exists in another project to prevent duplicates
             if self
-                .projects
-                .iter()
-                .enumerate()
-                .any(|(i, p)| i != project_index && p.name() == requested_new_name)
+                .project_position(requested_new_name)
+                .is_some_and(|i| i != project_index)
             {
-                tracing::error!(
-                    "Project with name {} already exists, unable to update project name",
+                return Err(ButlerError::Duplicate(format!(
+                    "Project with name {}, unable to update project name",
                     requested_new_name
-                );
-                return false;
+                )));
             }
 
             // Prompt user for confirmation before mutating the project name
-            if !Self::prompt_user_confirmation(&format!(
+            if !Self::prompt_user_confirmation(&i18n::tr_args(
                 "Are you sure you want to change the project name from {} to {}?",
-                current_project_name, requested_new_name
+                &[&current_project_name, &requested_new_name],
             )) {
-                tracing::info!(
-                    "User chose not to proceed with project name change, aborting modification"
-                );
-                return false;
+                return Err(ButlerError::Aborted(
+                    "User chose not to proceed with project name change".to_string(),
+                ));
             }
         }
 
         if let Some(ref requested_new_description) = new_description {
-            if !Self::prompt_user_confirmation(&format!(
+            if !Self::prompt_user_confirmation(&i18n::tr_args(
                 "Are you sure you want to change the project description from {} to {}?",
-                current_project_name, requested_new_description
+                &[&current_project_name, requested_new_description],
             )) {
-                tracing::info!(
-                    "User chose not to proceed with project description change, aborting modification"
-                );
-                return false;
+                return Err(ButlerError::Aborted(
+                    "User chose not to proceed with project description change".to_string(),
+                ));
             }
         }
 
-        let p = &mut self.projects[project_index];
-
+        // Sub-projects and allocations follow a new name
         if let Some(new_name) = new_name {
-            p.update_name(new_name);
+            self.rename_project(&current_project_name, &new_name)?;
         }
 
         if let Some(new_description) = new_description {
-            p.update_description(new_description);
+            self.projects[project_index].update_description(new_description);
         }
 
         tracing::info!("Project {} modified successfully", current_project_name);
-        true
+        Ok(())
+    }
+
+    /// Add a short alias to a project, the alias can be used anywhere a project name is accepted
+    pub fn add_project_alias(
+        &mut self,
+        project_name: &str,
+        alias: &str,
+    ) -> Result<(), ButlerError> {
+        let alias = alias.trim();
+        if alias.is_empty() {
+            return Err(ButlerError::InvalidInput(
+                "Alias can not be empty".to_string(),
+            ));
+        }
+
+        // The alias must not be confused with any other project name or alias
+        if self.project_position(alias).is_some() {
+            return Err(ButlerError::Duplicate(format!(
+                "Project name or alias {}, unable to add alias",
+                alias
+            )));
+        }
+
+        match self.project_position(project_name) {
+            Some(index) => {
+                let p = &mut self.projects[index];
+                p.add_alias(alias.to_string());
+                tracing::info!("Alias {} added to project {}", alias, p.name());
+                self.project_index.insert(alias.to_string(), index);
+                Ok(())
+            }
+            None => Err(ButlerError::NotFound(format!(
+                "Project with name {}, unable to add alias",
+                project_name
+            ))),
+        }
+    }
+
+    /// Archive or unarchive a project
+    pub fn set_project_archived(
+        &mut self,
+        project_name: &str,
+        archived: bool,
+    ) -> Result<(), ButlerError> {
+        match self.project_position(project_name) {
+            Some(index) => {
+                let p = &mut self.projects[index];
+                if p.archived() == archived {
+                    tracing::warn!(
+                        "Project {} is already {}",
+                        p.name(),
+                        if archived { "archived" } else { "active" }
+                    );
+                } else {
+                    p.set_archived(archived);
+                    tracing::info!(
+                        "Project {} {}",
+                        p.name(),
+                        if archived { "archived" } else { "unarchived" }
+                    );
+                }
+                Ok(())
+            }
+            None => Err(ButlerError::NotFound(format!(
+                "Project with name {}",
+                project_name
+            ))),
+        }
+    }
+
+    /// Set the budgeted hours and deadline of a project, None removes them
+    pub fn set_project_budget(
+        &mut self,
+        project_name: &str,
+        budget_hours: Option<f32>,
+        deadline: Option<NaiveDate>,
+    ) -> Result<(), ButlerError> {
+        if let Some(budget) = budget_hours {
+            validation::validate_budget_hours(budget)
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+        }
+
+        match self.project_position(project_name) {
+            Some(index) => {
+                let p = &mut self.projects[index];
+                p.set_budget(budget_hours, deadline);
+                match budget_hours {
+                    Some(budget) => {
+                        tracing::info!("Project {} budget set to {} hours", p.name(), budget)
+                    }
+                    None => tracing::info!("Project {} budget removed", p.name()),
+                }
+                Ok(())
+            }
+            None => Err(ButlerError::NotFound(format!(
+                "Project with name {}",
+                project_name
+            ))),
+        }
+    }
+
+    /// Set the client, color and external reference of a project. A value not given is kept, an empty value removes
+    /// it
+    pub fn set_project_metadata(
+        &mut self,
+        project_name: &str,
+        client: Option<String>,
+        color: Option<String>,
+        external_reference: Option<String>,
+    ) -> Result<(), ButlerError> {
+        if let Some(color) = color.as_deref().filter(|c| !c.trim().is_empty()) {
+            if display::parse_color(color).is_none() {
+                return Err(ButlerError::InvalidInput(format!(
+                    "Invalid color: {}, valid colors are \"#rrggbb\" and: {}",
+                    color,
+                    display::K_COLOR_NAMES.join(", ")
+                )));
+            }
+        }
+
+        let index = self
+            .project_position(project_name)
+            .ok_or_else(|| ButlerError::NotFound(format!("Project with name {}", project_name)))?;
+        let p = &mut self.projects[index];
+
+        // An empty value removes the field
+        let value = |v: String| Some(v.trim().to_string()).filter(|v| !v.is_empty());
+        if let Some(client) = client {
+            p.set_client(value(client));
+        }
+        if let Some(color) = color {
+            p.set_color(value(color));
+        }
+        if let Some(external_reference) = external_reference {
+            p.set_external_reference(value(external_reference));
+        }
+        tracing::info!(
+            "Project {} updated, client: {}, color: {}, external reference: {}",
+            p.name(),
+            p.client().unwrap_or("-"),
+            p.color().unwrap_or("-"),
+            p.external_reference().unwrap_or("-")
+        );
+        Ok(())
+    }
+
+    /// Rename a project, the ID, aliases and entries are kept
+    pub fn rename_project(
+        &mut self,
+        project_name: &str,
+        new_name: &str,
+    ) -> Result<(), ButlerError> {
+        let project_index = match self.project_position(project_name) {
+            Some(index) => index,
+            None => {
+                return Err(ButlerError::NotFound(format!(
+                    "Project with name {}, unable to rename project",
+                    project_name
+                )));
+            }
+        };
+
+        if self
+            .project_position(new_name)
+            .is_some_and(|i| i != project_index)
+        {
+            return Err(ButlerError::Duplicate(format!(
+                "Project with name {}, unable to rename project",
+                new_name
+            )));
+        }
+
+        let old_name = self.projects[project_index].name().to_string();
+        if Project::new(new_name.to_string(), None).is_descendant_of(&old_name) {
+            return Err(ButlerError::InvalidInput(format!(
+                "Project {} can not be moved below itself",
+                old_name
+            )));
+        }
+
+        // Sub-projects follow their parent
+        for p in self.projects.iter_mut() {
+            if p.is_descendant_of(&old_name) {
+                let sub_name = format!("{}{}", new_name, &p.name()[old_name.len()..]);
+                tracing::debug!("Renaming sub-project {} to {}", p.name(), sub_name);
+                p.update_name(sub_name);
+            }
+        }
+
+        // Allocations of days follow the project
+        for d in self
+            .weeks
+            .iter_mut()
+            .flat_map(|w| w.entries_mut().iter_mut())
+        {
+            d.rename_allocations(&old_name, new_name);
+        }
+
+        self.projects[project_index].update_name(new_name.to_string());
+        self.rebuild_indexes();
+        self.add_missing_parent_projects(new_name);
+        tracing::info!("Project {} renamed to {}", old_name, new_name);
+        Ok(())
+    }
+
+    /// Allocate the hours of a closed day to projects, replacing the existing allocations. An empty list removes the
+    /// allocations
+    pub fn allocate_day(
+        &mut self,
+        date: NaiveDate,
+        allocations: Vec<(String, f32)>,
+    ) -> Result<(), ButlerError> {
+        self.check_unlocked(date, "allocate the day")?;
+
+        // Aliases are resolved, the allocation is stored with the project name
+        let mut resolved: Vec<Allocation> = Vec::new();
+        for (name, hours) in allocations {
+            validation::validate_entry_hours(hours, self.configuration.validation())
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+            let index = self.project_position(&name).ok_or_else(|| {
+                ButlerError::NotFound(format!("Project with name {}, unable to allocate", name))
+            })?;
+            let project = self.projects[index].name();
+            if resolved.iter().any(|a| a.project() == project) {
+                return Err(ButlerError::InvalidInput(format!(
+                    "Project {} is allocated more than once",
+                    project
+                )));
+            }
+            resolved.push(Allocation::new(project.to_string(), hours));
+        }
+
+        let rounding = self.rounding;
+        let stored_day = self
+            .weeks
+            .iter_mut()
+            .flat_map(|w| w.entries_mut().iter_mut())
+            .find(|d| d.date() == date)
+            .ok_or_else(|| ButlerError::NotFound(format!("No day registered for {}", date)))?;
+
+        // Validate a copy so nothing is changed if the allocations are invalid
+        let mut day = stored_day.clone();
+        day.set_allocations(resolved);
+        let day_hours = rounding.round(day.hours());
+        validation::validate_allocations(&day, day_hours)
+            .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+
+        let allocated: f32 = day.allocations().iter().map(|a| a.hours()).sum();
+        if day.allocations().is_empty() {
+            outln!("Allocations removed from day {}", date);
+        } else {
+            outln!(
+                "Allocated {} of {} hours of day {}",
+                allocated,
+                day_hours,
+                date
+            );
+        }
+        *stored_day = day;
+        Ok(())
+    }
+
+    /// Internal function to get the allocations of days to the named projects as entries, used in the reports
+    fn allocation_entries(&self, project_names: &[&str]) -> Vec<Entry> {
+        let mut entries = Vec::new();
+        for d in self.weeks.iter().flat_map(|w| w.entries()) {
+            for a in d.allocations() {
+                if project_names.contains(&a.project()) {
+                    let mut entry = Entry::new_with_clock(
+                        a.hours(),
+                        Some(format!("Allocated from day {}", d.date())),
+                        self.clock.as_ref(),
+                    );
+                    entry.set_worked_on(d.date());
+                    entries.push(entry);
+                }
+            }
+        }
+        entries
     }
 
     pub fn modify_day(
@@ -276,108 +1129,404 @@ impl Butler {
         new_starting_time: Option<String>,
         new_ending_time: Option<String>,
         new_paused_hours: Option<String>,
-    ) -> bool {
-        // This can be moved out to a support function
-        let day_id = match Uuid::parse_str(&id) {
-            Ok(parsed_id) => {
-                tracing::debug!("Parsed day ID: {}", parsed_id);
-                parsed_id
-            }
-            Err(e) => {
-                tracing::error!("Failed to parse day ID: {}", e);
-                return false;
-            }
-        };
+    ) -> Result<(), ButlerError> {
+        let day_id = Self::parse_id(&id)?;
+        if let Some(date) = self.day_date(&day_id) {
+            self.check_unlocked(date, "modify day")?;
+        }
 
         for w in &mut self.weeks {
-            if let Some(day) = w.entries_mut().iter_mut().find(|d| d.id() == &day_id) {
-                // Day found
+            if let Some(stored_day) = w.entries_mut().iter_mut().find(|d| d.id() == &day_id) {
+                // Day found, modify a copy so nothing is changed if the modified day is invalid
+                let mut day = stored_day.clone();
 
                 if let Some(new_extra_info) = new_extra_info {
-                    if !Self::prompt_user_confirmation(&format!(
+                    if !Self::prompt_user_confirmation(&i18n::tr_args(
                         "Are you sure you want to change the Days extra info from '{}' to '{}'?",
-                        day.extra_info(),
-                        new_extra_info
+                        &[&day.extra_info(), &new_extra_info],
                     )) {
-                        tracing::info!(
-                    "User chose not to proceed with extra info change, aborting modification"
-                );
-                        return false;
+                        return Err(ButlerError::Aborted(
+                            "User chose not to proceed with extra info change".to_string(),
+                        ));
                     }
                     day.set_extra_info(new_extra_info);
                 }
 
                 if let Some(new_starting_time_str) = new_starting_time {
-                    match Self::parse_datetime_as_local_time(&new_starting_time_str) {
-                        Ok(new_starting_time) => {
-                            day.set_starting_time(Some(&new_starting_time));
-                        }
-                        Err(e) => {
-                            tracing::error!("Failed to parse new starting time: {}", e);
-                            return false;
-                        }
-                    }
+                    let new_starting_time = Self::parse_datetime_as_local_time(
+                        &new_starting_time_str,
+                    )
+                    .map_err(|e| {
+                        ButlerError::InvalidInput(format!(
+                            "Failed to parse new starting time: {}",
+                            e
+                        ))
+                    })?;
+                    day.set_starting_time(&new_starting_time);
                 }
 
                 if let Some(new_ending_time_str) = new_ending_time {
-                    match Self::parse_datetime_as_local_time(&new_ending_time_str) {
-                        Ok(new_ending_time) => {
-                            day.set_ending_time(Some(&new_ending_time));
-                        }
-                        Err(e) => {
-                            tracing::error!("Failed to parse new ending time: {}", e);
-                            return false;
-                        }
-                    }
+                    let new_ending_time = Self::parse_datetime_as_local_time(&new_ending_time_str)
+                        .map_err(|e| {
+                            ButlerError::InvalidInput(format!(
+                                "Failed to parse new ending time: {}",
+                                e
+                            ))
+                        })?;
+                    day.set_ending_time(&new_ending_time);
                 }
 
                 if let Some(new_paused_hours_str) = new_paused_hours {
-                    match new_paused_hours_str.parse::<f32>() {
-                        Ok(new_paused_hours) => {
-                            day.set_paused_time(new_paused_hours);
-                        }
-                        Err(e) => {
-                            tracing::error!("Failed to parse new paused hours: {}", e);
-                            return false;
-                        }
-                    }
+                    let new_paused_hours = new_paused_hours_str.parse::<f32>().map_err(|e| {
+                        ButlerError::InvalidInput(format!(
+                            "Failed to parse new paused hours: {}",
+                            e
+                        ))
+                    })?;
+                    day.set_paused_time(new_paused_hours);
                 }
 
+                validation::validate_day(&day, self.configuration.validation()).map_err(|e| {
+                    ButlerError::InvalidInput(format!("{}, unable to modify day", e))
+                })?;
+                *stored_day = day;
+
                 tracing::info!("Day with ID {} modified successfully", id);
-                tables::print_day_in_report_table(day);
-                return true;
+                let stored_day = stored_day.clone();
+                let stored_day = self.attribute_overnight_day(stored_day);
+                tables::print_day_in_report_table(&stored_day, &self.display);
+                self.warn_short_day(&stored_day);
+                return Ok(());
             }
         }
-        true
+
+        Err(ButlerError::NotFound(format!(
+            "Day with ID {}, unable to modify day",
+            day_id
+        )))
     }
 
-    /// Display information about the Butler
-    pub fn self_info(&self, short: bool) {
-        if short {
-            // Display info in normal log print
+    /// Modify an entry in a project, the entry can also be moved to another project
+    pub fn modify_entry(
+        &mut self,
+        project_name: &str,
+        id: String,
+        new_hours: Option<String>,
+        new_description: Option<String>,
+        new_date: Option<String>,
+        new_project: Option<String>,
+    ) -> Result<(), ButlerError> {
+        let entry_id = Self::parse_id(&id)?;
+
+        let project_index = self
+            .project_position(project_name)
+            .ok_or_else(|| ButlerError::NotFound(format!("Project with name {}", project_name)))?;
+
+        let entry = self.projects[project_index]
+            .get_entry_copy(&entry_id)
+            .ok_or_else(|| {
+                ButlerError::NotFound(format!(
+                    "Entry with ID: {} in project: {}",
+                    entry_id, project_name
+                ))
+            })?;
+
+        if new_hours.is_none()
+            && new_description.is_none()
+            && new_date.is_none()
+            && new_project.is_none()
+        {
+            return Err(ButlerError::InvalidInput(
+                "Nothing to modify, provide at least one new value".to_string(),
+            ));
+        }
 
-            let output_str = format!(
-                "\nNumber of stored projects: {}\nNumber of stored weeks: {}",
-                self.number_of_projects(),
-                self.number_of_weeks(),
-            );
+        self.check_unlocked(entry.work_date(), "modify entry")?;
+        let mut modified = entry.clone();
 
-            tracing::info!("{}", output_str);
+        if let Some(new_hours_str) = new_hours {
+            let hours = new_hours_str.parse::<f32>().map_err(|e| {
+                ButlerError::InvalidInput(format!("Failed to parse new hours: {}", e))
+            })?;
+            validation::validate_entry_hours(hours, self.configuration.validation())
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+            modified.set_hours(hours);
+        }
+
+        if let Some(new_description) = new_description {
+            modified.set_description(new_description);
+        }
+
+        if let Some(new_date_str) = new_date {
+            let date = NaiveDate::parse_from_str(&new_date_str, "%Y-%m-%d").map_err(|_| {
+                ButlerError::InvalidInput(format!(
+                    "Invalid date provided: {}, expected format YYYY-MM-DD",
+                    new_date_str
+                ))
+            })?;
+            modified.set_worked_on(date);
+            self.check_unlocked(date, "move entry")?;
+        }
+
+        let target_index = match new_project {
+            Some(ref new_project_name) => {
+                let index = self.project_position(new_project_name).ok_or_else(|| {
+                    ButlerError::NotFound(format!(
+                        "Project with name {}, unable to move entry",
+                        new_project_name
+                    ))
+                })?;
+                if self.projects[index].archived() {
+                    return Err(ButlerError::InvalidInput(format!(
+                        "Project {} is archived, unable to move entry",
+                        self.projects[index].name()
+                    )));
+                }
+                index
+            }
+            None => project_index,
+        };
+
+        // Show the entry before and after the change
+        outln!("Before:");
+        tables::print_entry_in_report_table(
+            &entry,
+            self.projects[project_index].name(),
+            &self.display,
+        );
+        outln!("After:");
+        tables::print_entry_in_report_table(
+            &modified,
+            self.projects[target_index].name(),
+            &self.display,
+        );
+
+        if !Self::prompt_user_confirmation(i18n::tr("Are you sure you want to modify the entry?")) {
+            return Err(ButlerError::Aborted(
+                "User chose not to proceed with entry change".to_string(),
+            ));
+        }
+
+        if target_index == project_index {
+            self.projects[project_index].update_entry(modified);
+        } else {
+            self.projects[project_index].remove_listed_entry(&entry_id);
+            self.projects[target_index].add_entry(modified);
+            tracing::info!(
+                "Entry {} moved from project {} to {}",
+                entry_id,
+                self.projects[project_index].name(),
+                self.projects[target_index].name()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Display information about the Butler. The detailed view has the storage files, the last backup, the
+    /// configuration file and where it was given, and the stored time items per year
+    pub fn self_info(&self, short: bool, config_file: &str, config_source: &str) {
+        if short {
+            // Display info in normal log print
+
+            let output_str = format!(
+                "\nNumber of stored projects: {}\nNumber of stored weeks: {}",
+                self.number_of_projects(),
+                self.number_of_weeks(),
+            );
+
+            tracing::info!("{}", output_str);
+        } else {
+            self.print_detailed_info(config_file, config_source);
+        }
+    }
+
+    /// Internal function to print the detailed info
+    fn print_detailed_info(&self, config_file: &str, config_source: &str) {
+        let file_info = |path: &str| match std::fs::metadata(path) {
+            Ok(metadata) => format!("{} ({})", path, Self::format_file_size(metadata.len())),
+            Err(_) => format!("{} (not created)", path),
+        };
+
+        let days = || self.weeks.iter().flat_map(|w| w.entries());
+        let entries = || self.projects.iter().flat_map(|p| p.entries());
+        let first_date = days()
+            .map(|d| d.date())
+            .chain(entries().map(|e| e.work_date()))
+            .min();
+        let last_date = days()
+            .map(|d| d.date())
+            .chain(entries().map(|e| e.work_date()))
+            .max();
+        let format_date = |date: Option<NaiveDate>| {
+            date.map(|d| d.to_string())
+                .unwrap_or_else(|| "N/A".to_string())
+        };
+
+        let mut table = tables::get_table_info();
+        table.add_row(vec![
+            "Project data file".to_string(),
+            file_info(self.storage_handler.project_data_file_path()),
+        ]);
+        table.add_row(vec![
+            "Week data file".to_string(),
+            file_info(self.storage_handler.week_data_file_path()),
+        ]);
+        table.add_row(vec![
+            "Backup directory".to_string(),
+            self.storage_handler.backup_directory().to_string(),
+        ]);
+        table.add_row(vec![
+            "Last backup".to_string(),
+            self.storage_handler
+                .last_backup()
+                .map(|t| {
+                    t.with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_else(|| "Never".to_string()),
+        ]);
+        table.add_row(vec![
+            "Configuration file".to_string(),
+            format!("{} ({})", config_file, config_source),
+        ]);
+        table.add_row(vec![
+            "Projects".to_string(),
+            self.number_of_projects().to_string(),
+        ]);
+        table.add_row(vec![
+            "Weeks".to_string(),
+            self.number_of_weeks().to_string(),
+        ]);
+        table.add_row(vec!["Days".to_string(), days().count().to_string()]);
+        table.add_row(vec!["Entries".to_string(), entries().count().to_string()]);
+        table.add_row(vec![
+            "Total day hours".to_string(),
+            self.display
+                .hours(days().map(|d| self.rounding.round(d.hours())).sum::<f32>()),
+        ]);
+        table.add_row(vec![
+            "Total entry hours".to_string(),
+            self.display
+                .hours(entries().map(|e| e.hours()).sum::<f32>()),
+        ]);
+        table.add_row(vec![
+            "First recorded date".to_string(),
+            format_date(first_date),
+        ]);
+        table.add_row(vec![
+            "Last recorded date".to_string(),
+            format_date(last_date),
+        ]);
+        outln!("{}", table);
+
+        // Weeks, days, entries, day hours and entry hours per year
+        let mut years: BTreeMap<i32, (usize, usize, usize, f32, f32)> = BTreeMap::new();
+        for w in &self.weeks {
+            let year = years.entry(w.year()).or_default();
+            year.0 += 1;
+            for d in w.entries() {
+                year.1 += 1;
+                year.3 += self.rounding.round(d.hours());
+            }
+        }
+        for e in entries() {
+            let year = years.entry(e.work_date().year()).or_default();
+            year.2 += 1;
+            year.4 += e.hours();
+        }
+
+        if years.is_empty() {
+            return;
+        }
+
+        let mut year_table = tables::get_table_year_counts();
+        for (year, (weeks, days, entries, day_hours, entry_hours)) in &years {
+            year_table.add_row(vec![
+                year.to_string(),
+                weeks.to_string(),
+                days.to_string(),
+                entries.to_string(),
+                self.display.hours(*day_hours),
+                self.display.hours(*entry_hours),
+            ]);
+        }
+        outln!("{}", year_table);
+    }
+
+    /// Internal function to format a file size in bytes, KiB or MiB
+    fn format_file_size(bytes: u64) -> String {
+        const K_KIB: f64 = 1024.0;
+        let bytes_f = bytes as f64;
+        if bytes_f < K_KIB {
+            format!("{} B", bytes)
+        } else if bytes_f < K_KIB * K_KIB {
+            format!("{:.1} KiB", bytes_f / K_KIB)
         } else {
-            tracing::warn!("Only short info implemented so far..sorry!")
+            format!("{:.1} MiB", bytes_f / (K_KIB * K_KIB))
         }
     }
 
     /// Add a new project to the Butler
-    pub fn add_project(&mut self, project: Project) -> bool {
+    pub fn add_project(
+        &mut self,
+        mut project: Project,
+        on_duplicate: DuplicateProjectAction,
+        entry: Option<Entry>,
+    ) -> Result<bool, ButlerError> {
+        Self::validate_project_path(project.name())?;
+        if let Some(budget) = project.budget_hours() {
+            validation::validate_budget_hours(budget)
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+        }
+
         //search the project list for the project name
-        for p in &self.projects {
-            if p.name() == project.name() {
-                tracing::error!(
-                    "Project with name {} already exists in list, unable to add project",
-                    project.name()
-                );
-                return false;
+        if self.project_position(project.name()).is_some() {
+            let name = project.name().to_string();
+            let suffixed_name = self.free_project_name(&name);
+            let action = match on_duplicate {
+                DuplicateProjectAction::Interactive => {
+                    Self::prompt_duplicate_project_action(&name, &suffixed_name)
+                }
+                action => action,
+            };
+
+            match action {
+                DuplicateProjectAction::Open => {
+                    tracing::info!("Project {} already exists, nothing added", name);
+                    self.list_specific_project(&name, &ListOptions::default())?;
+                    return Ok(false);
+                }
+                DuplicateProjectAction::AddEntry => {
+                    let entry = match (entry, on_duplicate) {
+                        (Some(entry), _) => entry,
+                        (None, DuplicateProjectAction::Interactive) => self.prompt_entry()?,
+                        (None, _) => {
+                            return Err(ButlerError::InvalidInput(
+                                "The entry hours and description are needed to add an entry to the existing project"
+                                    .to_string(),
+                            ))
+                        }
+                    };
+                    tracing::info!("Project {} already exists, adding the entry to it", name);
+                    self.add_entry(&name, entry, false)?;
+                    return Ok(true);
+                }
+                DuplicateProjectAction::Suffix => {
+                    tracing::info!(
+                        "Project {} already exists, adding it as {}",
+                        name,
+                        suffixed_name
+                    );
+                    project.update_name(suffixed_name);
+                }
+                DuplicateProjectAction::Abort | DuplicateProjectAction::Interactive => {
+                    return Err(ButlerError::Duplicate(format!(
+                        "Project with name {} already exists in list, unable to add project",
+                        name
+                    )));
+                }
             }
         }
 
@@ -387,794 +1536,4193 @@ impl Butler {
             project.description().unwrap_or("")
         );
         tracing::debug!("Project will be stored with ID: {}", project.id());
-        self.projects.push(project);
+        porcelain::set(
+            "project",
+            serde_json::json!({ "name": project.name(), "id": project.id() }),
+        );
+        self.add_missing_parent_projects(project.name());
+        self.push_project(project);
 
-        true
+        Ok(true)
+    }
+
+    /// Internal function to get the first free name with a number suffix, e.g. "acme-2"
+    fn free_project_name(&self, name: &str) -> String {
+        (2..)
+            .map(|n| format!("{}-{}", name, n))
+            .find(|candidate| self.project_position(candidate).is_none())
+            .unwrap_or_else(|| name.to_string()) // never reached, the range is unbounded
+    }
+
+    /// Internal function to ask what to do with an added project when the name exists
+    fn prompt_duplicate_project_action(name: &str, suffixed_name: &str) -> DuplicateProjectAction {
+        let answer = Self::prompt_user_input(&i18n::tr_args(
+            "Project {} already exists. Open it (o), add an entry to it (e), create it as {} (s) or abort (a)",
+            &[&name, &suffixed_name],
+        ));
+        match answer.trim().to_lowercase().as_str() {
+            "o" => DuplicateProjectAction::Open,
+            "e" => DuplicateProjectAction::AddEntry,
+            "s" => DuplicateProjectAction::Suffix,
+            _ => {
+                tracing::warn!("Not confirmed by user");
+                DuplicateProjectAction::Abort
+            }
+        }
+    }
+
+    /// Internal function to ask for the hours and description of an entry
+    fn prompt_entry(&self) -> Result<Entry, ButlerError> {
+        let answer = Self::prompt_user_input(i18n::tr("Hours worked"));
+        let hours = answer.trim().parse::<f32>().unwrap_or(0.0);
+        if hours <= 0.0 {
+            return Err(ButlerError::InvalidInput(format!(
+                "Invalid hours provided: {}. You can't report 0 or negative hours on a project.",
+                answer
+            )));
+        }
+        let description = Self::prompt_user_input(i18n::tr("Description of the work done"));
+        Ok(Entry::new_with_clock(
+            hours,
+            Some(description),
+            self.clock.as_ref(),
+        ))
     }
 
     /// Create a new project report
-    pub fn project_report(&self, project_name: &str, format: &str) -> bool {
+    pub fn project_report(
+        &self,
+        project_name: &str,
+        format: &str,
+        options: &ReportOptions,
+    ) -> Result<(), ButlerError> {
         let report_format = match format {
             "json" => ReportFormat::Json,
             "csv" => ReportFormat::Csv,
             "yaml" => ReportFormat::Yaml,
             "html" => ReportFormat::Html,
+            "template" => ReportFormat::Template(Self::template_path(options)?),
             "pdf" => ReportFormat::Pdf,
             "text" => ReportFormat::Text,
-            _ => {
-                tracing::error!("Invalid format: {}", format);
-                return false;
-            }
+            other => self.renderer_format(other)?,
         };
+        let report_columns =
+            Self::parse_report_columns(options.columns.as_deref(), &K_PROJECT_REPORT_COLUMNS)?;
+
         // Search for the project
-        for p in &self.projects {
-            if p.name() == project_name {
-                match self.storage_handler.create_report_dir() {
-                    Ok(_) => (),
-                    Err(e) => {
-                        tracing::error!("Failed to create report directory: {}", e);
-                        return false;
-                    }
+        if let Some(index) = self.project_position(project_name) {
+            let p = &self.projects[index];
+            self.storage_handler.create_report_dir().map_err(|e| {
+                ButlerError::StorageFailure(format!("Failed to create report directory: {}", e))
+            })?;
+
+            // Entries of the sub-projects are rolled up into the report, together with the allocated day hours
+            let mut rolled_up = p.clone();
+            let mut project_names = vec![p.name()];
+            for sub in self.sub_projects(p.name()) {
+                for e in sub.entries() {
+                    rolled_up.add_entry(e.clone());
                 }
+                project_names.push(sub.name());
+            }
+            for e in self.allocation_entries(&project_names) {
+                rolled_up.add_entry(e);
+            }
 
-                let generation_result =
-                    match self.report_mngr.generate_project_report(report_format, p) {
-                        Ok(_) => true,
-                        Err(e) => {
-                            tracing::error!("failed to generate report: {}", e);
-                            false
-                        }
-                    };
-
-                return generation_result;
+            // The summary is also what is copied to the clipboard for the full report
+            let mut report_summary = ReportSummary::new(format!("Project {}", p.name()));
+            for e in rolled_up.entries() {
+                let (week, year) = week_scheme::week_scheme().week_of(e.work_date());
+                report_summary.add(year, week, self.rounding.round(e.hours()));
             }
+            if options.summary {
+                return self.generate_summary(p.name(), report_format, &report_summary, options);
+            }
+
+            return self.deliver_report(
+                self.report_mngr.generate_project_report(
+                    report_format,
+                    &rolled_up,
+                    report_columns,
+                    self.configuration.project_report_output(p),
+                ),
+                options,
+                Some(&report_summary),
+            );
         }
 
-        tracing::error!("Project with name {} not found", project_name);
-        false
+        Err(self.project_not_found(project_name, ""))
     }
 
     /// Create a new week report
-    pub fn week_report(&self, week_number: u32, format: &str, year: u32) -> bool {
+    pub fn week_report(
+        &self,
+        week_number: u32,
+        format: &str,
+        year: u32,
+        options: &ReportOptions,
+    ) -> Result<(), ButlerError> {
         let report_format = match format {
             "json" => ReportFormat::Json,
             "csv" => ReportFormat::Csv,
             "yaml" => ReportFormat::Yaml,
             "html" => ReportFormat::Html,
+            "template" => ReportFormat::Template(Self::template_path(options)?),
             "pdf" => ReportFormat::Pdf,
             "text" => ReportFormat::Text,
-            _ => {
-                tracing::error!("Invalid format: {}", format);
-                return false;
-            }
+            other => self.renderer_format(other)?,
         };
 
-        // Search for the week with both week number and year
-        for w in &self.weeks {
-            if w.number() == week_number && w.year() as u32 == year {
-                match self.storage_handler.create_report_dir() {
-                    Ok(_) => (),
-                    Err(e) => {
-                        tracing::error!("Failed to create report directory: {}", e);
-                        return false;
-                    }
-                }
+        let report_columns =
+            Self::parse_report_columns(options.columns.as_deref(), &K_DAY_REPORT_COLUMNS)?;
 
-                let generation_result =
-                    match self.report_mngr.generate_week_report(report_format, w) {
-                        Ok(_) => true,
-                        Err(e) => {
-                            tracing::error!("failed to generate report: {}", e);
-                            false
-                        }
-                    };
+        // Search for the week with both week number and year
+        if let Some(index) = self.week_position(week_number, year as i32) {
+            let w = &self.weeks[index];
+            self.storage_handler.create_report_dir().map_err(|e| {
+                ButlerError::StorageFailure(format!("Failed to create report directory: {}", e))
+            })?;
 
-                return generation_result;
+            let mut report_summary = ReportSummary::new(format!("Week {}, {}", week_number, year));
+            for d in w.entries() {
+                report_summary.add(d.week_year(), d.week(), self.rounding.round(d.hours()));
             }
+            let status = WeeklyTargetStatus::new(w, &w.target_hours());
+            report_summary.set_target(*status.target_hours(), *status.percentage());
+            if options.summary {
+                return self.generate_summary(
+                    &format!("week{}", week_number),
+                    report_format,
+                    &report_summary,
+                    options,
+                );
+            }
+
+            return self.deliver_report(
+                self.report_mngr
+                    .generate_week_report(report_format, w, report_columns),
+                options,
+                Some(&report_summary),
+            );
         }
 
-        tracing::error!(
-            "Week with number {} and year {} not found",
-            week_number,
-            year
-        );
-        false
+        Err(ButlerError::NotFound(format!(
+            "Week with number {} and year {}",
+            week_number, year
+        )))
+    }
+
+    /// Write an SVG badge with the target attainment of the week, for embedding in a dashboard or a README
+    pub fn week_badge(
+        &self,
+        week_number: u32,
+        year: u32,
+        output: Option<&str>,
+    ) -> Result<(), ButlerError> {
+        let index = self
+            .week_position(week_number, year as i32)
+            .ok_or_else(|| {
+                ButlerError::NotFound(format!(
+                    "Week with number {} and year {}",
+                    week_number, year
+                ))
+            })?;
+        if output.is_none() {
+            self.storage_handler.create_report_dir().map_err(|e| {
+                ButlerError::StorageFailure(format!("Failed to create report directory: {}", e))
+            })?;
+        }
+
+        let file_path = self
+            .report_mngr
+            .generate_badge(&self.weeks[index], output)
+            .map_err(|e| ButlerError::ReportFailure(e.to_string()))?;
+        porcelain::set("badge", &file_path);
+        outln!("{}", file_path);
+        Ok(())
     }
 
-    pub fn month_report(&self, month_number: u32, format: &str, year: u32) -> bool {
+    pub fn month_report(
+        &self,
+        month_number: u32,
+        format: &str,
+        year: u32,
+        options: &ReportOptions,
+    ) -> Result<(), ButlerError> {
         let report_format = match format {
             "json" => ReportFormat::Json,
             "csv" => ReportFormat::Csv,
             "yaml" => ReportFormat::Yaml,
             "html" => ReportFormat::Html,
+            "template" => ReportFormat::Template(Self::template_path(options)?),
             //"pdf" => ReportFormat::Pdf,
             //"text" => ReportFormat::Text,
-            _ => {
-                tracing::error!("Invalid format: {}", format);
-                return false;
-            }
+            other => self.renderer_format(other)?,
         };
 
         if !(1..=12).contains(&month_number) {
-            tracing::error!("Invalid month number: {}", month_number);
-            return false;
+            return Err(ButlerError::InvalidInput(format!(
+                "Invalid month number: {}",
+                month_number
+            )));
         }
 
+        let report_columns =
+            Self::parse_report_columns(options.columns.as_deref(), &K_DAY_REPORT_COLUMNS)?;
+
         // Only include days from the specified year
-        let days: Vec<Day> = self
-            .get_days_in_month(month_number)
-            .into_iter()
-            .filter(|d| d.year() as u32 == year)
-            .collect();
+        let days = self.days_in_month_for_year(month_number, year);
 
-        if days.is_empty() {
-            tracing::warn!(
-                "No days found for month: {} and year: {}",
-                month_number,
-                year
-            );
-            return false;
+        if days.clone().next().is_none() {
+            return Err(ButlerError::NotFound(format!(
+                "No days for month: {} and year: {}",
+                month_number, year
+            )));
         }
 
-        match self.storage_handler.create_report_dir() {
-            Ok(_) => (),
-            Err(e) => {
-                tracing::error!("Failed to create report directory: {}", e);
-                return false;
-            }
-        }
+        self.storage_handler.create_report_dir().map_err(|e| {
+            ButlerError::StorageFailure(format!("Failed to create report directory: {}", e))
+        })?;
 
-        let generation_result =
-            match self
-                .report_mngr
-                .generate_month_report(month_number, report_format, &days)
-            {
-                Ok(_) => true,
-                Err(e) => {
-                    tracing::error!("failed to generate report: {}", e);
-                    false
-                }
-            };
+        let mut report_summary = ReportSummary::new(format!("Month {}, {}", month_number, year));
+        for d in days.clone() {
+            report_summary.add(d.week_year(), d.week(), self.rounding.round(d.hours()));
+        }
+        let status = MonthlyTargetStatus::new(days.clone(), &self.month_target_hours(month_number));
+        report_summary.set_target(*status.target_hours(), *status.percentage());
+        if options.summary {
+            return self.generate_summary(
+                &format!("month{}", month_number),
+                report_format,
+                &report_summary,
+                options,
+            );
+        }
 
-        generation_result
+        let target_status = ReportTargetStatus::from_month(&status);
+        self.deliver_report(
+            self.report_mngr.generate_month_report(
+                month_number,
+                report_format,
+                days,
+                &self.week_notes(),
+                report_columns,
+                Some(&target_status),
+            ),
+            options,
+            Some(&report_summary),
+        )
     }
 
-    /// List all projects
-    pub fn list_all_projects(&self) {
-        if self.projects.is_empty() {
-            tracing::warn!("No projects stored, unable to list projects");
-            return;
+    /// Create a new report for an arbitrary date range, the range can span multiple weeks and years
+    pub fn range_report(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        format: &str,
+        options: &ReportOptions,
+    ) -> Result<(), ButlerError> {
+        let report_format = match format {
+            "json" => ReportFormat::Json,
+            "csv" => ReportFormat::Csv,
+            "yaml" => ReportFormat::Yaml,
+            "html" => ReportFormat::Html,
+            "template" => ReportFormat::Template(Self::template_path(options)?),
+            other => self.renderer_format(other)?,
+        };
+
+        if from > to {
+            return Err(ButlerError::InvalidInput(format!(
+                "Invalid range, {} is after {}",
+                from, to
+            )));
         }
 
-        let mut table = Table::new();
-        table.set_content_arrangement(ContentArrangement::Dynamic);
+        let report_columns =
+            Self::parse_report_columns(options.columns.as_deref(), &K_DAY_REPORT_COLUMNS)?;
 
-        table.set_header(vec![
-            Cell::new("Name"),
-            Cell::new("Description"),
-            Cell::new("Number of Entries"),
-            Cell::new("ID"),
-        ]);
+        // Only the references are sorted, the days are not copied
+        let mut days: Vec<&Day> = self
+            .weeks
+            .iter()
+            .flat_map(|w| w.entries())
+            .filter(|d| d.date() >= from && d.date() <= to)
+            .collect();
+        days.sort_by_key(|d| d.date());
 
-        for p in &self.projects {
-            table.add_row(vec![
-                Cell::new(p.name()),
-                Cell::new(p.description().unwrap_or("")),
-                Cell::new(p.entries().len().to_string()),
-                Cell::new(p.id().to_string()),
-            ]);
+        if days.is_empty() {
+            return Err(ButlerError::NotFound(format!(
+                "No days between {} and {}",
+                from, to
+            )));
         }
 
-        println!("{}", table);
-    }
-
-    /// List a specific project, will show all entries stored for that specific project
-    pub fn list_specific_project(&self, project_name: &str) {
-        for p in &self.projects {
-            if p.name() == project_name {
-                let mut table = tables::get_table_entry();
-
-                for e in p.entries() {
-                    table.add_row(vec![
-                        Cell::new(p.name()),
-                        Cell::new(e.description()),
-                        Cell::new(e.hours().to_string()),
-                        Cell::new(e.created().to_string()),
-                        Cell::new(e.id().to_string()),
-                    ]);
-                }
+        self.storage_handler.create_report_dir().map_err(|e| {
+            ButlerError::StorageFailure(format!("Failed to create report directory: {}", e))
+        })?;
 
-                println!("{}", table);
-                return;
-            }
+        let mut report_summary = ReportSummary::new(format!("Range {} to {}", from, to));
+        for d in &days {
+            report_summary.add(d.week_year(), d.week(), self.rounding.round(d.hours()));
+        }
+        if options.summary {
+            return self.generate_summary(
+                &format!("range_{}_{}", from, to),
+                report_format,
+                &report_summary,
+                options,
+            );
         }
 
-        tracing::error!("Project with name {} not found", project_name);
+        self.deliver_report(
+            self.report_mngr.generate_range_report(
+                from,
+                to,
+                report_format,
+                days.iter().copied(),
+                &self.week_notes(),
+                report_columns,
+            ),
+            options,
+            Some(&report_summary),
+        )
     }
 
-    /// List all weeks stored, doesn't show the days stored in the weeks
-    pub fn list_all_weeks(&self) {
-        let mut table = Table::new();
-        table.set_content_arrangement(ContentArrangement::Dynamic);
+    /// Create a new report of the days of a year, grouped by week as the range reports
+    pub fn year_report(
+        &self,
+        year: i32,
+        format: &str,
+        options: &ReportOptions,
+    ) -> Result<(), ButlerError> {
+        let report_format = match format {
+            "json" => ReportFormat::Json,
+            "csv" => ReportFormat::Csv,
+            "yaml" => ReportFormat::Yaml,
+            "html" => ReportFormat::Html,
+            "template" => ReportFormat::Template(Self::template_path(options)?),
+            other => self.renderer_format(other)?,
+        };
 
-        table.set_header(vec![
-            Cell::new("Year"),
-            Cell::new("Week"),
-            Cell::new("Number of days registered"),
-        ]);
+        let report_columns =
+            Self::parse_report_columns(options.columns.as_deref(), &K_DAY_REPORT_COLUMNS)?;
 
-        for w in &self.weeks {
-            table.add_row(vec![
-                Cell::new(w.year().to_string()),
-                Cell::new(w.number().to_string()),
-                Cell::new(w.entries().len().to_string()),
-            ]);
+        // Only the references are sorted, the days are not copied
+        let mut days: Vec<&Day> = self.days_in_year(year).collect();
+        days.sort_by_key(|d| d.date());
+
+        if days.is_empty() {
+            return Err(ButlerError::NotFound(format!("No days in year {}", year)));
         }
 
-        println!("{}", table);
-    }
+        self.storage_handler.create_report_dir().map_err(|e| {
+            ButlerError::StorageFailure(format!("Failed to create report directory: {}", e))
+        })?;
 
-    /// List a specific week, will show all days stored for that specific week
-    pub fn list_specific_week(&self, week_number: u32) {
-        // Group weeks by year (for easier reading)
-        let mut weeks_by_year: BTreeMap<i32, &Week> = BTreeMap::new();
-        for w in &self.weeks {
-            if w.number() == week_number {
-                weeks_by_year.insert(w.year(), w);
-            }
+        let mut report_summary = ReportSummary::new(format!("Year {}", year));
+        for d in &days {
+            report_summary.add(d.week_year(), d.week(), self.rounding.round(d.hours()));
         }
-
-        if weeks_by_year.is_empty() {
-            tracing::error!("Week with number {} not found", week_number);
-            return;
+        if options.summary {
+            return self.generate_summary(
+                &format!("year{}", year),
+                report_format,
+                &report_summary,
+                options,
+            );
         }
 
-        for (year, week) in weeks_by_year {
-            println!("Year: {}", year);
-            let mut table = tables::get_table_day();
+        self.deliver_report(
+            self.report_mngr.generate_year_report(
+                year,
+                report_format,
+                days.iter().copied(),
+                &self.week_notes(),
+                report_columns,
+            ),
+            options,
+            Some(&report_summary),
+        )
+    }
 
-            for d in week.entries() {
-                let start_time = match d.starting_time() {
-                    Some(st) => st.to_string(),
-                    None => "N/A".to_string(),
-                };
+    /// Create a new overview report for a month, with the day hours and the project entry hours side by side
+    pub fn overview_report(
+        &self,
+        month_number: u32,
+        format: &str,
+        year: u32,
+        options: &ReportOptions,
+    ) -> Result<(), ButlerError> {
+        let report_format = match format {
+            "json" => ReportFormat::Json,
+            "csv" => ReportFormat::Csv,
+            "yaml" => ReportFormat::Yaml,
+            "html" => ReportFormat::Html,
+            "template" => ReportFormat::Template(Self::template_path(options)?),
+            other => self.renderer_format(other)?,
+        };
 
-                let end_time = match d.ending_time() {
-                    Some(et) => et.to_string(),
-                    None => "N/A".to_string(),
-                };
+        if !(1..=12).contains(&month_number) {
+            return Err(ButlerError::InvalidInput(format!(
+                "Invalid month number: {}",
+                month_number
+            )));
+        }
 
-                table.add_row(vec![
-                    Cell::new(d.week().to_string()),
-                    Cell::new(d.date().to_string()),
-                    Cell::new(start_time),
-                    Cell::new(end_time),
-                    Cell::new(d.hours_paused().to_string()),
-                    Cell::new(d.hours().to_string()),
-                    Cell::new(d.closed().to_string()),
-                    Cell::new(d.extra_info()),
-                    Cell::new(d.id().to_string()),
-                ]);
+        // Day hours and project hours per date
+        let mut hours_by_date: BTreeMap<NaiveDate, (f32, f32)> = BTreeMap::new();
+        for d in self.days_in_month_for_year(month_number, year) {
+            hours_by_date.entry(d.date()).or_default().0 += self.rounding.round(d.hours());
+        }
+        for e in self.projects.iter().flat_map(|p| p.entries()) {
+            let date = e.work_date();
+            if date.month() == month_number && date.year() as u32 == year {
+                hours_by_date.entry(date).or_default().1 += self.rounding.round(e.hours());
             }
-
-            println!("{}", table);
         }
-    }
 
-    pub fn list_specific_month(&self, month_number: u32) {
-        if !(1..=12).contains(&month_number) {
-            tracing::error!("Invalid month number: {}", month_number);
-            return;
+        if hours_by_date.is_empty() {
+            return Err(ButlerError::NotFound(format!(
+                "No days or entries for month: {} and year: {}",
+                month_number, year
+            )));
         }
 
-        let days = self.get_days_in_month(month_number);
-        if days.is_empty() {
-            tracing::warn!("No days found for month: {}", month_number);
-            return;
-        }
+        let rows: Vec<OverviewRow> = hours_by_date
+            .into_iter()
+            .map(|(date, (day_hours, project_hours))| {
+                OverviewRow::new(date, day_hours, project_hours)
+            })
+            .collect();
 
-        use std::collections::BTreeMap;
-        let mut days_by_year: BTreeMap<i32, Vec<Day>> = BTreeMap::new();
-        for d in days {
-            days_by_year.entry(d.year()).or_default().push(d);
+        let mismatches = rows.iter().filter(|r| !r.reconciled).count();
+        if mismatches > 0 {
+            tracing::warn!(
+                "{} date(s) where the day hours and project hours don't match",
+                mismatches
+            );
         }
 
-        for (year, days) in days_by_year {
-            println!("Year: {}", year);
-            let mut table = tables::get_table_day();
-
-            for d in days {
-                let start_time = match d.starting_time() {
-                    Some(st) => st.to_string(),
-                    None => "N/A".to_string(),
-                };
+        self.storage_handler.create_report_dir().map_err(|e| {
+            ButlerError::StorageFailure(format!("Failed to create report directory: {}", e))
+        })?;
 
-                let end_time = match d.ending_time() {
-                    Some(et) => et.to_string(),
-                    None => "N/A".to_string(),
-                };
+        let mut report_summary =
+            ReportSummary::new(format!("Overview month {}, {}", month_number, year));
+        for d in self.days_in_month_for_year(month_number, year) {
+            report_summary.add(d.week_year(), d.week(), self.rounding.round(d.hours()));
+        }
+        report_summary.set_reconciliation(rows.iter().map(|r| r.project_hours).sum(), mismatches);
+        if options.summary {
+            return self.generate_summary(
+                &format!("overview{}", month_number),
+                report_format,
+                &report_summary,
+                options,
+            );
+        }
+
+        self.deliver_report(
+            self.report_mngr
+                .generate_overview_report(month_number, year, report_format, &rows),
+            options,
+            Some(&report_summary),
+        )
+    }
+
+    /// Generate a timesheet report of a week, the entry and allocated hours of each project per day
+    pub fn timesheet_report(
+        &self,
+        week_number: u32,
+        format: &str,
+        year: u32,
+        options: &ReportOptions,
+    ) -> Result<(), ButlerError> {
+        let report_format = match format {
+            "csv" => ReportFormat::Csv,
+            "html" => ReportFormat::Html,
+            "xlsx" => ReportFormat::Xlsx,
+            _ => {
+                return Err(ButlerError::InvalidInput(format!(
+                    "Invalid format: {}, valid options are: csv, html, xlsx",
+                    format
+                )));
+            }
+        };
+
+        validation::validate_week(week_number, year as i32)
+            .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+        let mut timesheet = Timesheet::new(week_number, year as i32).ok_or_else(|| {
+            ButlerError::InvalidInput(format!(
+                "Week number {} doesn't exist in year {}",
+                week_number, year
+            ))
+        })?;
+
+        for p in &self.projects {
+            for e in p.entries() {
+                timesheet.add(p.name(), e.work_date(), self.rounding.round(e.hours()));
+            }
+        }
+        for d in self.weeks.iter().flat_map(|w| w.entries()) {
+            for a in d.allocations() {
+                timesheet.add(a.project(), d.date(), self.rounding.round(a.hours()));
+            }
+        }
+
+        if timesheet.rows.is_empty() {
+            return Err(ButlerError::NotFound(format!(
+                "No entries for week: {} and year: {}",
+                week_number, year
+            )));
+        }
+
+        self.storage_handler.create_report_dir().map_err(|e| {
+            ButlerError::StorageFailure(format!("Failed to create report directory: {}", e))
+        })?;
+
+        self.deliver_report(
+            self.report_mngr
+                .generate_timesheet_report(&timesheet, report_format),
+            options,
+            None,
+        )
+    }
+
+    /// Export the entries of the project and its sub-projects in the month as Jira worklogs. The issue key is the first
+    /// issue key in the entry description, or the external reference of the project. The worklogs are written to the
+    /// file, by default in the report directory, or posted to the configured Jira API
+    pub fn export_jira_worklogs(
+        &self,
+        project_name: &str,
+        month: u32,
+        year: u32,
+        output: Option<&str>,
+        post: bool,
+    ) -> Result<(), ButlerError> {
+        let index = self
+            .project_position(project_name)
+            .ok_or_else(|| self.project_not_found(project_name, ""))?;
+        let p = &self.projects[index];
+
+        let mut projects = vec![p];
+        projects.extend(self.sub_projects(p.name()));
+
+        let mut worklogs = Vec::new();
+        let mut skipped = 0;
+        for project in projects {
+            let project_key = project.external_reference().and_then(jira::issue_key);
+            for e in project.entries() {
+                let date = e.work_date();
+                if date.month() != month || date.year() != year as i32 {
+                    continue;
+                }
+                match jira::issue_key(e.description()).or_else(|| project_key.clone()) {
+                    Some(key) => worklogs.push(jira::Worklog::new(
+                        key,
+                        self.rounding.round(e.hours()),
+                        date,
+                        e.description(),
+                    )),
+                    None => {
+                        tracing::warn!(
+                            "No issue key for the entry {} of project {}, skipped",
+                            e.id(),
+                            project.name()
+                        );
+                        skipped += 1;
+                    }
+                }
+            }
+        }
+
+        if worklogs.is_empty() {
+            return Err(ButlerError::NotFound(format!(
+                "No entries with an issue key for project: {} in month {} {} ({} skipped)",
+                p.name(),
+                month,
+                year,
+                skipped
+            )));
+        }
+        let seconds: u64 = worklogs.iter().map(|w| w.time_spent_seconds()).sum();
+
+        if post {
+            let posted = jira::post_worklogs(self.configuration.jira(), &worklogs)
+                .map_err(|e| ButlerError::ReportFailure(e.to_string()))?;
+            outln!(
+                "Posted {} worklogs ({}) to {}",
+                posted,
+                self.display.hours(seconds as f32 / 3600.0),
+                self.configuration.jira().url
+            );
+        } else {
+            let file_path = match output {
+                Some(path) => path.to_string(),
+                None => {
+                    self.storage_handler.create_report_dir().map_err(|e| {
+                        ButlerError::StorageFailure(format!(
+                            "Failed to create report directory: {}",
+                            e
+                        ))
+                    })?;
+                    format!(
+                        "{}/jira-worklogs-{}-{}-{:02}.json",
+                        self.configuration.report_directory(),
+                        p.name().replace('/', "-"),
+                        year,
+                        month
+                    )
+                }
+            };
+            jira::write_worklogs(&file_path, &worklogs)
+                .map_err(|e| ButlerError::ReportFailure(e.to_string()))?;
+            porcelain::set("file", &file_path);
+            outln!("{}", file_path);
+        }
+
+        porcelain::set("worklogs", worklogs.len());
+        porcelain::set("skipped", skipped);
+        if skipped > 0 {
+            outln!("{} entries without an issue key were skipped", skipped);
+        }
+        Ok(())
+    }
+
+    /// List all projects
+    pub fn list_all_projects(
+        &self,
+        include_archived: bool,
+        options: &ListOptions,
+    ) -> Result<(), ButlerError> {
+        if self.projects.is_empty() {
+            tracing::warn!("No projects stored, unable to list projects");
+            return Ok(());
+        }
+
+        let mut table = SortableTable::new(&tables::K_PROJECT_COLUMNS);
+
+        let mut hidden = 0;
+        for p in &self.projects {
+            if p.archived() && !include_archived {
+                hidden += 1;
+                continue;
+            }
+
+            let name = if p.archived() {
+                format!("{} (archived)", p.name())
+            } else {
+                p.name().to_string()
+            };
+
+            // Sorted by date of the latest entry
+            let hours = self.rolled_up_hours(p);
+            let latest = p.entries().iter().map(|e| e.work_date()).max();
+            table.add_row(ListRow::new(
+                vec![
+                    self.display.project_name_cell(&name, p.color()),
+                    Cell::new(p.aliases().join(", ")),
+                    Cell::new(p.description().unwrap_or("")),
+                    Cell::new(p.client().unwrap_or("")),
+                    Cell::new(p.entries().len().to_string()),
+                    Cell::new(self.display.hours(hours)),
+                    Cell::new(
+                        p.budget_hours()
+                            .map(|b| self.display.hours(b))
+                            .unwrap_or_default(),
+                    ),
+                    Cell::new(p.deadline().map(|d| d.to_string()).unwrap_or_default()),
+                    Cell::new(p.external_reference().unwrap_or("")),
+                    Cell::new(p.id().to_string()),
+                ],
+                hours,
+                latest,
+                p.name(),
+            ));
+        }
+
+        let listed = Self::print_list_table(table, options)?;
+        if hidden > 0 {
+            outln!(
+                "{} archived project(s) hidden, use --include-archived to show them",
+                hidden
+            );
+        }
+        self.export_listed_rows(&listed, options)
+    }
+
+    /// Internal function to print a sortable table with the list options, invalid columns are invalid input.
+    /// Returns the shown rows for exporting
+    fn print_list_table(
+        table: SortableTable,
+        options: &ListOptions,
+    ) -> Result<ListedRows, ButlerError> {
+        let total = table.len();
+        let shown = options.shown_rows(total);
+        let (table, listed) = table
+            .build(options)
+            .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+        outln!("{}", table);
+        porcelain::set("total_rows", total);
+        porcelain::set(
+            "rows",
+            listed
+                .rows
+                .iter()
+                .map(|row| {
+                    listed
+                        .headers
+                        .iter()
+                        .cloned()
+                        .zip(row.iter().map(|v| serde_json::Value::from(v.as_str())))
+                        .collect::<serde_json::Map<String, serde_json::Value>>()
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        if shown.len() < total {
+            if shown.is_empty() {
+                outln!(
+                    "No rows shown, offset {} is past the {} rows",
+                    options.offset,
+                    total
+                );
+            } else {
+                outln!(
+                    "Showing rows {}-{} of {}, use --offset {} for the next rows",
+                    shown.start + 1,
+                    shown.end,
+                    total,
+                    shown.end
+                );
+            }
+        }
+        Ok(listed)
+    }
+
+    /// Internal function to export the listed rows, if an export file is given in the list options
+    fn export_listed_rows(
+        &self,
+        listed: &ListedRows,
+        options: &ListOptions,
+    ) -> Result<(), ButlerError> {
+        let Some(file_path) = &options.export else {
+            return Ok(());
+        };
+
+        self.report_mngr
+            .export_list(file_path, listed)
+            .map_err(|e| ButlerError::ReportFailure(e.to_string()))?;
+        outln!("Exported {} row(s) to {}", listed.rows.len(), file_path);
+        porcelain::set("export", file_path);
+        Ok(())
+    }
+
+    /// Internal function to get the sortable row of a day, sorted by name on the extra info
+    fn day_list_row(&self, day: &Day) -> ListRow {
+        ListRow::new(
+            self.display.day_row(day),
+            day.hours(),
+            Some(day.date()),
+            day.extra_info(),
+        )
+    }
+
+    /// List a specific project, will show all entries stored for that specific project
+    pub fn list_specific_project(
+        &self,
+        project_name: &str,
+        options: &ListOptions,
+    ) -> Result<(), ButlerError> {
+        if let Some(index) = self.project_position(project_name) {
+            let p = &self.projects[index];
+            let mut table = SortableTable::new(&tables::K_ENTRY_COLUMNS);
+
+            // Sorted by name on the project, to group the sub-projects
+            let entry_row = |e: &Entry, name: &str| {
+                ListRow::new(
+                    self.display.entry_row(e, name),
+                    e.hours(),
+                    Some(e.work_date()),
+                    name,
+                )
+            };
+            for e in p.entries() {
+                table.add_row(entry_row(e, p.name()));
+            }
+
+            for sub in self.sub_projects(p.name()) {
+                for e in sub.entries() {
+                    table.add_row(entry_row(e, sub.name()));
+                }
+            }
+
+            let listed = Self::print_list_table(table, options)?;
+            outln!(
+                "Total hours for {} (including sub-projects): {}",
+                p.name(),
+                self.display.hours(self.rolled_up_hours(p))
+            );
+            return self.export_listed_rows(&listed, options);
+        }
+
+        Err(self.project_not_found(project_name, ""))
+    }
+
+    /// Add a note to a week, the week is created if no days are registered in it yet
+    pub fn add_week_note(
+        &mut self,
+        week_number: u32,
+        year: u32,
+        note: String,
+    ) -> Result<(), ButlerError> {
+        if note.trim().is_empty() {
+            return Err(ButlerError::InvalidInput("Note can't be empty".to_string()));
+        }
+
+        let index = match self.week_position(week_number, year as i32) {
+            Some(index) => index,
+            None => {
+                tracing::info!(
+                    "Week {} in year {} doesn't exist, creating new week",
+                    week_number,
+                    year
+                );
+                self.push_week(Week::new(
+                    week_number,
+                    year as i32,
+                    self.configuration.week_target_hours(),
+                ))
+            }
+        };
+
+        self.weeks[index].add_note(note);
+        Ok(())
+    }
+
+    /// Internal function to print the notes of a week, nothing is printed for a week without notes
+    fn print_week_notes(week: &Week) {
+        for note in week.notes() {
+            outln!("Note (week {}): {}", week.number(), note);
+        }
+    }
+
+    /// List all weeks stored, doesn't show the days stored in the weeks
+    pub fn list_all_weeks(
+        &self,
+        filter: &WeekFilter,
+        options: &ListOptions,
+    ) -> Result<(), ButlerError> {
+        let mut table = SortableTable::new(&tables::K_WEEK_COLUMNS);
+
+        let mut weeks: Vec<&Week> = self
+            .weeks
+            .iter()
+            .filter(|w| filter.matches(w.number(), w.year()))
+            .collect();
+        if weeks.is_empty() && !filter.is_empty() {
+            return Err(ButlerError::NotFound(format!("Weeks for {}", filter)));
+        }
+        weeks.sort_by_key(|w| (w.year(), w.number()));
+
+        // Sorted by date on the monday of the week, and by name on "year-week"
+        for w in weeks {
+            let hours: f32 = w
+                .entries()
+                .iter()
+                .map(|d| self.rounding.round(d.hours()))
+                .sum();
+            table.add_row(ListRow::new(
+                vec![
+                    Cell::new(w.year().to_string()),
+                    Cell::new(w.number().to_string()),
+                    Cell::new(w.entries().len().to_string()),
+                    Cell::new(self.display.hours(hours)),
+                    Cell::new(w.notes().join("; ")),
+                    Cell::new(w.id().to_string()),
+                ],
+                hours,
+                week_scheme::week_scheme().first_day(w.number(), w.year()),
+                &format!("{}-{:02}", w.year(), w.number()),
+            ));
+        }
+
+        let listed = Self::print_list_table(table, options)?;
+        self.export_listed_rows(&listed, options)
+    }
+
+    /// List a specific week, will show all days stored for that specific week
+    pub fn list_specific_week(
+        &self,
+        week_number: u32,
+        filter: &WeekFilter,
+        options: &ListOptions,
+    ) -> Result<(), ButlerError> {
+        // Group weeks by year (for easier reading)
+        let mut weeks_by_year: BTreeMap<i32, &Week> = BTreeMap::new();
+        for w in &self.weeks {
+            if w.number() == week_number && filter.matches(w.number(), w.year()) {
+                weeks_by_year.insert(w.year(), w);
+            }
+        }
+
+        if weeks_by_year.is_empty() {
+            return Err(ButlerError::NotFound(match filter.year {
+                Some(year) => format!("Week with number {} in year {}", week_number, year),
+                None => format!("Week with number {}", week_number),
+            }));
+        }
+
+        // The days of all years are exported together
+        let mut listed = ListedRows::default();
+        for (year, week) in weeks_by_year {
+            outln!("Year: {}", year);
+            let mut table = SortableTable::new(&tables::K_DAY_COLUMNS);
+
+            for d in week.entries() {
+                table.add_row(self.day_list_row(d));
+            }
+
+            listed.append(Self::print_list_table(table, options)?);
+            Self::print_week_notes(week);
+            self.print_project_hours(|date| {
+                week_scheme::week_scheme().week_of(date) == (week_number, year)
+            });
+        }
+
+        self.export_listed_rows(&listed, options)
+    }
+
+    /// Display the status of today, including the hours so far if the day is running
+    pub fn display_today(&self) -> Result<(), ButlerError> {
+        let today = self.clock.today();
+        let day = self
+            .weeks
+            .iter()
+            .flat_map(|w| w.entries())
+            .find(|d| d.date() == today)
+            .ok_or_else(|| ButlerError::NotFound(format!("No day registered for {}", today)))?;
+
+        tables::print_day_in_report_table(day, &self.display);
+
+        if day.closed() {
+            outln!(
+                "Day is closed, {} hours worked",
+                self.display.hours(day.hours())
+            );
+        } else {
+            match day.running_hours(&self.clock.now()) {
+                Some(hours) => outln!(
+                    "Day is open, {} hours worked so far",
+                    self.display.hours(hours)
+                ),
+                None => outln!("Day is open, no starting time set"),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Show the running day of today as a stopwatch line, updated every second until interrupted: the time since the
+    /// start, the pause, the hours worked and the time the daily target is reached. The daily target follows changes of
+    /// the configuration while watching
+    pub fn watch_today(&mut self) -> Result<(), ButlerError> {
+        let today = self.clock.today();
+        let day = self
+            .weeks
+            .iter()
+            .flat_map(|w| w.entries())
+            .find(|d| d.date() == today)
+            .cloned()
+            .ok_or_else(|| ButlerError::NotFound(format!("No day registered for {}", today)))?;
+        if day.closed() {
+            return Err(ButlerError::InvalidInput(format!(
+                "Day {} is closed, {} hours worked, nothing to watch",
+                today,
+                self.display.hours(day.hours())
+            )));
+        }
+        let start = *day.starting_time().ok_or_else(|| {
+            ButlerError::InvalidInput(format!("Day {} has no starting time set", today))
+        })?;
+        let paused = day.hours_paused();
+
+        let stopwatch = |duration: chrono::Duration| {
+            let seconds = duration.num_seconds().max(0);
+            format!(
+                "{}:{:02}:{:02}",
+                seconds / 3600,
+                seconds % 3600 / 60,
+                seconds % 60
+            )
+        };
+        let scheme = week_scheme::week_scheme();
+        loop {
+            self.reload_configuration();
+            let daily_target = self
+                .stored_week_target(|y, week| scheme.week_of(today) == (week, y))
+                / stats::K_WORKING_DAYS_PER_WEEK;
+
+            let now = self.clock.now();
+            let worked = day.running_hours(&now).unwrap_or_default();
+            let remaining = daily_target - worked;
+            let projection = if remaining > 0.0 {
+                let end = now + chrono::Duration::seconds((remaining * 3600.0).round() as i64);
+                format!(
+                    "target {} at {}",
+                    self.display.hours(daily_target),
+                    end.format("%H:%M")
+                )
+            } else {
+                format!("target {} reached", self.display.hours(daily_target))
+            };
+
+            // The line is rewritten in place, the escape clears what is left of a longer line
+            out!(
+                "\rElapsed {}  Pause {}  Worked {}  {}\x1b[K",
+                stopwatch(now - start),
+                stopwatch(chrono::Duration::seconds((paused * 3600.0).round() as i64)),
+                self.display.hours(worked),
+                projection
+            );
+            io::stdout().flush().expect("Failed to flush stdout");
+            std::thread::sleep(std::time::Duration::from_secs(1));
+        }
+    }
+
+    /// Print the last days worked before today as a bullet list for a standup, the hours and descriptions of the
+    /// entries grouped by project. Only dates with a day or entries are included, so a Monday shows the Friday
+    pub fn display_standup(&self, days: usize) -> Result<(), ButlerError> {
+        let today = self.clock.today();
+
+        // Hours and descriptions per project and date, the allocated day hours are added to the project hours
+        let mut worked: BTreeMap<NaiveDate, BTreeMap<&str, (f32, Vec<&str>)>> = BTreeMap::new();
+        for p in &self.projects {
+            for e in p.entries().iter().filter(|e| e.work_date() < today) {
+                let (hours, descriptions) = worked
+                    .entry(e.work_date())
+                    .or_default()
+                    .entry(p.name())
+                    .or_default();
+                *hours += self.rounding.round(e.hours());
+                if !e.description().is_empty() && !descriptions.contains(&e.description()) {
+                    descriptions.push(e.description());
+                }
+            }
+        }
+        let mut day_by_date: BTreeMap<NaiveDate, &Day> = BTreeMap::new();
+        for d in self
+            .weeks
+            .iter()
+            .flat_map(|w| w.entries())
+            .filter(|d| d.date() < today)
+        {
+            day_by_date.insert(d.date(), d);
+            let projects = worked.entry(d.date()).or_default();
+            for a in d.allocations() {
+                projects.entry(a.project()).or_default().0 += self.rounding.round(a.hours());
+            }
+        }
+
+        let dates: Vec<NaiveDate> = worked.keys().rev().take(days).rev().copied().collect();
+        if dates.is_empty() {
+            return Err(ButlerError::NotFound(format!(
+                "No days or entries before {}",
+                today
+            )));
+        }
+
+        let hours_text = |hours: f32| match self.display.duration() {
+            DurationFormat::Decimal => format!("{:.1}h", hours),
+            DurationFormat::Clock => self.display.hours(hours),
+        };
+        let mut lines = Vec::new();
+        for date in &dates {
+            let mut heading = format!("{} {}", i18n::weekday_short(date.weekday()), date);
+            if let Some(day) = day_by_date.get(date) {
+                heading.push_str(&format!(
+                    ": {}",
+                    hours_text(self.rounding.round(day.hours()))
+                ));
+                if !day.extra_info().is_empty() {
+                    heading.push_str(&format!(" ({})", day.extra_info()));
+                }
+            }
+            lines.push(heading);
+
+            for (project, (hours, descriptions)) in &worked[date] {
+                let mut line = format!("- {} {}", project, hours_text(*hours));
+                if !descriptions.is_empty() {
+                    line.push_str(&format!(" — {}", descriptions.join(", ")));
+                }
+                lines.push(line);
+            }
+        }
+
+        let text = lines.join("\n");
+        porcelain::set("standup", &text);
+        porcelain::set("dates", &dates);
+        outln!("{}", text);
+        Ok(())
+    }
+
+    /// Print a one line summary of the current week: days registered, hours so far and the remaining hours of the
+    /// target, shown after adding a day or an entry when enabled in the configuration
+    pub fn display_week_glance(&self) {
+        let (week_number, year) = week_scheme::week_scheme().week_of(self.clock.today());
+        let Some(index) = self.week_position(week_number, year) else {
+            outln!("Week {}, {}: no days registered", week_number, year);
+            return;
+        };
+
+        let w = &self.weeks[index];
+        let status = WeeklyTargetStatus::new(w, &w.target_hours());
+        outln!(
+            "Week {}, {}: {} days, {} of {} hours ({}%), {} remaining",
+            week_number,
+            year,
+            w.entries().len(),
+            self.display.hours(*status.status_hours()),
+            self.display.hours(*status.target_hours()),
+            status.percentage(),
+            self.display.hours(status.remaining_hours().max(0.0))
+        );
+    }
+
+    /// Display the days and the target status of the current week
+    pub fn display_current_week(&self) -> Result<(), ButlerError> {
+        let (week_number, year) = week_scheme::week_scheme().week_of(self.clock.today());
+
+        let week = self
+            .week_position(week_number, year)
+            .map(|index| &self.weeks[index])
+            .ok_or_else(|| {
+                ButlerError::NotFound(format!(
+                    "Week with number {} and year {}",
+                    week_number, year
+                ))
+            })?;
+
+        outln!("Week: {}, Year: {}", week_number, year);
+        let mut table = tables::get_table_day();
+        for d in week.entries() {
+            table.add_row(self.display.day_row(d));
+        }
+        outln!("{}", table);
+        Self::print_week_notes(week);
+
+        self.display_week_target_status(week_number, year as u32)
+    }
+
+    pub fn list_specific_month(
+        &self,
+        month_number: u32,
+        options: &ListOptions,
+    ) -> Result<(), ButlerError> {
+        if !(1..=12).contains(&month_number) {
+            return Err(ButlerError::InvalidInput(format!(
+                "Invalid month number: {}",
+                month_number
+            )));
+        }
+
+        let mut days_by_year: BTreeMap<i32, Vec<&Day>> = BTreeMap::new();
+        for d in self.days_in_month(month_number) {
+            days_by_year.entry(d.year()).or_default().push(d);
+        }
+
+        if days_by_year.is_empty() {
+            return Err(ButlerError::NotFound(format!(
+                "No days for month: {}",
+                month_number
+            )));
+        }
+
+        // The days of all years are exported together
+        let mut listed = ListedRows::default();
+        for (year, days) in days_by_year {
+            outln!("Year: {}", year);
+            let mut table = SortableTable::new(&tables::K_DAY_COLUMNS);
+
+            for d in &days {
+                table.add_row(self.day_list_row(d));
+            }
+
+            listed.append(Self::print_list_table(table, options)?);
+            for week in self.weeks.iter().filter(|w| {
+                days.iter()
+                    .any(|d| d.week() == w.number() && d.week_year() == w.year())
+            }) {
+                Self::print_week_notes(week);
+            }
+            self.print_project_hours(|date| date.month() == month_number && date.year() == year);
+        }
+
+        self.export_listed_rows(&listed, options)
+    }
+
+    /// Print the statistics of all stored days: averages, streak, weekdays and the month-over-month trend
+    pub fn stats(&self) -> Result<(), ButlerError> {
+        let statistics = match stats::day_statistics(self.weeks.iter().flat_map(|w| w.entries())) {
+            Some(statistics) => statistics,
+            None => {
+                return Err(ButlerError::NotFound(
+                    "No days with registered hours".to_string(),
+                ))
+            }
+        };
+
+        let format_time = |t: Option<chrono::NaiveTime>| {
+            t.map(|t| t.format("%H:%M").to_string())
+                .unwrap_or_else(|| "N/A".to_string())
+        };
+        let format_weekday = |w: Option<(chrono::Weekday, f32)>| {
+            w.map(|(day, hours)| format!("{} ({}h on average)", day, self.display.hours(hours)))
+                .unwrap_or_else(|| "N/A".to_string())
+        };
+
+        let mut table = tables::get_table_statistics();
+        table.add_row(vec![
+            "Worked days".to_string(),
+            statistics.worked_days.to_string(),
+        ]);
+        table.add_row(vec![
+            "Average start time".to_string(),
+            format_time(statistics.average_start),
+        ]);
+        table.add_row(vec![
+            "Average end time".to_string(),
+            format_time(statistics.average_end),
+        ]);
+        table.add_row(vec![
+            "Average daily hours".to_string(),
+            self.display.hours(statistics.average_hours),
+        ]);
+        table.add_row(vec![
+            "Longest working streak".to_string(),
+            statistics
+                .longest_streak
+                .map(|(length, first, last)| format!("{} days ({} to {})", length, first, last))
+                .unwrap_or_else(|| "N/A".to_string()),
+        ]);
+        table.add_row(vec![
+            "Most worked weekday".to_string(),
+            format_weekday(statistics.most_worked_weekday),
+        ]);
+        table.add_row(vec![
+            "Least worked weekday".to_string(),
+            format_weekday(statistics.least_worked_weekday),
+        ]);
+        outln!("{}", table);
+
+        // Only the latest months are shown in the trend
+        let mut trend = tables::get_table_month_trend();
+        let skip = statistics
+            .months
+            .len()
+            .saturating_sub(stats::K_TREND_MONTHS);
+        for m in statistics.months.iter().skip(skip) {
+            let change = match m.change {
+                Some(change) => format!("{:+.2}", change),
+                None => "N/A".to_string(),
+            };
+            trend.add_row(vec![
+                format!("{}-{:02}", m.year, m.month),
+                self.display.hours(m.hours),
+                change,
+            ]);
+        }
+        outln!("{}", trend);
+
+        Ok(())
+    }
+
+    /// Print the estimated and actual hours per project, only the entries with an estimate are included
+    pub fn stats_estimates(&self, project_name: Option<&str>) -> Result<(), ButlerError> {
+        let projects: Vec<&Project> = match project_name {
+            Some(name) => {
+                let index = self
+                    .project_position(name)
+                    .ok_or_else(|| ButlerError::NotFound(format!("Project with name {}", name)))?;
+                let p = &self.projects[index];
+                std::iter::once(p)
+                    .chain(self.sub_projects(p.name()))
+                    .collect()
+            }
+            None => self.projects.iter().collect(),
+        };
+
+        let mut table = tables::get_table_estimates();
+        let (mut total_estimate, mut total_actual, mut total_entries) = (0.0, 0.0, 0);
+        for p in projects {
+            let estimated: Vec<(f32, f32)> = p
+                .entries()
+                .iter()
+                .filter_map(|e| e.estimate().map(|estimate| (estimate, e.hours())))
+                .collect();
+            if estimated.is_empty() {
+                continue;
+            }
+
+            let estimate: f32 = estimated.iter().map(|(estimate, _)| estimate).sum();
+            let actual: f32 = estimated.iter().map(|(_, hours)| hours).sum();
+            table.add_row(self.estimate_row(p.name(), estimated.len(), estimate, actual));
+            total_estimate += estimate;
+            total_actual += actual;
+            total_entries += estimated.len();
+        }
+
+        if total_entries == 0 {
+            return Err(ButlerError::NotFound(
+                "No entries with an estimate, add one with 'add entry --estimate'".to_string(),
+            ));
+        }
+
+        table.add_row(self.estimate_row("Total", total_entries, total_estimate, total_actual));
+        outln!("{}", table);
+        Ok(())
+    }
+
+    /// Internal function to get a row of the estimates table, the actual hours in percent of the estimate
+    fn estimate_row(&self, name: &str, entries: usize, estimate: f32, actual: f32) -> Vec<Cell> {
+        vec![
+            Cell::new(name),
+            Cell::new(entries.to_string()),
+            Cell::new(self.display.hours(estimate)),
+            Cell::new(self.display.hours(actual)),
+            Cell::new(format!(
+                "{}{}",
+                if actual < estimate { "-" } else { "+" },
+                self.display.hours((actual - estimate).abs())
+            )),
+            Cell::new(format!("{:.0}%", actual / estimate * 100.0)),
+        ]
+    }
+
+    /// Print bar charts of the hours per day and per week in a month, with the targets marked
+    pub fn stats_chart(&self, month_number: u32, year: u32) -> Result<(), ButlerError> {
+        if !(1..=12).contains(&month_number) {
+            return Err(ButlerError::InvalidInput(format!(
+                "Invalid month number: {}",
+                month_number
+            )));
+        }
+
+        let days = self.days_in_month_for_year(month_number, year);
+        if days.clone().next().is_none() {
+            return Err(ButlerError::NotFound(format!(
+                "No days for month: {} and year: {}",
+                month_number, year
+            )));
+        }
+
+        let mut day_hours: BTreeMap<NaiveDate, f32> = BTreeMap::new();
+        let mut week_hours: BTreeMap<(i32, u32), f32> = BTreeMap::new();
+        for d in days {
+            *day_hours.entry(d.date()).or_default() += d.hours();
+            *week_hours.entry((d.week_year(), d.week())).or_default() += d.hours();
+        }
+
+        let day_bars: Vec<(String, f32)> = day_hours
+            .iter()
+            .map(|(date, hours)| (date.format("%a %d").to_string(), *hours))
+            .collect();
+        let week_bars: Vec<(String, f32)> = week_hours
+            .iter()
+            .map(|((_, week), hours)| (format!("Week {}", week), *hours))
+            .collect();
+
+        let week_target =
+            self.stored_week_target(|year, week| week_hours.contains_key(&(year, week)));
+
+        outln!(
+            "{}",
+            stats::render_bar_chart(
+                &format!("Hours per day, month {} {}", month_number, year),
+                &day_bars,
+                week_target / stats::K_WORKING_DAYS_PER_WEEK,
+            )
+        );
+        outln!(
+            "{}",
+            stats::render_bar_chart(
+                &format!("Hours per week, month {} {}", month_number, year),
+                &week_bars,
+                week_target,
+            )
+        );
+
+        Ok(())
+    }
+
+    /// Internal function to get the week target of a period. The stored target of the first matching week is used,
+    /// the weeks normally share the same target. Defaults to the configured target
+    fn stored_week_target(&self, in_period: impl Fn(i32, u32) -> bool) -> f32 {
+        self.weeks
+            .iter()
+            .find(|w| in_period(w.year(), w.number()))
+            .map(|w| w.target_hours())
+            .filter(|t| *t > 0.0)
+            .unwrap_or_else(|| self.configuration.week_target_hours())
+    }
+
+    /// Print a side-by-side comparison of two periods: total hours, target attainment and the hours per project, with
+    /// the difference of the right period compared to the left
+    pub fn compare_periods(&self, left: &Period, right: &Period) {
+        tracing::debug!("Comparing {} with {}", left, right);
+        let left_summary = self.period_summary(left);
+        let right_summary = self.period_summary(right);
+
+        let signed_hours = |hours: f32| {
+            let text = self.display.hours(hours);
+            if hours > 0.0 && text != self.display.hours(0.0) {
+                format!("+{}", text)
+            } else {
+                text
+            }
+        };
+        let attainment = |summary: &PeriodSummary| {
+            (summary.target_hours > 0.0).then(|| summary.day_hours / summary.target_hours * 100.0)
+        };
+
+        let mut table =
+            tables::get_table_compare(&self.period_label(left), &self.period_label(right));
+        for (metric, l, r) in [
+            ("Day hours", left_summary.day_hours, right_summary.day_hours),
+            (
+                "Entry hours",
+                left_summary.entry_hours,
+                right_summary.entry_hours,
+            ),
+            (
+                "Target hours",
+                left_summary.target_hours,
+                right_summary.target_hours,
+            ),
+        ] {
+            table.add_row(vec![
+                Cell::new(i18n::tr(metric)),
+                Cell::new(self.display.hours(l)),
+                Cell::new(self.display.hours(r)),
+                Cell::new(signed_hours(r - l)),
+            ]);
+        }
+
+        let (l, r) = (attainment(&left_summary), attainment(&right_summary));
+        let percent = |p: Option<f32>| p.map_or("-".to_string(), |p| format!("{:.0}%", p));
+        let delta = match (l, r) {
+            (Some(l), Some(r)) if (r - l).round() != 0.0 => format!("{:+.0}", r - l),
+            (Some(_), Some(_)) => "0".to_string(),
+            _ => "-".to_string(),
+        };
+        table.add_row(vec![
+            Cell::new(i18n::tr("Target attainment")),
+            Cell::new(percent(l)),
+            Cell::new(percent(r)),
+            Cell::new(delta),
+        ]);
+
+        let mut projects: Vec<&String> = left_summary
+            .project_hours
+            .keys()
+            .chain(right_summary.project_hours.keys())
+            .collect();
+        projects.sort();
+        projects.dedup();
+        for project in projects {
+            let l = left_summary
+                .project_hours
+                .get(project)
+                .copied()
+                .unwrap_or(0.0);
+            let r = right_summary
+                .project_hours
+                .get(project)
+                .copied()
+                .unwrap_or(0.0);
+            table.add_row(vec![
+                Cell::new(project),
+                Cell::new(self.display.hours(l)),
+                Cell::new(self.display.hours(r)),
+                Cell::new(signed_hours(r - l)),
+            ]);
+        }
+
+        outln!("{}", table);
+    }
+
+    /// Internal function to get the translated label of a period, e.g. "Week 12 2025"
+    fn period_label(&self, period: &Period) -> String {
+        match period {
+            Period::Week { number, year } => format!("{} {} {}", i18n::tr("Week"), number, year),
+            Period::Month { number, year } => format!("{} {} {}", i18n::tr("Month"), number, year),
+        }
+    }
+
+    /// Internal function to sum the day hours, entry hours per project and the target of a period. The week target is
+    /// the stored target of the week, the month target is calculated as for the month status
+    fn period_summary(&self, period: &Period) -> PeriodSummary {
+        let day_hours = self
+            .weeks
+            .iter()
+            .flat_map(|w| w.entries())
+            .filter(|d| period.contains(d.date()))
+            .fold(0.0, |sum, d| sum + self.rounding.round(d.hours()));
+
+        let mut project_hours = BTreeMap::new();
+        for p in &self.projects {
+            let hours = p
+                .entries()
+                .iter()
+                .filter(|e| period.contains(e.work_date()))
+                .fold(0.0, |sum, e| sum + self.rounding.round(e.hours()));
+            if hours > 0.0 {
+                project_hours.insert(p.name().to_string(), hours);
+            }
+        }
+        let entry_hours = project_hours.values().fold(0.0, |sum, h| sum + h);
+
+        let target_hours = match period {
+            Period::Week { number, year } => self
+                .week_position(*number, *year as i32)
+                .map(|i| self.weeks[i].target_hours())
+                .unwrap_or_else(|| self.configuration.week_target_hours()),
+            Period::Month { .. } if self.configuration.weekly_target_for_month() => self
+                .weeks
+                .iter()
+                .filter(|w| w.entries().iter().any(|d| period.contains(d.date())))
+                .map(|w| w.target_hours())
+                .sum(),
+            Period::Month { .. } => self.configuration.month_target_hours(),
+        };
+
+        PeriodSummary {
+            day_hours,
+            entry_hours,
+            target_hours,
+            project_hours,
+        }
+    }
+
+    /// Print a calendar grid of a month, a row per week with the hours worked each day. The days are colored
+    /// compared to the daily target, so gaps and overtime are visible at a glance
+    pub fn display_calendar(&self, month_number: u32, year: u32) -> Result<(), ButlerError> {
+        let first = NaiveDate::from_ymd_opt(year as i32, month_number, 1).ok_or_else(|| {
+            ButlerError::InvalidInput(format!(
+                "Invalid month number: {} for year: {}",
+                month_number, year
+            ))
+        })?;
+        let last = first
+            .checked_add_months(chrono::Months::new(1))
+            .and_then(|d| d.pred_opt())
+            .unwrap_or(first);
+
+        let mut day_hours: BTreeMap<NaiveDate, f32> = BTreeMap::new();
+        for d in self.days_in_month_for_year(month_number, year) {
+            *day_hours.entry(d.date()).or_default() += d.hours();
+        }
+
+        let scheme = week_scheme::week_scheme();
+        let week_target = self.stored_week_target(|y, week| {
+            day_hours.keys().any(|d| scheme.week_of(*d) == (week, y))
+        });
+        let daily_target = week_target / stats::K_WORKING_DAYS_PER_WEEK;
+        let today = self.clock.today();
+
+        let mut table = tables::get_table_calendar(scheme.start());
+        let mut date = scheme.week_start(first);
+        while date <= last {
+            let mut row = vec![Cell::new(scheme.week_of(date).0)];
+            for _ in 0..7 {
+                if date.month() != month_number {
+                    row.push(Cell::new(""));
+                } else {
+                    let hours = day_hours.get(&date).copied();
+                    let text = match hours {
+                        Some(h) => format!("{}\n{}h", date.day(), self.display.hours(h)),
+                        None => date.day().to_string(),
+                    };
+                    let is_weekday =
+                        !matches!(date.weekday(), chrono::Weekday::Sat | chrono::Weekday::Sun);
+                    let is_gap = is_weekday && date < today;
+                    row.push(
+                        self.display
+                            .calendar_cell(text, hours, daily_target, is_gap),
+                    );
+                }
+                date += chrono::Duration::days(1);
+            }
+            table.add_row(row);
+        }
+
+        outln!("{}", first.format("%B %Y"));
+        outln!("{}", table);
+        outln!(
+            "{} hours worked over {} days, daily target {} hours",
+            self.display.hours(day_hours.values().sum::<f32>()),
+            day_hours.len(),
+            self.display.hours(daily_target)
+        );
+
+        Ok(())
+    }
+
+    /// Add new entry to project
+    pub fn add_entry(
+        &mut self,
+        project_name: &str,
+        entry: Entry,
+        force: bool,
+    ) -> Result<(), ButlerError> {
+        validation::validate_entry(&entry, self.configuration.validation())
+            .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+        self.check_unlocked(entry.work_date(), "add entry")?;
+
+        // search for the project
+        if let Some(index) = self.project_position(project_name) {
+            let p = &mut self.projects[index];
+            if p.archived() && !force {
+                return Err(ButlerError::InvalidInput(format!(
+                    "Project {} is archived, use --force to add the entry anyway",
+                    p.name()
+                )));
+            }
+
+            // Get the entry ID before ownership transfer
+            let entry_clone = entry.clone();
+            p.add_entry(entry);
+
+            // Print new entry as confirmation to user
+            tables::print_entry_in_report_table(&entry_clone, p.name(), &self.display);
+            porcelain::set("project", p.name());
+            porcelain::set("entry", &entry_clone);
+            return Ok(());
+        }
+
+        Err(self.project_not_found(project_name, ", unable to add entry"))
+    }
+
+    /// Add entries in bulk from CSV data, invalid rows are skipped and reported
+    pub fn add_entries<R: io::Read>(&mut self, reader: R, force: bool) -> Result<(), ButlerError> {
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .flexible(true)
+            .from_reader(reader);
+
+        let mut added = 0;
+        let mut skipped = 0;
+
+        for (i, row) in csv_reader.deserialize::<EntryImportRow>().enumerate() {
+            // Header is line 1
+            let line = i + 2;
+            let result = match row {
+                Ok(row) => self.bulk_entry_from_row(&row, force).map(|(index, entry)| {
+                    self.projects[index].add_entry(entry);
+                }),
+                Err(e) => Err(ButlerError::InvalidInput(e.to_string())),
+            };
+
+            match result {
+                Ok(_) => added += 1,
+                Err(e) => {
+                    tracing::warn!("Skipping line {}: {}", line, e);
+                    skipped += 1;
+                }
+            }
+        }
+
+        outln!("Entries added: {}, skipped: {}", added, skipped);
+        porcelain::set("added", added);
+        porcelain::set("skipped", skipped);
+
+        if added == 0 && skipped > 0 {
+            return Err(ButlerError::InvalidInput(
+                "No valid entries found, nothing added".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Internal function to validate a bulk entry row, returns the project index and the entry to add
+    fn bulk_entry_from_row(
+        &self,
+        row: &EntryImportRow,
+        force: bool,
+    ) -> Result<(usize, Entry), ButlerError> {
+        let index = self
+            .project_position(&row.project)
+            .ok_or_else(|| ButlerError::NotFound(format!("Project with name {}", row.project)))?;
+
+        if self.projects[index].archived() && !force {
+            return Err(ButlerError::InvalidInput(format!(
+                "Project {} is archived",
+                row.project
+            )));
+        }
+
+        let hours = row.hours.parse::<f32>().map_err(|_| {
+            ButlerError::InvalidInput(format!("Invalid hours provided: {}", row.hours))
+        })?;
+        validation::validate_entry_hours(hours, self.configuration.validation())
+            .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+
+        let mut entry = Entry::new_with_clock(hours, row.description.clone(), self.clock());
+
+        if let Some(date) = row.date.as_deref().filter(|d| !d.is_empty()) {
+            let parsed = NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+                ButlerError::InvalidInput(format!(
+                    "Invalid date provided: {}, expected format YYYY-MM-DD",
+                    date
+                ))
+            })?;
+            entry.set_worked_on(parsed);
+        }
+        self.check_unlocked(entry.work_date(), "add entry")?;
+
+        Ok((index, entry))
+    }
+
+    /// Add a new entry template to the configuration
+    pub fn add_entry_template(
+        &mut self,
+        name: &str,
+        project_name: &str,
+        hours: f32,
+        description: Option<String>,
+    ) -> Result<(), ButlerError> {
+        if hours <= 0.0 {
+            return Err(ButlerError::InvalidInput(format!(
+                "Invalid hours provided: {}. You can't report 0 or negative hours on a project.",
+                hours
+            )));
+        }
+
+        if self.project_position(project_name).is_none() {
+            return Err(ButlerError::NotFound(format!(
+                "Project with name {}, unable to add template",
+                project_name
+            )));
+        }
+
+        let template = EntryTemplate {
+            name: name.to_string(),
+            project: project_name.to_string(),
+            hours,
+            description,
+        };
+
+        if !self.configuration.add_template(template) {
+            return Err(ButlerError::Duplicate(format!(
+                "Template with name {}",
+                name
+            )));
+        }
+
+        tracing::info!("Template {} added", name);
+        Ok(())
+    }
+
+    /// Remove an entry template from the configuration
+    pub fn remove_entry_template(&mut self, name: &str) -> Result<(), ButlerError> {
+        if self.configuration.remove_template(name) {
+            tracing::info!("Template {} removed", name);
+            Ok(())
+        } else {
+            Err(ButlerError::NotFound(format!(
+                "Template with name {}",
+                name
+            )))
+        }
+    }
+
+    /// List all entry templates
+    pub fn list_entry_templates(&self) {
+        if self.configuration.templates().is_empty() {
+            tracing::warn!("No templates stored, unable to list templates");
+            return;
+        }
+
+        let mut table = Table::new();
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec![
+            Cell::new("Name"),
+            Cell::new("Project"),
+            Cell::new("Hours"),
+            Cell::new("Description"),
+        ]);
+
+        for t in self.configuration.templates() {
+            table.add_row(vec![
+                Cell::new(&t.name),
+                Cell::new(&t.project),
+                Cell::new(self.display.hours(t.hours)),
+                Cell::new(t.description.as_deref().unwrap_or("")),
+            ]);
+        }
+
+        outln!("{}", table);
+    }
+
+    /// Add entries from a template, one entry for each of the given dates
+    pub fn add_from_template(
+        &mut self,
+        name: &str,
+        dates: &[NaiveDate],
+        force: bool,
+    ) -> Result<(), ButlerError> {
+        let template = self
+            .configuration
+            .templates()
+            .iter()
+            .find(|t| t.name == name)
+            .cloned()
+            .ok_or_else(|| ButlerError::NotFound(format!("Template with name {}", name)))?;
+
+        if dates.is_empty() {
+            return Err(ButlerError::InvalidInput(
+                "No dates matching the repeat mode in the given range".to_string(),
+            ));
+        }
+
+        validation::validate_entry_hours(template.hours, self.configuration.validation())
+            .map_err(|e| ButlerError::InvalidInput(format!("Template {}: {}", template.name, e)))?;
+        for date in dates {
+            self.check_unlocked(*date, "add entries from template")?;
+        }
+
+        let display = self.display.clone();
+        let index = self.project_position(&template.project).ok_or_else(|| {
+            ButlerError::NotFound(format!(
+                "Project with name {}, used by template {}",
+                template.project, name
+            ))
+        })?;
+        let p = &mut self.projects[index];
+
+        if p.archived() && !force {
+            return Err(ButlerError::InvalidInput(format!(
+                "Project {} is archived, use --force to add the entries anyway",
+                p.name()
+            )));
+        }
+
+        let mut table = tables::get_table_entry();
+        for date in dates {
+            let mut entry = Entry::new_with_clock(
+                template.hours,
+                template.description.clone(),
+                self.clock.as_ref(),
+            );
+            entry.set_worked_on(*date);
+            table.add_row(display.entry_row(&entry, p.name()));
+            p.add_entry(entry);
+        }
+
+        outln!("{}", table);
+        tracing::info!("{} entries added from template {}", dates.len(), name);
+        Ok(())
+    }
+
+    /// Add the missing occurrences of the configured recurring entries, up to and including the given date.
+    /// Already added occurrences are detected by the entry origin, so re-runs never duplicate entries
+    pub fn sync_recurring(&mut self, until: NaiveDate) -> Result<(), ButlerError> {
+        let rules = self.configuration.recurring_entries().clone();
+        if rules.is_empty() {
+            tracing::warn!("No recurring entries in the configuration, nothing to sync");
+            return Ok(());
+        }
+
+        let mut table = Table::new();
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec![
+            Cell::new("Recurring entry"),
+            Cell::new("Project"),
+            Cell::new("Added entries"),
+        ]);
+
+        for rule in &rules {
+            if let Err(e) =
+                validation::validate_entry_hours(rule.hours, self.configuration.validation())
+            {
+                tracing::warn!("Skipping recurring entry {}: {}", rule.name, e);
+                continue;
+            }
+
+            let mode = match rule.repeat.parse::<RepeatMode>() {
+                Ok(mode) => mode,
+                Err(e) => {
+                    tracing::warn!("Skipping recurring entry {}: {}", rule.name, e);
+                    continue;
+                }
+            };
+
+            let start = match NaiveDate::parse_from_str(&rule.start_date, "%Y-%m-%d") {
+                Ok(start) => start,
+                Err(_) => {
+                    tracing::warn!(
+                        "Skipping recurring entry {}: invalid start-date {}, expected format YYYY-MM-DD",
+                        rule.name,
+                        rule.start_date
+                    );
+                    continue;
+                }
+            };
+
+            let p = match self
+                .project_position(&rule.project)
+                .map(|index| &mut self.projects[index])
+            {
+                Some(p) if p.archived() => {
+                    tracing::warn!(
+                        "Skipping recurring entry {}: project {} is archived",
+                        rule.name,
+                        p.name()
+                    );
+                    continue;
+                }
+                Some(p) => p,
+                None => {
+                    tracing::warn!(
+                        "Skipping recurring entry {}: project {} not found",
+                        rule.name,
+                        rule.project
+                    );
+                    continue;
+                }
+            };
+
+            let mut added = 0;
+            for date in expand_dates(&mode, start, until) {
+                if !self.force_unlock && self.period_metadata.lock_for(date).is_some() {
+                    tracing::debug!("Skipping {} of recurring entry {}, locked", date, rule.name);
+                    continue;
+                }
+
+                let exists = p
+                    .entries()
+                    .iter()
+                    .any(|e| e.origin() == Some(rule.name.as_str()) && e.work_date() == date);
+
+                if !exists {
+                    let mut entry = Entry::new_with_clock(
+                        rule.hours,
+                        rule.description.clone(),
+                        self.clock.as_ref(),
+                    );
+                    entry.set_worked_on(date);
+                    entry.set_origin(rule.name.clone());
+                    p.add_entry(entry);
+                    added += 1;
+                }
+            }
+
+            tracing::debug!("{} entries added for recurring entry {}", added, rule.name);
+            table.add_row(vec![
+                Cell::new(&rule.name),
+                Cell::new(p.name()),
+                Cell::new(added.to_string()),
+            ]);
+        }
+
+        outln!("{}", table);
+        Ok(())
+    }
+
+    /// Add new day to a week
+    pub fn add_day(&mut self, mut day: Day, strategy: MergeStrategy) -> Result<(), ButlerError> {
+        // Ending a shift after midnight closes the day started on the previous date
+        if day.ending_time_set() && !day.start_time_set() && !self.day_exists(day.date()) {
+            if let Some(previous) = day.date().pred_opt() {
+                let started_previous = self
+                    .weeks
+                    .iter()
+                    .flat_map(|w| w.entries())
+                    .any(|d| d.date() == previous && d.start_time_set() && !d.closed());
+                if started_previous {
+                    tracing::info!(
+                        "Closing the day started on {}, ended after midnight",
+                        previous
+                    );
+                    day.set_date(previous);
+                }
+            }
+        }
+
+        let skip_default_break = day.skip_default_break();
+        let was_closed = self
+            .weeks
+            .iter()
+            .flat_map(|w| w.entries())
+            .any(|d| d.date() == day.date() && d.closed());
+        let mut stored_day = self.insert_day(day, strategy)?;
+
+        // The default break is added when the day is closed, also when closed by merging into the started day
+        let break_minutes = self.configuration.days().default_break_minutes;
+        if let Some(d) = self
+            .weeks
+            .iter_mut()
+            .flat_map(|w| w.entries_mut().iter_mut())
+            .find(|d| d.id() == stored_day.id())
+        {
+            if skip_default_break {
+                d.set_skip_default_break(true);
+            }
+            if !was_closed && d.add_default_break(break_minutes) {
+                tracing::info!(
+                    "Default break of {} minutes added to the paused hours",
+                    break_minutes
+                );
+            }
+            stored_day = d.clone();
+        }
+
+        // Print the new added day as confirmation to user, quite nice verification
+        tables::print_day_in_report_table(&stored_day, &self.display);
+        self.warn_short_day(&stored_day);
+        porcelain::set("day", &stored_day);
+        Ok(())
+    }
+
+    /// Import days and entries with the importer of the source, the days are added or merged into the stored days with
+    /// the strategy and the entries are added to their projects. Invalid rows are skipped and reported. With dry run the
+    /// imported data is only previewed and nothing is changed
+    pub fn import_from(
+        &mut self,
+        source: &str,
+        input: &str,
+        strategy: MergeStrategy,
+        dry_run: bool,
+    ) -> Result<(), ButlerError> {
+        let importer = self.importers.get(source).ok_or_else(|| {
+            ButlerError::InvalidInput(format!(
+                "Unknown import source: {}, valid sources are: {}",
+                source,
+                self.importers
+                    .importers()
+                    .map(|i| i.name().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+        })?;
+        tracing::debug!("Importing {} with the {} importer", input, importer.name());
+        let data = importer
+            .import(input)
+            .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+
+        let mut skipped = data.rejected.len();
+        for rejected in &data.rejected {
+            tracing::warn!("Skipping {}", rejected);
+        }
+
+        let mut table = tables::get_table_day();
+        let mut added = 0;
+        let mut merged = 0;
+        for (i, row) in data.days.iter().enumerate() {
+            let result = self.day_from_import_row(row).and_then(|day| {
+                let exists = self.day_exists(day.date());
+                if dry_run {
+                    Ok((exists, day))
+                } else {
+                    self.insert_day(day, strategy).map(|day| (exists, day))
+                }
+            });
+
+            match result {
+                Ok((exists, day)) => {
+                    if exists {
+                        merged += 1;
+                    } else {
+                        added += 1;
+                    }
+                    table.add_row(self.display.day_row(&day));
+                }
+                Err(e) => {
+                    tracing::warn!("Skipping day {} ({}): {}", i + 1, row.date, e);
+                    skipped += 1;
+                }
+            }
+        }
+        if added + merged > 0 {
+            outln!("{}", table);
+        }
+
+        let mut entry_table = tables::get_table_entry();
+        let mut entries_added = 0;
+        for (i, row) in data.entries.iter().enumerate() {
+            match self.bulk_entry_from_row(row, false) {
+                Ok((index, entry)) => {
+                    entry_table
+                        .add_row(self.display.entry_row(&entry, self.projects[index].name()));
+                    if !dry_run {
+                        self.projects[index].add_entry(entry);
+                    }
+                    entries_added += 1;
+                }
+                Err(e) => {
+                    tracing::warn!("Skipping entry {} ({}): {}", i + 1, row.project, e);
+                    skipped += 1;
+                }
+            }
+        }
+        if entries_added > 0 {
+            outln!("{}", entry_table);
+        }
+
+        if dry_run {
+            outln!(
+                "Dry run, nothing imported. Days to add: {}, to merge: {}, entries to add: {}, skipped: {}",
+                added,
+                merged,
+                entries_added,
+                skipped
+            );
+        } else {
+            outln!(
+                "Days added: {}, merged: {}, entries added: {}, skipped: {}",
+                added,
+                merged,
+                entries_added,
+                skipped
+            );
+        }
+        porcelain::set("dry_run", dry_run);
+        porcelain::set("added", added);
+        porcelain::set("merged", merged);
+        porcelain::set("entries_added", entries_added);
+        porcelain::set("skipped", skipped);
+
+        if added + merged + entries_added == 0 && skipped > 0 {
+            return Err(ButlerError::InvalidInput(
+                "No valid days or entries found, nothing imported".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// List the import sources, the built-in importers and the importers of the configuration
+    pub fn list_import_sources(&self) {
+        let mut table = Table::new();
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec![Cell::new("Source"), Cell::new("Description")]);
+
+        for importer in self.importers.importers() {
+            table.add_row(vec![
+                Cell::new(importer.name()),
+                Cell::new(importer.description()),
+            ]);
+        }
+
+        outln!("{}", table);
+    }
+
+    /// Install the login and logout hooks of the kind, or print them, with the behavior of the configuration. The hooks
+    /// run the current executable. With uninstall, the installed hooks of the kind are removed
+    pub fn install_hooks(
+        &self,
+        kind: HookKind,
+        print: bool,
+        uninstall: bool,
+    ) -> Result<(), ButlerError> {
+        if uninstall {
+            let removed =
+                hooks::uninstall(kind).map_err(|e| ButlerError::StorageFailure(e.to_string()))?;
+            if removed.is_empty() {
+                outln!("No installed hooks found");
+            }
+            for path in &removed {
+                outln!("Removed the hooks from {}", path.display());
+            }
+            porcelain::set("removed", removed.len());
+            return Ok(());
+        }
+
+        let executable = std::env::current_exe().map_err(|e| {
+            ButlerError::InvalidInput(format!("Failed to find the executable: {}", e))
+        })?;
+        let files = hooks::hook_files(
+            kind,
+            &executable.display().to_string(),
+            self.configuration.hooks(),
+        )
+        .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+
+        if print {
+            for file in &files {
+                outln!("# {}\n{}", file.path().display(), file.content());
+            }
+            return Ok(());
+        }
+
+        hooks::install(kind, &files).map_err(|e| ButlerError::StorageFailure(e.to_string()))?;
+        for file in &files {
+            outln!("Installed the hooks in {}", file.path().display());
+        }
+        porcelain::set("installed", files.len());
+        Ok(())
+    }
+
+    /// Import the meetings of the CalDAV calendars in the month as entries of the project, the hours are the length of
+    /// the meeting. The entries have the origin "meeting:<uid>", so meetings already imported are skipped
+    #[allow(clippy::too_many_arguments)]
+    pub fn import_caldav(
+        &mut self,
+        url: Option<&str>,
+        project_name: &str,
+        month: u32,
+        year: u32,
+        calendars: &[String],
+        keywords: &[String],
+        dry_run: bool,
+    ) -> Result<(), ButlerError> {
+        let index = self
+            .project_position(project_name)
+            .ok_or_else(|| self.project_not_found(project_name, ", unable to import meetings"))?;
+        if self.projects[index].archived() {
+            return Err(ButlerError::InvalidInput(format!(
+                "Project {} is archived",
+                project_name
+            )));
+        }
+
+        let config = self.configuration.caldav().clone();
+        let url = url.unwrap_or(&config.url);
+        if url.is_empty() {
+            return Err(ButlerError::InvalidInput(
+                "No calendar url, use --url or set url in the caldav section of the configuration"
+                    .to_string(),
+            ));
+        }
+        let calendars = if calendars.is_empty() {
+            &config.calendars
+        } else {
+            calendars
+        };
+        let urls = if calendars.is_empty() {
+            vec![url.to_string()]
+        } else {
+            calendars
+                .iter()
+                .map(|c| format!("{}/{}/", url.trim_end_matches('/'), c.trim_matches('/')))
+                .collect()
+        };
+
+        let from = NaiveDate::from_ymd_opt(year as i32, month, 1).ok_or_else(|| {
+            ButlerError::InvalidInput(format!("Invalid month provided: {} {}", month, year))
+        })?;
+        let to = from
+            .checked_add_months(chrono::Months::new(1))
+            .and_then(|d| d.pred_opt())
+            .unwrap_or(from);
+
+        let mut meetings = Vec::new();
+        for url in &urls {
+            meetings.extend(
+                caldav::fetch_meetings(&config, url, from, to)
+                    .map_err(|e| ButlerError::InvalidInput(e.to_string()))?,
+            );
+        }
+        meetings.sort_by_key(|m| m.date());
+
+        let mut table = tables::get_table_entry();
+        let mut added = 0;
+        let mut skipped = 0;
+        let mut filtered = 0;
+        for meeting in meetings {
+            if !meeting.matches(keywords) {
+                filtered += 1;
+                continue;
+            }
+
+            let origin = format!("meeting:{}", meeting.uid());
+            let imported = self.projects[index]
+                .entries()
+                .iter()
+                .any(|e| e.origin() == Some(origin.as_str()));
+            if imported {
+                tracing::debug!("Meeting {} already imported, skipped", meeting.uid());
+                skipped += 1;
+                continue;
+            }
+
+            let description = Some(meeting.summary().to_string()).filter(|s| !s.is_empty());
+            let mut entry = Entry::new_with_clock(meeting.hours(), description, self.clock());
+            entry.set_worked_on(meeting.date());
+            entry.set_origin(origin);
+            let valid =
+                validation::validate_entry_hours(entry.hours(), self.configuration.validation())
+                    .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                    .and_then(|_| self.check_unlocked(entry.work_date(), "add entry"));
+            if let Err(e) = valid {
+                tracing::warn!(
+                    "Skipping meeting {} ({}): {}",
+                    meeting.summary(),
+                    meeting.date(),
+                    e
+                );
+                skipped += 1;
+                continue;
+            }
+
+            table.add_row(self.display.entry_row(&entry, self.projects[index].name()));
+            if !dry_run {
+                self.projects[index].add_entry(entry);
+            }
+            added += 1;
+        }
+        if added > 0 {
+            outln!("{}", table);
+        }
+
+        if dry_run {
+            outln!(
+                "Dry run, nothing imported. Meetings to add: {}, skipped: {}, not matching: {}",
+                added,
+                skipped,
+                filtered
+            );
+        } else {
+            outln!(
+                "Meetings added: {}, skipped: {}, not matching: {}",
+                added,
+                skipped,
+                filtered
+            );
+        }
+        porcelain::set("dry_run", dry_run);
+        porcelain::set("added", added);
+        porcelain::set("skipped", skipped);
+        porcelain::set("filtered", filtered);
+        Ok(())
+    }
+
+    /// Internal function to create the day of an import row. The day is created for its date and the times are set
+    /// as when adding a day, the ending time first since the date follows the starting time when the day is closed.
+    /// The ending time is on the next date when marked so, or when it's before the starting time and the shift is
+    /// within the max overnight hours
+    fn day_from_import_row(&self, row: &DayImportRow) -> Result<Day, ButlerError> {
+        let parse_date = |date: &str| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+                ButlerError::InvalidInput(format!(
+                    "Invalid date provided: {}, expected format YYYY-MM-DD",
+                    date
+                ))
+            })
+        };
+        let date = parse_date(&row.date)?;
+        let (end_time, days_after) = match row.end.as_deref() {
+            Some(end) => Self::split_next_day_marker(end)?,
+            None => (None, 0),
+        };
+        let end_date = match row.end_date.as_deref().filter(|d| !d.is_empty()) {
+            Some(_) if days_after > 0 => {
+                return Err(ButlerError::InvalidInput(format!(
+                    "Ending time {} is marked with a day and given an end date, use one of them",
+                    row.end.as_deref().unwrap_or_default()
+                )))
+            }
+            Some(end_date) => parse_date(end_date)?,
+            None => date + chrono::Duration::days(days_after),
+        };
+        let time_on_date =
+            |date: NaiveDate, time: Option<&str>| -> Result<Option<DateTime<Local>>, ButlerError> {
+                match time.filter(|t| !t.is_empty()) {
+                    Some(t) => {
+                        let seconds = if t.matches(':').count() == 1 {
+                            ":00"
+                        } else {
+                            ""
+                        };
+                        Self::parse_datetime_as_local_time(&format!("{}T{}{}", date, t, seconds))
+                            .map(Some)
+                            .map_err(ButlerError::InvalidInput)
+                    }
+                    None => Ok(None),
+                }
+            };
+        let start = time_on_date(date, row.start.as_deref())?;
+        let mut end = time_on_date(end_date, end_time)?;
+        let pause = match row.pause.as_deref().filter(|p| !p.is_empty()) {
+            Some(p) => p
+                .parse::<f32>()
+                .map_err(|_| ButlerError::InvalidInput(format!("Invalid pause provided: {}", p)))?,
+            None => 0.0,
+        };
+        if pause != 0.0 && start.is_none() {
+            return Err(ButlerError::InvalidInput(
+                "Pause provided without a starting time".to_string(),
+            ));
+        }
+
+        // The row is validated as given, an unmarked ending time before the starting time is then an overnight shift,
+        // e.g. from 22:00 to 06:00
+        if let (Some(start), Some(e)) = (start, end) {
+            validation::validate_import_times(
+                &start,
+                &e,
+                pause,
+                self.configuration.days().max_overnight_hours,
+            )
+            .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+            if e < start {
+                end = Some(e + chrono::Duration::days(1));
+            }
+        }
+
+        // The day is created at noon if not started, midnight may not exist locally
+        let created = match start {
+            Some(start) => start,
+            None => Self::parse_datetime_as_local_time(&format!("{}T12:00:00", date))
+                .map_err(ButlerError::InvalidInput)?,
+        };
+        let extra_info = row.extra_info.clone().filter(|i| !i.is_empty());
+        let mut day = Day::new_with_clock(extra_info, &FixedClock::new(created));
+        if let Some(end) = end {
+            day.set_ending_time(&end);
+        }
+        if let Some(start) = start {
+            day.set_starting_time(&start);
+        }
+        if pause != 0.0 {
+            day.set_paused_time(pause);
+        }
+
+        validation::validate_day(&day, self.configuration.validation())
+            .map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+        self.check_unlocked(day.date(), "import day")?;
+        Ok(day)
+    }
+
+    /// Internal function to split the "+<days>d" marker of an imported ending time on a later date, e.g. "06:00+1d".
+    /// Returns the time and the number of days after the date of the day
+    fn split_next_day_marker(end: &str) -> Result<(Option<&str>, i64), ButlerError> {
+        let end = end.trim();
+        let Some((time, marker)) = end.split_once('+') else {
+            return Ok((Some(end), 0));
+        };
+
+        match marker.trim().strip_suffix('d').map(|d| d.parse::<i64>()) {
+            Some(Ok(days)) if days > 0 => Ok((Some(time.trim()), days)),
+            _ => Err(ButlerError::InvalidInput(format!(
+                "Invalid ending time provided: {}, expected format HH:MM, HH:MM+1d for the next date",
+                end
+            ))),
+        }
+    }
+
+    /// Internal function to check if a day is stored for the date
+    fn day_exists(&self, date: NaiveDate) -> bool {
+        self.weeks
+            .iter()
+            .flat_map(|w| w.entries())
+            .any(|d| d.date() == date)
+    }
+
+    /// Internal function to add or merge a day into its week, returns a copy of the stored day
+    fn insert_day(&mut self, day: Day, strategy: MergeStrategy) -> Result<Day, ButlerError> {
+        validation::validate_day(&day, self.configuration.validation())
+            .map_err(|e| ButlerError::InvalidInput(format!("{}, unable to add day", e)))?;
+        self.check_unlocked(day.date(), "add day")?;
+
+        let index = match self.week_position(day.week(), day.week_year()) {
+            Some(index) => index,
+            None => {
+                tracing::debug!("Didn't find week {}, creating new week", day.week());
+                self.push_week(Week::new(
+                    day.week(),
+                    day.week_year(),
+                    self.configuration.week_target_hours(),
+                ))
+            }
+        };
+
+        let w = &mut self.weeks[index];
+        if !w.exists(&day) {
+            tracing::debug!("Day added to week {}", day.week());
+            w.add_entry(day.clone());
+            return Ok(self.attribute_overnight_day(day));
+        }
+
+        tracing::info!(
+            "Day already exists in week {}, merging day entries",
+            day.week()
+        );
+
+        // Merge into a copy, so nothing is changed if the merged day is invalid
+        let mut merged_week = w.clone();
+        let outcome = merged_week.merge_day(&day, strategy, |conflict| {
+            Self::prompt_user_confirmation(&i18n::tr_args(
+                "The {} is already set to '{}'. Replace it with '{}'?",
+                &[&conflict.field, &conflict.existing, &conflict.new],
+            ))
+        });
+        match outcome {
+            MergeOutcome::Merged => tracing::info!("Day merged successfully"),
+            MergeOutcome::NotFound => {
+                return Err(ButlerError::NotFound(format!(
+                    "Day {} not found in week {}",
+                    day.date(),
+                    day.week()
+                )));
+            }
+            MergeOutcome::Closed => {
+                return Err(ButlerError::InvalidInput(format!(
+                    "Failed to merge day {}, day already closed. Use --on-conflict overwrite to modify it",
+                    day.date()
+                )));
+            }
+        }
+
+        let day_cpy = merged_week.get_day_copy(&day.date()).unwrap(); // safe since day exists already
+        validation::validate_day(&day_cpy, self.configuration.validation())
+            .map_err(|e| ButlerError::InvalidInput(format!("{}, unable to merge day", e)))?;
+        *w = merged_week;
+        tracing::debug!("Day: {}, updated in week:{}", day.date(), day.week());
+
+        Ok(self.attribute_overnight_day(day_cpy))
+    }
+
+    /// Internal function to move a stored day crossing midnight to its ending date, when the hours are attributed to the
+    /// ending date. The day is kept on the starting date if a day is already stored on the ending date. Returns a copy of
+    /// the stored day
+    fn attribute_overnight_day(&mut self, day: Day) -> Day {
+        let end_date = match day.ending_time() {
+            Some(end) if self.overnight_date == OvernightDate::End && day.closed() => {
+                end.date_naive()
+            }
+            _ => return day,
+        };
+        if end_date == day.date() {
+            return day;
+        }
+        if self.day_exists(end_date) {
+            tracing::warn!(
+                "Day {} ended on {}, kept on {} since a day is already stored on {}",
+                day.date(),
+                end_date,
+                day.date(),
+                end_date
+            );
+            return day;
+        }
+
+        for w in &mut self.weeks {
+            w.entries_mut().retain(|d| d.id() != day.id());
+        }
+        let mut moved = day.clone();
+        moved.set_date(end_date);
+        match self.insert_day(moved, MergeStrategy::KeepExisting) {
+            Ok(moved) => {
+                tracing::info!(
+                    "Day {} ended after midnight, moved to {}",
+                    day.date(),
+                    end_date
+                );
+                moved
+            }
+            Err(e) => {
+                tracing::warn!("Day {} kept on its starting date: {}", day.date(), e);
+                if let Some(index) = self.week_position(day.week(), day.week_year()) {
+                    self.weeks[index].add_entry(day.clone());
+                }
+                day
+            }
+        }
+    }
+
+    /// Internal function to get the days before the date with a starting time but no ending time, oldest first
+    fn open_days_before(&self, date: NaiveDate) -> Vec<Day> {
+        let mut days: Vec<Day> = self
+            .weeks
+            .iter()
+            .flat_map(|w| w.entries())
+            .filter(|d| d.start_time_set() && !d.ending_time_set() && d.date() < date)
+            .cloned()
+            .collect();
+        days.sort_by_key(|d| d.date());
+        days
+    }
+
+    /// Close the days before the date that were started but never ended, each after confirmation from the user. The
+    /// days are closed at the configured end of day, or at the starting time if the day was started later. Returns the
+    /// number of closed days
+    pub fn close_open_days(&mut self, before: NaiveDate) -> Result<usize, ButlerError> {
+        let mut closed = 0;
+        for open_day in self.open_days_before(before) {
+            let starting_time = *open_day.starting_time().unwrap(); // safe, open days have a starting time
+            let ending_time = self.end_of_day_time(&open_day);
+            if let Err(e) = self.check_unlocked(open_day.date(), "close day") {
+                tracing::warn!("{}", e);
+                continue;
+            }
+
+            if !Self::prompt_user_confirmation(&i18n::tr_args(
+                "Day {} was started at {} but never ended. Close it at {}?",
+                &[
+                    &open_day.date(),
+                    &starting_time.format("%H:%M"),
+                    &ending_time.format("%H:%M"),
+                ],
+            )) {
+                continue;
+            }
+
+            self.close_day(open_day.id(), &ending_time)?;
+            tracing::info!(
+                "Day {} closed at {}",
+                open_day.date(),
+                ending_time.format("%H:%M")
+            );
+            closed += 1;
+        }
+
+        Ok(closed)
+    }
+
+    /// Close the days that were started more than the configured number of hours ago and never ended, without asking
+    /// the user. Disabled if no limit is configured
+    fn auto_close_open_days(&mut self) {
+        let Some(limit) = self.configuration.auto_close_open_days_after_hours() else {
+            return;
+        };
+
+        let now = self.clock.now();
+        let stale_days: Vec<Day> = self
+            .open_days_before(now.date_naive().succ_opt().unwrap_or(NaiveDate::MAX))
+            .into_iter()
+            .filter(|d| {
+                let starting_time = d.starting_time().unwrap(); // safe, open days have a starting time
+                (now - *starting_time).num_minutes() as f32 / 60.0 > limit
+            })
+            .collect();
+
+        for stale_day in stale_days {
+            let ending_time = self.end_of_day_time(&stale_day).min(now);
+            match self.close_day(stale_day.id(), &ending_time) {
+                Ok(()) => tracing::info!(
+                    "Auto-closed day {} at {}, open for more than {} hours",
+                    stale_day.date(),
+                    ending_time.format("%H:%M"),
+                    limit
+                ),
+                Err(e) => tracing::warn!("Failed to auto-close day {}: {}", stale_day.date(), e),
+            }
+        }
+    }
+
+    /// Internal function to get the time an open day is closed at, the configured end of day on the date of the day,
+    /// or the starting time if the day was started later
+    fn end_of_day_time(&self, day: &Day) -> DateTime<Local> {
+        let starting_time = *day.starting_time().unwrap(); // safe, only used for days with a starting time
+        match Local
+            .from_local_datetime(&day.date().and_time(self.end_of_day))
+            .earliest()
+        {
+            Some(t) if t > starting_time => t,
+            _ => starting_time,
+        }
+    }
+
+    /// Internal function to warn when a closed day has fewer hours than the configured threshold of its weekday
+    fn warn_short_day(&self, day: &Day) {
+        let Some(thresholds) = self.short_day_hours else {
+            return;
+        };
+        let threshold = thresholds[day.date().weekday().num_days_from_monday() as usize];
+        if day.closed() && day.hours() < threshold {
+            tracing::warn!(
+                "You have worked less than {} hours on {}",
+                self.display.hours(threshold),
+                day.date()
+            );
+        }
+    }
+
+    /// Internal function to set the ending time of a stored day, nothing is changed if the closed day is invalid
+    fn close_day(&mut self, id: &Uuid, ending_time: &DateTime<Local>) -> Result<(), ButlerError> {
+        if let Some(date) = self.day_date(id) {
+            self.check_unlocked(date, "close day")?;
+        }
+
+        let stored_day = self
+            .weeks
+            .iter_mut()
+            .flat_map(|w| w.entries_mut().iter_mut())
+            .find(|d| d.id() == id)
+            .ok_or_else(|| ButlerError::NotFound(format!("Day with ID {}", id)))?;
+
+        let mut day = stored_day.clone();
+        day.set_ending_time(ending_time);
+        day.add_default_break(self.configuration.days().default_break_minutes);
+        validation::validate_day(&day, self.configuration.validation())
+            .map_err(|e| ButlerError::InvalidInput(format!("{}, unable to close day", e)))?;
+        *stored_day = day.clone();
+        self.attribute_overnight_day(day);
+        Ok(())
+    }
+
+    /// Check for days from before today that were started but never ended, and offer to close them. Returns the number
+    /// of closed days
+    pub fn doctor_open_days(&mut self) -> Result<usize, ButlerError> {
+        let today = self.clock.today();
+        let open_days = self.open_days_before(today);
+        if open_days.is_empty() {
+            outln!("No open days found");
+            return Ok(0);
+        }
+
+        let mut table = tables::get_table_day();
+        for d in &open_days {
+            table.add_row(self.display.day_row(d));
+        }
+        outln!("{} open days found:\n{}", open_days.len(), table);
+
+        self.close_open_days(today)
+    }
+
+    /// Save the butler data to storage, in bin format
+    pub fn save(&mut self) -> Result<(), ButlerError> {
+        tracing::debug!("Saving data to storage");
+        self.auto_close_open_days();
+
+        if !self.version_mgnr.ok_to_save_files() {
+            return Err(ButlerError::VersionIncompatible(
+                "Not allowed to save files due to version incompatibility. Force override is not enabled".to_string(),
+            ));
+        }
+
+        let project_storage_result = self.storage_handler.store_projects(&self.projects);
+        let week_storage_result = self.storage_handler.store_weeks(&self.weeks);
+
+        if let Err(e) = project_storage_result {
+            return Err(ButlerError::StorageFailure(format!(
+                "Failed to save projects to storage: {}",
+                e
+            )));
+        }
 
-                table.add_row(vec![
-                    Cell::new(d.week().to_string()),
-                    Cell::new(d.date().to_string()),
-                    Cell::new(start_time),
-                    Cell::new(end_time),
-                    Cell::new(d.hours_paused().to_string()),
-                    Cell::new(d.hours().to_string()),
-                    Cell::new(d.closed().to_string()),
-                    Cell::new(d.extra_info()),
-                    Cell::new(d.id().to_string()),
-                ]);
-            }
+        if let Err(e) = week_storage_result {
+            return Err(ButlerError::StorageFailure(format!(
+                "Failed to save weeks to storage: {}",
+                e
+            )));
+        }
 
-            println!("{}", table);
+        self.storage_handler
+            .write_period_metadata(&self.period_metadata)
+            .map_err(|e| {
+                ButlerError::StorageFailure(format!(
+                    "Failed to save period metadata to storage: {}",
+                    e
+                ))
+            })?;
+
+        self.purge_trash();
+        self.storage_handler.write_trash(&self.trash).map_err(|e| {
+            ButlerError::StorageFailure(format!("Failed to save trash to storage: {}", e))
+        })?;
+
+        // Only saved with the synced data, otherwise the changes of the other machines would be seen as removed locally
+        if let Some(state) = &self.sync_state {
+            self.storage_handler.write_sync_state(state).map_err(|e| {
+                ButlerError::StorageFailure(format!("Failed to save sync state to storage: {}", e))
+            })?;
         }
+
+        self.storage_handler.backup_storage_files(
+            self.configuration.periodic_backup_enabled(),
+            self.configuration.override_existing_backup(),
+            self.configuration.periodic_backup_interval(),
+        );
+
+        Ok(())
     }
 
-    /// Add new entry to project
-    pub fn add_entry(&mut self, project_name: &str, entry: Entry) -> bool {
-        // search for the project
-        for p in &mut self.projects {
-            if p.name() == project_name {
-                // Get the entry ID before ownership transfer
-                let entry_clone = entry.clone();
-                p.add_entry(entry);
+    /// Replace the stored data with a generated data set, for development and benchmarking. Existing data is only
+    /// replaced if forced
+    pub fn generate_synthetic_data(
+        &mut self,
+        years: u32,
+        entries_per_day: u32,
+        force: bool,
+    ) -> Result<(), ButlerError> {
+        if years == 0 || entries_per_day == 0 {
+            return Err(ButlerError::InvalidInput(
+                "The number of years and entries per day must be at least 1".to_string(),
+            ));
+        }
 
-                // Print new entry as confirmation to user
-                tables::print_entry_in_report_table(&entry_clone, p.name());
-                return true;
-            }
+        if !force && (!self.projects.is_empty() || !self.weeks.is_empty()) {
+            return Err(ButlerError::Duplicate(
+                "Stored data, use --force to replace it with generated data".to_string(),
+            ));
         }
 
-        tracing::error!(
-            "Project with name {} not found, unable to add entry",
-            project_name
+        let data = dev::generate(
+            years,
+            entries_per_day,
+            self.clock.today(),
+            self.configuration.week_target_hours(),
+        );
+        self.projects = data.projects;
+        self.weeks = data.weeks;
+        self.rebuild_indexes();
+
+        tracing::info!(
+            "Generated {} weeks with {} days and {} entries",
+            self.number_of_weeks(),
+            self.weeks.iter().map(|w| w.entries().len()).sum::<usize>(),
+            self.projects
+                .iter()
+                .map(|p| p.entries().len())
+                .sum::<usize>()
         );
-        false
+        Ok(())
     }
 
-    /// Add new day to a week
-    pub fn add_day(&mut self, day: Day) -> bool {
-        // search for the Week
-        if day.week() > 52 && day.week() < 1 {
-            tracing::error!("Week number is invalid: {}, unable to add day", day.week());
-            return false;
+    /// Time loading, saving, adding days and report generation on the stored data. The stored data is left
+    /// unchanged and the reports are written to a temporary directory
+    pub fn run_benchmarks(&mut self, iterations: u32) -> Result<(), ButlerError> {
+        if iterations == 0 {
+            return Err(ButlerError::InvalidInput(
+                "The number of iterations must be at least 1".to_string(),
+            ));
         }
 
-        // If no week exists, no idea to search and do the potential merge. Just create and add
-        if self.weeks.is_empty() {
-            tracing::info!("Weeks list is empty, creating new week");
-            let mut new_week = Week::new(
-                day.week(),
-                day.year(),
-                self.configuration.week_target_hours(),
+        let last_date = match self
+            .weeks
+            .iter()
+            .flat_map(|w| w.entries())
+            .map(|d| d.date())
+            .max()
+        {
+            Some(date) => date,
+            None => {
+                return Err(ButlerError::NotFound(
+                    "No stored days to benchmark, generate data with: dev generate".to_string(),
+                ))
+            }
+        };
+
+        let mut results = Vec::new();
+
+        results.push(dev::measure("load", iterations, || {
+            self.storage_handler.load_projects();
+            self.storage_handler.load_weeks();
+        }));
+
+        results.push(dev::measure("save, unchanged", iterations, || {
+            let _ = self.storage_handler.store_projects(&self.projects);
+            let _ = self.storage_handler.store_weeks(&self.weeks);
+        }));
+
+        // Same data is written, but all items are seen as changed
+        let mut save_res = Ok(true);
+        results.push(dev::measure("save, all changed", iterations, || {
+            self.storage_handler.reset_stored_checksums();
+            save_res = self
+                .storage_handler
+                .store_projects(&self.projects)
+                .and_then(|_| self.storage_handler.store_weeks(&self.weeks));
+        }));
+        if let Err(e) = save_res {
+            return Err(ButlerError::StorageFailure(format!(
+                "Failed to save data during benchmark: {}",
+                e
+            )));
+        }
+
+        // The days are added after the stored days, and removed afterwards
+        let stored_weeks = self.weeks.clone();
+        let mut date = last_date;
+        let mut add_res = Ok(());
+        results.push(dev::measure("add day", iterations, || {
+            date += chrono::Duration::days(1);
+            if let (Some(day), true) = (dev::synthetic_day(date, 8 * 60, 9 * 60), add_res.is_ok()) {
+                add_res = self
+                    .insert_day(day, MergeStrategy::KeepExisting)
+                    .map(|_| ());
+            }
+        }));
+        self.weeks = stored_weeks;
+        self.rebuild_indexes();
+        add_res?;
+
+        // Reports are written to a temporary directory, not to the configured report directory
+        let report_dir = std::env::temp_dir().join(format!("time-butler-bench-{}", process::id()));
+        std::fs::create_dir_all(&report_dir).map_err(|e| {
+            ButlerError::StorageFailure(format!(
+                "Failed to create benchmark report directory {}: {}",
+                report_dir.display(),
+                e
+            ))
+        })?;
+        self.report_mngr
+            .set_report_storage_dir(report_dir.display().to_string());
+        // The iterations write the same report file
+        self.report_mngr
+            .set_write_policy(ReportOverwrite::Overwrite, false);
+
+        let week_notes = self.week_notes();
+        let mut report_res = Ok(String::new());
+        {
+            let month_days =
+                self.days_in_month_for_year(last_date.month(), last_date.year() as u32);
+            results.push(dev::measure("month report", iterations, || {
+                report_res = self.report_mngr.generate_month_report(
+                    last_date.month(),
+                    ReportFormat::Json,
+                    month_days.clone(),
+                    &week_notes,
+                    None,
+                    None,
+                );
+            }));
+        }
+
+        let mut all_days: Vec<&Day> = self.weeks.iter().flat_map(|w| w.entries()).collect();
+        all_days.sort_by_key(|d| d.date());
+        let first_date = all_days.first().map(|d| d.date()).unwrap_or(last_date);
+        results.push(dev::measure("range report, all days", iterations, || {
+            if report_res.is_ok() {
+                report_res = self.report_mngr.generate_range_report(
+                    first_date,
+                    last_date,
+                    ReportFormat::Json,
+                    all_days.iter().copied(),
+                    &week_notes,
+                    None,
+                );
+            }
+        }));
+
+        self.report_mngr
+            .set_report_storage_dir(self.configuration.report_directory().to_string());
+        self.report_mngr
+            .set_write_policy(ReportOverwrite::default(), false);
+        let _ = std::fs::remove_dir_all(&report_dir);
+        if report_res.is_err() {
+            return Err(ButlerError::ReportFailure(
+                "Failed to generate report during benchmark".to_string(),
+            ));
+        }
+
+        outln!(
+            "Data set: {} projects, {} entries, {} weeks, {} days",
+            self.number_of_projects(),
+            self.projects
+                .iter()
+                .map(|p| p.entries().len())
+                .sum::<usize>(),
+            self.number_of_weeks(),
+            all_days.len()
+        );
+        let mut table = tables::get_table_benchmarks();
+        for r in &results {
+            table.add_row(vec![
+                r.name.clone(),
+                r.iterations.to_string(),
+                format!("{:.3}", r.total.as_secs_f64() * 1000.0),
+                format!("{:.3}", r.average().as_secs_f64() * 1000.0),
+            ]);
+        }
+        outln!("{}", table);
+
+        Ok(())
+    }
+
+    pub fn force_backup(&self) -> Result<(), ButlerError> {
+        tracing::info!("Forcing backup of time-butler data");
+        self.storage_handler
+            .do_backup_now()
+            .map_err(|e| ButlerError::StorageFailure(format!("Backup failed: {}", e)))
+    }
+
+    /// Remove a project from the Butler
+    pub fn remove_project(&mut self, project_name: &str) -> Result<(), ButlerError> {
+        let index = self.project_position(project_name);
+        if let Some(i) = index {
+            tracing::debug!(
+                "Project found: {}, at index: {}",
+                self.projects[i].name(),
+                i
             );
-            // Print the new added day as confirmation to user, quite nice verification
-            tables::print_day_in_report_table(&day);
-            new_week.add_entry(day);
-            self.weeks.push(new_week);
-            return true;
+            if !self.sub_projects(self.projects[i].name()).is_empty() {
+                return Err(ButlerError::InvalidInput(format!(
+                    "Project {} has sub-projects, remove them first",
+                    self.projects[i].name()
+                )));
+            }
+        }
+
+        if let Some(index) = index {
+            if Self::prompt_user_confirmation(&i18n::tr_args(
+                "Are you sure you want to remove {}",
+                &[&project_name],
+            )) {
+                let project = self.projects.remove(index);
+                self.rebuild_indexes();
+                tracing::info!(
+                    "Project {} moved to the trash, restore it with: trash restore {}",
+                    project_name,
+                    project.id()
+                );
+                self.trash
+                    .add(TrashedItem::Project { project }, self.clock.now());
+                Ok(())
+            } else {
+                Err(ButlerError::Aborted("Confirmation not given".to_string()))
+            }
         } else {
-            let last_item_index = self.weeks.len() - 1;
-            // If weeks exists, search for the week and add the day
-            for (i, w) in self.weeks.iter_mut().enumerate() {
-                // Find correct week
-                if w.number() == day.week() && w.year() == day.year() {
-                    // Correct week found
-                    if w.exists(&day) {
-                        tracing::info!(
-                            "Day already exists in week {}, merging day entries",
-                            day.week()
-                        );
+            Err(ButlerError::NotFound(format!(
+                "Project with name {}",
+                project_name
+            )))
+        }
+    }
 
-                        if w.merge_day(&day) {
-                            tracing::info!("Day merged successfully");
-                        } else {
-                            tracing::info!("Failed to merge day");
-                            return false;
-                        }
+    /// Remove an entry from a project. Without a project the entry is searched for by ID in all projects
+    pub fn remove_entry(
+        &mut self,
+        project: Option<&str>,
+        selector: &EntrySelector,
+    ) -> Result<(), ButlerError> {
+        if self.projects.is_empty() {
+            return Err(ButlerError::NotFound(
+                "No projects stored, unable to remove entry".to_string(),
+            ));
+        }
+
+        // Search for project, if project exists -> search for the entry
+        let (index, parsed_id) = match project {
+            Some(project) => {
+                let index = self.project_position(project).ok_or_else(|| {
+                    ButlerError::NotFound(format!("Project with name {}", project))
+                })?;
+                let entries: Vec<&Entry> = self.projects[index].entries().iter().collect();
+                let scope = format!("project: {}", project);
+                (index, Self::select_entry(&entries, selector, &scope)?)
+            }
+            None => {
+                if !matches!(selector, EntrySelector::Id(_)) {
+                    return Err(ButlerError::InvalidInput(
+                        "A project is needed to select an entry by position".to_string(),
+                    ));
+                }
+                let entries: Vec<&Entry> = self.projects.iter().flat_map(|p| p.entries()).collect();
+                let parsed_id = Self::select_entry(&entries, selector, "any project")?;
+                let index = self
+                    .projects
+                    .iter()
+                    .position(|p| p.entry_exists(&parsed_id))
+                    .ok_or_else(|| {
+                        ButlerError::NotFound(format!(
+                            "Entry with ID: {} in any project",
+                            parsed_id
+                        ))
+                    })?;
+                (index, parsed_id)
+            }
+        };
 
-                        tracing::debug!("Day: {}, updated in week:{}", day.date(), day.week());
-                        let day_cpy = w.get_day_copy(&day.date()).unwrap(); // safe since day exists already
+        if let Some(entry) = self.projects[index].get_entry_copy(&parsed_id) {
+            self.check_unlocked(entry.work_date(), "remove entry")?;
+        }
 
-                        // Print the new added day as confirmation to user, quite nice verification
-                        tables::print_day_in_report_table(&day_cpy);
-                        return true;
-                    } else {
-                        // Day don't exists in week
-                        tracing::debug!(
-                            "Week:{} not found, adding new day: {}",
-                            day.week(),
-                            day.date()
-                        );
-                        tracing::debug!("Day added to week {}", day.week());
-                        // Get the date of newly added day, since ownership is moved to week
-                        let new_day_date = day.date();
-                        w.add_entry(day);
+        let p = &mut self.projects[index];
+        if !p.entry_exists(&parsed_id) {
+            return Err(ButlerError::NotFound(format!(
+                "Entry with ID: {} in project: {}",
+                parsed_id,
+                p.name()
+            )));
+        }
 
-                        let day_cpy = w.get_day_copy(&new_day_date).unwrap(); // safe since day exists already
+        let entry_cpy = p.get_entry_copy(&parsed_id).unwrap(); // safe since we know it exists
 
-                        // Print the new added day as confirmation to user, quite nice verification
-                        tables::print_day_in_report_table(&day_cpy);
-                        return true;
-                    }
-                } else {
-                    // Week not found check if last element in week list
-                    if i == last_item_index {
-                        // Last element in list, create new week
-                        tracing::debug!("Didn't find week {}, creating new week", day.week());
-                        let mut new_week = Week::new(
-                            day.week(),
-                            day.year(),
-                            self.configuration.week_target_hours(),
-                        );
+        let mut table = tables::get_table_entry();
+        table.add_row(self.display.entry_row(&entry_cpy, p.name()));
+        outln!("{}", table);
 
-                        // Print the new added day as confirmation to user, before adding to week and loose ownership
-                        tables::print_day_in_report_table(&day);
+        if Self::prompt_user_confirmation(&i18n::tr_args(
+            "Are you sure you want to remove entry {}",
+            &[&parsed_id],
+        )) {
+            if p.remove_listed_entry(&parsed_id) {
+                tracing::info!(
+                    "Entry {}, removed from project {} and moved to the trash",
+                    parsed_id.to_string(),
+                    p.name()
+                );
+                let item = TrashedItem::Entry {
+                    project_id: *p.id(),
+                    project: p.name().to_string(),
+                    entry: entry_cpy,
+                };
+                self.trash.add(item, self.clock.now());
+                Ok(())
+            } else {
+                Err(ButlerError::NotFound(format!(
+                    "Failed to remove entry {}",
+                    parsed_id
+                )))
+            }
+        } else {
+            Err(ButlerError::Aborted("Confirmation not given".to_string()))
+        }
+    }
 
-                        new_week.add_entry(day);
-                        self.weeks.push(new_week);
+    /// List the removed items in the trash, the last removed first
+    pub fn list_trash(&self) {
+        porcelain::set("trash", self.trash.records());
+        if self.trash.records().is_empty() {
+            outln!("The trash is empty");
+            return;
+        }
 
-                        return true;
-                    }
-                    // Not last element, continue searching
+        let mut table = tables::get_table_trash();
+        for record in self.trash.records().iter().rev() {
+            let description = match record.item() {
+                TrashedItem::Project { project } => {
+                    format!("{} ({} entries)", project.name(), project.entries().len())
                 }
-            }
+                TrashedItem::Entry { project, entry, .. } => format!(
+                    "{}: {} ({} h, {})",
+                    project,
+                    entry.description(),
+                    self.display.hours(entry.hours()),
+                    entry.work_date()
+                ),
+                TrashedItem::Day { day } => {
+                    format!("{} ({} h)", day.date(), self.display.hours(day.hours()))
+                }
+            };
+            table.add_row(vec![
+                record.item().id().to_string(),
+                i18n::tr(record.item().kind()).to_string(),
+                record.removed().format("%Y-%m-%d %H:%M").to_string(),
+                description,
+            ]);
         }
-        false
+        outln!("{}", table);
     }
 
-    /// Save the butler data to storage, in bin format
-    pub fn save(&self) -> bool {
-        tracing::debug!("Saving data to storage");
+    /// Restore an item from the trash, by ID or a unique ID prefix. The item is put back in the trash if it can't be
+    /// restored, e.g. if a project with the same name was added since
+    pub fn restore_from_trash(&mut self, id: &str) -> Result<(), ButlerError> {
+        let matching = self.trash.matching(id);
+        let item_id = match matching.as_slice() {
+            [] => {
+                return Err(ButlerError::NotFound(format!(
+                    "Item with ID {} in the trash",
+                    id
+                )))
+            }
+            [item_id] => *item_id,
+            _ => {
+                return Err(ButlerError::InvalidInput(format!(
+                    "ID prefix '{}' is ambiguous, matching items: {}",
+                    id,
+                    matching
+                        .iter()
+                        .map(|m| m.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )))
+            }
+        };
 
-        if !self.version_mgnr.ok_to_save_files() {
-            tracing::error!("Not allowed to save files due to version incompatibility. Force override is not enabled");
-            return false;
+        let record = self.trash.take(&item_id).ok_or_else(|| {
+            ButlerError::NotFound(format!("Item with ID {} in the trash", item_id))
+        })?;
+        let restored = self.restore_item(record.item().clone());
+        if restored.is_err() {
+            self.trash.put_back(record);
         }
+        restored
+    }
 
-        let project_storage_result =
-            match self.storage_handler.store_projects(self.projects.clone()) {
-                Ok(_) => true,
-                Err(e) => {
-                    tracing::error!("Failed to save projects to storage: {}", e);
-                    false
+    /// Internal function to put a removed item back, without touching the trash
+    fn restore_item(&mut self, item: TrashedItem) -> Result<(), ButlerError> {
+        match item {
+            TrashedItem::Project { project } => {
+                if self.project_position(project.name()).is_some() {
+                    return Err(ButlerError::Duplicate(format!(
+                        "Project with name {} already exists, unable to restore it",
+                        project.name()
+                    )));
                 }
-            };
+                tracing::info!("Project {} restored", project.name());
+                self.projects.push(project);
+                self.rebuild_indexes();
+            }
+            TrashedItem::Entry {
+                project_id,
+                project,
+                entry,
+            } => {
+                self.check_unlocked(entry.work_date(), "restore entry")?;
+                // The project may have been renamed since, the ID is preferred
+                let index = self
+                    .projects
+                    .iter()
+                    .position(|p| p.id() == &project_id)
+                    .or_else(|| self.project_position(&project))
+                    .ok_or_else(|| {
+                        ButlerError::NotFound(format!(
+                            "Project {} of the entry, restore the project first",
+                            project
+                        ))
+                    })?;
+                let p = &mut self.projects[index];
+                if p.entry_exists(entry.id()) {
+                    return Err(ButlerError::Duplicate(format!(
+                        "Entry with ID {} in project {}",
+                        entry.id(),
+                        p.name()
+                    )));
+                }
+                tracing::info!("Entry {} restored to project {}", entry.id(), p.name());
+                p.entries_mut().push(entry);
+            }
+            TrashedItem::Day { day } => {
+                if self.day_exists(day.date()) {
+                    return Err(ButlerError::Duplicate(format!(
+                        "Day with date {} already exists, unable to restore it",
+                        day.date()
+                    )));
+                }
+                tracing::info!("Day {} restored", day.date());
+                self.insert_day(day, MergeStrategy::KeepExisting)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Internal function to purge the items kept in the trash longer than the configured retention
+    fn purge_trash(&mut self) {
+        let retention_days = self.configuration.trash_retention_days();
+        if retention_days == 0 {
+            return;
+        }
 
-        let week_storage_result = match self.storage_handler.store_weeks(self.weeks.clone()) {
-            Ok(_) => true,
+        let before = self.clock.now() - chrono::Duration::days(retention_days as i64);
+        let purged = self.trash.purge_before(before);
+        if purged > 0 {
+            tracing::info!(
+                "Purged {} items removed more than {} days ago from the trash",
+                purged,
+                retention_days
+            );
+        }
+    }
+
+    /// Get the number and year of the week with the ID
+    pub fn week_by_id(&self, id: &str) -> Result<(u32, u32), ButlerError> {
+        let week_id = Self::parse_id(id)?;
+        self.weeks
+            .iter()
+            .find(|w| w.id() == &week_id)
+            .map(|w| (w.number(), w.year() as u32))
+            .ok_or_else(|| ButlerError::NotFound(format!("Week with ID {}", id)))
+    }
+
+    /// Remove the day with the ID, after confirmation by the user
+    pub fn remove_day_by_id(&mut self, id: &str) -> Result<(), ButlerError> {
+        let day_id = Self::parse_id(id)?;
+        let (week, date) = self
+            .weeks
+            .iter()
+            .flat_map(|w| w.entries())
+            .find(|d| d.id() == &day_id)
+            .map(|d| (d.week(), d.date()))
+            .ok_or_else(|| ButlerError::NotFound(format!("Day with ID {}", id)))?;
+        self.remove_day(week, date.format("%Y-%m-%d").to_string())
+    }
+
+    pub fn remove_day(&mut self, week: u32, date: String) -> Result<(), ButlerError> {
+        if self.weeks.is_empty() {
+            return Err(ButlerError::NotFound(
+                "No weeks stored, unable to remove day".to_string(),
+            ));
+        }
+
+        let date_format = "%Y-%m-%d";
+        let parsed_date = match chrono::NaiveDate::parse_from_str(&date, date_format) {
+            Ok(parsed_date) => {
+                tracing::debug!("Parsed date: {}", parsed_date);
+                parsed_date
+            }
             Err(e) => {
-                tracing::error!("Failed to save weeks to storage: {}", e);
-                false
+                return Err(ButlerError::InvalidInput(format!(
+                    "Failed to parse date: {}, for format: y-m-d",
+                    e
+                )));
             }
         };
 
-        if !project_storage_result || !week_storage_result {
-            tracing::debug!("Save failed");
-            return false;
+        self.check_unlocked(parsed_date, "remove day")?;
+        let (_, year) = week_scheme::week_scheme().week_of(parsed_date);
+
+        // Search for week with both week number and year
+        if let Some(index) = self.week_position(week, year) {
+            let w = &mut self.weeks[index];
+            if !w.exist(&parsed_date) {
+                return Err(ButlerError::NotFound(format!(
+                    "Day with date {} in week {} year {}",
+                    date, week, year
+                )));
+            }
+
+            let day_cpy = w.get_day_copy(&parsed_date).unwrap(); // safe since we know it exists
+
+            let mut table = tables::get_table_day();
+            table.add_row(self.display.day_row(&day_cpy));
+            outln!("{}", table);
+            if Self::prompt_user_confirmation(&i18n::tr_args(
+                "Are you sure you want to remove day {}",
+                &[&parsed_date],
+            )) {
+                // Remove day
+                w.remove_listed_day(&parsed_date);
+                tracing::info!(
+                    "Day {}, removed from week {} year {} and moved to the trash",
+                    parsed_date,
+                    week,
+                    year
+                );
+                self.trash
+                    .add(TrashedItem::Day { day: day_cpy }, self.clock.now());
+                return Ok(());
+            } else {
+                return Err(ButlerError::Aborted("Confirmation not given".to_string()));
+            }
         }
 
-        self.storage_handler.backup_storage_files(
-            self.configuration.periodic_backup_enabled(),
-            self.configuration.override_existing_backup(),
-            self.configuration.periodic_backup_interval(),
+        Err(ButlerError::NotFound(format!(
+            "Day with date {} in week {} year {}",
+            date, week, year
+        )))
+    }
+
+    /// Display the burn-down of a project budget: hours used, remaining budget and the projected completion. The
+    /// hours of the sub-projects and the allocated day hours are included
+    pub fn display_project_target_status(&self, project_name: &str) -> Result<(), ButlerError> {
+        let index = self
+            .project_position(project_name)
+            .ok_or_else(|| ButlerError::NotFound(format!("Project with name {}", project_name)))?;
+        let p = &self.projects[index];
+        let budget = p.budget_hours().ok_or_else(|| {
+            ButlerError::NotFound(format!(
+                "Budget for project {}, set it with 'project budget'",
+                p.name()
+            ))
+        })?;
+
+        let mut project_names = vec![p.name()];
+        let mut entries: Vec<(NaiveDate, f32)> = p
+            .entries()
+            .iter()
+            .map(|e| (e.work_date(), e.hours()))
+            .collect();
+        for sub in self.sub_projects(p.name()) {
+            entries.extend(sub.entries().iter().map(|e| (e.work_date(), e.hours())));
+            project_names.push(sub.name());
+        }
+        entries.extend(
+            self.allocation_entries(&project_names)
+                .iter()
+                .map(|e| (e.work_date(), e.hours())),
         );
 
-        true
+        let status = ProjectBurnDown::new(budget, entries, p.deadline(), self.clock.today());
+        let mut table = tables::get_table_target_project();
+        table.add_row(vec![
+            Cell::new(p.name()),
+            Cell::new(self.display.hours(*status.budget_hours())),
+            Cell::new(self.display.hours(*status.used_hours())),
+            Cell::new(status.percentage().to_string()),
+            Cell::new(self.display.hours(status.remaining_hours().max(0.0))),
+            Cell::new(self.display.hours(*status.daily_pace())),
+            Cell::new(
+                status
+                    .projected_completion()
+                    .map(|d| d.to_string())
+                    .unwrap_or("N/A".to_string()),
+            ),
+            Cell::new(
+                status
+                    .deadline()
+                    .map(|d| d.to_string())
+                    .unwrap_or("N/A".to_string()),
+            ),
+            self.display.target_status_cell(&status.status_string()),
+        ]);
+
+        outln!("{}", table);
+        porcelain::set(
+            "target_status",
+            serde_json::json!({
+                "project": p.name(),
+                "budget_hours": status.budget_hours(),
+                "used_hours": status.used_hours(),
+                "remaining_hours": status.remaining_hours(),
+                "percentage": status.percentage(),
+                "deadline": status.deadline(),
+                "projected_completion": status.projected_completion(),
+                "status": status.status_string(),
+            }),
+        );
+        Ok(())
     }
 
-    pub fn force_backup(&self) -> bool {
-        tracing::info!("Forcing backup of time-butler data");
-        match self.storage_handler.do_backup_now() {
-            Ok(_) => {
-                tracing::info!("Backup completed successfully");
-                true
+    /// Sync the data with the sync directory. The changes of the other machines are pulled if the directory is a git
+    /// repository and synced into the stored data, see `sync_data`. All data is then written to the directory, the
+    /// files of the removed items are removed, and the directory is committed and pushed. The state of the sync is
+    /// saved with the data
+    pub fn sync(&mut self, strategy: MergeStrategy) -> Result<(), ButlerError> {
+        let directory_path = self.configuration.sync_directory();
+        let directory = SyncDirectory::new(&directory_path);
+        let sync_failure = |e: crate::sync::SyncError| ButlerError::StorageFailure(e.to_string());
+        let git = self.configuration.sync_with_git() && directory.is_git_repository();
+
+        let mut synced = self
+            .storage_handler
+            .load_sync_state()
+            .map_err(|e| ButlerError::StorageFailure(format!("Failed to load sync state: {}", e)))?
+            .of_directory(&directory_path);
+
+        if git && directory.pull().map_err(sync_failure)? {
+            tracing::info!("Pulled the changes of the other machines");
+        }
+
+        let (projects, weeks) = directory.read().map_err(sync_failure)?;
+        // An emptied directory, e.g. a new clone, doesn't remove all the stored data
+        if projects.is_empty() && weeks.is_empty() && !synced.is_empty() {
+            tracing::warn!(
+                "The sync directory {} is empty, all stored data is written to it again",
+                directory_path
+            );
+            synced = SyncState::default();
+        }
+        let summary = self.sync_data(projects, weeks, &synced, strategy);
+        let (written, removed) = directory
+            .write(&self.projects, &self.weeks, &synced)
+            .map_err(sync_failure)?;
+        self.sync_state = Some(SyncState::new(&directory_path, &self.projects, &self.weeks));
+
+        let committed = git
+            && directory
+                .commit_and_push(&format!(
+                    "time-butler sync {}",
+                    self.clock.now().format("%Y-%m-%d %H:%M:%S")
+                ))
+                .map_err(sync_failure)?;
+
+        outln!(
+            "Synced with {}, projects added: {}, entries added: {}, days added: {}, merged: {}, updated: {}, removed: {}, skipped: {}",
+            directory_path,
+            summary.added_projects,
+            summary.added_entries,
+            summary.added_days,
+            summary.merged_days,
+            summary.updated_items,
+            summary.removed_items,
+            summary.skipped_entries + summary.skipped_days
+        );
+        tracing::info!(
+            "{} files written, {} removed, committed: {}, changed: {}",
+            written,
+            removed,
+            committed,
+            summary.changed()
+        );
+        porcelain::set("directory", directory_path);
+        porcelain::set("committed", committed);
+        summary.set_porcelain();
+
+        Ok(())
+    }
+
+    /// Internal function to sync the projects and weeks of the sync directory into the stored data. Each project,
+    /// week, entry and day is compared with the state of the last sync: an item only changed by the other machines is
+    /// added, replaced or removed, an item only changed or removed locally is kept as it is. Days changed on both sides
+    /// are merged with the strategy, the other items are replaced with overwrite and else kept. An item removed on one
+    /// side and changed on the other is kept. Removed items are moved to the trash, days and entries in locked periods
+    /// are skipped
+    fn sync_data(
+        &mut self,
+        projects: Vec<Project>,
+        weeks: Vec<Week>,
+        synced: &SyncState,
+        strategy: MergeStrategy,
+    ) -> MergeSummary {
+        let mut summary = MergeSummary::default();
+        let local = sync::item_checksums(&self.projects, &self.weeks);
+        let remote = sync::item_checksums(&projects, &weeks);
+        let change = |key: &str| {
+            sync::sync_change(
+                local.get(key).copied(),
+                remote.get(key).copied(),
+                synced.get(key),
+            )
+        };
+        // The item of the other machines is taken, also when removed locally and changed by the other machines
+        let take_remote = |change: SyncChange, stored: bool| match change {
+            SyncChange::Remote => true,
+            SyncChange::Both => !stored || strategy == MergeStrategy::Overwrite,
+            _ => false,
+        };
+        // Only removed when removed by the other machines and unchanged locally
+        let removed_remotely =
+            |key: &str| !remote.contains_key(key) && change(key) == SyncChange::Remote;
+
+        for project in &projects {
+            let index = self.projects.iter().position(|p| p.id() == project.id());
+            if !take_remote(change(&sync::project_key(project.id())), index.is_some()) {
+                continue;
             }
-            Err(e) => {
-                tracing::error!("Backup failed: {}", e);
-                false
+            match index {
+                Some(index) => {
+                    tracing::debug!("Updating synced project {}", project.name());
+                    let mut updated = project.clone();
+                    *updated.entries_mut() = std::mem::take(self.projects[index].entries_mut());
+                    self.projects[index] = updated;
+                    summary.updated_items += 1;
+                }
+                // A project created on several machines has its entries added to the stored project
+                None if self.project_position(project.name()).is_some() => {}
+                None => {
+                    tracing::debug!("Adding synced project {}", project.name());
+                    let mut new_project = project.clone();
+                    new_project.entries_mut().clear();
+                    self.push_project(new_project);
+                    summary.added_projects += 1;
+                }
             }
+            self.rebuild_indexes();
         }
+
+        let removed_projects: Vec<Uuid> = self
+            .projects
+            .iter()
+            .filter(|p| removed_remotely(&sync::project_key(p.id())))
+            .map(|p| *p.id())
+            .collect();
+        for id in removed_projects {
+            let index = self.projects.iter().position(|p| *p.id() == id).unwrap(); // safe, collected above
+            let locked = self.projects[index]
+                .entries()
+                .iter()
+                .find_map(|e| self.check_unlocked(e.work_date(), "remove project").err());
+            if let Some(e) = locked {
+                tracing::warn!("Skipping removal of project {}: {}", id, e);
+                summary.skipped_entries += 1;
+                continue;
+            }
+            let project = self.projects.remove(index);
+            tracing::info!(
+                "Project {} removed by sync, moved to the trash",
+                project.name()
+            );
+            self.trash
+                .add(TrashedItem::Project { project }, self.clock.now());
+            summary.removed_items += 1;
+        }
+        self.rebuild_indexes();
+
+        for project in &projects {
+            for entry in project.entries() {
+                let stored = self
+                    .projects
+                    .iter()
+                    .position(|p| p.entry_exists(entry.id()));
+                if !take_remote(change(&sync::entry_key(entry.id())), stored.is_some()) {
+                    continue;
+                }
+                let target = self
+                    .projects
+                    .iter()
+                    .position(|p| p.id() == project.id())
+                    .or_else(|| self.project_position(project.name()));
+                let Some(target) = target else {
+                    tracing::warn!(
+                        "Skipping entry {}, its project {} is removed",
+                        entry.id(),
+                        project.name()
+                    );
+                    summary.skipped_entries += 1;
+                    continue;
+                };
+                let stored_date = stored
+                    .and_then(|index| self.projects[index].get_entry_copy(entry.id()))
+                    .map(|e| e.work_date());
+                let unlocked = stored_date
+                    .into_iter()
+                    .chain([entry.work_date()])
+                    .try_for_each(|date| self.check_unlocked(date, "sync entry"));
+                if let Err(e) = unlocked {
+                    tracing::warn!("Skipping entry {}: {}", entry.id(), e);
+                    summary.skipped_entries += 1;
+                    continue;
+                }
+
+                match stored {
+                    Some(index) => {
+                        self.projects[index].remove_listed_entry(entry.id());
+                        summary.updated_items += 1;
+                    }
+                    None => summary.added_entries += 1,
+                }
+                self.projects[target].add_entry(entry.clone());
+            }
+        }
+
+        let removed_entries: Vec<(usize, Entry)> = self
+            .projects
+            .iter()
+            .enumerate()
+            .flat_map(|(index, p)| p.entries().iter().map(move |e| (index, e)))
+            .filter(|(_, e)| removed_remotely(&sync::entry_key(e.id())))
+            .map(|(index, e)| (index, e.clone()))
+            .collect();
+        for (index, entry) in removed_entries {
+            if let Err(e) = self.check_unlocked(entry.work_date(), "remove entry") {
+                tracing::warn!("Skipping removal of entry {}: {}", entry.id(), e);
+                summary.skipped_entries += 1;
+                continue;
+            }
+            let p = &mut self.projects[index];
+            p.remove_listed_entry(entry.id());
+            tracing::info!("Entry {} removed by sync, moved to the trash", entry.id());
+            let item = TrashedItem::Entry {
+                project_id: *p.id(),
+                project: p.name().to_string(),
+                entry,
+            };
+            self.trash.add(item, self.clock.now());
+            summary.removed_items += 1;
+        }
+
+        for week in &weeks {
+            // A new week keeps the target and notes of the synced week
+            let week_change = change(&sync::week_key(week.year(), week.number()));
+            match self.week_position(week.number(), week.year()) {
+                Some(index) if take_remote(week_change, true) => {
+                    let mut updated = week.clone();
+                    *updated.entries_mut() = std::mem::take(self.weeks[index].entries_mut());
+                    self.weeks[index] = updated;
+                    summary.updated_items += 1;
+                }
+                // Notes added on both sides are kept
+                Some(index) if week_change == SyncChange::Both => {
+                    let stored = &mut self.weeks[index];
+                    for note in week.notes() {
+                        if !stored.notes().contains(note) {
+                            stored.add_note(note.clone());
+                        }
+                    }
+                }
+                Some(_) => {}
+                None => {
+                    let mut new_week = week.clone();
+                    new_week.entries_mut().clear();
+                    self.push_week(new_week);
+                }
+            }
+
+            for day in week.entries() {
+                let stored = self.weeks.iter().position(|w| w.exist(&day.date()));
+                let day_change = change(&sync::day_key(&day.date()));
+                if matches!(day_change, SyncChange::Unchanged | SyncChange::Local) {
+                    continue;
+                }
+
+                let result = match (stored, day_change) {
+                    // Both changed, merged with the strategy
+                    (Some(_), SyncChange::Both) => self
+                        .insert_day(day.clone(), strategy)
+                        .map(|_| summary.merged_days += 1),
+                    // Only changed by the other machines, the stored day is replaced if the synced day is valid
+                    (Some(index), _) => {
+                        validation::validate_day(day, self.configuration.validation())
+                            .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                            .and_then(|_| self.check_unlocked(day.date(), "sync day"))
+                            .and_then(|_| {
+                                self.weeks[index].remove_listed_day(&day.date());
+                                self.insert_day(day.clone(), MergeStrategy::KeepExisting)
+                            })
+                            .map(|_| summary.updated_items += 1)
+                    }
+                    (None, _) => self
+                        .insert_day(day.clone(), strategy)
+                        .map(|_| summary.added_days += 1),
+                };
+                if let Err(e) = result {
+                    tracing::warn!("Skipping day {}: {}", day.date(), e);
+                    summary.skipped_days += 1;
+                }
+            }
+        }
+
+        let removed_days: Vec<(usize, NaiveDate)> = self
+            .weeks
+            .iter()
+            .enumerate()
+            .flat_map(|(index, w)| w.entries().iter().map(move |d| (index, d.date())))
+            .filter(|(_, date)| removed_remotely(&sync::day_key(date)))
+            .collect();
+        for (index, date) in removed_days {
+            if let Err(e) = self.check_unlocked(date, "remove day") {
+                tracing::warn!("Skipping removal of day {}: {}", date, e);
+                summary.skipped_days += 1;
+                continue;
+            }
+            if let Some(day) = self.weeks[index].get_day_copy(&date) {
+                self.weeks[index].remove_listed_day(&date);
+                tracing::info!("Day {} removed by sync, moved to the trash", date);
+                self.trash.add(TrashedItem::Day { day }, self.clock.now());
+                summary.removed_items += 1;
+            }
+        }
+
+        self.rebuild_indexes();
+        summary
     }
 
-    /// Remove a project from the Butler
-    pub fn remove_project(&mut self, project_name: &str) -> bool {
-        let mut index = 0;
-        let mut found = false;
-        for (i, p) in self.projects.iter().enumerate() {
-            if p.name() == project_name {
-                tracing::debug!("Project found: {}, at index: {}", p.name(), i);
-                index = i;
-                found = true;
-                break;
+    /// Internal function to merge projects and weeks, e.g. of another machine, into the stored data. Projects are
+    /// matched by ID, or by name for projects created on several machines, and the entries not stored in any project
+    /// are added by ID. Days are added by date, days stored in both with other values are merged with the strategy.
+    /// Days and entries in locked periods, and days that can't be merged, are skipped
+    fn merge_data(
+        &mut self,
+        projects: Vec<Project>,
+        weeks: Vec<Week>,
+        strategy: MergeStrategy,
+    ) -> MergeSummary {
+        let mut summary = MergeSummary::default();
+
+        for project in projects {
+            let index = self
+                .projects
+                .iter()
+                .position(|p| p.id() == project.id())
+                .or_else(|| self.project_position(project.name()));
+            let index = match index {
+                Some(index) => index,
+                None => {
+                    tracing::debug!("Adding merged project {}", project.name());
+                    summary.added_projects += 1;
+                    let mut new_project = project.clone();
+                    new_project.entries_mut().clear();
+                    self.push_project(new_project)
+                }
+            };
+
+            for entry in project.entries() {
+                if self.projects.iter().any(|p| p.entry_exists(entry.id())) {
+                    continue;
+                }
+                match self.check_unlocked(entry.work_date(), "merge entry") {
+                    Ok(_) => {
+                        self.projects[index].add_entry(entry.clone());
+                        summary.added_entries += 1;
+                    }
+                    Err(e) => {
+                        tracing::warn!("Skipping entry {}: {}", entry.id(), e);
+                        summary.skipped_entries += 1;
+                    }
+                }
             }
         }
 
-        if found {
-            if Self::prompt_user_confirmation(&format!(
-                "Are you sure you want to remove {}",
-                project_name
-            )) {
-                self.projects.remove(index);
-                tracing::debug!("Project {}, removed", project_name);
-                true
-            } else {
-                tracing::info!("Confirmation not given, aborting");
-                false
+        for week in weeks {
+            // A new week keeps the target and notes of the merged week
+            match self.week_position(week.number(), week.year()) {
+                Some(index) => {
+                    let stored = &mut self.weeks[index];
+                    for note in week.notes() {
+                        if !stored.notes().contains(note) {
+                            stored.add_note(note.clone());
+                        }
+                    }
+                }
+                None => {
+                    let mut new_week = week.clone();
+                    new_week.entries_mut().clear();
+                    self.push_week(new_week);
+                }
+            }
+
+            for day in week.entries() {
+                let stored = self
+                    .week_position(week.number(), week.year())
+                    .and_then(|index| self.weeks[index].get_day_copy(&day.date()));
+                // Only a real conflict is merged or skipped, not the same day stored on both machines
+                if stored
+                    .as_ref()
+                    .is_some_and(|stored| stored.same_values(day))
+                {
+                    continue;
+                }
+
+                match self.insert_day(day.clone(), strategy) {
+                    Ok(_) if stored.is_some() => summary.merged_days += 1,
+                    Ok(_) => summary.added_days += 1,
+                    Err(e) => {
+                        tracing::warn!("Skipping day {}: {}", day.date(), e);
+                        summary.skipped_days += 1;
+                    }
+                }
             }
-        } else {
-            tracing::warn!("Project with name {} not found", project_name);
-            false
         }
+
+        self.rebuild_indexes();
+        summary
     }
 
-    /// Remove an entry from a project
-    pub fn remove_entry(&mut self, project: &str, id: String) -> bool {
-        if self.projects.is_empty() {
-            tracing::warn!("No projects stored, unable to remove entry");
-            return false;
+    /// Merge the projects and weeks of another storage, e.g. copied from another machine, into the stored data. See
+    /// `merge_data` for how the data is merged, a summary of the added, merged and skipped items is shown. Returns
+    /// true if anything was added or merged
+    pub fn merge_storage(
+        &mut self,
+        directory: &str,
+        strategy: MergeStrategy,
+    ) -> Result<bool, ButlerError> {
+        let (projects, weeks) = StorageHandler::read_other_storage(directory).map_err(|e| {
+            ButlerError::StorageFailure(format!("Failed to read storage {}: {}", directory, e))
+        })?;
+        tracing::info!(
+            "Merging {} projects and {} weeks from {}",
+            projects.len(),
+            weeks.len(),
+            directory
+        );
+
+        let summary = self.merge_data(projects, weeks, strategy);
+        let mut table = tables::get_table_merge_summary();
+        for (item, added, merged, skipped) in [
+            ("Projects", summary.added_projects, 0, 0),
+            ("Entries", summary.added_entries, 0, summary.skipped_entries),
+            (
+                "Days",
+                summary.added_days,
+                summary.merged_days,
+                summary.skipped_days,
+            ),
+        ] {
+            table.add_row(vec![
+                Cell::new(i18n::tr(item)),
+                Cell::new(added),
+                Cell::new(merged),
+                Cell::new(skipped),
+            ]);
         }
+        outln!("{}", table);
+        summary.set_porcelain();
 
-        let parsed_id = match Uuid::parse_str(&id) {
-            Ok(parsed_id) => {
-                tracing::debug!("Parsed ID: {}", parsed_id);
-                parsed_id
-            }
-            Err(e) => {
-                tracing::error!("Failed to parse ID: {}", e);
-                return false;
-            }
-        };
+        Ok(summary.changed())
+    }
 
-        // Search for project, if project exists -> search for the entry
-        for p in &mut self.projects {
-            if p.name() == project {
-                if !p.entry_exists(&parsed_id) {
-                    tracing::warn!(
-                        "Entry with ID: {} not found in project: {}",
-                        parsed_id.to_string(),
-                        project
-                    );
-                    return false;
-                }
+    /// Internal function to get the positions of the stored weeks with a target differing from the configured week
+    /// target, from the week and year on if given, in week order. Weeks with a target set for the week are kept
+    fn weeks_with_outdated_target(&self, from: Option<(u32, i32)>) -> Vec<usize> {
+        let target = self.configuration.week_target_hours();
+        let mut positions: Vec<usize> = self
+            .weeks
+            .iter()
+            .enumerate()
+            .filter(|(_, w)| w.target_hours() != target && !w.target_override())
+            .filter(|(_, w)| from.is_none_or(|(week, year)| (w.year(), w.number()) >= (year, week)))
+            .map(|(i, _)| i)
+            .collect();
+        positions.sort_by_key(|i| (self.weeks[*i].year(), self.weeks[*i].number()));
+        positions
+    }
 
-                let entry_cpy = p.get_entry_copy(&parsed_id).unwrap(); // safe since we know it exists
+    /// Update the target of the stored weeks to the configured week target, from the week and year on if given. The
+    /// changed targets are shown and confirmed by the user before updating. Returns the number of updated weeks
+    pub fn reapply_week_targets(&mut self, from: Option<(u32, i32)>) -> Result<usize, ButlerError> {
+        let positions = self.weeks_with_outdated_target(from);
+        if positions.is_empty() {
+            outln!("All stored weeks already have the configured target");
+            return Ok(0);
+        }
 
-                let mut table = Table::new();
-                table.set_content_arrangement(ContentArrangement::Dynamic);
+        let target = self.configuration.week_target_hours();
+        let mut table = tables::get_table_target_reapply();
+        for i in &positions {
+            let w = &self.weeks[*i];
+            table.add_row(vec![
+                Cell::new(w.number()),
+                Cell::new(w.year()),
+                Cell::new(self.display.hours(w.target_hours())),
+                Cell::new(self.display.hours(target)),
+            ]);
+        }
+        outln!("{}", table);
+
+        if !Self::prompt_user_confirmation(&i18n::tr_args(
+            "Update the target of {} weeks to {} hours?",
+            &[&positions.len(), &self.display.hours(target)],
+        )) {
+            return Err(ButlerError::Aborted(
+                "User chose not to update the week targets".to_string(),
+            ));
+        }
 
-                table.set_header(vec![
-                    Cell::new("ID"),
-                    Cell::new("Description"),
-                    Cell::new("Hours"),
-                    Cell::new("Created"),
-                ]);
+        for i in &positions {
+            self.weeks[*i].set_target_hours(target);
+        }
+        Ok(positions.len())
+    }
 
-                table.add_row(vec![
-                    Cell::new(entry_cpy.id().to_string()),
-                    Cell::new(entry_cpy.description()),
-                    Cell::new(entry_cpy.hours().to_string()),
-                    Cell::new(entry_cpy.created().to_string()),
-                ]);
-                println!("{}", table);
+    /// Internal function to update the target of the stored weeks from the current week on to the configured week
+    /// target, without asking the user. Only done if enabled in the configuration
+    fn auto_reapply_week_targets(&mut self) {
+        if !self.configuration.auto_reapply_week_target() {
+            return;
+        }
 
-                if Self::prompt_user_confirmation(&format!(
-                    "Are you sure you want to remove entry {}",
-                    parsed_id
-                )) {
-                    if p.remove_listed_entry(&parsed_id) {
-                        tracing::info!(
-                            "Entry {}, removed from project {}",
-                            parsed_id.to_string(),
-                            project
-                        );
-                        return true;
-                    } else {
-                        tracing::warn!("Failed to remove entry {}", parsed_id.to_string());
-                        return false;
-                    }
-                } else {
-                    tracing::warn!("Confirmation not given, aborting");
-                    return false;
-                }
-            }
+        let target = self.configuration.week_target_hours();
+        let current_week = week_scheme::week_scheme().week_of(self.clock.today());
+        for i in self.weeks_with_outdated_target(Some(current_week)) {
+            let w = &mut self.weeks[i];
+            tracing::info!(
+                "Week {} {} target updated from {} to {} hours",
+                w.number(),
+                w.year(),
+                w.target_hours(),
+                target
+            );
+            w.set_target_hours(target);
         }
-        tracing::warn!("Project with name {} not found", project);
-        false
     }
 
-    pub fn remove_day(&mut self, week: u32, date: String) -> bool {
-        if self.weeks.is_empty() {
-            tracing::warn!("No weeks stored, unable to remove day");
-            return false;
+    /// Show the target status of the week and set a target for the week only, the new status is shown after the
+    /// change. The target is asked for if not given, an empty answer keeps the target and "reset" goes back to the
+    /// configured week target. Returns true if the target was changed
+    pub fn edit_week_target(
+        &mut self,
+        week: u32,
+        year: u32,
+        target: Option<WeekTargetEdit>,
+    ) -> Result<bool, ButlerError> {
+        let index = self.week_position(week, year as i32).ok_or_else(|| {
+            ButlerError::NotFound(format!("Week with number {} and year {}", week, year))
+        })?;
+        if let Some(first_day) = week_scheme::week_scheme().first_day(week, year as i32) {
+            self.check_unlocked(first_day, "change the week target")?;
         }
+        self.display_week_target_status(week, year)?;
 
-        let date_format = "%Y-%m-%d";
-        let parsed_date = match chrono::NaiveDate::parse_from_str(&date, date_format) {
-            Ok(parsed_date) => {
-                tracing::debug!("Parsed date: {}", parsed_date);
-                parsed_date
-            }
-            Err(e) => {
-                tracing::error!("Failed to parse date: {}, for format: y-m-d", e);
-                return false;
+        let target = match target {
+            Some(target) => target,
+            None => {
+                let answer = Self::prompt_user_input(&i18n::tr_args(
+                    "New target hours for week {}, empty to keep {} or \"reset\" for the configured target",
+                    &[&week, &self.display.hours(self.weeks[index].target_hours())],
+                ));
+                match answer.trim() {
+                    "" => {
+                        outln!("{}", i18n::tr("Target not changed"));
+                        return Ok(false);
+                    }
+                    "reset" => WeekTargetEdit::Reset,
+                    hours => WeekTargetEdit::Hours(hours.parse::<f32>().map_err(|_| {
+                        ButlerError::InvalidInput(format!("Invalid target hours: {}", hours))
+                    })?),
+                }
             }
         };
 
-        let year = parsed_date.year();
-
-        // Search for week with both week number and year
-        for w in &mut self.weeks {
-            if w.number() == week && w.year() == year {
-                if !w.exist(&parsed_date) {
-                    tracing::warn!(
-                        "Day with date {} not found in week {} year {}",
-                        date,
-                        week,
-                        year
-                    );
-                    return false;
-                }
-
-                let day_cpy = w.get_day_copy(&parsed_date).unwrap(); // safe since we know it exists
-
-                let mut table = tables::get_table_day();
-
-                table.add_row(vec![
-                    Cell::new(day_cpy.id().to_string()),
-                    Cell::new(day_cpy.week().to_string()),
-                    Cell::new(day_cpy.date().to_string()),
-                    Cell::new(
-                        day_cpy
-                            .starting_time()
-                            .map(|dt| dt.to_string())
-                            .unwrap_or_else(|| "N/A".to_string()),
-                    ),
-                    Cell::new(
-                        day_cpy
-                            .ending_time()
-                            .map(|dt| dt.to_string())
-                            .unwrap_or_else(|| "N/A".to_string()),
-                    ),
-                    Cell::new(day_cpy.hours_paused().to_string()),
-                    Cell::new(day_cpy.hours().to_string()),
-                    Cell::new(day_cpy.closed().to_string()),
-                    Cell::new(day_cpy.extra_info()),
-                ]);
-                println!("{}", table);
-                if Self::prompt_user_confirmation(&format!(
-                    "Are you sure you want to remove day {}",
-                    parsed_date
-                )) {
-                    // Remove day
-                    w.remove_listed_day(&parsed_date);
-                    tracing::info!(
-                        "Day {}, removed from week {} year {}",
-                        parsed_date,
-                        week,
-                        year
-                    );
-                    return true;
-                } else {
-                    tracing::warn!("Confirmation not given, aborting");
-                    return false;
-                }
+        let configured = self.configuration.week_target_hours();
+        match target {
+            WeekTargetEdit::Hours(hours) if hours <= 0.0 => {
+                return Err(ButlerError::InvalidInput(format!(
+                    "Invalid target hours: {}, the target must be more than 0 hours",
+                    hours
+                )))
             }
+            WeekTargetEdit::Hours(hours) => {
+                self.weeks[index].set_target_override(Some(hours), configured)
+            }
+            WeekTargetEdit::Reset => self.weeks[index].set_target_override(None, configured),
         }
-
-        tracing::warn!(
-            "Day with date {} not found in week {} year {}",
-            date,
+        tracing::info!(
+            "Target of week {} {} set to {} hours",
             week,
-            year
+            year,
+            self.weeks[index].target_hours()
         );
-        false
+
+        self.display_week_target_status(week, year)?;
+        Ok(true)
     }
 
-    pub fn display_week_target_status(&self, week: u32, year: u32) -> bool {
+    pub fn display_week_target_status(&self, week: u32, year: u32) -> Result<(), ButlerError> {
         if self.weeks.is_empty() {
-            tracing::warn!("No weeks stored, unable to display weekly target status");
-            return false;
+            return Err(ButlerError::NotFound(
+                "No weeks stored, unable to display weekly target status".to_string(),
+            ));
         }
 
-        for w in &self.weeks {
-            if w.number() == week && w.year() as u32 == year {
-                let status = WeeklyTargetStatus::new(w, &w.target_hours());
-                let mut table = tables::get_table_target_week();
-                table.add_row(vec![
-                    Cell::new(week),
-                    Cell::new(status.target_hours().to_string()),
-                    Cell::new(status.status_hours().to_string()),
-                    Cell::new(status.percentage().to_string()),
-                    Cell::new(status.target_status_string()),
-                    if status.remaining_hours() > &0.0 {
-                        Cell::new(status.remaining_hours().to_string())
-                    } else {
-                        Cell::new("0.0")
-                    },
-                    if status.remaining_hours() < &0.0 {
-                        Cell::new(status.hours_difference().to_string())
-                    } else {
-                        Cell::new("0.0")
-                    },
-                    Cell::new(status.target_set_method_string()),
-                ]);
+        if let Some(index) = self.week_position(week, year as i32) {
+            let w = &self.weeks[index];
+            let status = WeeklyTargetStatus::new(w, &w.target_hours());
+            let mut table = tables::get_table_target_week();
+            table.add_row(vec![
+                Cell::new(week),
+                Cell::new(self.display.hours(*status.target_hours())),
+                Cell::new(self.display.hours(*status.status_hours())),
+                Cell::new(status.percentage().to_string()),
+                self.display
+                    .target_status_cell(&status.target_status_string()),
+                if status.remaining_hours() > &0.0 {
+                    Cell::new(self.display.hours(*status.remaining_hours()))
+                } else {
+                    Cell::new("0.0")
+                },
+                if status.remaining_hours() < &0.0 {
+                    Cell::new(self.display.hours(*status.hours_difference()))
+                } else {
+                    Cell::new("0.0")
+                },
+                Cell::new(status.target_set_method_string()),
+            ]);
 
-                println!("{}", table);
-                return true;
-            }
+            outln!("{}", table);
+            porcelain::set("week", week);
+            porcelain::set("year", year);
+            porcelain::set("target_status", ReportTargetStatus::from_week(&status));
+            return Ok(());
         }
 
-        tracing::warn!("Week with number {} not found", week);
-        false
+        Err(ButlerError::NotFound(format!(
+            "Week with number {} and year {}",
+            week, year
+        )))
     }
 
-    pub fn display_month_target_status(&self, month_number: u32, year: u32) -> bool {
+    pub fn display_month_target_status(
+        &self,
+        month_number: u32,
+        year: u32,
+    ) -> Result<(), ButlerError> {
         if !(1..=12).contains(&month_number) {
-            tracing::error!("Invalid month number: {}", month_number);
-            return false;
+            return Err(ButlerError::InvalidInput(format!(
+                "Invalid month number: {}",
+                month_number
+            )));
         }
 
-        let days_vec = self.get_days_in_month_for_year(month_number, year);
-        if days_vec.is_empty() {
-            tracing::warn!("No days found for month: {}", month_number);
-            return false;
-        }
-
-        let weeks_in_month = self.get_weeks_in_month(month_number);
-        if weeks_in_month.is_empty() {
-            tracing::warn!("No weeks found for month: {}", month_number);
-            return false;
+        let days = self.days_in_month_for_year(month_number, year);
+        if days.clone().next().is_none() {
+            return Err(ButlerError::NotFound(format!(
+                "No days for month: {}",
+                month_number
+            )));
         }
 
-        let mut month_target_hours: f32 = 0.0;
-        if self.configuration.weekly_target_for_month() {
-            tracing::debug!("Calculating month target hours based on weekly target hours");
-            // Calculate month target hours based on number of weeks in month and weekly target hours
-            for w in &weeks_in_month {
-                month_target_hours += w.target_hours();
-            }
-        } else {
-            tracing::debug!("Calculating month target hours based on configuration value");
-            month_target_hours = self.configuration.month_target_hours();
+        if self.weeks_in_month(month_number).next().is_none() {
+            return Err(ButlerError::NotFound(format!(
+                "No weeks for month: {}",
+                month_number
+            )));
         }
 
-        let status = MonthlyTargetStatus::new(&days_vec, &month_target_hours);
+        let status = MonthlyTargetStatus::new(days, &self.month_target_hours(month_number));
         let mut table = tables::get_table_target_month();
 
         table.add_row(vec![
             Cell::new(month_number),
-            Cell::new(status.target_hours().to_string()),
-            Cell::new(status.status_hours().to_string()),
+            Cell::new(self.display.hours(*status.target_hours())),
+            Cell::new(self.display.hours(*status.status_hours())),
             Cell::new(status.percentage().to_string()),
-            Cell::new(status.target_status_string()),
+            self.display
+                .target_status_cell(&status.target_status_string()),
             if status.remaining_hours() > &0.0 {
-                Cell::new(status.remaining_hours().to_string())
+                Cell::new(self.display.hours(*status.remaining_hours()))
             } else {
                 Cell::new("0.0")
             },
             if status.remaining_hours() < &0.0 {
-                Cell::new(status.hours_difference().to_string())
+                Cell::new(self.display.hours(*status.hours_difference()))
             } else {
                 Cell::new("0.0")
             },
             Cell::new(status.target_set_method_string()),
         ]);
 
-        println!("{}", table);
-        true
+        outln!("{}", table);
+        porcelain::set("month", month_number);
+        porcelain::set("year", year);
+        porcelain::set("target_status", ReportTargetStatus::from_month(&status));
+        Ok(())
+    }
+
+    /// Display the forecast of the month target, based on the average daily hours so far and the remaining working days
+    pub fn display_month_target_forecast(
+        &self,
+        month_number: u32,
+        year: u32,
+    ) -> Result<(), ButlerError> {
+        if !(1..=12).contains(&month_number) {
+            return Err(ButlerError::InvalidInput(format!(
+                "Invalid month number: {}",
+                month_number
+            )));
+        }
+
+        let forecast = MonthlyForecast::new(
+            self.days_in_month_for_year(month_number, year),
+            &self.month_target_hours(month_number),
+            month_number,
+            year as i32,
+            self.clock.today(),
+        );
+
+        let mut table = tables::get_table_target_forecast();
+        table.add_row(vec![
+            Cell::new(month_number),
+            Cell::new(self.display.hours(*forecast.target_hours())),
+            Cell::new(self.display.hours(*forecast.status_hours())),
+            Cell::new(self.display.hours(*forecast.average_daily_hours())),
+            Cell::new(forecast.remaining_working_days().to_string()),
+            Cell::new(self.display.hours(*forecast.forecast_hours())),
+            self.display
+                .target_status_cell(&forecast.forecast_status_string()),
+            match forecast.required_daily_hours() {
+                Some(hours) => Cell::new(self.display.hours(hours)),
+                None => Cell::new("N/A"),
+            },
+        ]);
+
+        outln!("{}", table);
+        Ok(())
+    }
+
+    /// Getter for `configuration`
+    pub fn configuration(&self) -> &AppConfiguration {
+        &self.configuration
     }
 
     pub fn dump_configuration_to_terminal(&self, configuration_file_path: String) {
         let config_str = self.configuration.get_as_string();
-        println!(
+        outln!(
             "Current configuration (from {}):\n{}",
-            configuration_file_path, config_str
+            configuration_file_path,
+            config_str
         );
     }
 
-    pub fn dump_configuration_to_file(&self, configuration_file_path: String, output_file: String) {
+    pub fn dump_configuration_to_file(
+        &self,
+        configuration_file_path: String,
+        output_file: String,
+    ) -> Result<(), ButlerError> {
         let config_str = self.configuration.get_as_string();
         let output = format!(
             "Current configuration (from {}):\n{}",
@@ -1184,67 +5732,380 @@ impl Butler {
         match std::fs::write(&output_file, output) {
             Ok(_) => {
                 tracing::debug!("Configuration successfully dumped to file: {}", output_file);
+                Ok(())
             }
-            Err(e) => {
-                tracing::error!(
-                    "Failed to write configuration to file {}: {}",
-                    output_file,
-                    e
-                );
+            Err(e) => Err(ButlerError::StorageFailure(format!(
+                "Failed to write configuration to file {}: {}",
+                output_file, e
+            ))),
+        }
+    }
+
+    /// Print the JSON Schema of a document, "import" or "report", or write it to a file
+    pub fn dump_schema(&self, document: &str, output: Option<&str>) -> Result<(), ButlerError> {
+        let schema = schema::schema(document).ok_or_else(|| {
+            ButlerError::InvalidInput(format!(
+                "Unknown schema document {}, valid documents are: {}",
+                document,
+                K_SCHEMA_DOCUMENTS.join(", ")
+            ))
+        })?;
+
+        match output {
+            Some(file_path) => {
+                std::fs::write(file_path, schema).map_err(|e| {
+                    ButlerError::StorageFailure(format!(
+                        "Failed to write schema to file {}: {}",
+                        file_path, e
+                    ))
+                })?;
+                porcelain::set("schema", file_path);
+                outln!("{}", file_path);
             }
+            None => print!("{}", schema),
         }
+        Ok(())
     }
 
-    fn get_days_in_month(&self, month: u32) -> Vec<Day> {
-        let mut days = Vec::new();
-        for w in &self.weeks {
-            for d in w.entries() {
-                if d.month() == month {
-                    days.push(d.clone());
-                }
+    /// Internal function to iterate the days of a month in all years, the days are not copied
+    fn days_in_month(&self, month: u32) -> impl Iterator<Item = &Day> + Clone + '_ {
+        self.weeks.iter().flat_map(move |w| w.days_in_month(month))
+    }
+
+    /// Internal function to get the notes of all weeks with notes, keyed on year and week number
+    fn week_notes(&self) -> BTreeMap<(i32, u32), Vec<String>> {
+        self.weeks
+            .iter()
+            .filter(|w| !w.notes().is_empty())
+            .map(|w| ((w.year(), w.number()), w.notes().clone()))
+            .collect()
+    }
+
+    /// Internal function to iterate the days of a month in a year, the days are not copied
+    fn days_in_month_for_year(
+        &self,
+        month: u32,
+        year: u32,
+    ) -> impl Iterator<Item = &Day> + Clone + '_ {
+        self.days_in_month(month)
+            .filter(move |d| d.year() as u32 == year)
+    }
+
+    /// Get the days with a date in the year
+    fn days_in_year(&self, year: i32) -> impl Iterator<Item = &Day> + Clone + '_ {
+        self.weeks
+            .iter()
+            .flat_map(|w| w.entries())
+            .filter(move |d| d.year() == year)
+    }
+
+    /// Get the weeks with days in a month
+    fn weeks_in_month(&self, month: u32) -> impl Iterator<Item = &Week> + Clone + '_ {
+        self.weeks
+            .iter()
+            .filter(move |w| w.days_in_month(month).next().is_some())
+    }
+
+    /// Internal function to print the project work done on the dates matching the filter, nothing is printed if no work is found
+    fn print_project_hours<F: Fn(NaiveDate) -> bool>(&self, date_filter: F) {
+        let mut table = tables::get_table_project_hours();
+        let mut found = false;
+
+        for p in &self.projects {
+            let entries: Vec<&Entry> = p
+                .entries()
+                .iter()
+                .filter(|e| date_filter(e.work_date()))
+                .collect();
+
+            if entries.is_empty() {
+                continue;
             }
+
+            found = true;
+            let hours: f32 = entries.iter().map(|e| e.hours()).sum();
+            table.add_row(vec![
+                Cell::new(p.name()),
+                Cell::new(entries.len().to_string()),
+                Cell::new(self.display.hours(hours)),
+            ]);
+        }
+
+        if found {
+            outln!("Project work:");
+            outln!("{}", table);
         }
-        days
     }
 
-    fn get_days_in_month_for_year(&self, month: u32, year: u32) -> Vec<Day> {
-        let mut days = Vec::new();
-        for w in &self.weeks {
-            for d in w.entries() {
-                if d.month() == month && d.year() as u32 == year {
-                    days.push(d.clone());
-                }
+    /// Internal function to get the target hours for a month, from the weekly targets or the configuration
+    fn month_target_hours(&self, month_number: u32) -> f32 {
+        let mut month_target_hours: f32 = 0.0;
+        if self.configuration.weekly_target_for_month() {
+            tracing::debug!("Calculating month target hours based on weekly target hours");
+            // Calculate month target hours based on number of weeks in month and weekly target hours
+            for w in self.weeks_in_month(month_number) {
+                month_target_hours += w.target_hours();
             }
+        } else {
+            tracing::debug!("Calculating month target hours based on configuration value");
+            month_target_hours = self.configuration.month_target_hours();
         }
-        days
+        month_target_hours
     }
 
-    // Prep for future functionality, when the year report is implemented
-    #[allow(dead_code)]
-    fn get_days_in_year(&self, year: i32) -> Vec<Day> {
-        let mut days = Vec::new();
-        for w in &self.weeks {
-            for d in w.entries() {
-                if d.year() == year {
-                    days.push(d.clone());
-                }
+    /// Internal support function to parse the columns selected for a report, None keeps the default columns
+    fn parse_report_columns(
+        columns: Option<&str>,
+        available: &[ReportColumn],
+    ) -> Result<Option<Vec<ReportColumn>>, ButlerError> {
+        match columns {
+            Some(c) => report::parse_report_columns(c, available)
+                .map(Some)
+                .map_err(|e| ButlerError::InvalidInput(e.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Internal support function to get the template file of a report, required for the template format
+    fn template_path(options: &ReportOptions) -> Result<String, ButlerError> {
+        options.template.clone().ok_or_else(|| {
+            ButlerError::InvalidInput("The template format requires a template file".to_string())
+        })
+    }
+
+    /// Internal function to get the format of a renderer of the configuration, for the formats that aren't built in
+    fn renderer_format(&self, format: &str) -> Result<ReportFormat, ButlerError> {
+        match self.report_mngr.renderers().get(format) {
+            Some(renderer) => Ok(ReportFormat::Renderer(renderer.name().to_string())),
+            None => Err(ButlerError::InvalidInput(format!(
+                "Invalid format: {}, see report --list-formats for the valid formats",
+                format
+            ))),
+        }
+    }
+
+    /// List the report formats, the built-in formats and the renderers of the configuration
+    pub fn list_report_formats(&self) {
+        let mut table = Table::new();
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec![
+            Cell::new("Format"),
+            Cell::new("Extension"),
+            Cell::new("Description"),
+        ]);
+
+        for (format, description) in K_BUILTIN_FORMATS {
+            let extension = match format {
+                "text" => "txt",
+                "template" => "",
+                _ => format,
+            };
+            table.add_row(vec![
+                Cell::new(format),
+                Cell::new(extension),
+                Cell::new(description),
+            ]);
+        }
+        for renderer in self.report_mngr.renderers().renderers() {
+            table.add_row(vec![
+                Cell::new(renderer.name()),
+                Cell::new(renderer.extension()),
+                Cell::new(renderer.description()),
+            ]);
+        }
+
+        outln!("{}", table);
+    }
+
+    /// Internal function to handle a generated report, the report file is emailed when an address is given and the
+    /// summary of the report is copied to the clipboard when asked for
+    fn deliver_report(
+        &self,
+        generated: Result<String, ReportGenerationFailure>,
+        options: &ReportOptions,
+        summary: Option<&ReportSummary>,
+    ) -> Result<(), ButlerError> {
+        let file_path = generated.map_err(|e| ButlerError::ReportFailure(e.to_string()))?;
+
+        // Only the path on stdout, so it can be used by scripts
+        porcelain::set("report", &file_path);
+        porcelain::set("dry_run", self.report_mngr.dry_run());
+        if self.report_mngr.dry_run() {
+            outln!("Dry run, report not written: {}", file_path);
+            return Ok(());
+        }
+        outln!("{}", file_path);
+
+        if let Some(to) = &options.email {
+            mailer::send_report(self.configuration.email(), to, &file_path)
+                .map_err(|e| ButlerError::ReportFailure(e.to_string()))?;
+            tracing::info!("Report {} emailed to {}", file_path, to);
+        }
+
+        if self.publish_reports || self.configuration.publish().automatic {
+            let url = publisher::publish(self.configuration.publish(), &file_path)
+                .map_err(|e| ButlerError::ReportFailure(e.to_string()))?;
+            tracing::info!("Report {} published to {}", file_path, url);
+            porcelain::set("published", &url);
+        }
+
+        if options.clipboard {
+            let summary = summary.ok_or_else(|| {
+                ButlerError::InvalidInput("The report has no summary to copy".to_string())
+            })?;
+            let tool = clipboard::copy(&self.report_mngr.markdown_summary(summary))
+                .map_err(|e| ButlerError::ReportFailure(e.to_string()))?;
+            tracing::info!("Summary copied to the clipboard with {}", tool);
+            porcelain::set("clipboard", true);
+        }
+        Ok(())
+    }
+
+    /// Internal function to write a summary report
+    fn generate_summary(
+        &self,
+        file_prefix: &str,
+        format: ReportFormat,
+        summary: &ReportSummary,
+        options: &ReportOptions,
+    ) -> Result<(), ButlerError> {
+        self.storage_handler.create_report_dir().map_err(|e| {
+            ButlerError::StorageFailure(format!("Failed to create report directory: {}", e))
+        })?;
+
+        self.deliver_report(
+            self.report_mngr
+                .generate_summary_report(file_prefix, format, summary),
+            options,
+            Some(summary),
+        )
+    }
+
+    /// Internal support function to validate a project path, no level can be empty
+    fn validate_project_path(name: &str) -> Result<(), ButlerError> {
+        if name
+            .split(project::K_PROJECT_PATH_SEPARATOR)
+            .any(|level| level.trim().is_empty())
+        {
+            return Err(ButlerError::InvalidInput(format!(
+                "Project name {} contains an empty level",
+                name
+            )));
+        }
+        Ok(())
+    }
+
+    /// Internal function to create the parent projects of a project path that doesn't exist yet
+    fn add_missing_parent_projects(&mut self, name: &str) {
+        let mut parents = Vec::new();
+        let mut current = Project::new(name.to_string(), None);
+        while let Some(parent) = current.parent_name() {
+            parents.push(parent.to_string());
+            current = Project::new(parent.to_string(), None);
+        }
+
+        // Create from the top level and down
+        for parent in parents.into_iter().rev() {
+            if !self.projects.iter().any(|p| p.name() == parent) {
+                tracing::info!("Adding missing parent project: {}", parent);
+                self.push_project(Project::new(parent, None));
             }
         }
-        days
     }
 
-    /// Get the weeks in a month
-    fn get_weeks_in_month(&self, month: u32) -> Vec<Week> {
-        let mut weeks = Vec::new();
-        for w in &self.weeks {
-            for d in w.entries() {
-                if d.month() == month {
-                    weeks.push(w.clone());
-                    break; // No need to check other days in the week
+    /// Internal function to get all sub-projects (at any depth) of a project
+    fn sub_projects(&self, project_name: &str) -> Vec<&Project> {
+        self.projects
+            .iter()
+            .filter(|p| p.is_descendant_of(project_name))
+            .collect()
+    }
+
+    /// Internal function to get the project hours, including the hours of all sub-projects
+    fn rolled_up_hours(&self, project: &Project) -> f32 {
+        project.total_hours()
+            + self
+                .sub_projects(project.name())
+                .iter()
+                .map(|p| p.total_hours())
+                .sum::<f32>()
+    }
+
+    /// Internal function to get the ID of the selected entry. The positions count the entries in the order they are
+    /// listed, an ID prefix must match a single entry. The scope, e.g. "project: acme", is used in the messages
+    fn select_entry(
+        entries: &[&Entry],
+        selector: &EntrySelector,
+        scope: &str,
+    ) -> Result<Uuid, ButlerError> {
+        let selected = match selector {
+            EntrySelector::Id(id) => {
+                if let Ok(parsed_id) = Uuid::parse_str(id) {
+                    return Ok(parsed_id);
+                }
+
+                let prefix = id.trim().to_lowercase();
+                if prefix.len() < K_MIN_ID_PREFIX_LENGTH {
+                    return Err(ButlerError::InvalidInput(format!(
+                        "ID prefix '{}' is too short, at least {} characters are needed",
+                        id, K_MIN_ID_PREFIX_LENGTH
+                    )));
+                }
+
+                let matching: Vec<&Entry> = entries
+                    .iter()
+                    .copied()
+                    .filter(|e| e.id().to_string().starts_with(&prefix))
+                    .collect();
+                match matching.as_slice() {
+                    [] => None,
+                    [entry] => Some(*entry),
+                    _ => {
+                        return Err(ButlerError::InvalidInput(format!(
+                            "ID prefix '{}' is ambiguous, matching entries: {}",
+                            id,
+                            matching
+                                .iter()
+                                .map(|e| e.id().to_string())
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        )))
+                    }
                 }
             }
+            EntrySelector::Nth(position) => position
+                .checked_sub(1)
+                .and_then(|i| entries.get(i))
+                .copied(),
+            EntrySelector::Last => entries.last().copied(),
+        };
+
+        selected.map(|e| *e.id()).ok_or_else(|| match selector {
+            EntrySelector::Id(id) => {
+                ButlerError::NotFound(format!("Entry with ID: {} in {}", id, scope))
+            }
+            EntrySelector::Nth(position) => ButlerError::NotFound(format!(
+                "Entry number {} in {}, {} entries listed",
+                position,
+                scope,
+                entries.len()
+            )),
+            EntrySelector::Last => ButlerError::NotFound(format!("Entries in {}", scope)),
+        })
+    }
+
+    /// Internal support function to parse an ID string to Uuid
+    fn parse_id(id: &str) -> Result<Uuid, ButlerError> {
+        match Uuid::parse_str(id) {
+            Ok(parsed_id) => {
+                tracing::debug!("Parsed ID: {}", parsed_id);
+                Ok(parsed_id)
+            }
+            Err(e) => Err(ButlerError::InvalidInput(format!(
+                "Failed to parse ID '{}': {}",
+                id, e
+            ))),
         }
-        weeks
     }
 
     /// Accepted input:
@@ -1281,3 +6142,96 @@ impl Butler {
         }
     }
 }
+
+/// Get the names closest to the name by Levenshtein distance, closest first. Only names within a third of the length of
+/// the name are close enough, at most K_MAX_PROJECT_SUGGESTIONS names are returned
+fn closest_names<'a>(name: &str, names: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let name = name.to_lowercase();
+    let max_distance = (name.chars().count() / 3).max(1);
+
+    let mut candidates: Vec<(usize, &String)> = names
+        .map(|stored| (levenshtein(&name, &stored.to_lowercase()), stored))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    candidates.sort();
+
+    candidates
+        .into_iter()
+        .take(K_MAX_PROJECT_SUGGESTIONS)
+        .map(|(_, stored)| stored.clone())
+        .collect()
+}
+
+/// Levenshtein distance between two strings, the number of single character edits to turn one into the other
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Internal function to get owned names of the tests
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn levenshtein_distances() {
+        assert_eq!(levenshtein("", ""), 0);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("abc", ""), 3);
+        assert_eq!(levenshtein("butler", "butler"), 0);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("flaw", "lawn"), 2);
+        // Case is not ignored, the callers lowercase the names
+        assert_eq!(levenshtein("Butler", "butler"), 1);
+    }
+
+    #[test]
+    fn levenshtein_counts_characters_not_bytes() {
+        assert_eq!(levenshtein("å", "a"), 1);
+        assert_eq!(levenshtein("möte", "mote"), 1);
+        assert_eq!(levenshtein("日本語", "日本"), 1);
+        assert_eq!(levenshtein("🦀", ""), 1);
+        assert_eq!(levenshtein("grüße", "grüße"), 0);
+    }
+
+    #[test]
+    fn suggestions_are_cut_off_by_the_name_length() {
+        let stored = names(&["backend", "frontend", "support", "Backlog"]);
+
+        // A third of the length of the given name, 2 edits for a name of 6 characters and 1 for 5 characters
+        assert_eq!(closest_names("bakend", stored.iter()), names(&["backend"]));
+        assert_eq!(closest_names("bakcend", stored.iter()), names(&["backend"]));
+        assert!(closest_names("bcknd", stored.iter()).is_empty());
+        // Case is ignored, the stored name is returned as stored
+        assert_eq!(closest_names("BACKLOG", stored.iter()), names(&["Backlog"]));
+        // Short names allow at least one edit
+        assert_eq!(
+            closest_names("ab", names(&["ac", "abc", "xyz"]).iter()),
+            names(&["abc", "ac"])
+        );
+        assert!(closest_names("", stored.iter()).is_empty());
+    }
+
+    #[test]
+    fn suggestions_are_closest_first_and_limited() {
+        let stored = names(&["projectc", "projecta", "project", "projectb", "projectd"]);
+        let suggestions = closest_names("project", stored.iter());
+        assert_eq!(suggestions.len(), K_MAX_PROJECT_SUGGESTIONS);
+        assert_eq!(suggestions, names(&["project", "projecta", "projectb"]));
+    }
+}
@@ -0,0 +1,301 @@
+/*
+ * File: caldav.rs
+ * Description: Import of meetings from a CalDAV calendar. The events of a period are fetched with a calendar query
+ *              sent with curl, and read from the iCalendar data of the response.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use std::error::Error;
+use std::fmt;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::config::CalDavConfig;
+use crate::mailer::quote_config_value;
+
+/// Environment variable replacing the CalDAV password in the configuration
+const K_CALDAV_PASSWORD_ENV: &str = "TIME_BUTLER_CALDAV_PASSWORD";
+
+/// Format of the UTC times in the calendar query and the iCalendar data
+const K_ICAL_UTC_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// Format of the local times in the iCalendar data
+const K_ICAL_LOCAL_FORMAT: &str = "%Y%m%dT%H%M%S";
+
+/// Error for importing from a CalDAV calendar
+#[derive(Debug)]
+pub struct CalDavError(String);
+
+/// Implement Display trait for CalDavError
+impl fmt::Display for CalDavError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to import the calendar: {}", self.0)
+    }
+}
+
+/// Implement Error trait for CalDavError
+impl Error for CalDavError {}
+
+/// Meeting of the calendar, an event with a starting and ending time
+#[derive(Debug, Clone)]
+pub struct Meeting {
+    /// Unique ID of the event, with the start for the occurrences of a recurring event
+    uid: String,
+    /// Summary of the event
+    summary: String,
+    /// Starting time
+    start: DateTime<Local>,
+    /// Ending time
+    end: DateTime<Local>,
+}
+
+impl Meeting {
+    /// Getter for `uid`
+    pub fn uid(&self) -> &str {
+        &self.uid
+    }
+
+    /// Getter for `summary`
+    pub fn summary(&self) -> &str {
+        &self.summary
+    }
+
+    /// Date of the meeting, the date it starts
+    pub fn date(&self) -> NaiveDate {
+        self.start.date_naive()
+    }
+
+    /// Length of the meeting in hours
+    pub fn hours(&self) -> f32 {
+        (self.end - self.start).num_minutes() as f32 / 60.0
+    }
+
+    /// Check if the summary contains one of the keywords, case insensitive. Any meeting matches without keywords
+    pub fn matches(&self, keywords: &[String]) -> bool {
+        let summary = self.summary.to_lowercase();
+        keywords.is_empty() || keywords.iter().any(|k| summary.contains(&k.to_lowercase()))
+    }
+}
+
+/// Internal function to get the calendar query of the events between the dates, the recurring events are expanded by
+/// the server
+fn calendar_query(from: NaiveDate, to: NaiveDate) -> String {
+    let utc = |date: NaiveDate| {
+        Local
+            .from_local_datetime(&date.and_time(chrono::NaiveTime::MIN))
+            .earliest()
+            .map(|t| t.with_timezone(&Utc).format(K_ICAL_UTC_FORMAT).to_string())
+            .unwrap_or_default()
+    };
+    let (start, end) = (utc(from), utc(to + Duration::days(1)));
+    format!(
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<C:calendar-query xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <D:prop>
+    <C:calendar-data>
+      <C:expand start="{start}" end="{end}"/>
+    </C:calendar-data>
+  </D:prop>
+  <C:filter>
+    <C:comp-filter name="VCALENDAR">
+      <C:comp-filter name="VEVENT">
+        <C:time-range start="{start}" end="{end}"/>
+      </C:comp-filter>
+    </C:comp-filter>
+  </C:filter>
+</C:calendar-query>"#
+    )
+}
+
+/// Fetch the meetings of the calendar between the dates, inclusive. All-day and cancelled events aren't meetings
+pub fn fetch_meetings(
+    config: &CalDavConfig,
+    url: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<Meeting>, CalDavError> {
+    let password = std::env::var(K_CALDAV_PASSWORD_ENV).unwrap_or_else(|_| config.password.clone());
+    tracing::debug!("Fetching the events of {} from {} to {}", url, from, to);
+
+    let result = Command::new("curl")
+        .arg("--silent")
+        .arg("--show-error")
+        .arg("--fail")
+        .arg("--request")
+        .arg("REPORT")
+        .arg("--header")
+        .arg("Depth: 1")
+        .arg("--header")
+        .arg("Content-Type: application/xml; charset=utf-8")
+        .arg("--url")
+        .arg(url)
+        .arg("--data")
+        .arg(calendar_query(from, to))
+        // The credentials are given on stdin, to keep them out of the process list
+        .arg("--config")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            if let Some(mut stdin) = child.stdin.take() {
+                if !config.username.is_empty() {
+                    writeln!(
+                        stdin,
+                        "user = \"{}:{}\"",
+                        quote_config_value(&config.username),
+                        quote_config_value(&password)
+                    )?;
+                }
+            }
+            child.wait_with_output()
+        });
+
+    let output = match result {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => {
+            return Err(CalDavError(format!(
+                "the calendar query to {} failed: {}",
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )))
+        }
+        Err(e) => return Err(CalDavError(format!("could not run curl: {}", e))),
+    };
+
+    let meetings = parse_meetings(&String::from_utf8_lossy(&output.stdout));
+    Ok(meetings
+        .into_iter()
+        .filter(|m| m.date() >= from && m.date() <= to)
+        .collect())
+}
+
+/// Internal function to replace the XML escapes of the calendar data in the response
+fn unescape_xml(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#13;", "\r")
+        .replace("&#xD;", "\r")
+        .replace("&amp;", "&")
+}
+
+/// Internal function to unescape an iCalendar text value, e.g. "\," in a summary
+fn unescape_ical(text: &str) -> String {
+    text.replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
+
+/// Internal function to parse an iCalendar time, UTC or local (also with a TZID, which is taken as local time). Dates
+/// without a time are all-day events and give None
+fn parse_ical_time(value: &str) -> Option<DateTime<Local>> {
+    if let Ok(utc) = NaiveDateTime::parse_from_str(value, K_ICAL_UTC_FORMAT) {
+        return Some(Utc.from_utc_datetime(&utc).with_timezone(&Local));
+    }
+    NaiveDateTime::parse_from_str(value, K_ICAL_LOCAL_FORMAT)
+        .ok()
+        .and_then(|t| Local.from_local_datetime(&t).earliest())
+}
+
+/// Internal function to parse an iCalendar duration, e.g. "PT1H30M"
+fn parse_ical_duration(value: &str) -> Option<Duration> {
+    let value = value.strip_prefix('P')?;
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in value.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            unit => {
+                let n: i64 = number.parse().ok()?;
+                number.clear();
+                total += match unit {
+                    'W' => Duration::weeks(n),
+                    'D' => Duration::days(n),
+                    'H' => Duration::hours(n),
+                    'M' => Duration::minutes(n),
+                    'S' => Duration::seconds(n),
+                    _ => return None,
+                };
+            }
+        }
+    }
+    Some(total)
+}
+
+/// Parse the meetings of the iCalendar data in a calendar query response
+pub fn parse_meetings(response: &str) -> Vec<Meeting> {
+    let data = unescape_xml(response);
+    // Folded lines continue with a space or tab on the next line
+    let unfolded = data
+        .replace("\r\n ", "")
+        .replace("\r\n\t", "")
+        .replace("\n ", "")
+        .replace("\n\t", "");
+
+    let mut meetings = Vec::new();
+    let mut event: Option<Vec<(String, String)>> = None;
+    for line in unfolded.lines().map(|l| l.trim_end_matches('\r')) {
+        match line.trim() {
+            "BEGIN:VEVENT" => event = Some(Vec::new()),
+            "END:VEVENT" => {
+                if let Some(meeting) = event.take().and_then(|p| meeting_of_event(&p)) {
+                    meetings.push(meeting);
+                }
+            }
+            _ => {
+                if let (Some(properties), Some((name, value))) =
+                    (event.as_mut(), line.split_once(':'))
+                {
+                    // The parameters are dropped, e.g. "DTSTART;TZID=Europe/Stockholm"
+                    let name = name.split(';').next().unwrap_or_default().to_uppercase();
+                    properties.push((name, value.to_string()));
+                }
+            }
+        }
+    }
+    meetings
+}
+
+/// Internal function to create the meeting of the properties of an event
+fn meeting_of_event(properties: &[(String, String)]) -> Option<Meeting> {
+    let property = |name: &str| {
+        properties
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.trim())
+    };
+
+    if property("STATUS").is_some_and(|s| s.eq_ignore_ascii_case("CANCELLED")) {
+        return None;
+    }
+    let start = parse_ical_time(property("DTSTART")?)?;
+    let end = match property("DTEND") {
+        Some(end) => parse_ical_time(end)?,
+        None => start + parse_ical_duration(property("DURATION")?)?,
+    };
+    if end <= start {
+        return None;
+    }
+
+    let uid = property("UID").unwrap_or_default();
+    // The occurrences of an expanded recurring event have the same UID
+    let uid = match property("RECURRENCE-ID") {
+        Some(recurrence) => format!("{}/{}", uid, recurrence),
+        None => uid.to_string(),
+    };
+    Some(Meeting {
+        uid,
+        summary: unescape_ical(property("SUMMARY").unwrap_or_default()),
+        start,
+        end,
+    })
+}
@@ -8,6 +8,9 @@
 
 use clap::{Parser, Subcommand};
 
+/// Value accepted by week/month options meaning the current week/month
+pub const K_CURRENT_PERIOD: &str = "current";
+
 /// Struct to define the CLI structure
 #[derive(Parser)]
 #[command(
@@ -22,12 +25,30 @@ pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
     /// Verbose logging flag
-    #[arg(short, long, action = clap::ArgAction::SetTrue)]
+    #[arg(short, long, action = clap::ArgAction::SetTrue, conflicts_with = "quiet")]
     pub verbose: bool,
+    /// Quiet mode, suppress info logging and only print essential output
+    #[arg(short, long, action = clap::ArgAction::SetTrue)]
+    pub quiet: bool,
     /// Generate JSON output from logging
     #[arg(short, long, action = clap::ArgAction::SetTrue)]
     pub json: bool,
-    /// Configuration file path. Absolute path.
+    /// Print the result of the command as a single JSON document on stdout, other output and the logs go to stderr
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub porcelain: bool,
+    /// Disable colors in table output, same as setting the NO_COLOR environment variable
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub no_color: bool,
+    /// Show hours as "decimal" (7.75) or "hh:mm" (7:45), overrides the hours format in the configuration
+    #[arg(long)]
+    pub duration_format: Option<String>,
+    /// Allow adding, modifying and removing days and entries in locked periods
+    #[arg(long, action = clap::ArgAction::SetTrue)]
+    pub force_unlock: bool,
+    /// Run the command as if the current time is the given time, "YYYY-MM-DD" or "YYYY-MM-DD HH:MM"
+    #[arg(long)]
+    pub as_of: Option<String>,
+    /// Configuration file path. Absolute path, the format is given by the extension: .json, .toml or .yaml
     #[arg(short, long, default_value = "tb-config.json")]
     pub config: String,
 }
@@ -43,25 +64,87 @@ pub enum Commands {
     /// Generate a time overview report
     Report {
         #[command(subcommand)]
-        entity: ReportSubcommands,
+        entity: Option<ReportSubcommands>,
+
+        /// List the report formats, the built-in formats and the renderers of the configuration
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        list_formats: bool,
+
+        /// Print the path the report would be written to, without writing it
+        #[arg(long, global = true, action = clap::ArgAction::SetTrue)]
+        dry_run: bool,
+
+        /// Replace the report file if it already exists, by default an existing file is never replaced
+        #[arg(long, global = true, action = clap::ArgAction::SetTrue, conflicts_with = "unique_name")]
+        overwrite: bool,
+
+        /// Add a number to the file name if the report file already exists, e.g. "report_2.csv"
+        #[arg(long, global = true, action = clap::ArgAction::SetTrue)]
+        unique_name: bool,
+
+        /// Upload the report to the WebDAV or S3 endpoint of the configuration, encrypted if an age recipient is set
+        #[arg(long, global = true, action = clap::ArgAction::SetTrue)]
+        publish: bool,
+
+        /// Start the CSV reports with the metadata as "# <key>: <value>" comment lines, not read by all CSV readers
+        #[arg(long, global = true, action = clap::ArgAction::SetTrue)]
+        csv_metadata: bool,
+
+        /// End the CSV reports with the total and target status rows, they are counted twice when the rows are summed
+        #[arg(long, global = true, action = clap::ArgAction::SetTrue)]
+        csv_totals: bool,
     },
     /// List already reported time items
     List {
         /// Project name
         #[arg(short, long)]
         project: Option<String>,
-        /// Week number - if multiple weeks exists with same number, all will be listed
-        #[arg(short, long)]
+        /// Week number or "current", "last", "next" - if multiple weeks exists with same number, all will be listed. Current week if no number is given
+        #[arg(short, long, num_args = 0..=1, default_missing_value = K_CURRENT_PERIOD)]
         week: Option<String>,
-        /// Month number - if multiple months exists with same number, all will be listed
-        #[arg(short, long)]
+        /// Month number, name (e.g. "march") or "current", "last", "next" - if multiple months exists with same number, all will be listed. Current month if no number is given
+        #[arg(short, long, num_args = 0..=1, default_missing_value = K_CURRENT_PERIOD)]
         month: Option<String>,
+        /// Period alias to list: "this-week", "last-week", "this-month", "last-month" (also "next-")
+        #[arg(long)]
+        period: Option<String>,
         /// Display all weeks
         #[arg(long, action = clap::ArgAction::SetTrue)]
         all_weeks: bool,
+        /// Only list weeks in this year. Lists all weeks of the year if no week is given
+        #[arg(long)]
+        year: Option<u32>,
+        /// Only list weeks from this week number, inclusive
+        #[arg(long, conflicts_with = "week")]
+        from_week: Option<u32>,
+        /// Only list weeks up to this week number, inclusive
+        #[arg(long, conflicts_with = "week")]
+        to_week: Option<u32>,
         /// Display all projects
         #[arg(long, action = clap::ArgAction::SetTrue)]
         all_projects: bool,
+        /// Include archived projects when listing all projects
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        include_archived: bool,
+        /// Sort the listed rows, valid options are: "hours, date, name". Name is the project name for projects and
+        /// entries, and the extra info for days
+        #[arg(long)]
+        sort: Option<String>,
+        /// Reverse the sort order, e.g. the biggest projects or the most recent days first
+        #[arg(long, action = clap::ArgAction::SetTrue, requires = "sort")]
+        desc: bool,
+        /// Comma separated columns to list, e.g. "name,hours". Invalid columns are reported with the valid options
+        #[arg(long)]
+        columns: Option<String>,
+        /// Maximum number of rows to list in each table, counted after sorting
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Number of rows to skip in each table, counted after sorting
+        #[arg(long, default_value_t = 0)]
+        offset: usize,
+        /// Export the listed rows to a file, the format is given by the extension: "csv, json, yaml"
+        #[arg(long)]
+        export: Option<String>,
     },
     /// Remove a already stored time item
     Remove {
@@ -76,6 +159,79 @@ pub enum Commands {
         entity: ModifySubcommands,
     },
 
+    /// Project aliases, renaming and archival
+    Project {
+        #[command(subcommand)]
+        entity: ProjectSubcommands,
+    },
+
+    /// Templates for recurring entries
+    Template {
+        #[command(subcommand)]
+        entity: TemplateSubcommands,
+    },
+
+    /// Add the missing occurrences of the recurring entries in the configuration, up to today. Safe to re-run, e.g. from cron
+    SyncRecurring,
+
+    /// Allocate the hours of a closed day to projects, e.g. "acme=5 internal=3"
+    Allocate {
+        /// Date of the day, "YYYY-MM-DD", defaults to today
+        #[arg(short, long)]
+        date: Option<String>,
+        /// Allocations as "project=hours", replacing the existing allocations of the day
+        #[arg(required_unless_present = "clear")]
+        allocations: Vec<String>,
+        /// Remove the allocations of the day
+        #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with = "allocations")]
+        clear: bool,
+    },
+    /// Quick status of today, hours so far if the day is running
+    #[command(visible_alias = "status")]
+    Today {
+        /// Keep showing the running day as a stopwatch, updated every second until interrupted
+        #[arg(short, long, action = clap::ArgAction::SetTrue)]
+        watch: bool,
+    },
+
+    /// Quick status of the current week, days and target status
+    Week,
+
+    /// SVG badge with the target attainment of a week, e.g. "38.5/40h • 96%", to embed in a dashboard or a README
+    Badge {
+        /// Week number or "current", "last", "next", defaults to the current week
+        #[arg(short, long, default_value = K_CURRENT_PERIOD)]
+        week: String,
+        /// Year number, defaults to the current year
+        #[arg(short, long)]
+        year: Option<u32>,
+        /// File to write, defaults to a file in the report directory. An existing file is replaced
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Entries of the last days worked as a bullet list per project, for pasting into a standup chat
+    Standup {
+        /// Number of days worked to include, counting back from yesterday
+        #[arg(short, long, default_value_t = 1)]
+        days: usize,
+    },
+
+    /// Calendar grid of a month, showing the hours worked each day compared to the daily target
+    Calendar {
+        /// Month number, name (e.g. "march") or "current", "last", "next", defaults to the current month
+        #[arg(short, long, default_value = K_CURRENT_PERIOD)]
+        month: String,
+        /// Year number, defaults to the current year
+        #[arg(short, long)]
+        year: Option<u32>,
+    },
+    /// Statistics of all stored days: average times and hours, longest streak, weekdays and month-over-month trend
+    Stats {
+        #[command(subcommand)]
+        entity: Option<StatsSubcommands>,
+    },
+
     /// Short info regarding internal storage
     Info {
         /// Short summary
@@ -96,7 +252,132 @@ pub enum Commands {
         now: bool,
     },
 
-    // Time-butler configuration
+    /// Sync the data with other machines through the sync directory, one JSON file per project and week. When the
+    /// directory is a git repository the changes of the other machines are pulled first, then committed and pushed
+    Sync {
+        /// Handling of values already set when a day exists in both: keep-existing, overwrite, append-info or interactive
+        #[arg(long, default_value = "keep-existing")]
+        on_conflict: String,
+    },
+
+    /// Merge the projects and weeks of another storage, e.g. copied from another machine, into the stored data
+    Merge {
+        /// Storage directory with the data files, or the time-butler data directory containing it
+        #[arg(long)]
+        from: String,
+        /// Handling of values already set when a day exists in both: keep-existing, overwrite, append-info or interactive
+        #[arg(long, default_value = "keep-existing")]
+        on_conflict: String,
+    },
+
+    /// Export the entries to other tools
+    Export {
+        #[command(subcommand)]
+        target: ExportSubcommands,
+    },
+
+    /// List or restore the removed projects, entries and days
+    Trash {
+        #[command(subcommand)]
+        command: TrashSubcommands,
+    },
+
+    /// Install hooks starting the day at the first login of the day and ending it, or reminding about it, at logout.
+    /// The behavior is set in the hooks section of the configuration
+    InstallHooks {
+        /// Kind of hooks: "systemd" for a systemd user unit, "profile" for blocks in ~/.profile and ~/.bash_logout
+        #[arg(short, long, default_value = "systemd")]
+        kind: String,
+        /// Print the generated hooks without installing them
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        print: bool,
+        /// Remove the installed hooks of the kind
+        #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with = "print")]
+        uninstall: bool,
+    },
+
+    /// Check the stored data for problems, all checks are run if no check is selected
+    Doctor {
+        /// Days from before today that were started but never ended, offered to be closed at the configured end of day
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        open_days: bool,
+    },
+
+    /// Compare two periods side by side, e.g. total hours, target attainment and the hours per project
+    Compare {
+        /// Left period, as "week:<week>[:<year>]" or "month:<month>[:<year>]", e.g. "month:2:2025", or an alias like "last-month"
+        #[arg(long)]
+        left: String,
+
+        /// Right period, in the same format as the left period. The difference is shown as right compared to left
+        #[arg(long)]
+        right: String,
+    },
+
+    /// Lock a week or month, e.g. after submitting a timesheet. Days and entries in it can't be changed until unlocked
+    Lock {
+        #[command(subcommand)]
+        entity: LockSubcommands,
+    },
+
+    /// Unlock a locked week or month
+    Unlock {
+        #[command(subcommand)]
+        entity: UnlockSubcommands,
+    },
+
+    /// Import data from other tools, e.g. days from a spreadsheet
+    #[command(args_conflicts_with_subcommands = true)]
+    Import {
+        #[command(subcommand)]
+        entity: Option<ImportSubcommands>,
+
+        /// Importer to read the file with: "csv, toggl, git" or an importer of the configuration, see --list-sources
+        #[arg(long, requires = "file")]
+        source: Option<String>,
+        /// File to read, the repository directory for the git source. Reads from stdin if "-"
+        #[arg(short, long, requires = "source")]
+        file: Option<String>,
+        /// Preview the imported days and entries without storing anything
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        dry_run: bool,
+        /// Handling of values already set when the day exists: keep-existing, overwrite, append-info or interactive
+        #[arg(long, default_value = "keep-existing")]
+        on_conflict: String,
+        /// List the import sources, the built-in importers and the importers of the configuration
+        #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with = "source")]
+        list_sources: bool,
+    },
+
+    /// JSON Schemas of the import document and the JSON reports, for validating them in other tools
+    Schema {
+        #[command(subcommand)]
+        command: SchemaSubcommands,
+    },
+
+    /// Free-form notes on time periods
+    Note {
+        #[command(subcommand)]
+        entity: NoteSubcommands,
+    },
+
+    /// Run many commands against the loaded data, which is saved once when all commands succeeded. One command per
+    /// line, e.g. "add entry --project acme --hours 2 --description planning", or a JSON array of commands
+    Batch {
+        /// File with the commands, reads from stdin if not given or "-"
+        #[arg(short, long)]
+        file: Option<String>,
+    },
+
+    /// Development tools, synthetic data and benchmarks
+    #[command(hide = true)]
+    Dev {
+        #[command(subcommand)]
+        entity: DevSubcommands,
+    },
+
+    /// Time-butler configuration
+    #[command(visible_alias = "config")]
     Configuration {
         /// Target times info
         #[command(subcommand)]
@@ -109,12 +390,27 @@ pub enum Commands {
 pub enum AddSubcommands {
     /// Add a new project
     Project {
-        /// Project name
+        /// Project name, use a path for sub-projects, e.g. "client/backend"
         #[arg(short, long)]
         name: String,
         /// Description of the project
         #[arg(short, long)]
         description: Option<String>,
+        /// Budgeted hours of the project
+        #[arg(long)]
+        budget: Option<f32>,
+        /// Date the budgeted hours should be used by, "YYYY-MM-DD"
+        #[arg(long, requires = "budget")]
+        deadline: Option<String>,
+        /// What to do if the project exists: abort, open, add-entry, suffix or interactive
+        #[arg(long, default_value = "abort")]
+        on_duplicate: String,
+        /// Hours of the entry added to the existing project with "--on-duplicate add-entry"
+        #[arg(long, requires = "entry_description")]
+        entry_hours: Option<f32>,
+        /// Description of the entry added to the existing project with "--on-duplicate add-entry"
+        #[arg(long, requires = "entry_hours")]
+        entry_description: Option<String>,
     },
     /// Add a new time entry to an existing project
     Entry {
@@ -124,9 +420,47 @@ pub enum AddSubcommands {
         /// Hours worked
         #[arg(long)]
         hours: Option<String>,
+        /// Estimated hours, compared to the hours worked by "stats estimates"
+        #[arg(long)]
+        estimate: Option<f32>,
         /// Description of the work done
         #[arg(long)]
         description: String,
+        /// Date the work was done, "YYYY-MM-DD". Defaults to today
+        #[arg(long)]
+        date: Option<String>,
+        /// Add the entry even if the project is archived
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        force: bool,
+    },
+    /// Add many entries at once from a CSV file with the columns: project, hours, description, date (optional)
+    Entries {
+        /// CSV file to read, reads from stdin if not given or "-"
+        #[arg(short, long)]
+        file: Option<String>,
+        /// Add the entries even if a project is archived
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        force: bool,
+    },
+    /// Add entries from a template, for a single date or repeated over a date range
+    FromTemplate {
+        /// Template name
+        name: String,
+        /// Date the work was done, "YYYY-MM-DD". Defaults to today
+        #[arg(long, conflicts_with = "repeat")]
+        date: Option<String>,
+        /// Repeat the template over a date range, valid options are: "daily, weekdays, weekly"
+        #[arg(long, requires_all = ["from", "to"])]
+        repeat: Option<String>,
+        /// First date of the range, "YYYY-MM-DD"
+        #[arg(long, requires = "repeat")]
+        from: Option<String>,
+        /// Last date of the range (inclusive), "YYYY-MM-DD"
+        #[arg(long, requires = "repeat")]
+        to: Option<String>,
+        /// Add the entries even if the project is archived
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        force: bool,
     },
     /// Add new day
     Day {
@@ -142,6 +476,54 @@ pub enum AddSubcommands {
         /// Paused time during day - Time to exclude from the reported hours
         #[arg(short, long)]
         paused_hours: Option<String>,
+        /// Handling of values already set when the day exists: keep-existing, overwrite, append-info or interactive
+        #[arg(long, default_value = "keep-existing")]
+        on_conflict: String,
+        /// Don't add the default break of the configuration to this day
+        #[arg(long)]
+        no_break: bool,
+    },
+}
+
+/// Enum for "import" subcommands
+#[derive(Subcommand)]
+pub enum ImportSubcommands {
+    /// Import days from CSV with the columns date, start, end, pause and extra_info. Existing days are merged
+    Days {
+        /// CSV file to read, reads from stdin if "-"
+        #[arg(short, long)]
+        file: String,
+        /// Preview the imported days without storing anything
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        dry_run: bool,
+        /// Handling of values already set when the day exists: keep-existing, overwrite, append-info or interactive
+        #[arg(long, default_value = "keep-existing")]
+        on_conflict: String,
+    },
+    /// Import the meetings of a CalDAV calendar in a month as entries of a project, the hours are the length of the
+    /// meeting. Meetings already imported are skipped
+    Caldav {
+        /// Url of the calendar, or of the calendar collection with --calendar. Defaults to the url in the configuration
+        #[arg(long)]
+        url: Option<String>,
+        /// Name of the project to add the entries to
+        #[arg(short, long)]
+        project: String,
+        /// Month number, name (e.g. "march") or "current", "last", "next", defaults to the current month
+        #[arg(short, long, default_value = K_CURRENT_PERIOD)]
+        month: String,
+        /// Year number, defaults to the current year
+        #[arg(short, long)]
+        year: Option<u32>,
+        /// Calendar in the collection to import from, can be repeated. Defaults to the calendars in the configuration
+        #[arg(long)]
+        calendar: Vec<String>,
+        /// Only import meetings with the keyword in the summary, case insensitive. Can be repeated
+        #[arg(short, long)]
+        keyword: Vec<String>,
+        /// Preview the imported entries without storing anything
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        dry_run: bool,
     },
 }
 
@@ -156,21 +538,30 @@ pub enum RemoveSubcommands {
     },
     /// Remove a specific entry from a project
     Entry {
-        /// Project name
-        #[arg(long)]
-        project: String,
-        /// Entry ID
-        #[arg(long)]
+        /// Project name, all projects are searched for the entry ID if not given
+        #[arg(long, required_unless_present = "id")]
+        project: Option<String>,
+        /// Entry ID, or a unique prefix of at least 4 characters
+        #[arg(long, required_unless_present_any = ["nth", "last"], conflicts_with_all = ["nth", "last"])]
         id: Option<String>,
+        /// Position of the entry in the listed entries of the project, starting at 1
+        #[arg(long, conflicts_with = "last", requires = "project")]
+        nth: Option<usize>,
+        /// Remove the last listed entry of the project
+        #[arg(long, requires = "project")]
+        last: bool,
     },
     /// Remove a specific day from a week
     Day {
         /// Date
-        #[arg(long)]
-        date: String,
+        #[arg(long, required_unless_present = "id")]
+        date: Option<String>,
         /// Week number
-        #[arg(long)]
-        week: u32,
+        #[arg(long, required_unless_present = "id")]
+        week: Option<u32>,
+        /// ID of the day, instead of the date and week
+        #[arg(long, conflicts_with_all = ["date", "week"])]
+        id: Option<String>,
     },
 }
 
@@ -189,6 +580,26 @@ pub enum ModifySubcommands {
         #[arg(long)]
         new_description: Option<String>,
     },
+    Entry {
+        /// Project name
+        #[arg(long)]
+        project: String,
+        /// Entry ID
+        #[arg(long)]
+        id: String,
+        /// New hours for the entry
+        #[arg(long)]
+        hours: Option<String>,
+        /// New description of the work done
+        #[arg(long)]
+        description: Option<String>,
+        /// New date the work was done, "YYYY-MM-DD"
+        #[arg(long)]
+        date: Option<String>,
+        /// Move the entry to another project
+        #[arg(long)]
+        new_project: Option<String>,
+    },
     Day {
         /// ID
         #[arg(long)]
@@ -208,6 +619,90 @@ pub enum ModifySubcommands {
     },
 }
 
+/// Enum for "project" subcommands
+#[derive(Subcommand)]
+pub enum ProjectSubcommands {
+    /// Add a short alias to a project, accepted anywhere a project name is taken
+    Alias {
+        /// Project name or existing alias
+        name: String,
+        /// New alias
+        alias: String,
+    },
+    /// Archive a project, hides it from listings and blocks new entries
+    Archive {
+        /// Project name or alias
+        name: String,
+    },
+    /// Restore an archived project
+    Unarchive {
+        /// Project name or alias
+        name: String,
+    },
+    /// Set the budgeted hours and deadline of a project, shown by "targets project"
+    Budget {
+        /// Project name or alias
+        name: String,
+        /// Budgeted hours
+        #[arg(long, required_unless_present = "clear")]
+        hours: Option<f32>,
+        /// Date the budgeted hours should be used by, "YYYY-MM-DD"
+        #[arg(long, requires = "hours")]
+        deadline: Option<String>,
+        /// Remove the budget and deadline
+        #[arg(long, action = clap::ArgAction::SetTrue, conflicts_with_all = ["hours", "deadline"])]
+        clear: bool,
+    },
+    /// Set the client, color and external reference of a project, an empty value removes it
+    #[command(group(clap::ArgGroup::new("fields").required(true).multiple(true)))]
+    Set {
+        /// Project name or alias
+        name: String,
+        /// Name of the client the work is done for
+        #[arg(long, group = "fields")]
+        client: Option<String>,
+        /// Color of the project name in the tables and HTML reports, a color name (e.g. "cyan") or "#rrggbb"
+        #[arg(long, group = "fields")]
+        color: Option<String>,
+        /// Reference in another system, e.g. a Jira key or a PO number
+        #[arg(long, group = "fields")]
+        reference: Option<String>,
+    },
+    /// Rename a project, the ID and entries are kept
+    Rename {
+        /// Project name or alias
+        name: String,
+        /// New project name
+        new_name: String,
+    },
+}
+
+/// Enum for "template" subcommands
+#[derive(Subcommand)]
+pub enum TemplateSubcommands {
+    /// Add a new entry template
+    Add {
+        /// Template name
+        name: String,
+        /// Project name or alias
+        #[arg(long)]
+        project: String,
+        /// Hours for each entry
+        #[arg(long)]
+        hours: f32,
+        /// Description of the work done
+        #[arg(long)]
+        description: Option<String>,
+    },
+    /// List all entry templates
+    List,
+    /// Remove an entry template
+    Remove {
+        /// Template name
+        name: String,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum ReportSubcommands {
     /// Project report
@@ -215,42 +710,340 @@ pub enum ReportSubcommands {
         /// Project name
         #[arg(short, long)]
         name: String,
-        /// Report format, valid options are: "json, csv, yaml, html, pdf, text"`
+        /// Report format, valid options are: "json, csv, yaml, html, template" or a renderer of the configuration, see --list-formats
         #[arg(short, long)]
         format: String,
+        /// Only report the aggregates, e.g. total hours, per-week totals and target attainment
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        summary: bool,
+        /// Template file used by the "template" format, e.g. "timesheet.html.tera"
+        #[arg(long)]
+        template: Option<String>,
+        /// Email the generated report to this address, using the SMTP settings in the configuration
+        #[arg(long)]
+        email: Option<String>,
+        /// Copy the summary of the report to the clipboard as Markdown, e.g. to paste into a chat
+        #[arg(long)]
+        clipboard: bool,
+        /// Comma separated columns to report, valid options are: "date, hours, description, created, id"
+        #[arg(long, conflicts_with = "summary")]
+        columns: Option<String>,
     },
     /// Week report
     Week {
-        /// Week number
+        /// Week number or "current", "last", "next", defaults to the current week
+        #[arg(short, long, default_value = K_CURRENT_PERIOD)]
+        number: String,
+        /// Period alias: "this-week", "last-week" or "next-week". Replaces number and year
+        #[arg(long, conflicts_with_all = ["number", "year"])]
+        period: Option<String>,
+        /// Year number, defaults to the current year
         #[arg(short, long)]
-        number: u32,
-        /// Year number
-        #[arg(short, long)]
-        year: u32,
-        /// Report format, valid options are: "json, csv, yaml, html, pdf, text"`
+        year: Option<u32>,
+        /// Report format, valid options are: "json, csv, yaml, html, template" or a renderer of the configuration, see --list-formats
         #[arg(short, long)]
         format: String,
+        /// Only report the aggregates, e.g. total hours, per-week totals and target attainment
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        summary: bool,
+        /// Template file used by the "template" format, e.g. "timesheet.html.tera"
+        #[arg(long)]
+        template: Option<String>,
+        /// Email the generated report to this address, using the SMTP settings in the configuration
+        #[arg(long)]
+        email: Option<String>,
+        /// Copy the summary of the report to the clipboard as Markdown, e.g. to paste into a chat
+        #[arg(long)]
+        clipboard: bool,
+        /// Comma separated columns to report, valid options are: "week, date, start, end, paused, hours, description, closed"
+        #[arg(long, conflicts_with = "summary")]
+        columns: Option<String>,
     },
     /// Month report
     Month {
-        /// Month number
+        /// Month number, name (e.g. "march") or "current", "last", "next", defaults to the current month
+        #[arg(short, long, default_value = K_CURRENT_PERIOD)]
+        number: String,
+        /// Period alias: "this-month", "last-month" or "next-month". Replaces number and year
+        #[arg(long, conflicts_with_all = ["number", "year"])]
+        period: Option<String>,
+        /// Year number, defaults to the current year
         #[arg(short, long)]
-        number: u32,
-        /// Year number
+        year: Option<u32>,
+        /// Report format, valid options are: "json, csv, yaml, html, template" or a renderer of the configuration, see --list-formats
         #[arg(short, long)]
-        year: u32,
-        /// Report format, valid options are: "json, csv, yaml, html, pdf, text"`
+        format: String,
+        /// Only report the aggregates, e.g. total hours, per-week totals and target attainment
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        summary: bool,
+        /// Template file used by the "template" format, e.g. "timesheet.html.tera"
+        #[arg(long)]
+        template: Option<String>,
+        /// Email the generated report to this address, using the SMTP settings in the configuration
+        #[arg(long)]
+        email: Option<String>,
+        /// Copy the summary of the report to the clipboard as Markdown, e.g. to paste into a chat
+        #[arg(long)]
+        clipboard: bool,
+        /// Comma separated columns to report, valid options are: "week, date, start, end, paused, hours, description, closed"
+        #[arg(long, conflicts_with = "summary")]
+        columns: Option<String>,
+    },
+    /// Timesheet report of a week, a row per project and a column per day with totals per row and column
+    Timesheet {
+        /// Week number or "current", "last", "next", defaults to the current week
+        #[arg(short, long, default_value = K_CURRENT_PERIOD)]
+        number: String,
+        /// Period alias: "this-week", "last-week" or "next-week". Replaces number and year
+        #[arg(long, conflicts_with_all = ["number", "year"])]
+        period: Option<String>,
+        /// Year number, defaults to the current year
+        #[arg(short, long)]
+        year: Option<u32>,
+        /// Report format, valid options are: "csv, html, xlsx"`
+        #[arg(short, long)]
+        format: String,
+        /// Email the generated report to this address, using the SMTP settings in the configuration
+        #[arg(long)]
+        email: Option<String>,
+    },
+    /// Overview report, day hours and project entry hours side by side for a month
+    Overview {
+        /// Month number, name (e.g. "march") or "current", "last", "next", defaults to the current month
+        #[arg(short, long, default_value = K_CURRENT_PERIOD)]
+        number: String,
+        /// Year number, defaults to the current year
+        #[arg(short, long)]
+        year: Option<u32>,
+        /// Report format, valid options are: "json, csv, yaml, html, template" or a renderer of the configuration, see --list-formats
         #[arg(short, long)]
         format: String,
+        /// Only report the aggregates, e.g. total hours, per-week totals and target attainment
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        summary: bool,
+        /// Template file used by the "template" format, e.g. "timesheet.html.tera"
+        #[arg(long)]
+        template: Option<String>,
+        /// Email the generated report to this address, using the SMTP settings in the configuration
+        #[arg(long)]
+        email: Option<String>,
+        /// Copy the summary of the report to the clipboard as Markdown, e.g. to paste into a chat
+        #[arg(long)]
+        clipboard: bool,
     },
-    /// Year report
+    /// Report of all days in a date range, can span multiple weeks and years
+    Range {
+        /// First date of the range, "YYYY-MM-DD"
+        #[arg(long)]
+        from: String,
+        /// Last date of the range (inclusive), "YYYY-MM-DD"
+        #[arg(long)]
+        to: String,
+        /// Report format, valid options are: "json, csv, yaml, html, template" or a renderer of the configuration, see --list-formats
+        #[arg(short, long)]
+        format: String,
+        /// Only report the aggregates, e.g. total hours, per-week totals and target attainment
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        summary: bool,
+        /// Template file used by the "template" format, e.g. "timesheet.html.tera"
+        #[arg(long)]
+        template: Option<String>,
+        /// Email the generated report to this address, using the SMTP settings in the configuration
+        #[arg(long)]
+        email: Option<String>,
+        /// Copy the summary of the report to the clipboard as Markdown, e.g. to paste into a chat
+        #[arg(long)]
+        clipboard: bool,
+        /// Comma separated columns to report, valid options are: "week, date, start, end, paused, hours, description, closed"
+        #[arg(long, conflicts_with = "summary")]
+        columns: Option<String>,
+    },
+    /// Report of all days in a year, grouped by week
     Year {
-        /// Year number
+        /// Year number, defaults to the current year
         #[arg(short, long)]
-        number: u32,
-        /// Report format, valid options are: "json, csv, yaml, html, pdf, text"`
+        number: Option<u32>,
+        /// Report format, valid options are: "json, csv, yaml, html, template" or a renderer of the configuration, see --list-formats
         #[arg(short, long)]
         format: String,
+        /// Only report the aggregates, e.g. total hours and per-week totals
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        summary: bool,
+        /// Template file used by the "template" format, e.g. "timesheet.html.tera"
+        #[arg(long)]
+        template: Option<String>,
+        /// Email the generated report to this address, using the SMTP settings in the configuration
+        #[arg(long)]
+        email: Option<String>,
+        /// Copy the summary of the report to the clipboard as Markdown, e.g. to paste into a chat
+        #[arg(long)]
+        clipboard: bool,
+        /// Comma separated columns to report, valid options are: "week, date, start, end, paused, hours, description, closed"
+        #[arg(long, conflicts_with = "summary")]
+        columns: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum StatsSubcommands {
+    /// Estimated compared to actual hours per project, for the entries with an estimate
+    Estimates {
+        /// Only this project and its sub-projects
+        #[arg(short, long)]
+        project: Option<String>,
+    },
+    /// Bar chart of the hours per day and per week in a month, with the target marked
+    Chart {
+        /// Month number, name (e.g. "march") or "current", "last", "next", defaults to the current month
+        #[arg(short, long, default_value = K_CURRENT_PERIOD)]
+        month: String,
+        /// Year number, defaults to the current year
+        #[arg(short, long)]
+        year: Option<u32>,
+    },
+}
+
+/// Enum for "note" subcommands
+#[derive(Subcommand)]
+pub enum NoteSubcommands {
+    /// Add a note to a week, shown when listing the week and included in week and month reports
+    Week {
+        /// Week number or "current", "last", "next"
+        number: String,
+        /// The note, e.g. "short week, conference"
+        text: String,
+        /// Year number, defaults to the current year
+        #[arg(short, long)]
+        year: Option<u32>,
+    },
+}
+
+/// Enum for "lock" subcommands
+#[derive(Subcommand)]
+pub enum LockSubcommands {
+    /// Lock a week
+    Week {
+        /// Week number or "current", "last", "next"
+        number: String,
+        /// Year number, defaults to the current year
+        #[arg(short, long)]
+        year: Option<u32>,
+        /// Write a read-only JSON snapshot of the week and its SHA-256 digest to the report directory
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        snapshot: bool,
+        /// Sign the snapshot with gpg, using the default key
+        #[arg(long, action = clap::ArgAction::SetTrue, requires = "snapshot")]
+        sign: bool,
+    },
+    /// Lock a month
+    Month {
+        /// Month number, name (e.g. "march") or "current", "last", "next"
+        number: String,
+        /// Year number, defaults to the current year
+        #[arg(short, long)]
+        year: Option<u32>,
+        /// Write a read-only JSON snapshot of the month and its SHA-256 digest to the report directory
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        snapshot: bool,
+        /// Sign the snapshot with gpg, using the default key
+        #[arg(long, action = clap::ArgAction::SetTrue, requires = "snapshot")]
+        sign: bool,
+    },
+    /// List the locked periods
+    List,
+}
+
+/// Enum for "trash" subcommands
+#[derive(Subcommand)]
+pub enum TrashSubcommands {
+    /// List the removed items, the last removed first
+    List,
+    /// Restore a removed item
+    Restore {
+        /// ID of the removed item, or a unique prefix of it
+        id: String,
+    },
+}
+
+/// Enum for "export" subcommands
+#[derive(Subcommand)]
+pub enum ExportSubcommands {
+    /// Jira worklogs of a project in the Tempo JSON format, written to a file or posted to the Jira API. The issue key
+    /// is taken from the entry description (e.g. "ABC-123 review"), else from the external reference of the project
+    Jira {
+        /// Name of the project, the entries of the sub-projects are included
+        #[arg(short, long)]
+        project: String,
+        /// Month number, name (e.g. "march") or "current", "last", "next", defaults to the current month
+        #[arg(short, long, default_value = K_CURRENT_PERIOD)]
+        month: String,
+        /// Year number, defaults to the current year
+        #[arg(short, long)]
+        year: Option<u32>,
+        /// File to write, defaults to a file in the report directory
+        #[arg(short, long, conflicts_with = "post")]
+        output: Option<String>,
+        /// Post the worklogs to the Jira API in the configuration instead of writing a file
+        #[arg(long)]
+        post: bool,
+    },
+}
+
+/// Enum for "unlock" subcommands
+#[derive(Subcommand)]
+pub enum UnlockSubcommands {
+    /// Unlock a week
+    Week {
+        /// Week number or "current", "last", "next"
+        number: String,
+        /// Year number, defaults to the current year
+        #[arg(short, long)]
+        year: Option<u32>,
+    },
+    /// Unlock a month
+    Month {
+        /// Month number, name (e.g. "march") or "current", "last", "next"
+        number: String,
+        /// Year number, defaults to the current year
+        #[arg(short, long)]
+        year: Option<u32>,
+    },
+}
+
+/// Enum for the "schema" subcommands
+#[derive(Subcommand)]
+pub enum SchemaSubcommands {
+    /// Print the JSON Schema of a document: "import" for the JSON of the importer commands, or "report" for the JSON
+    /// week, month, range and year reports
+    Dump {
+        /// Document of the schema, "import" or "report"
+        document: String,
+        /// File to write the schema to instead of printing it. An existing file is replaced
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+}
+
+/// Enum for the hidden "dev" subcommands
+#[derive(Subcommand)]
+pub enum DevSubcommands {
+    /// Replace the stored data with generated projects, days and entries, one closed day per weekday
+    Generate {
+        /// Number of years of data, back from today
+        #[arg(long, default_value_t = 1)]
+        years: u32,
+        /// Number of entries per day, spread over the generated projects
+        #[arg(long, default_value_t = 3)]
+        entries_per_day: u32,
+        /// Replace already stored data
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        force: bool,
+    },
+    /// Time loading, saving, adding days and report generation on the stored data
+    Bench {
+        /// Number of times each operation is run
+        #[arg(short, long, default_value_t = 5)]
+        iterations: u32,
     },
 }
 
@@ -258,21 +1051,73 @@ pub enum ReportSubcommands {
 pub enum TargetTimesSubcommands {
     /// Set target for the week
     Week {
-        /// Week number
-        #[arg(short, long)]
-        number: u32,
-        /// Year number
+        /// Week number or "current", "last", "next", defaults to the current week
+        #[arg(short, long, default_value = K_CURRENT_PERIOD)]
+        number: String,
+        /// Period alias: "this-week", "last-week" or "next-week". Replaces number and year
+        #[arg(long, conflicts_with_all = ["number", "year"])]
+        period: Option<String>,
+        /// Year number, defaults to the current year
         #[arg(short, long)]
-        year: u32,
+        year: Option<u32>,
     },
     /// Set target for the month
     Month {
-        /// Month number
+        /// Month number, name (e.g. "march") or "current", "last", "next", defaults to the current month
+        #[arg(short, long, default_value = K_CURRENT_PERIOD)]
+        number: String,
+        /// Period alias: "this-month", "last-month" or "next-month". Replaces number and year
+        #[arg(long, conflicts_with_all = ["number", "year"])]
+        period: Option<String>,
+        /// Year number, defaults to the current year
         #[arg(short, long)]
-        number: u32,
-        /// Year number
+        year: Option<u32>,
+    },
+    /// Burn-down of a project budget: hours used, remaining budget and the projected completion at the current pace
+    Project {
+        /// Project name or alias
+        #[arg(short, long)]
+        name: String,
+    },
+    /// Forecast the month total from the average daily hours so far, and the hours per day needed to reach the target
+    Forecast {
+        /// Month number, name (e.g. "march") or "current", "last", "next", defaults to the current month
+        #[arg(short, long, default_value = K_CURRENT_PERIOD)]
+        month: String,
+        /// Year number, defaults to the current year
         #[arg(short, long)]
-        year: u32,
+        year: Option<u32>,
+    },
+    /// Show the target status of a week and set a target for that week only, asked for if not given
+    Edit {
+        /// Week number or "current", "last", "next", defaults to the current week
+        #[arg(short, long, default_value = K_CURRENT_PERIOD)]
+        number: String,
+        /// Period alias: "this-week", "last-week" or "next-week". Replaces number and year
+        #[arg(long, conflicts_with_all = ["number", "year"])]
+        period: Option<String>,
+        /// Year number, defaults to the current year
+        #[arg(short, long)]
+        year: Option<u32>,
+        /// New target hours of the week, without asking
+        #[arg(long, conflicts_with = "reset")]
+        hours: Option<f32>,
+        /// Go back to the week target in the configuration
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        reset: bool,
+        /// ID of the week, instead of the number and year
+        #[arg(long, conflicts_with_all = ["number", "period", "year"])]
+        id: Option<String>,
+    },
+    /// Update the target of the stored weeks to the week target in the configuration, all weeks if no week is given.
+    /// Weeks with a target set with "targets edit" are kept
+    Reapply {
+        /// Only update weeks from this week number on, inclusive
+        #[arg(long)]
+        from_week: Option<u32>,
+        /// Year of the first week to update, defaults to the current year
+        #[arg(short, long, requires = "from_week")]
+        year: Option<u32>,
     },
 }
 
@@ -287,6 +1132,37 @@ pub enum ConfigurationSubcommands {
         #[arg(short, long)]
         dump_file: Option<String>,
     },
+    /// Show current used configuration
+    Show,
+    /// Print the path of the configuration file
+    Path,
+    /// Write a fresh default configuration file
+    Init {
+        /// Replace an already existing configuration file
+        #[arg(long, action = clap::ArgAction::SetTrue)]
+        force: bool,
+    },
+    /// Open the configuration file in $EDITOR, the result is validated before it's saved
+    Edit,
+    /// Print a configuration value, e.g. "backup.enable-periodic-backup"
+    Get {
+        /// Key of the value, section and name separated by a dot. A section gives all its values
+        key: String,
+    },
+    /// Convert the configuration file to another format, the current file is kept with a ".bak" suffix
+    Convert {
+        /// Format to convert to: json, toml or yaml
+        #[arg(long)]
+        to: String,
+    },
+    /// Set a configuration value, e.g. "targets.total-week-target 37.5"
+    Set {
+        /// Key of the value, section and name separated by a dot
+        key: String,
+        /// New value, must have the same type as the current value
+        #[arg(allow_hyphen_values = true)]
+        value: String,
+    },
 }
 
 //TODO (later):
@@ -0,0 +1,97 @@
+/*
+ * File: clipboard.rs
+ * Description: Copy of text to the system clipboard, with the clipboard tool of the platform, e.g. "wl-copy", "xclip"
+ *              or "pbcopy".
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+/// Clipboard tools with their arguments, tried in order until one is installed
+const K_CLIPBOARD_TOOLS: [(&str, &[&str]); 5] = [
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("pbcopy", &[]),
+    ("clip.exe", &[]),
+];
+
+/// Error for copying to the clipboard
+#[derive(Debug)]
+pub struct ClipboardError(String);
+
+/// Implement Display trait for ClipboardError
+impl fmt::Display for ClipboardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to copy to the clipboard: {}", self.0)
+    }
+}
+
+/// Implement Error trait for ClipboardError
+impl Error for ClipboardError {}
+
+/// Internal function to pipe the text to the tool, returns false if the tool isn't installed
+fn copy_with(tool: &str, args: &[&str], text: &str) -> Result<bool, ClipboardError> {
+    let child = Command::new(tool)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(ClipboardError(format!("could not run {}: {}", tool, e))),
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|e| ClipboardError(format!("could not write to {}: {}", tool, e)))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| ClipboardError(format!("could not run {}: {}", tool, e)))?;
+    if !output.status.success() {
+        return Err(ClipboardError(format!(
+            "{} failed: {}",
+            tool,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(true)
+}
+
+/// Copy the text to the clipboard with the first installed clipboard tool, returns the name of the tool
+pub fn copy(text: &str) -> Result<&'static str, ClipboardError> {
+    for (tool, args) in K_CLIPBOARD_TOOLS {
+        // The Wayland and X11 tools need a display, e.g. both are installed but only one is running
+        let needs_display = match tool {
+            "wl-copy" => std::env::var_os("WAYLAND_DISPLAY").is_none(),
+            "xclip" | "xsel" => std::env::var_os("DISPLAY").is_none(),
+            _ => false,
+        };
+        if needs_display {
+            continue;
+        }
+
+        tracing::debug!("Copying to the clipboard with {}", tool);
+        if copy_with(tool, args, text)? {
+            return Ok(tool);
+        }
+    }
+
+    Err(ClipboardError(format!(
+        "no clipboard tool found, install one of: {}",
+        K_CLIPBOARD_TOOLS
+            .iter()
+            .map(|(tool, _)| *tool)
+            .collect::<Vec<_>>()
+            .join(", ")
+    )))
+}
@@ -0,0 +1,50 @@
+/*
+ * File: clock.rs
+ * Description: Source of the current time. Injected into the butler and the time items, so the time can be frozen
+ *              or moved to another date instead of always reading the system time.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use chrono::{DateTime, Local, NaiveDate};
+
+/// Source of the current time
+pub trait Clock {
+    /// Current local time
+    fn now(&self) -> DateTime<Local>;
+
+    /// Current local date
+    fn today(&self) -> NaiveDate {
+        self.now().date_naive()
+    }
+}
+
+/// Clock reading the system time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+/// Implement Clock trait for SystemClock
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Local> {
+        Local::now()
+    }
+}
+
+/// Clock frozen at a given time
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(DateTime<Local>);
+
+impl FixedClock {
+    /// Create a new clock frozen at the time
+    pub fn new(time: DateTime<Local>) -> Self {
+        Self(time)
+    }
+}
+
+/// Implement Clock trait for FixedClock
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Local> {
+        self.0
+    }
+}
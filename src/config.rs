@@ -7,14 +7,41 @@
  */
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-/// Application configuration struct
-#[derive(Serialize, Deserialize, Clone)]
+use crate::paths;
+use crate::project::Project;
+
+/// Project name of a project report output used for all projects without their own output
+pub const K_ALL_PROJECTS: &str = "*";
+
+/// Application configuration struct. All sections and values are optional in the file, missing ones get the defaults
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
 pub struct AppConfiguration {
     file_paths: FilePathsConfig,
     targets: TargetsConfig,
     backup: BackupConfig,
     version: VersionConfiguration,
+    display: DisplayConfig,
+    templates: Vec<EntryTemplate>,
+    recurring: Vec<RecurringEntry>,
+    email: EmailConfig,
+    #[serde(rename = "project-reports")]
+    project_reports: Vec<ProjectReportOutput>,
+    hours: HoursConfig,
+    validation: ValidationConfig,
+    weeks: WeeksConfig,
+    days: DaysConfig,
+    sync: SyncConfig,
+    trash: TrashConfig,
+    jira: JiraConfig,
+    caldav: CalDavConfig,
+    hooks: HooksConfig,
+    publish: PublishConfig,
+    #[serde(rename = "report-renderers")]
+    report_renderers: Vec<RendererConfig>,
+    importers: Vec<ImporterConfig>,
 }
 
 impl AppConfiguration {
@@ -50,6 +77,10 @@ impl AppConfiguration {
         self.targets.weekly_target_for_month
     }
 
+    pub fn auto_reapply_week_target(&self) -> bool {
+        self.targets.auto_reapply_week_target
+    }
+
     pub fn periodic_backup_enabled(&self) -> bool {
         self.backup.enable_periodic_backup
     }
@@ -66,6 +97,149 @@ impl AppConfiguration {
         self.version.always_force_halt_on_version_incompatibility
     }
 
+    pub fn hours_rounding(&self) -> String {
+        self.hours.rounding.clone()
+    }
+
+    pub fn hours_format(&self) -> String {
+        self.hours.format.clone()
+    }
+
+    pub fn hours_precision(&self) -> String {
+        self.hours.precision.clone()
+    }
+
+    pub fn validation(&self) -> &ValidationConfig {
+        &self.validation
+    }
+
+    pub fn color_theme(&self) -> String {
+        self.display.color_theme.clone()
+    }
+
+    pub fn html_dark_mode(&self) -> bool {
+        self.display.html_dark_mode
+    }
+
+    pub fn week_summary_after_add(&self) -> bool {
+        self.display.week_summary_after_add
+    }
+
+    pub fn week_start(&self) -> String {
+        self.weeks.week_start.clone()
+    }
+
+    pub fn week_numbering(&self) -> String {
+        self.weeks.week_numbering.clone()
+    }
+
+    pub fn end_of_day(&self) -> String {
+        self.days.end_of_day.clone()
+    }
+
+    pub fn auto_close_open_days_after_hours(&self) -> Option<f32> {
+        self.days.auto_close_open_days_after_hours
+    }
+
+    pub fn days(&self) -> &DaysConfig {
+        &self.days
+    }
+
+    pub fn locale(&self) -> String {
+        self.display.locale.clone()
+    }
+
+    pub fn templates(&self) -> &Vec<EntryTemplate> {
+        &self.templates
+    }
+
+    pub fn recurring_entries(&self) -> &Vec<RecurringEntry> {
+        &self.recurring
+    }
+
+    /// Directory of the synced files, "sync" in the storage directory if not set
+    pub fn sync_directory(&self) -> String {
+        if self.sync.directory.is_empty() {
+            format!("{}/sync", self.file_paths.storage_directory)
+        } else {
+            self.sync.directory.clone()
+        }
+    }
+
+    pub fn sync_with_git(&self) -> bool {
+        self.sync.git
+    }
+
+    pub fn trash_retention_days(&self) -> u32 {
+        self.trash.retention_days
+    }
+
+    pub fn email(&self) -> &EmailConfig {
+        &self.email
+    }
+
+    pub fn jira(&self) -> &JiraConfig {
+        &self.jira
+    }
+
+    pub fn caldav(&self) -> &CalDavConfig {
+        &self.caldav
+    }
+
+    pub fn publish(&self) -> &PublishConfig {
+        &self.publish
+    }
+
+    pub fn hooks(&self) -> &HooksConfig {
+        &self.hooks
+    }
+
+    pub fn report_renderers(&self) -> &Vec<RendererConfig> {
+        &self.report_renderers
+    }
+
+    pub fn importers(&self) -> &Vec<ImporterConfig> {
+        &self.importers
+    }
+
+    /// Get the report output of a project, an entry for the project itself is preferred over the "*" entry
+    pub fn project_report_output(&self, project: &Project) -> Option<&ProjectReportOutput> {
+        self.project_reports
+            .iter()
+            .find(|o| project.is_named(&o.project))
+            .or_else(|| {
+                self.project_reports
+                    .iter()
+                    .find(|o| o.project == K_ALL_PROJECTS)
+            })
+    }
+
+    /// Add an entry template, returns false if a template with the same name already exists
+    pub fn add_template(&mut self, template: EntryTemplate) -> bool {
+        if self.templates.iter().any(|t| t.name == template.name) {
+            false
+        } else {
+            self.templates.push(template);
+            true
+        }
+    }
+
+    /// Apply the settings which can be changed while running, targets and backup, from another configuration.
+    /// Returns true if any of them changed
+    pub fn apply_live_settings(&mut self, other: &AppConfiguration) -> bool {
+        let changed = self.targets != other.targets || self.backup != other.backup;
+        self.targets = other.targets.clone();
+        self.backup = other.backup.clone();
+        changed
+    }
+
+    /// Remove an entry template by name, returns false if no template was found
+    pub fn remove_template(&mut self, name: &str) -> bool {
+        let count = self.templates.len();
+        self.templates.retain(|t| t.name != name);
+        count != self.templates.len()
+    }
+
     pub fn get_as_string(&self) -> String {
         // format complete config as a String in order to print/write it
         let mut out = String::new();
@@ -102,6 +276,10 @@ impl AppConfiguration {
             "  use-total-week-target-for-month: {}\n",
             self.targets.weekly_target_for_month
         ));
+        out.push_str(&format!(
+            "  auto-reapply-week-target: {}\n",
+            self.targets.auto_reapply_week_target
+        ));
         out.push_str(&format!(
             "  enable-periodic-backup: {}\n",
             self.backup.enable_periodic_backup
@@ -118,50 +296,182 @@ impl AppConfiguration {
             "  always-force-halt-on-version-incompatibility: {}\n",
             self.version.always_force_halt_on_version_incompatibility
         ));
+        out.push_str(&format!("  color-theme: {}\n", self.display.color_theme));
+        out.push_str(&format!("  locale: {}\n", self.display.locale));
+        out.push_str(&format!(
+            "  html-dark-mode: {}\n",
+            self.display.html_dark_mode
+        ));
+        out.push_str(&format!(
+            "  week-summary-after-add: {}\n",
+            self.display.week_summary_after_add
+        ));
+        out.push_str(&format!("  rounding: {}\n", self.hours.rounding));
+        out.push_str(&format!("  hours-format: {}\n", self.hours.format));
+        out.push_str(&format!("  hours-precision: {}\n", self.hours.precision));
+        out.push_str(&format!("  week-start: {}\n", self.weeks.week_start));
+        out.push_str(&format!(
+            "  week-numbering: {}\n",
+            self.weeks.week_numbering
+        ));
+        out.push_str(&format!("  end-of-day: {}\n", self.days.end_of_day));
+        out.push_str(&format!(
+            "  auto-close-open-days-after-hours: {}\n",
+            self.days
+                .auto_close_open_days_after_hours
+                .map_or(String::new(), |h| h.to_string())
+        ));
+        out.push_str(&format!("  overnight-date: {}\n", self.days.overnight_date));
+        out.push_str(&format!(
+            "  max-overnight-hours: {}\n",
+            self.days.max_overnight_hours
+        ));
+        out.push_str(&format!(
+            "  default-break-minutes: {}\n",
+            self.days.default_break_minutes
+        ));
+        out.push_str(&format!(
+            "  short-day-warning: {}\n",
+            self.days.short_day_warning
+        ));
+        out.push_str(&format!(
+            "  short-day-warning-hours: {}\n",
+            self.days.short_day_warning_hours
+        ));
+        for (weekday, hours) in &self.days.short_day_warning_weekdays {
+            out.push_str(&format!(
+                "  short-day-warning-weekday: {} ({} hours)\n",
+                weekday, hours
+            ));
+        }
+        out.push_str(&format!(
+            "  max-entry-hours: {}\n",
+            self.validation.max_entry_hours
+        ));
+        out.push_str(&format!(
+            "  max-day-hours: {}\n",
+            self.validation.max_day_hours
+        ));
+        out.push_str(&format!("  sync-directory: {}\n", self.sync_directory()));
+        out.push_str(&format!("  sync-git: {}\n", self.sync.git));
+        out.push_str(&format!(
+            "  trash-retention-days: {}\n",
+            self.trash.retention_days
+        ));
+        out.push_str(&format!("  smtp-server: {}\n", self.email.smtp_server));
+        out.push_str(&format!("  smtp-username: {}\n", self.email.username));
+        // The password is never printed
+        out.push_str(&format!(
+            "  smtp-password: {}\n",
+            if self.email.password.is_empty() {
+                ""
+            } else {
+                "********"
+            }
+        ));
+        out.push_str(&format!("  email-from: {}\n", self.email.from));
+        out.push_str(&format!("  jira-url: {}\n", self.jira.url));
+        out.push_str(&format!("  jira-username: {}\n", self.jira.username));
+        // The token is never printed
+        out.push_str(&format!(
+            "  jira-token: {}\n",
+            if self.jira.token.is_empty() {
+                ""
+            } else {
+                "********"
+            }
+        ));
+        out.push_str(&format!("  caldav-url: {}\n", self.caldav.url));
+        out.push_str(&format!("  caldav-username: {}\n", self.caldav.username));
+        out.push_str(&format!(
+            "  caldav-password: {}\n",
+            if self.caldav.password.is_empty() {
+                ""
+            } else {
+                "********"
+            }
+        ));
+        out.push_str(&format!(
+            "  caldav-calendars: {}\n",
+            self.caldav.calendars.join(", ")
+        ));
+        out.push_str(&format!(
+            "  hooks-start-on-login: {}\n",
+            self.hooks.start_on_login
+        ));
+        out.push_str(&format!("  hooks-on-logout: {}\n", self.hooks.on_logout));
+        out.push_str(&format!("  publish-target: {}\n", self.publish.target));
+        out.push_str(&format!("  publish-url: {}\n", self.publish.url));
+        out.push_str(&format!("  publish-username: {}\n", self.publish.username));
+        out.push_str(&format!(
+            "  publish-password: {}\n",
+            if self.publish.password.is_empty() {
+                ""
+            } else {
+                "********"
+            }
+        ));
+        out.push_str(&format!("  publish-region: {}\n", self.publish.region));
+        out.push_str(&format!(
+            "  publish-age-recipient: {}\n",
+            self.publish.age_recipient
+        ));
+        out.push_str(&format!(
+            "  publish-automatic: {}\n",
+            self.publish.automatic
+        ));
+        for t in &self.templates {
+            out.push_str(&format!(
+                "  template: {} (project: {}, hours: {}, description: {})\n",
+                t.name,
+                t.project,
+                t.hours,
+                t.description.as_deref().unwrap_or("")
+            ));
+        }
+        for o in &self.project_reports {
+            out.push_str(&format!(
+                "  project-report: {} (directory: {}, file-name: {})\n",
+                o.project,
+                o.directory.as_deref().unwrap_or(""),
+                o.file_name.as_deref().unwrap_or("")
+            ));
+        }
+        for r in &self.recurring {
+            out.push_str(&format!(
+                "  recurring: {} (project: {}, hours: {}, repeat: {}, start-date: {})\n",
+                r.name, r.project, r.hours, r.repeat, r.start_date
+            ));
+        }
+        for r in &self.report_renderers {
+            out.push_str(&format!(
+                "  report-renderer: {} (command: {}, extension: {})\n",
+                r.name, r.command, r.extension
+            ));
+        }
+        for i in &self.importers {
+            out.push_str(&format!(
+                "  importer: {} (command: {})\n",
+                i.name, i.command
+            ));
+        }
         out
     }
 }
 
 impl AppConfiguration {
-    /// Create a default configuration, but with a custom storage directory.
-    pub fn new_default(user_home: &String) -> Self {
-        let file_paths = FilePathsConfig {
-            storage_directory: format!("{}/.local/time-butler", user_home),
-            project_data_path: format!(
-                "{}/.local/time-butler/.app_storage/prj_data.bin",
-                user_home
-            ),
-            week_data_path: format!(
-                "{}/.local/time-butler/.app_storage/week_data.bin",
-                user_home
-            ),
-            report_directory: format!("{}/.local/time-butler/generated-reports", user_home),
-            backups_directory: format!("{}/.local/time-butler/backups", user_home),
-        };
-        let targets = TargetsConfig {
-            week_target_hours: 40.0,
-            month_target_hours: 160.0,
-            weekly_target_for_month: false,
-        };
-        let backup = BackupConfig {
-            enable_periodic_backup: false,
-            periodic_backup_interval_days: 14,
-            override_existing_backup: true,
-        };
-        let version = VersionConfiguration {
-            always_force_halt_on_version_incompatibility: true,
-        };
+    /// Create a default configuration, with all paths in the given data directory.
+    pub fn new_default(data_directory: &String) -> Self {
         Self {
-            file_paths,
-            targets,
-            backup,
-            version,
+            file_paths: FilePathsConfig::in_directory(data_directory),
+            ..Self::default()
         }
     }
 }
 
 /// Fs configuration struct
 #[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct FilePathsConfig {
     #[serde(rename = "time-butler-storage-directory")]
     pub storage_directory: String,
@@ -174,8 +484,29 @@ pub struct FilePathsConfig {
     #[serde(rename = "time-butler-backups-directory")]
     pub backups_directory: String,
 }
+
+impl FilePathsConfig {
+    /// Create the default paths in the given data directory
+    pub fn in_directory(data_directory: &String) -> Self {
+        Self {
+            storage_directory: data_directory.clone(),
+            project_data_path: format!("{}/.app_storage/prj_data.bin", data_directory),
+            week_data_path: format!("{}/.app_storage/week_data.bin", data_directory),
+            report_directory: format!("{}/generated-reports", data_directory),
+            backups_directory: format!("{}/backups", data_directory),
+        }
+    }
+}
+
+impl Default for FilePathsConfig {
+    fn default() -> Self {
+        Self::in_directory(&paths::data_directory().unwrap_or_else(|| ".".to_string()))
+    }
+}
+
 /// Targets configuration struct
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
 pub struct TargetsConfig {
     /// Target hours for the week
     #[serde(rename = "total-week-target")]
@@ -186,10 +517,25 @@ pub struct TargetsConfig {
     /// Use the combined weekly target for the month
     #[serde(rename = "use-total-week-target-for-month")]
     pub weekly_target_for_month: bool,
+    /// Update the target of the stored weeks from the current week on when the week target is changed
+    #[serde(rename = "auto-reapply-week-target")]
+    pub auto_reapply_week_target: bool,
+}
+
+impl Default for TargetsConfig {
+    fn default() -> Self {
+        Self {
+            week_target_hours: 40.0,
+            month_target_hours: 160.0,
+            weekly_target_for_month: false,
+            auto_reapply_week_target: false,
+        }
+    }
 }
 
 /// Backup configuration struct
-#[derive(Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
 pub struct BackupConfig {
     /// Target hours for the week
     #[serde(rename = "enable-periodic-backup")]
@@ -202,10 +548,366 @@ pub struct BackupConfig {
     pub override_existing_backup: bool,
 }
 
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            enable_periodic_backup: false,
+            periodic_backup_interval_days: 14,
+            override_existing_backup: true,
+        }
+    }
+}
+
 /// Version configuration struct
 #[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct VersionConfiguration {
     /// Force halt on version incompatibility
     #[serde(rename = "always-force-halt-on-version-incompatibility")]
     pub always_force_halt_on_version_incompatibility: bool,
 }
+
+impl Default for VersionConfiguration {
+    fn default() -> Self {
+        Self {
+            always_force_halt_on_version_incompatibility: true,
+        }
+    }
+}
+
+/// Display configuration struct
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// Color theme for the tables, valid options are: "default, high-contrast, none"
+    #[serde(rename = "color-theme")]
+    pub color_theme: String,
+    /// Language of the table headers, prompts and report column names, valid options are: "en, sv"
+    pub locale: String,
+    /// Use dark colors in the HTML reports when the browser prefers a dark color scheme
+    #[serde(rename = "html-dark-mode")]
+    pub html_dark_mode: bool,
+    /// Print a summary of the current week after a day or an entry is added
+    #[serde(rename = "week-summary-after-add")]
+    pub week_summary_after_add: bool,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            color_theme: "default".to_string(),
+            locale: "en".to_string(),
+            html_dark_mode: false,
+            week_summary_after_add: false,
+        }
+    }
+}
+
+/// Hours configuration struct
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct HoursConfig {
+    /// Rounding of the worked hours, valid options are: "none, nearest-<minutes>, up-<minutes>", e.g. "nearest-15"
+    pub rounding: String,
+    /// Format of the hours in the tables and reports, valid options are: "decimal, hh:mm"
+    pub format: String,
+    /// Precision of the calculated and shown hours, valid options are: "minutes, seconds"
+    pub precision: String,
+}
+
+impl Default for HoursConfig {
+    fn default() -> Self {
+        Self {
+            rounding: "none".to_string(),
+            format: "decimal".to_string(),
+            precision: "minutes".to_string(),
+        }
+    }
+}
+
+/// Weeks configuration struct, the first day of the week and the week numbering
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct WeeksConfig {
+    /// First day of the week, valid options are: "monday, sunday"
+    #[serde(rename = "week-start")]
+    pub week_start: String,
+    /// Week numbering scheme, valid options are: "iso, us"
+    #[serde(rename = "week-numbering")]
+    pub week_numbering: String,
+}
+
+impl Default for WeeksConfig {
+    fn default() -> Self {
+        Self {
+            week_start: "monday".to_string(),
+            week_numbering: "iso".to_string(),
+        }
+    }
+}
+
+/// Days configuration struct
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct DaysConfig {
+    /// Time a forgotten open day is closed at, "HH:MM"
+    #[serde(rename = "end-of-day")]
+    pub end_of_day: String,
+    /// Open days started more than this many hours ago are closed automatically when saving, disabled if not set
+    #[serde(rename = "auto-close-open-days-after-hours")]
+    pub auto_close_open_days_after_hours: Option<f32>,
+    /// Date the hours of a day crossing midnight are attributed to, valid options are: "start, end"
+    #[serde(rename = "overnight-date")]
+    pub overnight_date: String,
+    /// Longest shift in hours an imported day with an ending time before the starting time is taken as, the ending
+    /// time is then on the next date
+    #[serde(rename = "max-overnight-hours")]
+    pub max_overnight_hours: f32,
+    /// Break added to the paused hours of a day spanning midday without a recorded pause, in minutes. 0 to disable
+    #[serde(rename = "default-break-minutes")]
+    pub default_break_minutes: u32,
+    /// Warn when a day is closed with fewer hours than the threshold
+    #[serde(rename = "short-day-warning")]
+    pub short_day_warning: bool,
+    /// Hours below which a closed day is short
+    #[serde(rename = "short-day-warning-hours")]
+    pub short_day_warning_hours: f32,
+    /// Thresholds of single weekdays replacing the hours, e.g. {"friday": 4}
+    #[serde(rename = "short-day-warning-weekdays")]
+    pub short_day_warning_weekdays: BTreeMap<String, f32>,
+}
+
+impl Default for DaysConfig {
+    fn default() -> Self {
+        Self {
+            end_of_day: "17:00".to_string(),
+            auto_close_open_days_after_hours: None,
+            overnight_date: "start".to_string(),
+            max_overnight_hours: 12.0,
+            default_break_minutes: 0,
+            short_day_warning: true,
+            short_day_warning_hours: 8.0,
+            short_day_warning_weekdays: BTreeMap::new(),
+        }
+    }
+}
+
+/// Sync configuration struct, the directory with the text copy of the data used by `sync`
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct SyncConfig {
+    /// Directory of the synced files, "sync" in the storage directory if empty
+    pub directory: String,
+    /// Commit, pull and push the directory with git when it's a git repository
+    pub git: bool,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            directory: String::new(),
+            git: true,
+        }
+    }
+}
+
+/// Trash configuration struct, how long removed items can be restored
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TrashConfig {
+    /// Days a removed item is kept in the trash, 0 keeps the items until restored
+    #[serde(rename = "retention-days")]
+    pub retention_days: u32,
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self { retention_days: 30 }
+    }
+}
+
+/// Validation configuration struct, limits for the added data
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct ValidationConfig {
+    /// Highest number of hours for a single entry
+    #[serde(rename = "max-entry-hours")]
+    pub max_entry_hours: f32,
+    /// Highest number of worked hours for a day
+    #[serde(rename = "max-day-hours")]
+    pub max_day_hours: f32,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            max_entry_hours: 24.0,
+            max_day_hours: 24.0,
+        }
+    }
+}
+
+/// Entry template struct, used for recurring entries
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EntryTemplate {
+    /// Name of the template
+    pub name: String,
+    /// Project name or alias the entries are added to
+    pub project: String,
+    /// Hours for each entry
+    pub hours: f32,
+    /// Description for each entry
+    pub description: Option<String>,
+}
+
+/// Recurring entry struct, materialized by the sync-recurring command
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RecurringEntry {
+    /// Name of the recurring entry, used to detect already added occurrences
+    pub name: String,
+    /// Project name or alias the entries are added to
+    pub project: String,
+    /// Hours for each occurrence
+    pub hours: f32,
+    /// Description for each occurrence
+    pub description: Option<String>,
+    /// How the entry is repeated, valid options are: "daily, weekdays, weekly"
+    pub repeat: String,
+    /// First date of the recurrence, "YYYY-MM-DD". Weekly entries are repeated on the same weekday
+    #[serde(rename = "start-date")]
+    pub start_date: String,
+}
+
+/// Email configuration struct, the SMTP settings used when emailing reports
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct EmailConfig {
+    /// SMTP server url, e.g. "smtps://smtp.example.com:465" or "smtp://smtp.example.com:587"
+    #[serde(rename = "smtp-server")]
+    pub smtp_server: String,
+    /// User name for the SMTP server
+    #[serde(rename = "smtp-username")]
+    pub username: String,
+    /// Password for the SMTP server, replaced by the TIME_BUTLER_SMTP_PASSWORD environment variable when set
+    #[serde(rename = "smtp-password")]
+    pub password: String,
+    /// Sender address of the emails
+    #[serde(rename = "from")]
+    pub from: String,
+}
+
+/// Jira configuration struct, the API used by `export jira --post`
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct JiraConfig {
+    /// Base url of the Jira site, e.g. "https://example.atlassian.net"
+    pub url: String,
+    /// Account email for Jira Cloud, empty to use the token as a personal access token (Jira Data Center)
+    pub username: String,
+    /// API token, replaced by the TIME_BUTLER_JIRA_TOKEN environment variable when set
+    pub token: String,
+}
+
+/// CalDAV configuration struct, the calendar used by `import caldav`
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct CalDavConfig {
+    /// Url of the calendar, or of the calendar collection when calendars are set
+    pub url: String,
+    /// User name, empty if the server needs no login
+    pub username: String,
+    /// Password, replaced by the TIME_BUTLER_CALDAV_PASSWORD environment variable when set
+    pub password: String,
+    /// Calendars in the collection to import from, all events of the url if empty
+    pub calendars: Vec<String>,
+}
+
+/// Hooks configuration struct, the behavior of the login and logout hooks installed by `install-hooks`
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct HooksConfig {
+    /// Start the day at the first login of the day
+    #[serde(rename = "start-on-login")]
+    pub start_on_login: bool,
+    /// Action at logout: "stop" ends the day, "remind" reminds about ending it, "none" does nothing
+    #[serde(rename = "on-logout")]
+    pub on_logout: String,
+}
+
+impl Default for HooksConfig {
+    fn default() -> Self {
+        Self {
+            start_on_login: true,
+            on_logout: "remind".to_string(),
+        }
+    }
+}
+
+/// Publish configuration struct, the endpoint the reports are uploaded to by `report --publish`
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct PublishConfig {
+    /// Kind of endpoint, "webdav" or "s3"
+    pub target: String,
+    /// Url of the directory the reports are uploaded to, e.g. a WebDAV folder or "https://bucket.s3.region.amazonaws.com/reports"
+    pub url: String,
+    /// User name, or the access key for S3
+    pub username: String,
+    /// Password, or the secret key for S3. Replaced by the TIME_BUTLER_PUBLISH_PASSWORD environment variable when set
+    pub password: String,
+    /// Region of the S3 bucket, e.g. "eu-north-1"
+    pub region: String,
+    /// Public key the reports are encrypted to with age before the upload, empty to upload the plain report
+    #[serde(rename = "age-recipient")]
+    pub age_recipient: String,
+    /// Publish every generated report, not only with --publish
+    pub automatic: bool,
+}
+
+impl Default for PublishConfig {
+    fn default() -> Self {
+        Self {
+            target: "webdav".to_string(),
+            url: String::new(),
+            username: String::new(),
+            password: String::new(),
+            region: String::new(),
+            age_recipient: String::new(),
+            automatic: false,
+        }
+    }
+}
+
+/// Report renderer struct, a report format rendered by a shell command
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RendererConfig {
+    /// Name of the format, as given to --format
+    pub name: String,
+    /// Shell command getting the report data as JSON on stdin and writing the report to stdout
+    pub command: String,
+    /// Extension of the report files, "txt" if not set
+    #[serde(default)]
+    pub extension: String,
+}
+
+/// Importer struct, an import source read by a shell command
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ImporterConfig {
+    /// Name of the source, as given to --source
+    pub name: String,
+    /// Shell command getting the input as $1 and printing the import JSON on stdout
+    pub command: String,
+}
+
+/// Output of the project reports, directory and file name pattern
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProjectReportOutput {
+    /// Project name or alias, "*" for all projects without their own output
+    pub project: String,
+    /// Output directory, relative paths are in the report directory. The report directory if not set
+    pub directory: Option<String>,
+    /// File name pattern, may contain sub-directories, e.g. "{project}/{year}-{month}.{ext}". Placeholders are
+    /// {project}, {year}, {month}, {day}, {date}, {time} and {ext}, the extension is added if not in the pattern
+    #[serde(rename = "file-name")]
+    pub file_name: Option<String>,
+}
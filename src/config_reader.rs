@@ -9,8 +9,103 @@
 use std::fs;
 use std::io::Write;
 use std::path::Path;
+use std::process::Command;
+
+use serde_json::Value;
 
 use crate::config::AppConfiguration;
+use crate::toml_format;
+
+/// Default configuration file names, searched in this order in the storage directory
+pub const K_CONFIG_FILE_NAMES: [&str; 4] = [
+    "tb-config.json",
+    "tb-config.toml",
+    "tb-config.yaml",
+    "tb-config.yml",
+];
+
+/// Format of the configuration file, given by the file extension
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Get the format from a file extension or format name, None if not supported
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_lowercase().as_str() {
+            "json" => Some(ConfigFormat::Json),
+            "toml" => Some(ConfigFormat::Toml),
+            "yaml" | "yml" => Some(ConfigFormat::Yaml),
+            _ => None,
+        }
+    }
+
+    /// Get the format of the file, JSON if the extension is unknown
+    pub fn from_path(path: &str) -> Self {
+        Path::new(path)
+            .extension()
+            .and_then(|e| Self::from_extension(&e.to_string_lossy()))
+            .unwrap_or(ConfigFormat::Json)
+    }
+
+    /// Getter for the file extension
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ConfigFormat::Json => "json",
+            ConfigFormat::Toml => "toml",
+            ConfigFormat::Yaml => "yaml",
+        }
+    }
+
+    /// Internal function to parse the configuration, the same field names are used in all formats
+    fn parse(&self, content: &str) -> Result<AppConfiguration, Box<dyn std::error::Error>> {
+        Ok(match self {
+            ConfigFormat::Json => serde_json::from_str(content)?,
+            ConfigFormat::Toml => serde_json::from_value(toml_format::from_str(content)?)?,
+            ConfigFormat::Yaml => serde_yaml::from_str(content)?,
+        })
+    }
+
+    /// Internal function to parse the file content without the configuration struct, to see which values are set
+    fn parse_value(&self, content: &str) -> Result<Value, Box<dyn std::error::Error>> {
+        Ok(match self {
+            ConfigFormat::Json => serde_json::from_str(content)?,
+            ConfigFormat::Toml => toml_format::from_str(content)?,
+            ConfigFormat::Yaml => serde_yaml::from_str(content)?,
+        })
+    }
+
+    /// Internal function to write the configuration in the format
+    fn serialize(&self, config: &AppConfiguration) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(match self {
+            ConfigFormat::Json => serde_json::to_string_pretty(config)?,
+            ConfigFormat::Toml => toml_format::to_string(&serde_json::to_value(config)?)?,
+            ConfigFormat::Yaml => serde_yaml::to_string(config)?,
+        })
+    }
+}
+
+/// Internal function to collect the keys of all single values, e.g. "targets.total-week-target"
+fn value_keys(value: &Value, prefix: &str, keys: &mut Vec<String>) {
+    if let Value::Object(map) = value {
+        for (name, v) in map {
+            let key = if prefix.is_empty() {
+                name.clone()
+            } else {
+                format!("{}.{}", prefix, name)
+            };
+            match v {
+                Value::Object(_) => value_keys(v, &key, keys),
+                // Lists, e.g. templates, are managed by their own commands
+                Value::Array(_) => {}
+                _ => keys.push(key),
+            }
+        }
+    }
+}
 
 pub struct ConfigReader {
     config_path: String,
@@ -33,12 +128,52 @@ impl ConfigReader {
         }
 
         let config_content = fs::read_to_string(path)?;
-        let config: AppConfiguration = serde_json::from_str(&config_content)?;
+        let format = ConfigFormat::from_path(&self.config_path);
+        let config = format.parse(&config_content)?;
+
+        if let Ok(file_value) = format.parse_value(&config_content) {
+            Self::log_default_values(&file_value, &serde_json::to_value(&config)?);
+        }
         self.configuration = Some(config);
 
         Ok(())
     }
 
+    /// Internal function to log which values are read from the file, and which are missing and got the default
+    fn log_default_values(file_value: &Value, config_value: &Value) {
+        let mut file_keys = Vec::new();
+        value_keys(file_value, "", &mut file_keys);
+        let mut config_keys = Vec::new();
+        value_keys(config_value, "", &mut config_keys);
+
+        let mut defaults = Vec::new();
+        for key in config_keys {
+            // Credentials are never logged
+            let value = if key.ends_with("password") {
+                "********".to_string()
+            } else {
+                key.split('.')
+                    .try_fold(config_value, |v, part| v.get(part))
+                    .map(|v| v.to_string())
+                    .unwrap_or_default()
+            };
+            if file_keys.contains(&key) {
+                tracing::debug!("Configuration {} = {} (from file)", key, value);
+            } else {
+                tracing::debug!("Configuration {} = {} (default)", key, value);
+                defaults.push(key);
+            }
+        }
+
+        if !defaults.is_empty() {
+            tracing::info!(
+                "{} configuration values not in the file, using defaults for: {}",
+                defaults.len(),
+                defaults.join(", ")
+            );
+        }
+    }
+
     /// Get a reference to the configuration
     pub fn get_configuration(&self) -> Option<&AppConfiguration> {
         self.configuration.as_ref()
@@ -54,10 +189,174 @@ impl ConfigReader {
         &self,
         config: &AppConfiguration,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let json = serde_json::to_string_pretty(config)?;
+        let content = ConfigFormat::from_path(&self.config_path).serialize(config)?;
         let path = Path::new(&self.config_path);
+        // The default file may be placed in a not yet existing XDG directory
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
         let mut file = fs::File::create(path)?;
-        file.write_all(json.as_bytes())?;
+        file.write_all(content.as_bytes())?;
+        Ok(())
+    }
+
+    /// Convert the configuration file to another format. The converted file is written next to the current one,
+    /// with the extension of the format, and the current file is kept with a ".bak" suffix. Returns the new path
+    pub fn convert_config(
+        &self,
+        format: ConfigFormat,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        if ConfigFormat::from_path(&self.config_path) == format {
+            return Err(format!(
+                "Configuration file {} is already {}",
+                self.config_path,
+                format.extension()
+            )
+            .into());
+        }
+
+        let config = self
+            .configuration
+            .as_ref()
+            .ok_or("Configuration is not read")?;
+        let new_path = Path::new(&self.config_path).with_extension(format.extension());
+        if new_path.exists() {
+            return Err(format!("Configuration file {} already exists", new_path.display()).into());
+        }
+
+        fs::write(&new_path, format.serialize(config)?)?;
+        fs::rename(&self.config_path, format!("{}.bak", self.config_path))?;
+
+        Ok(new_path.to_string_lossy().to_string())
+    }
+
+    /// Edit the configuration file with the given editor. The changes are made to a copy, which is validated
+    /// before it replaces the configuration file. Returns false if the file was left unchanged
+    pub fn edit_config(&self, editor: &str) -> Result<bool, Box<dyn std::error::Error>> {
+        let path = Path::new(&self.config_path);
+        if !path.exists() {
+            return Err(format!("Configuration file not found: {}", self.config_path).into());
+        }
+
+        let original = fs::read_to_string(path)?;
+        let edit_path = std::env::temp_dir().join(format!(
+            "time-butler-config-{}.{}",
+            uuid::Uuid::new_v4(),
+            ConfigFormat::from_path(&self.config_path).extension()
+        ));
+        fs::write(&edit_path, &original)?;
+
+        // The editor may be given with arguments, e.g. "code --wait"
+        let mut editor_parts = editor.split_whitespace();
+        let program = editor_parts.next().ok_or("No editor given")?;
+        let status = Command::new(program)
+            .args(editor_parts)
+            .arg(&edit_path)
+            .status()
+            .map_err(|e| format!("Failed to start editor '{}': {}", editor, e))?;
+        if !status.success() {
+            fs::remove_file(&edit_path)?;
+            return Err(format!("Editor '{}' exited with {}", editor, status).into());
+        }
+
+        let edited = fs::read_to_string(&edit_path)?;
+        if edited == original {
+            fs::remove_file(&edit_path)?;
+            return Ok(false);
+        }
+
+        // Keep the edited copy on failure, so the changes are not lost
+        let mut edited_reader = ConfigReader::new(&edit_path.to_string_lossy());
+        if let Err(e) = edited_reader.read_config() {
+            return Err(format!(
+                "Edited configuration is invalid, {} is not updated: {}. The edited copy is kept at {}",
+                self.config_path,
+                e,
+                edit_path.display()
+            )
+            .into());
+        }
+
+        fs::write(path, edited)?;
+        fs::remove_file(&edit_path)?;
+        Ok(true)
+    }
+
+    /// Internal function to get the read configuration as JSON value, with the keys as in the file. The keys are
+    /// the same in all file formats
+    fn configuration_value(&self) -> Result<Value, Box<dyn std::error::Error>> {
+        let config = self
+            .configuration
+            .as_ref()
+            .ok_or("Configuration is not read")?;
+        Ok(serde_json::to_value(config)?)
+    }
+
+    /// Internal function to get an error for an unknown key, with the valid keys listed
+    fn unknown_key_error(key: &str, config: &Value) -> Box<dyn std::error::Error> {
+        let mut keys = Vec::new();
+        value_keys(config, "", &mut keys);
+        format!(
+            "Unknown configuration key '{}', valid keys are: {}",
+            key,
+            keys.join(", ")
+        )
+        .into()
+    }
+
+    /// Get a value of the read configuration by key, e.g. "backup.enable-periodic-backup".
+    /// A section key, e.g. "backup", gives the whole section
+    pub fn get_value(&self, key: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let config = self.configuration_value()?;
+        let value = key
+            .split('.')
+            .try_fold(&config, |v, part| v.get(part))
+            .ok_or_else(|| Self::unknown_key_error(key, &config))?;
+
+        Ok(match value {
+            Value::String(s) => s.clone(),
+            Value::Object(_) | Value::Array(_) => serde_json::to_string_pretty(value)?,
+            other => other.to_string(),
+        })
+    }
+
+    /// Set a single value of the read configuration by key and write it to the file. The value must have the
+    /// same type as the current one, and the updated configuration must be valid
+    pub fn set_value(&mut self, key: &str, value: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let mut config = self.configuration_value()?;
+        let unknown_key = Self::unknown_key_error(key, &config);
+        let current = key
+            .split('.')
+            .try_fold(&mut config, |v, part| v.get_mut(part))
+            .ok_or(unknown_key)?;
+
+        let new_value = match current {
+            Value::String(_) => Value::String(value.to_string()),
+            Value::Bool(_) => match value {
+                "true" => Value::Bool(true),
+                "false" => Value::Bool(false),
+                _ => return Err(format!("'{}' expects true or false, got '{}'", key, value).into()),
+            },
+            Value::Number(_) => match serde_json::from_str::<Value>(value) {
+                Ok(number @ Value::Number(_)) => number,
+                _ => return Err(format!("'{}' expects a number, got '{}'", key, value).into()),
+            },
+            _ => {
+                return Err(format!(
+                    "'{}' is not a single value, set one of its keys instead",
+                    key
+                )
+                .into())
+            }
+        };
+        *current = new_value;
+
+        // Deserializing validates the value, e.g. negative numbers for unsigned fields
+        let updated: AppConfiguration = serde_json::from_value(config)
+            .map_err(|e| format!("Invalid value '{}' for '{}': {}", value, key, e))?;
+        self.write_config(&updated)?;
+        self.configuration = Some(updated);
+
         Ok(())
     }
 }
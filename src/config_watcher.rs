@@ -0,0 +1,68 @@
+/*
+ * File: config_watcher.rs
+ * Description: Watcher of the configuration file, used by the long-running modes to apply changes without a restart.
+ *              The modification time is polled, so no file system notification support is needed.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use std::fs;
+use std::time::SystemTime;
+
+use crate::config::AppConfiguration;
+use crate::config_reader::ConfigReader;
+
+/// Watcher of the configuration file
+pub struct ConfigWatcher {
+    /// Path to the watched configuration file
+    config_path: String,
+    /// Modification time at the last check, None if the file could not be read
+    last_modified: Option<SystemTime>,
+}
+
+impl ConfigWatcher {
+    /// Create a new watcher, changes are detected from the current state of the file
+    pub fn new(config_path: &str) -> Self {
+        Self {
+            config_path: config_path.to_string(),
+            last_modified: Self::modified(config_path),
+        }
+    }
+
+    /// Internal function to get the modification time of the file
+    fn modified(config_path: &str) -> Option<SystemTime> {
+        fs::metadata(config_path).and_then(|m| m.modified()).ok()
+    }
+
+    /// Getter for `config_path`
+    pub fn config_path(&self) -> &str {
+        &self.config_path
+    }
+
+    /// Check if the file changed since the last check. Returns the new configuration if it changed and is valid,
+    /// an invalid file is logged and skipped until it changes again
+    pub fn poll(&mut self) -> Option<AppConfiguration> {
+        let modified = Self::modified(&self.config_path);
+        if modified == self.last_modified {
+            return None;
+        }
+        self.last_modified = modified;
+
+        let mut reader = ConfigReader::new(&self.config_path);
+        match reader.read_config() {
+            Ok(_) => {
+                tracing::debug!("Configuration file {} changed", self.config_path);
+                reader.get_configuration().cloned()
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Changed configuration file {} is invalid, keeping the current configuration: {}",
+                    self.config_path,
+                    e
+                );
+                None
+            }
+        }
+    }
+}
@@ -6,14 +6,186 @@
  * License: MIT
  */
 
-use chrono::{DateTime, Datelike, Local, NaiveDate, Utc};
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveTime};
 use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
 use uuid::Uuid;
 
-const K_WORK_HOURS_DEFAULT: f32 = 8.0;
+use crate::clock::{Clock, SystemClock};
+use crate::duration::{self, TimePrecision};
+use crate::project::K_PROJECT_PATH_SEPARATOR;
+use crate::week_scheme::{self, WeekScheme};
+
 const K_NO_HOURS: f32 = 0.0;
 
+/// Rounding of the worked hours of the days, set once from the configuration
+static K_ROUNDING_POLICY: RwLock<RoundingPolicy> = RwLock::new(RoundingPolicy::None);
+
+/// Rounding of the worked hours, to a step of minutes
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RoundingPolicy {
+    /// Hours are kept as calculated
+    #[default]
+    None,
+    /// Rounded to the nearest step, halfway rounded up
+    Nearest(u32),
+    /// Always rounded up to the next step
+    Up(u32),
+}
+
+impl RoundingPolicy {
+    /// Round the hours according to the policy
+    pub fn round(&self, hours: f32) -> f32 {
+        // Work on whole minutes to avoid float artifacts, e.g. 7.1h being rounded up to the next step
+        let minutes = (hours.max(K_NO_HOURS) * 60.0).round() as u32;
+        let rounded = match self {
+            RoundingPolicy::None => return hours,
+            RoundingPolicy::Nearest(step) => (minutes + step / 2) / step * step,
+            RoundingPolicy::Up(step) => minutes.div_ceil(*step) * step,
+        };
+        rounded as f32 / 60.0
+    }
+}
+
+/// Implement Display trait for RoundingPolicy, same format as parsed
+impl fmt::Display for RoundingPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoundingPolicy::None => write!(f, "none"),
+            RoundingPolicy::Nearest(step) => write!(f, "nearest-{}", step),
+            RoundingPolicy::Up(step) => write!(f, "up-{}", step),
+        }
+    }
+}
+
+/// Parse error for RoundingPolicy
+#[derive(Debug)]
+pub struct ParseRoundingPolicyError(String);
+
+/// Implement Display trait for ParseRoundingPolicyError
+impl fmt::Display for ParseRoundingPolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid rounding policy: {}, valid policies are: none, nearest-<minutes>, up-<minutes>",
+            self.0
+        )
+    }
+}
+
+/// Implement Error trait for ParseRoundingPolicyError
+impl Error for ParseRoundingPolicyError {}
+
+/// Parse a rounding policy, "none", "nearest-15" or "up-6"
+impl FromStr for RoundingPolicy {
+    type Err = ParseRoundingPolicyError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim().to_lowercase();
+        if input == "none" || input.is_empty() {
+            return Ok(RoundingPolicy::None);
+        }
+
+        let (mode, step) = input
+            .split_once('-')
+            .ok_or_else(|| ParseRoundingPolicyError(input.clone()))?;
+        let step = step
+            .parse::<u32>()
+            .ok()
+            .filter(|s| (1..=60).contains(s))
+            .ok_or_else(|| ParseRoundingPolicyError(input.clone()))?;
+
+        match mode {
+            "nearest" => Ok(RoundingPolicy::Nearest(step)),
+            "up" => Ok(RoundingPolicy::Up(step)),
+            _ => Err(ParseRoundingPolicyError(input.clone())),
+        }
+    }
+}
+
+/// Set the rounding of the worked hours of the days
+pub fn set_rounding_policy(rounding: RoundingPolicy) {
+    if let Ok(mut current) = K_ROUNDING_POLICY.write() {
+        *current = rounding;
+    }
+}
+
+/// Get the rounding of the worked hours of the days
+pub fn rounding_policy() -> RoundingPolicy {
+    K_ROUNDING_POLICY.read().map(|r| *r).unwrap_or_default()
+}
+
+/// Date the hours of a day crossing midnight are attributed to
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OvernightDate {
+    /// The date the day was started
+    #[default]
+    Start,
+    /// The date the day was ended
+    End,
+}
+
+/// Parse error for OvernightDate
+#[derive(Debug)]
+pub struct ParseOvernightDateError(String);
+
+/// Implement Display trait for ParseOvernightDateError
+impl fmt::Display for ParseOvernightDateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid overnight date: {}, valid options are: start, end",
+            self.0
+        )
+    }
+}
+
+/// Implement Error trait for ParseOvernightDateError
+impl Error for ParseOvernightDateError {}
+
+/// Parse the overnight date, "start" or "end"
+impl FromStr for OvernightDate {
+    type Err = ParseOvernightDateError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            "start" => Ok(OvernightDate::Start),
+            "end" => Ok(OvernightDate::End),
+            other => Err(ParseOvernightDateError(other.to_string())),
+        }
+    }
+}
+
+/// Hours of a day allocated to a project
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Allocation {
+    /// Name of the project
+    project: String,
+    /// Allocated hours
+    hours: f32,
+}
+
+impl Allocation {
+    /// Create a new allocation
+    pub fn new(project: String, hours: f32) -> Self {
+        Self { project, hours }
+    }
+
+    /// Getter for `project`
+    pub fn project(&self) -> &str {
+        &self.project
+    }
+
+    /// Getter for `hours`
+    pub fn hours(&self) -> f32 {
+        self.hours
+    }
+}
+
 /// Day struct to store time entries
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Day {
@@ -21,7 +193,8 @@ pub struct Day {
     starting_time: Option<DateTime<Local>>,
     /// End time of the day
     ending_time: Option<DateTime<Local>>,
-    /// Hours logged
+    /// Hours logged when the day was last changed, kept for the readers of the stored data. The hours are derived from
+    /// the times and the pause when read
     hours: f32,
     /// extra_info of the work done
     extra_info: String,
@@ -41,30 +214,105 @@ pub struct Day {
     hours_paused: f32,
     /// Unique ID for the day
     id: uuid::Uuid,
+    /// Hours of the day allocated to projects
+    allocations: Vec<Allocation>,
+    /// Don't add the default break when the day is closed
+    skip_default_break: bool,
+    /// Derived hours with the rounding and precision they were derived with, invalidated when the times or the pause
+    /// are changed
+    #[serde(skip)]
+    cached_hours: Cell<Option<(RoundingPolicy, TimePrecision, f32)>>,
+}
+
+/// Layout of a day in storage file version 1
+#[derive(Deserialize, Debug)]
+pub struct DayV1 {
+    starting_time: Option<DateTime<Local>>,
+    ending_time: Option<DateTime<Local>>,
+    hours: f32,
+    extra_info: String,
+    created: DateTime<Local>,
+    week: u32,
+    date: NaiveDate,
+    start_time_set: bool,
+    ending_time_set: bool,
+    closed: bool,
+    hours_paused: f32,
+    id: uuid::Uuid,
+}
+
+/// Convert a day of storage file version 1, the day has no allocations
+impl From<DayV1> for Day {
+    fn from(day: DayV1) -> Self {
+        Self {
+            starting_time: day.starting_time,
+            ending_time: day.ending_time,
+            hours: day.hours,
+            extra_info: day.extra_info,
+            created: day.created,
+            week: day.week,
+            date: day.date,
+            start_time_set: day.start_time_set,
+            ending_time_set: day.ending_time_set,
+            closed: day.closed,
+            hours_paused: day.hours_paused,
+            id: day.id,
+            allocations: Vec::new(),
+            skip_default_break: false,
+            cached_hours: Cell::new(None),
+        }
+    }
 }
 
 impl Day {
-    /// Create a new Day
+    /// Create a new Day, for today
     pub fn new(extra_info: Option<String>) -> Self {
+        Self::new_with_clock(extra_info, &SystemClock)
+    }
+
+    /// Create a new Day, for the current date of the clock
+    pub fn new_with_clock(extra_info: Option<String>, clock: &dyn Clock) -> Self {
+        let now = clock.now();
         Self {
             starting_time: None,
             ending_time: None,
             hours: K_NO_HOURS,
             extra_info: extra_info.unwrap_or_default(),
-            created: Local::now(),
-            week: Utc::now().iso_week().week(),
-            date: Local::now().date_naive(),
+            created: now,
+            week: week_scheme::week_scheme().week_of(now.date_naive()).0,
+            date: now.date_naive(),
             start_time_set: false,
             ending_time_set: false,
             closed: false,
             hours_paused: K_NO_HOURS,
             id: Uuid::new_v4(),
+            allocations: Vec::new(),
+            skip_default_break: false,
+            cached_hours: Cell::new(None),
         }
     }
 
-    /// Getter for `hours`
+    /// Hours worked, derived from the times and the pause of a closed day with the configured precision and rounding.
+    /// An open day has no hours yet
     pub fn hours(&self) -> f32 {
-        self.hours
+        if !self.closed {
+            return K_NO_HOURS;
+        }
+        let (rounding, precision) = (rounding_policy(), duration::time_precision());
+        match self.cached_hours.get() {
+            Some((r, p, hours)) if r == rounding && p == precision => hours,
+            _ => {
+                let hours = self.calculate_hours(rounding);
+                self.cached_hours.set(Some((rounding, precision, hours)));
+                hours
+            }
+        }
+    }
+
+    /// Internal function to derive the hours again after a change, the stored hours follow
+    fn refresh_hours(&mut self) {
+        self.cached_hours.set(None);
+        self.hours = self.hours();
     }
 
     /// Getter for `extra_info`
@@ -104,15 +352,30 @@ impl Day {
         &self.id
     }
 
-    /// Setter for `starting_time`
-    pub fn set_starting_time(&mut self, t: Option<&DateTime<Local>>) {
-        match t {
-            Some(value) => self.starting_time = Some(*value),
-            None => {
-                self.starting_time = Some(Local::now());
-                tracing::debug!("No starting time provided, setting current time");
+    /// Getter for `allocations`
+    pub fn allocations(&self) -> &Vec<Allocation> {
+        &self.allocations
+    }
+
+    /// Setter for `allocations`, replaces the existing allocations
+    pub fn set_allocations(&mut self, allocations: Vec<Allocation>) {
+        self.allocations = allocations;
+    }
+
+    /// Rename the project of the allocations, the sub-projects of the project follow
+    pub fn rename_allocations(&mut self, old_name: &str, new_name: &str) {
+        for a in self.allocations.iter_mut() {
+            let is_sub_project = a.project.starts_with(old_name)
+                && a.project[old_name.len()..].starts_with(K_PROJECT_PATH_SEPARATOR);
+            if a.project == old_name || is_sub_project {
+                a.project = format!("{}{}", new_name, &a.project[old_name.len()..]);
             }
         }
+    }
+
+    /// Setter for `starting_time`, closes the day if the ending time is set
+    pub fn set_starting_time(&mut self, t: &DateTime<Local>) {
+        self.starting_time = Some(*t);
 
         tracing::debug!(
             "Setting starting time for the day: {:?}",
@@ -122,38 +385,22 @@ impl Day {
 
         if self.start_time_set && self.ending_time_set {
             self.closed = true;
-
-            self.hours = self.calculate_hours();
-
-            if self.hours < K_WORK_HOURS_DEFAULT {
-                println!("WARNING: You have worked less than 8 hours today.");
-            }
             self.update_date_and_week_();
         }
+        self.refresh_hours();
     }
 
-    /// Setter for `ending_time`
-    pub fn set_ending_time(&mut self, t: Option<&DateTime<Local>>) {
-        match t {
-            Some(value) => self.ending_time = Some(*value),
-            None => {
-                self.ending_time = Some(Local::now());
-                tracing::debug!("No ending time provided, setting current time");
-            }
-        }
+    /// Setter for `ending_time`, closes the day if the starting time is set
+    pub fn set_ending_time(&mut self, t: &DateTime<Local>) {
+        self.ending_time = Some(*t);
 
         tracing::debug!("Setting ending time for the day: {:?}", self.ending_time);
         self.ending_time_set = true;
 
         if self.start_time_set && self.ending_time_set {
             self.closed = true;
-
-            self.hours = self.calculate_hours();
-
-            if self.hours < K_WORK_HOURS_DEFAULT {
-                println!("WARNING: You have worked less than 8 hours today.");
-            }
         }
+        self.refresh_hours();
     }
 
     /// Set paused time
@@ -164,10 +411,54 @@ impl Day {
             tracing::warn!("Cannot set paused time before starting time is set. Provided paused time will be ignored");
         }
 
-        // If used during modification, recalculate hours if both times are set
-        if self.start_time_set && self.ending_time_set {
-            self.hours = self.calculate_hours();
+        self.refresh_hours();
+    }
+
+    /// Getter for `skip_default_break`
+    pub fn skip_default_break(&self) -> bool {
+        self.skip_default_break
+    }
+
+    /// Setter for `skip_default_break`
+    pub fn set_skip_default_break(&mut self, skip: bool) {
+        self.skip_default_break = skip;
+    }
+
+    /// Add the default break to the paused hours, if the day is closed, spans midday and has no pause recorded. Returns
+    /// true if the break was added
+    pub fn add_default_break(&mut self, minutes: u32) -> bool {
+        if minutes == 0 || self.skip_default_break || !self.closed || self.hours_paused > K_NO_HOURS
+        {
+            return false;
+        }
+        let (Some(start), Some(end)) = (self.starting_time, self.ending_time) else {
+            return false;
+        };
+
+        let midday = NaiveTime::from_hms_opt(12, 0, 0).unwrap(); // safe, valid time
+        let spans_midday =
+            start.time() < midday && (end.time() > midday || end.date_naive() > start.date_naive());
+        // A break longer than the day would remove all hours
+        if !spans_midday || (end - start).num_minutes() <= i64::from(minutes) {
+            return false;
         }
+
+        self.set_paused_time(minutes as f32 / 60.0);
+        true
+    }
+
+    /// Number of days the ending time is after the starting date, 1 for a shift ending after midnight
+    pub fn overnight_days(&self) -> i64 {
+        match (self.starting_time, self.ending_time) {
+            (Some(start), Some(end)) => (end.date_naive() - start.date_naive()).num_days().max(0),
+            _ => 0,
+        }
+    }
+
+    /// Move the day to another date, e.g. the ending date of a shift crossing midnight. The times are kept
+    pub fn set_date(&mut self, date: NaiveDate) {
+        self.date = date;
+        self.week = week_scheme::week_scheme().week_of(date).0;
     }
 
     /// Getter for `week`
@@ -190,6 +481,17 @@ impl Day {
         self.date.year()
     }
 
+    /// Get the year the week of the day belongs to in the configured week scheme, differs from the year of the date
+    /// for the weeks around new year
+    pub fn week_year(&self) -> i32 {
+        self.week_year_in(week_scheme::week_scheme())
+    }
+
+    /// Get the year the week of the day belongs to in the week scheme
+    pub fn week_year_in(&self, scheme: WeekScheme) -> i32 {
+        scheme.week_of(self.date).1
+    }
+
     /// Getter for `start_time_set`
     pub fn start_time_set(&self) -> bool {
         self.start_time_set
@@ -205,9 +507,30 @@ impl Day {
         self.closed
     }
 
-    /// Calculate the hours worked
-    fn calculate_hours(&self) -> f32 {
-        //TODO: Refactor this function to not cut minutes so hard
+    /// Check if the other day has the same times, pause, extra info and closed state, the values taken by a merge. The
+    /// ID and creation time of a day stored on another machine differ
+    pub fn same_values(&self, other: &Day) -> bool {
+        self.starting_time == other.starting_time
+            && self.ending_time == other.ending_time
+            && self.hours_paused == other.hours_paused
+            && self.extra_info == other.extra_info
+            && self.closed == other.closed
+    }
+
+    /// Hours worked so far for a day that is started but not closed yet
+    pub fn running_hours(&self, now: &DateTime<Local>) -> Option<f32> {
+        if self.closed {
+            return None;
+        }
+
+        self.starting_time.map(|start| {
+            let worked_hours = duration::time_precision().hours(*now - start);
+            (worked_hours - self.hours_paused).max(K_NO_HOURS)
+        })
+    }
+
+    /// Calculate the hours worked with the configured precision, rounded with the policy
+    fn calculate_hours(&self, rounding: RoundingPolicy) -> f32 {
         let duration = match (self.ending_time, self.starting_time) {
             (Some(end), Some(start)) => end - start,
             _ => {
@@ -218,26 +541,39 @@ impl Day {
 
         tracing::debug!("Calculated duration: {:?}", duration);
 
-        // Get the duration as minutes, and then convert to hours on order to not round away the minutes
-        let worked_hours = duration.num_minutes() as f32 / 60.0;
+        // Cut the duration to whole minutes or seconds before converting to hours, to not get float artifacts
+        let worked_hours = duration::time_precision().hours(duration);
 
         let net_hours = worked_hours - self.hours_paused;
 
         // Prevent negative hours
-        if net_hours < 0.0 {
+        let hours = if net_hours < 0.0 {
             tracing::warn!("Worked hours (net hours) calculated as negative, [paused time = {}h]. Paused hours will not be considered", self.hours_paused);
             tracing::debug!("Setting worked hours to: {}", worked_hours);
             worked_hours
         } else {
             net_hours
+        };
+
+        let rounded = rounding.round(hours);
+        if rounded != hours {
+            tracing::debug!(
+                "Worked hours {} rounded to {} ({})",
+                hours,
+                rounded,
+                rounding
+            );
         }
+        rounded
     }
 
     fn update_date_and_week_(&mut self) {
         // Will be used when modifying a day, to update the date and week if the starting time is modified.
         if let Some(starting_time) = self.starting_time {
             let updated_date = starting_time.date_naive();
-            let updated_week = starting_time.iso_week().week();
+            let updated_week = week_scheme::week_scheme()
+                .week_of(starting_time.date_naive())
+                .0;
             tracing::debug!(
                 "Updating date and week based on starting time. New date: {}, New week: {}",
                 updated_date,
@@ -261,7 +597,141 @@ impl fmt::Display for Day {
         write!(
             f,
             "Created: {}\nstarting_time - {:?}\nend_time: {:?}\nhours - {}",
-            self.created, self.starting_time, self.ending_time, self.hours
+            self.created,
+            self.starting_time,
+            self.ending_time,
+            self.hours()
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::FixedClock;
+    use chrono::TimeZone;
+
+    /// Internal function to get a local time of the tests
+    fn time(year: i32, month: u32, day: u32, hour: u32, min: u32) -> DateTime<Local> {
+        Local
+            .with_ymd_and_hms(year, month, day, hour, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn week_year_follows_the_week_scheme() {
+        let us = WeekScheme::new(chrono::Weekday::Sun, week_scheme::WeekNumbering::Us);
+
+        // Friday, in ISO week 53 of 2020 and in US week 1 of 2021
+        let day = Day::new_with_clock(None, &FixedClock::new(time(2021, 1, 1, 8, 0)));
+        assert_eq!(day.year(), 2021);
+        assert_eq!(day.week_year_in(WeekScheme::iso()), 2020);
+        assert_eq!(day.week_year_in(us), 2021);
+
+        // Wednesday, in week 1 of 2026 in both schemes
+        let day = Day::new_with_clock(None, &FixedClock::new(time(2025, 12, 31, 8, 0)));
+        assert_eq!(day.week_year_in(WeekScheme::iso()), 2026);
+        assert_eq!(day.week_year_in(us), 2026);
+
+        // Saturday, in ISO week 52 and US week 53 of 2022
+        let day = Day::new_with_clock(None, &FixedClock::new(time(2022, 12, 31, 8, 0)));
+        assert_eq!(day.week_year_in(WeekScheme::iso()), 2022);
+        assert_eq!(day.week_year_in(us), 2022);
+    }
+
+    #[test]
+    fn new_day_is_on_the_date_of_the_clock() {
+        let clock = FixedClock::new(time(2025, 3, 10, 8, 0));
+        let mut day = Day::new_with_clock(None, &clock);
+        assert_eq!(day.date(), NaiveDate::from_ymd_opt(2025, 3, 10).unwrap());
+        assert_eq!(day.week(), 11);
+
+        day.set_starting_time(&clock.now());
+        assert_eq!(day.starting_time(), Some(&time(2025, 3, 10, 8, 0)));
+        assert!(!day.closed());
+    }
+
+    /// Internal function to compare hours of the tests, with a margin for the float conversion
+    fn assert_hours(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-5,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    /// Internal function to get whole minutes as hours
+    fn minutes(minutes: u32) -> f32 {
+        minutes as f32 / 60.0
+    }
+
+    #[test]
+    fn rounding_none_keeps_the_hours() {
+        assert_hours(RoundingPolicy::None.round(7.1), 7.1);
+        assert_hours(RoundingPolicy::None.round(minutes(427)), minutes(427));
+        assert_hours(RoundingPolicy::None.round(0.0), 0.0);
+    }
+
+    #[test]
+    fn rounding_nearest_rounds_halfway_up() {
+        let nearest = RoundingPolicy::Nearest(15);
+        // Exactly on a step
+        assert_hours(nearest.round(7.25), 7.25);
+        // One minute past a step
+        assert_hours(nearest.round(minutes(436)), 7.25);
+        // Below and at the halfway point of the step
+        assert_hours(nearest.round(minutes(427)), 7.0);
+        assert_hours(nearest.round(minutes(428)), 7.25);
+        assert_hours(nearest.round(0.0), 0.0);
+        // Negative hours are not rounded below zero
+        assert_hours(nearest.round(-1.0), 0.0);
+    }
+
+    #[test]
+    fn rounding_up_rounds_to_the_next_step() {
+        let up = RoundingPolicy::Up(15);
+        // Exactly on a step
+        assert_hours(up.round(7.25), 7.25);
+        // One minute past a step
+        assert_hours(up.round(minutes(436)), 7.5);
+        assert_hours(up.round(minutes(421)), 7.25);
+        assert_hours(up.round(0.0), 0.0);
+
+        // 7.1h is exactly on a step of 6 minutes, not rounded up because of the float
+        let up = RoundingPolicy::Up(6);
+        assert_hours(up.round(7.1), 7.1);
+        assert_hours(up.round(minutes(427)), 7.2);
+    }
+
+    #[test]
+    fn rounding_of_a_day_crossing_midnight() {
+        let mut day = Day::new_with_clock(None, &FixedClock::new(time(2025, 3, 10, 22, 53)));
+        day.set_starting_time(&time(2025, 3, 10, 22, 53));
+        day.set_ending_time(&time(2025, 3, 11, 1, 0));
+        assert!(day.closed());
+
+        // Worked 2h 7min
+        assert_hours(day.calculate_hours(RoundingPolicy::None), minutes(127));
+        assert_hours(day.calculate_hours(RoundingPolicy::Nearest(15)), 2.0);
+        assert_hours(day.calculate_hours(RoundingPolicy::Up(15)), 2.25);
+        assert_hours(day.calculate_hours(RoundingPolicy::Up(60)), 3.0);
+    }
+
+    #[test]
+    fn rounding_policies_parse_and_display() {
+        for policy in [
+            RoundingPolicy::None,
+            RoundingPolicy::Nearest(15),
+            RoundingPolicy::Up(6),
+        ] {
+            assert_eq!(
+                policy.to_string().parse::<RoundingPolicy>().unwrap(),
+                policy
+            );
+        }
+        assert!("nearest".parse::<RoundingPolicy>().is_err());
+        assert!("down-15".parse::<RoundingPolicy>().is_err());
+        assert!("up-0".parse::<RoundingPolicy>().is_err());
+    }
+}
@@ -0,0 +1,168 @@
+/*
+ * File: dev.rs
+ * Description: Development support, generation of synthetic data sets and timing of the core operations. Used by
+ *              the hidden "dev" command to make performance regressions in the storage visible.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use chrono::{Datelike, Duration as DateDuration, Local, NaiveDate, TimeZone, Weekday};
+use std::time::{Duration, Instant};
+
+use crate::day::Day;
+use crate::entry::Entry;
+use crate::project::Project;
+use crate::week::Week;
+
+/// Number of projects the generated entries are spread over
+const K_GENERATED_PROJECTS: usize = 5;
+/// Seed of the generated data, the same arguments always give the same days and hours
+const K_GENERATOR_SEED: u64 = 0x05ee_db07_e1e5;
+/// Earliest starting time of a generated day, in minutes after midnight
+const K_EARLIEST_START_MINUTES: u32 = 7 * 60;
+/// Spread of the starting times, in minutes
+const K_START_SPREAD_MINUTES: u32 = 120;
+/// Shortest generated working day including the pause, in minutes. Long enough to keep the "less than 8 hours"
+/// warning quiet
+const K_MIN_DAY_MINUTES: u32 = 8 * 60 + 30;
+/// Spread of the day lengths, in minutes
+const K_DAY_SPREAD_MINUTES: u32 = 60;
+/// Paused hours of a generated day
+const K_PAUSED_HOURS: f32 = 0.5;
+
+/// Small deterministic pseudo random generator (xorshift), good enough for synthetic data
+struct SyntheticRng(u64);
+
+impl SyntheticRng {
+    /// Create a new generator from a seed, the seed can't be zero
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    /// Next value in the range 0..bound
+    fn below(&mut self, bound: u32) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 % bound.max(1) as u64) as u32
+    }
+}
+
+/// Generated projects and weeks
+pub struct SyntheticData {
+    /// Generated projects, with the entries of all days
+    pub projects: Vec<Project>,
+    /// Generated weeks, with a closed day for each weekday
+    pub weeks: Vec<Week>,
+}
+
+/// Generate the weekdays of the given number of years back from `until`, each with a closed day and the given number
+/// of entries spread over the generated projects. The entry hours add up to the hours of the day
+pub fn generate(
+    years: u32,
+    entries_per_day: u32,
+    until: NaiveDate,
+    week_target_hours: f32,
+) -> SyntheticData {
+    let mut rng = SyntheticRng::new(K_GENERATOR_SEED);
+    let mut projects: Vec<Project> = (1..=K_GENERATED_PROJECTS)
+        .map(|i| {
+            Project::new(
+                format!("generated-{}", i),
+                Some("Generated by dev generate".to_string()),
+            )
+        })
+        .collect();
+    let mut weeks: Vec<Week> = Vec::new();
+
+    let from = until
+        .with_year(until.year() - years as i32)
+        .unwrap_or(until - DateDuration::days(365 * years as i64))
+        + DateDuration::days(1);
+
+    let mut date = from;
+    while date <= until {
+        if matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+            date += DateDuration::days(1);
+            continue;
+        }
+
+        let start_minutes = K_EARLIEST_START_MINUTES + rng.below(K_START_SPREAD_MINUTES);
+        let day_minutes = K_MIN_DAY_MINUTES + rng.below(K_DAY_SPREAD_MINUTES);
+
+        // Skipped if the time doesn't exist locally, e.g. at a daylight saving change
+        if let Some(day) = synthetic_day(date, start_minutes, day_minutes) {
+            let entry_hours = day.hours() / entries_per_day as f32;
+            for i in 0..entries_per_day {
+                let project = rng.below(K_GENERATED_PROJECTS as u32) as usize;
+                let mut entry = Entry::new(entry_hours, Some(format!("Generated entry {}", i + 1)));
+                entry.set_worked_on(date);
+                projects[project].add_entry(entry);
+            }
+
+            match weeks.last_mut() {
+                Some(w) if w.number() == day.week() && w.year() == day.week_year() => {
+                    w.add_entry(day)
+                }
+                _ => {
+                    let mut week = Week::new(day.week(), day.week_year(), week_target_hours);
+                    week.add_entry(day);
+                    weeks.push(week);
+                }
+            }
+        }
+
+        date += DateDuration::days(1);
+    }
+
+    SyntheticData { projects, weeks }
+}
+
+/// Create a closed day on the date, starting the given minutes after midnight and lasting the given minutes
+/// including the pause. None if the times don't exist locally
+pub fn synthetic_day(date: NaiveDate, start_minutes: u32, day_minutes: u32) -> Option<Day> {
+    let start = date.and_hms_opt(start_minutes / 60, start_minutes % 60, 0)?;
+    let end = start + DateDuration::minutes(day_minutes as i64);
+    let start = Local.from_local_datetime(&start).single()?;
+    let end = Local.from_local_datetime(&end).single()?;
+
+    // The date and week are set from the starting time when the day is closed, so it is set last. The pause can
+    // only be set once started
+    let mut day = Day::new(None);
+    day.set_ending_time(&end);
+    day.set_starting_time(&start);
+    day.set_paused_time(K_PAUSED_HOURS);
+    Some(day)
+}
+
+/// Timing of a benchmarked operation
+pub struct BenchmarkResult {
+    /// Name of the operation
+    pub name: String,
+    /// Number of times the operation was run
+    pub iterations: u32,
+    /// Total time of all iterations
+    pub total: Duration,
+}
+
+impl BenchmarkResult {
+    /// Average time of an iteration
+    pub fn average(&self) -> Duration {
+        self.total / self.iterations.max(1)
+    }
+}
+
+/// Run an operation the given number of times and measure the total time
+pub fn measure<F: FnMut()>(name: &str, iterations: u32, mut operation: F) -> BenchmarkResult {
+    let started = Instant::now();
+    for _ in 0..iterations {
+        operation();
+    }
+
+    BenchmarkResult {
+        name: name.to_string(),
+        iterations,
+        total: started.elapsed(),
+    }
+}
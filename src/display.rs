@@ -0,0 +1,270 @@
+/*
+ * File: display.rs
+ * Description: The display theme for the tables printed by the butler. Handles colors and highlighting.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use chrono::NaiveDate;
+use comfy_table::{Attribute, Cell, Color};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::day::Day;
+use crate::duration::DurationFormat;
+use crate::entry::Entry;
+
+/// Environment variable disabling colors, see https://no-color.org
+const K_NO_COLOR_ENV: &str = "NO_COLOR";
+
+/// Enum to represent the available color themes
+#[derive(Clone, Debug, PartialEq)]
+pub enum ColorTheme {
+    /// Standard colors
+    Default,
+    /// Bold colors, no dimmed rows
+    HighContrast,
+    /// No colors at all
+    None,
+}
+
+/// Parse error for ColorTheme
+#[derive(Debug)]
+pub struct ParseColorThemeError;
+
+/// Implement Display trait for ParseColorThemeError
+impl fmt::Display for ParseColorThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid color theme, valid options are: default, high-contrast, none"
+        )
+    }
+}
+
+/// Implement FromStr trait for ColorTheme
+impl FromStr for ColorTheme {
+    type Err = ParseColorThemeError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.to_lowercase().as_str() {
+            "default" => Ok(ColorTheme::Default),
+            "high-contrast" => Ok(ColorTheme::HighContrast),
+            "none" => Ok(ColorTheme::None),
+            _ => Err(ParseColorThemeError),
+        }
+    }
+}
+
+/// Names of the colors accepted for a project, in addition to "#rrggbb"
+pub const K_COLOR_NAMES: [&str; 8] = [
+    "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+
+/// Parse a project color, a color name or a hex color "#rrggbb"
+pub fn parse_color(input: &str) -> Option<Color> {
+    let input = input.trim().to_lowercase();
+    if let Some(hex) = input.strip_prefix('#') {
+        if hex.len() != 6 {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        return Some(Color::Rgb {
+            r: channel(0)?,
+            g: channel(2)?,
+            b: channel(4)?,
+        });
+    }
+
+    match input.as_str() {
+        "black" => Some(Color::Black),
+        "red" => Some(Color::Red),
+        "green" => Some(Color::Green),
+        "yellow" => Some(Color::Yellow),
+        "blue" => Some(Color::Blue),
+        "magenta" => Some(Color::Magenta),
+        "cyan" => Some(Color::Cyan),
+        "white" => Some(Color::White),
+        _ => None,
+    }
+}
+
+/// Display struct, decides how the table cells should be styled
+#[derive(Clone, Debug)]
+pub struct Display {
+    /// Theme in use
+    theme: ColorTheme,
+    /// Format of the shown hours
+    duration: DurationFormat,
+    /// Date of today, the rows of today are highlighted
+    today: NaiveDate,
+}
+
+impl Display {
+    /// Create a new Display from the configured theme name, with the date of today
+    pub fn new(theme_name: &str, no_color: bool, today: NaiveDate) -> Self {
+        let mut theme = match ColorTheme::from_str(theme_name) {
+            Ok(theme) => theme,
+            Err(e) => {
+                tracing::warn!("{}: '{}', using default theme", e, theme_name);
+                ColorTheme::Default
+            }
+        };
+
+        // Any non-empty value of NO_COLOR disables colors
+        let no_color_env = std::env::var(K_NO_COLOR_ENV)
+            .map(|v| !v.is_empty())
+            .unwrap_or(false);
+
+        if no_color || no_color_env {
+            tracing::debug!("Colors disabled by flag or {} variable", K_NO_COLOR_ENV);
+            theme = ColorTheme::None;
+        }
+
+        Self {
+            theme,
+            duration: DurationFormat::Decimal,
+            today,
+        }
+    }
+
+    /// Set the date of today, the rows of today are highlighted
+    pub fn set_today(&mut self, today: NaiveDate) {
+        self.today = today;
+    }
+
+    /// Getter for `duration`
+    pub fn duration(&self) -> DurationFormat {
+        self.duration
+    }
+
+    /// Set the format of the shown hours
+    pub fn set_duration_format(&mut self, duration: DurationFormat) {
+        self.duration = duration;
+    }
+
+    /// Format the hours with the duration format in use
+    pub fn hours(&self, hours: f32) -> String {
+        self.duration.format(hours)
+    }
+
+    /// Get a cell for the target status, red when not reached and green otherwise
+    pub fn target_status_cell(&self, status: &str) -> Cell {
+        let cell = Cell::new(status);
+        let color = match status {
+            "NotReached" | "Late" => Color::Red,
+            _ => Color::Green,
+        };
+
+        match self.theme {
+            ColorTheme::Default => cell.fg(color),
+            ColorTheme::HighContrast => cell.fg(color).add_attribute(Attribute::Bold),
+            ColorTheme::None => cell,
+        }
+    }
+
+    /// Get a calendar cell for a day, colored by the hours worked compared to the daily target: green when reached,
+    /// yellow when below and red for a passed weekday without hours
+    pub fn calendar_cell(
+        &self,
+        text: String,
+        hours: Option<f32>,
+        daily_target: f32,
+        is_gap: bool,
+    ) -> Cell {
+        let cell = Cell::new(text);
+        let color = match hours {
+            Some(h) if h >= daily_target => Color::Green,
+            Some(_) => Color::Yellow,
+            None if is_gap => Color::Red,
+            None => return cell,
+        };
+
+        match self.theme {
+            ColorTheme::Default => cell.fg(color),
+            ColorTheme::HighContrast => cell.fg(color).add_attribute(Attribute::Bold),
+            ColorTheme::None => cell,
+        }
+    }
+
+    /// Get the cell of a project name, in the color of the project if it has one
+    pub fn project_name_cell(&self, name: &str, color: Option<&str>) -> Cell {
+        let cell = Cell::new(name);
+        match color.and_then(parse_color) {
+            Some(color) if self.theme != ColorTheme::None => cell.fg(color),
+            _ => cell,
+        }
+    }
+
+    /// Apply the row style, today's row is highlighted and closed days are dimmed
+    pub fn style_row(&self, cells: Vec<Cell>, is_today: bool, is_closed: bool) -> Vec<Cell> {
+        cells
+            .into_iter()
+            .map(|cell| match self.theme {
+                ColorTheme::Default => {
+                    if is_today {
+                        cell.fg(Color::Cyan)
+                    } else if is_closed {
+                        cell.add_attribute(Attribute::Dim)
+                    } else {
+                        cell
+                    }
+                }
+                ColorTheme::HighContrast => {
+                    if is_today {
+                        cell.fg(Color::Yellow).add_attribute(Attribute::Bold)
+                    } else {
+                        cell
+                    }
+                }
+                ColorTheme::None => cell,
+            })
+            .collect()
+    }
+
+    /// Get the styled row for a day, matching the header of the day table
+    pub fn day_row(&self, day: &Day) -> Vec<Cell> {
+        let start_time = match day.starting_time() {
+            Some(st) => st.to_string(),
+            None => "N/A".to_string(),
+        };
+
+        let end_time = match (day.ending_time(), day.overnight_days()) {
+            (Some(et), 0) => et.to_string(),
+            (Some(et), days) => format!("{} (+{}d)", et, days),
+            (None, _) => "N/A".to_string(),
+        };
+
+        let cells = vec![
+            Cell::new(day.week().to_string()),
+            Cell::new(day.date().to_string()),
+            Cell::new(start_time),
+            Cell::new(end_time),
+            Cell::new(self.hours(day.hours_paused())),
+            Cell::new(self.hours(day.hours())),
+            Cell::new(day.closed().to_string()),
+            Cell::new(day.extra_info()),
+            Cell::new(day.id().to_string()),
+        ];
+
+        let is_today = day.date() == self.today;
+        self.style_row(cells, is_today, day.closed())
+    }
+
+    /// Get the styled row for a project entry, matching the header of the entry table
+    pub fn entry_row(&self, entry: &Entry, project_name: &str) -> Vec<Cell> {
+        let cells = vec![
+            Cell::new(project_name),
+            Cell::new(entry.description()),
+            Cell::new(self.hours(entry.hours())),
+            Cell::new(entry.estimate().map(|e| self.hours(e)).unwrap_or_default()),
+            Cell::new(entry.work_date().to_string()),
+            Cell::new(entry.created().to_string()),
+            Cell::new(entry.id().to_string()),
+        ];
+
+        let is_today = entry.work_date() == self.today;
+        self.style_row(cells, is_today, false)
+    }
+}
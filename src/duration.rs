@@ -0,0 +1,287 @@
+/*
+ * File: duration.rs
+ * Description: Formatting of the hours shown in the tables and reports, as decimal hours (7.75) or as hours and
+ *              minutes (7:45).
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+/// Number of decimals shown for decimal hours
+const K_HOUR_DECIMALS: usize = 2;
+/// Number of decimals shown for decimal hours with second precision, a second is 0.0003h
+const K_HOUR_DECIMALS_SECONDS: usize = 4;
+/// Seconds per hour
+const K_SECONDS_PER_HOUR: f32 = 3600.0;
+/// Minutes per hour
+const K_MINUTES_PER_HOUR: f32 = 60.0;
+
+/// Enum to represent how durations are shown
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum DurationFormat {
+    /// Decimal hours, e.g. 7.75
+    #[default]
+    Decimal,
+    /// Hours and minutes, e.g. 7:45
+    Clock,
+}
+
+/// Precision used for durations, set once from the configuration
+static K_TIME_PRECISION: RwLock<TimePrecision> = RwLock::new(TimePrecision::Minutes);
+
+/// Enum to represent the precision of the calculated and shown durations
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum TimePrecision {
+    /// Whole minutes, started seconds are dropped
+    #[default]
+    Minutes,
+    /// Whole seconds
+    Seconds,
+}
+
+/// Parse error for TimePrecision
+#[derive(Debug)]
+pub struct ParseTimePrecisionError;
+
+/// Implement Display trait for ParseTimePrecisionError
+impl fmt::Display for ParseTimePrecisionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid time precision, valid options are: minutes, seconds"
+        )
+    }
+}
+
+/// Implement Error trait for ParseTimePrecisionError
+impl Error for ParseTimePrecisionError {}
+
+/// Implement FromStr trait for TimePrecision
+impl FromStr for TimePrecision {
+    type Err = ParseTimePrecisionError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            "minutes" | "minute" => Ok(TimePrecision::Minutes),
+            "seconds" | "second" => Ok(TimePrecision::Seconds),
+            _ => Err(ParseTimePrecisionError),
+        }
+    }
+}
+
+/// Implement Display trait for TimePrecision, same name as used in the configuration
+impl fmt::Display for TimePrecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimePrecision::Minutes => write!(f, "minutes"),
+            TimePrecision::Seconds => write!(f, "seconds"),
+        }
+    }
+}
+
+impl TimePrecision {
+    /// Get the duration as hours, cut to the precision
+    pub fn hours(&self, duration: chrono::Duration) -> f32 {
+        match self {
+            TimePrecision::Minutes => duration.num_minutes() as f32 / K_MINUTES_PER_HOUR,
+            TimePrecision::Seconds => duration.num_seconds() as f32 / K_SECONDS_PER_HOUR,
+        }
+    }
+}
+
+/// Set the precision used for durations
+pub fn set_time_precision(precision: TimePrecision) {
+    if let Ok(mut current) = K_TIME_PRECISION.write() {
+        *current = precision;
+    }
+}
+
+/// Get the precision used for durations
+pub fn time_precision() -> TimePrecision {
+    K_TIME_PRECISION.read().map(|p| *p).unwrap_or_default()
+}
+
+/// Parse error for DurationFormat
+#[derive(Debug)]
+pub struct ParseDurationFormatError;
+
+/// Implement Display trait for ParseDurationFormatError
+impl fmt::Display for ParseDurationFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid duration format, valid options are: decimal, hh:mm"
+        )
+    }
+}
+
+/// Implement Error trait for ParseDurationFormatError
+impl Error for ParseDurationFormatError {}
+
+/// Implement FromStr trait for DurationFormat
+impl FromStr for DurationFormat {
+    type Err = ParseDurationFormatError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            "decimal" => Ok(DurationFormat::Decimal),
+            "hh:mm" | "clock" => Ok(DurationFormat::Clock),
+            _ => Err(ParseDurationFormatError),
+        }
+    }
+}
+
+/// Implement Display trait for DurationFormat, same name as used in the configuration
+impl fmt::Display for DurationFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DurationFormat::Decimal => write!(f, "decimal"),
+            DurationFormat::Clock => write!(f, "hh:mm"),
+        }
+    }
+}
+
+impl DurationFormat {
+    /// Format the hours, decimal hours are shown with at most two decimals and without trailing zeros. With second
+    /// precision, decimal hours get four decimals and hh:mm gets the seconds, e.g. 7:45:30
+    pub fn format(&self, hours: f32) -> String {
+        self.format_with_precision(hours, time_precision())
+    }
+
+    /// Format the hours with the precision instead of the configured precision
+    pub fn format_with_precision(&self, hours: f32, precision: TimePrecision) -> String {
+        match self {
+            DurationFormat::Decimal => {
+                let decimals = match precision {
+                    TimePrecision::Minutes => K_HOUR_DECIMALS,
+                    TimePrecision::Seconds => K_HOUR_DECIMALS_SECONDS,
+                };
+                let text = format!("{:.*}", decimals, hours);
+                let text = text.trim_end_matches('0').trim_end_matches('.');
+                // Avoid showing "-0" for small negative values
+                if text == "-0" {
+                    "0".to_string()
+                } else {
+                    text.to_string()
+                }
+            }
+            DurationFormat::Clock if precision == TimePrecision::Seconds => {
+                let seconds = (hours.abs() * K_SECONDS_PER_HOUR).round() as u32;
+                let sign = if hours < 0.0 && seconds > 0 { "-" } else { "" };
+                format!(
+                    "{}{}:{:02}:{:02}",
+                    sign,
+                    seconds / 3600,
+                    seconds / 60 % 60,
+                    seconds % 60
+                )
+            }
+            DurationFormat::Clock => {
+                let minutes = (hours.abs() * K_MINUTES_PER_HOUR).round() as u32;
+                let sign = if hours < 0.0 && minutes > 0 { "-" } else { "" };
+                format!("{}{}:{:02}", sign, minutes / 60, minutes % 60)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Internal function to format the hours with minute precision
+    fn minutes(format: DurationFormat, hours: f32) -> String {
+        format.format_with_precision(hours, TimePrecision::Minutes)
+    }
+
+    /// Internal function to format the hours with second precision
+    fn seconds(format: DurationFormat, hours: f32) -> String {
+        format.format_with_precision(hours, TimePrecision::Seconds)
+    }
+
+    #[test]
+    fn decimal_hours_drop_trailing_zeros() {
+        let cases = [
+            (7.75, "7.75"),
+            (7.5, "7.5"),
+            (8.0, "8"),
+            (25.25, "25.25"),
+            (1.0 / 3.0, "0.33"),
+            // Fractional minutes are rounded to the shown decimals
+            (1.999, "2"),
+            (0.0, "0"),
+            (-1.5, "-1.5"),
+            (-0.001, "0"),
+        ];
+        for (hours, expected) in cases {
+            assert_eq!(
+                minutes(DurationFormat::Decimal, hours),
+                expected,
+                "{}",
+                hours
+            );
+        }
+    }
+
+    #[test]
+    fn clock_hours_round_to_whole_minutes() {
+        let cases = [
+            (7.75, "7:45"),
+            (8.0, "8:00"),
+            (25.5, "25:30"),
+            (1.0 / 60.0, "0:01"),
+            // 119.94 minutes, rounded up to the next hour
+            (1.999, "2:00"),
+            // Below half a minute
+            (0.008, "0:00"),
+            (0.0, "0:00"),
+            (-1.5, "-1:30"),
+            // No sign when rounded to zero
+            (-0.001, "0:00"),
+        ];
+        for (hours, expected) in cases {
+            assert_eq!(minutes(DurationFormat::Clock, hours), expected, "{}", hours);
+        }
+    }
+
+    #[test]
+    fn second_precision_shows_the_seconds() {
+        let hours = 7.75 + 30.0 / K_SECONDS_PER_HOUR;
+        assert_eq!(seconds(DurationFormat::Decimal, hours), "7.7583");
+        assert_eq!(seconds(DurationFormat::Clock, hours), "7:45:30");
+        assert_eq!(seconds(DurationFormat::Clock, 1.99999), "2:00:00");
+        assert_eq!(seconds(DurationFormat::Clock, 0.0), "0:00:00");
+        assert_eq!(seconds(DurationFormat::Clock, -0.5), "-0:30:00");
+        assert_eq!(seconds(DurationFormat::Decimal, 8.0), "8");
+    }
+
+    #[test]
+    fn duration_formats_parse_and_display() {
+        for format in [DurationFormat::Decimal, DurationFormat::Clock] {
+            assert_eq!(
+                format.to_string().parse::<DurationFormat>().unwrap(),
+                format
+            );
+        }
+        assert_eq!(
+            "clock".parse::<DurationFormat>().unwrap(),
+            DurationFormat::Clock
+        );
+        assert!("minutes".parse::<DurationFormat>().is_err());
+    }
+
+    #[test]
+    fn precision_cuts_started_units() {
+        let duration = chrono::Duration::seconds(7 * 3600 + 45 * 60 + 59);
+        assert_eq!(TimePrecision::Minutes.hours(duration), 7.75);
+        assert_eq!(
+            TimePrecision::Seconds.hours(duration),
+            (7 * 3600 + 45 * 60 + 59) as f32 / K_SECONDS_PER_HOUR
+        );
+    }
+}
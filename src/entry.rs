@@ -6,11 +6,24 @@
  * License: MIT
  */
 
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use uuid::Uuid;
 
+use crate::clock::{Clock, SystemClock};
+
+/// Selection of an entry of a project, by ID or by position in the listed entries
+#[derive(Debug, Clone, PartialEq)]
+pub enum EntrySelector {
+    /// Full entry ID, or a unique prefix of it
+    Id(String),
+    /// Position in the listed entries, starting at 1
+    Nth(usize),
+    /// Last listed entry
+    Last,
+}
+
 /// Entry struct to store time entries
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Entry {
@@ -22,17 +35,55 @@ pub struct Entry {
     created: DateTime<Local>,
     /// Unique ID for the entry
     id: uuid::Uuid,
+    /// Date the work was done, if different from the creation date
+    worked_on: Option<NaiveDate>,
+    /// Name of the recurring entry rule which created the entry, or "meeting:<uid>" for an imported meeting, if any
+    origin: Option<String>,
+    /// Estimated hours, compared to the logged hours
+    estimate: Option<f32>,
+}
+
+/// Layout of an entry in storage file version 1
+#[derive(Deserialize, Debug)]
+pub struct EntryV1 {
+    hours: f32,
+    description: Option<String>,
+    created: DateTime<Local>,
+    id: uuid::Uuid,
+}
+
+/// Convert an entry of storage file version 1, the work date is the creation date
+impl From<EntryV1> for Entry {
+    fn from(entry: EntryV1) -> Self {
+        Self {
+            hours: entry.hours,
+            description: entry.description,
+            created: entry.created,
+            id: entry.id,
+            worked_on: None,
+            origin: None,
+            estimate: None,
+        }
+    }
 }
 
 /// Implementation for Entry functionality
 impl Entry {
     /// Create a new Entry
     pub fn new(hours: f32, description: Option<String>) -> Self {
+        Self::new_with_clock(hours, description, &SystemClock)
+    }
+
+    /// Create a new Entry, created at the current time of the clock
+    pub fn new_with_clock(hours: f32, description: Option<String>, clock: &dyn Clock) -> Self {
         Self {
             hours,
             description,
-            created: Local::now(),
+            created: clock.now(),
             id: Uuid::new_v4(),
+            worked_on: None,
+            origin: None,
+            estimate: None,
         }
     }
 
@@ -60,6 +111,46 @@ impl Entry {
     pub fn id(&self) -> &uuid::Uuid {
         &self.id
     }
+
+    /// Set the hours logged
+    pub fn set_hours(&mut self, hours: f32) {
+        self.hours = hours;
+    }
+
+    /// Set the description of the work done
+    pub fn set_description(&mut self, description: String) {
+        self.description = Some(description);
+    }
+
+    /// Set the date the work was done, used when backfilling entries
+    pub fn set_worked_on(&mut self, date: NaiveDate) {
+        self.worked_on = Some(date);
+    }
+
+    /// Getter for `origin`
+    pub fn origin(&self) -> Option<&str> {
+        self.origin.as_deref()
+    }
+
+    /// Set the name of the recurring entry rule which created the entry
+    pub fn set_origin(&mut self, origin: String) {
+        self.origin = Some(origin);
+    }
+
+    /// Getter for `estimate`
+    pub fn estimate(&self) -> Option<f32> {
+        self.estimate
+    }
+
+    /// Set the estimated hours
+    pub fn set_estimate(&mut self, estimate: f32) {
+        self.estimate = Some(estimate);
+    }
+
+    /// Get the date the work was done, the creation date if no date has been set
+    pub fn work_date(&self) -> NaiveDate {
+        self.worked_on.unwrap_or(self.created.date_naive())
+    }
 }
 
 /// Implement Display for Entry
@@ -67,8 +158,11 @@ impl fmt::Display for Entry {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "Description: {:?}\nhours - {}\n (Created: {})",
-            self.description, self.hours, self.created
+            "Description: {:?}\nhours - {}\n (Worked on: {}, Created: {})",
+            self.description,
+            self.hours,
+            self.work_date(),
+            self.created
         )
     }
 }
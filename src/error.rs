@@ -0,0 +1,49 @@
+/*
+ * File: error.rs
+ * Description: The definition of the errors returned by the butler.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use std::error::Error;
+use std::fmt;
+
+/// Enum to represent the errors the butler can return
+#[derive(Debug, Clone, PartialEq)]
+pub enum ButlerError {
+    /// Requested time item could not be found
+    NotFound(String),
+    /// Time item already exists
+    Duplicate(String),
+    /// Reading from or writing to storage failed
+    StorageFailure(String),
+    /// Provided input is not valid
+    InvalidInput(String),
+    /// Operation was not confirmed by the user
+    Aborted(String),
+    /// Generation of a report failed
+    ReportFailure(String),
+    /// Stored data is not compatible with the current application version
+    VersionIncompatible(String),
+}
+
+/// Implement Display trait for ButlerError
+impl fmt::Display for ButlerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ButlerError::NotFound(msg) => write!(f, "Not found: {}", msg),
+            ButlerError::Duplicate(msg) => write!(f, "Already exists: {}", msg),
+            ButlerError::StorageFailure(msg) => write!(f, "Storage failure: {}", msg),
+            ButlerError::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
+            ButlerError::Aborted(msg) => write!(f, "Aborted: {}", msg),
+            ButlerError::ReportFailure(msg) => write!(f, "Report failure: {}", msg),
+            ButlerError::VersionIncompatible(msg) => {
+                write!(f, "Version incompatibility: {}", msg)
+            }
+        }
+    }
+}
+
+/// Implement Error trait for ButlerError
+impl Error for ButlerError {}
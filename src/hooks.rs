@@ -0,0 +1,370 @@
+/*
+ * File: hooks.rs
+ * Description: Generation and installation of the login and logout hooks, which start the day at the first login of the
+ *              day and end it, or remind about it, at logout. The hooks are a systemd user unit or blocks in the shell
+ *              profile files.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+
+use crate::config::HooksConfig;
+use crate::paths::{K_CONFIG_ENV, K_STORAGE_DIR_ENV};
+
+/// Name of the systemd user unit
+pub const K_SYSTEMD_UNIT_NAME: &str = "time-butler-session.service";
+
+/// First line of the block in the shell profile files
+const K_PROFILE_BLOCK_START: &str = "# >>> time-butler hooks >>>";
+
+/// Last line of the block in the shell profile files
+const K_PROFILE_BLOCK_END: &str = "# <<< time-butler hooks <<<";
+
+/// Shell profile file run at login
+const K_LOGIN_PROFILE: &str = ".profile";
+
+/// Shell profile file run at logout
+const K_LOGOUT_PROFILE: &str = ".bash_logout";
+
+/// Reminder shown at logout when the day is left open
+const K_LOGOUT_REMINDER: &str =
+    "time-butler: remember to end the day, time-butler add day --ending-time";
+
+/// Error for generating or installing the hooks
+#[derive(Debug)]
+pub struct HooksError(String);
+
+/// Implement Display trait for HooksError
+impl fmt::Display for HooksError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to install the hooks: {}", self.0)
+    }
+}
+
+/// Implement Error trait for HooksError
+impl Error for HooksError {}
+
+/// Kind of hooks to install
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum HookKind {
+    /// A systemd user unit, started with the user session and stopped when it ends
+    #[default]
+    Systemd,
+    /// Blocks in ~/.profile and ~/.bash_logout
+    Profile,
+}
+
+/// Error for parsing the kind of hooks
+#[derive(Debug)]
+pub struct ParseHookKindError(String);
+
+impl fmt::Display for ParseHookKindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid hook kind '{}', valid values are: systemd, profile",
+            self.0
+        )
+    }
+}
+
+impl FromStr for HookKind {
+    type Err = ParseHookKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "systemd" => Ok(HookKind::Systemd),
+            "profile" => Ok(HookKind::Profile),
+            _ => Err(ParseHookKindError(s.to_string())),
+        }
+    }
+}
+
+/// What the logout hook does with the day
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LogoutAction {
+    /// End the day with the logout time
+    Stop,
+    /// Remind about ending the day
+    #[default]
+    Remind,
+    /// Nothing, no logout hook
+    Nothing,
+}
+
+/// Error for parsing the logout action
+#[derive(Debug)]
+pub struct ParseLogoutActionError(String);
+
+impl fmt::Display for ParseLogoutActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid logout action '{}', valid values are: stop, remind, none",
+            self.0
+        )
+    }
+}
+
+impl FromStr for LogoutAction {
+    type Err = ParseLogoutActionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "stop" => Ok(LogoutAction::Stop),
+            "remind" => Ok(LogoutAction::Remind),
+            "none" => Ok(LogoutAction::Nothing),
+            _ => Err(ParseLogoutActionError(s.to_string())),
+        }
+    }
+}
+
+/// File written by the hooks, the whole file for systemd or the block of a profile file
+pub struct HookFile {
+    /// Path of the file
+    path: PathBuf,
+    /// Content of the file, or of the block
+    content: String,
+}
+
+impl HookFile {
+    /// Getter for `path`
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Getter for `content`
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+}
+
+/// Internal function to quote a value for the shell and the systemd command lines
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Internal function to get the home directory
+fn home_directory() -> Result<PathBuf, HooksError> {
+    dirs::home_dir().ok_or_else(|| HooksError("no home directory found".to_string()))
+}
+
+/// Internal function to get the directory of the systemd user units, in $XDG_CONFIG_HOME or ~/.config
+fn systemd_unit_directory() -> Result<PathBuf, HooksError> {
+    let config_dir = match std::env::var("XDG_CONFIG_HOME") {
+        Ok(dir) if !dir.trim().is_empty() => PathBuf::from(dir),
+        _ => home_directory()?.join(".config"),
+    };
+    Ok(config_dir.join("systemd").join("user"))
+}
+
+/// Internal function to get the environment variables of the paths, passed on so the hooks use the same data and
+/// configuration as the installing command
+fn path_environment() -> Vec<(&'static str, String)> {
+    [K_STORAGE_DIR_ENV, K_CONFIG_ENV]
+        .into_iter()
+        .filter_map(|name| {
+            std::env::var(name)
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+                .map(|v| (name, v))
+        })
+        .collect()
+}
+
+/// Generate the hook files of the kind for the executable, from the configured behavior
+pub fn hook_files(
+    kind: HookKind,
+    executable: &str,
+    config: &HooksConfig,
+) -> Result<Vec<HookFile>, HooksError> {
+    let logout = config
+        .on_logout
+        .parse::<LogoutAction>()
+        .map_err(|e| HooksError(e.to_string()))?;
+    if !config.start_on_login && logout == LogoutAction::Nothing {
+        return Err(HooksError(
+            "nothing to install, start-on-login is off and on-logout is none in the hooks configuration"
+                .to_string(),
+        ));
+    }
+
+    let exe = quote(executable);
+    let start = format!("{} -q add day --starting-time", exe);
+    let stop = format!("{} -q add day --ending-time", exe);
+
+    match kind {
+        HookKind::Systemd => {
+            let mut content = String::from(
+                "[Unit]\nDescription=time-butler day start at login and end at logout\n\n[Service]\nType=oneshot\nRemainAfterExit=yes\n",
+            );
+            for (name, value) in path_environment() {
+                // systemd quotes the whole assignment
+                content.push_str(&format!(
+                    "Environment=\"{}={}\"\n",
+                    name,
+                    value.replace('\\', "\\\\").replace('"', "\\\"")
+                ));
+            }
+            // The "-" prefix keeps a time-butler error out of the session start and end
+            if config.start_on_login {
+                content.push_str(&format!("ExecStart=-{}\n", start));
+            } else {
+                content.push_str("ExecStart=/bin/true\n");
+            }
+            match logout {
+                LogoutAction::Stop => content.push_str(&format!("ExecStop=-{}\n", stop)),
+                LogoutAction::Remind => content.push_str(&format!(
+                    "ExecStop=-notify-send time-butler {}\n",
+                    quote(K_LOGOUT_REMINDER)
+                )),
+                LogoutAction::Nothing => {}
+            }
+            content.push_str("\n[Install]\nWantedBy=default.target\n");
+
+            Ok(vec![HookFile {
+                path: systemd_unit_directory()?.join(K_SYSTEMD_UNIT_NAME),
+                content,
+            }])
+        }
+        HookKind::Profile => {
+            let home = home_directory()?;
+            let environment: String = path_environment()
+                .iter()
+                .map(|(name, value)| format!("{}={} ", name, quote(value)))
+                .collect();
+
+            let mut files = Vec::new();
+            if config.start_on_login {
+                files.push(HookFile {
+                    path: home.join(K_LOGIN_PROFILE),
+                    content: format!("{}{} >/dev/null 2>&1\n", environment, start),
+                });
+            }
+            let logout_line = match logout {
+                LogoutAction::Stop => Some(format!("{}{} >/dev/null 2>&1\n", environment, stop)),
+                LogoutAction::Remind => Some(format!("echo {}\n", quote(K_LOGOUT_REMINDER))),
+                LogoutAction::Nothing => None,
+            };
+            if let Some(content) = logout_line {
+                files.push(HookFile {
+                    path: home.join(K_LOGOUT_PROFILE),
+                    content,
+                });
+            }
+            Ok(files)
+        }
+    }
+}
+
+/// Internal function to remove the time-butler block of a profile file content
+fn without_profile_block(text: &str) -> String {
+    let mut result = String::new();
+    let mut in_block = false;
+    for line in text.lines() {
+        match line.trim() {
+            K_PROFILE_BLOCK_START => in_block = true,
+            K_PROFILE_BLOCK_END => in_block = false,
+            _ if !in_block => {
+                result.push_str(line);
+                result.push('\n');
+            }
+            _ => {}
+        }
+    }
+    result
+}
+
+/// Internal function to run systemctl for the user units
+fn systemctl(args: &[&str]) -> Result<(), HooksError> {
+    let output = Command::new("systemctl")
+        .arg("--user")
+        .args(args)
+        .output()
+        .map_err(|e| HooksError(format!("could not run systemctl: {}", e)))?;
+    if !output.status.success() {
+        return Err(HooksError(format!(
+            "systemctl --user {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Install the hook files, the systemd unit is written and enabled, the profile blocks replace earlier blocks
+pub fn install(kind: HookKind, files: &[HookFile]) -> Result<(), HooksError> {
+    for file in files {
+        if let Some(dir) = file.path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| HooksError(format!("could not create {}: {}", dir.display(), e)))?;
+        }
+        let content = match kind {
+            HookKind::Systemd => file.content.clone(),
+            HookKind::Profile => {
+                let existing = fs::read_to_string(&file.path).unwrap_or_default();
+                format!(
+                    "{}{}\n{}{}\n",
+                    without_profile_block(&existing),
+                    K_PROFILE_BLOCK_START,
+                    file.content,
+                    K_PROFILE_BLOCK_END
+                )
+            }
+        };
+        fs::write(&file.path, content)
+            .map_err(|e| HooksError(format!("could not write {}: {}", file.path.display(), e)))?;
+        tracing::debug!("Wrote hook file {}", file.path.display());
+    }
+
+    if kind == HookKind::Systemd {
+        systemctl(&["daemon-reload"])?;
+        systemctl(&["enable", K_SYSTEMD_UNIT_NAME])?;
+    }
+    Ok(())
+}
+
+/// Remove the installed hooks of the kind, returns the paths of the changed files
+pub fn uninstall(kind: HookKind) -> Result<Vec<PathBuf>, HooksError> {
+    let mut changed = Vec::new();
+    match kind {
+        HookKind::Systemd => {
+            let unit = systemd_unit_directory()?.join(K_SYSTEMD_UNIT_NAME);
+            if unit.exists() {
+                // A unit that was never enabled can still be removed
+                if let Err(e) = systemctl(&["disable", K_SYSTEMD_UNIT_NAME]) {
+                    tracing::warn!("{}", e);
+                }
+                fs::remove_file(&unit).map_err(|e| {
+                    HooksError(format!("could not remove {}: {}", unit.display(), e))
+                })?;
+                changed.push(unit);
+            }
+        }
+        HookKind::Profile => {
+            let home = home_directory()?;
+            for name in [K_LOGIN_PROFILE, K_LOGOUT_PROFILE] {
+                let path = home.join(name);
+                let existing = match fs::read_to_string(&path) {
+                    Ok(text) => text,
+                    Err(_) => continue,
+                };
+                let cleaned = without_profile_block(&existing);
+                if cleaned.trim_end() != existing.trim_end() {
+                    fs::write(&path, cleaned).map_err(|e| {
+                        HooksError(format!("could not write {}: {}", path.display(), e))
+                    })?;
+                    changed.push(path);
+                }
+            }
+        }
+    }
+    Ok(changed)
+}
@@ -0,0 +1,279 @@
+/*
+ * File: i18n.rs
+ * Description: Message catalogs for the table headers, prompts and report column names. The English text is the key,
+ *              text missing in a catalog is shown in English.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use chrono::Weekday;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+/// Locale used for the output, set once from the configuration
+static K_LOCALE: RwLock<Locale> = RwLock::new(Locale::English);
+
+/// Swedish catalog, the English text and the translation
+const K_SWEDISH: &[(&str, &str)] = &[
+    // Table headers
+    ("Week", "Vecka"),
+    ("Date", "Datum"),
+    ("Start time", "Starttid"),
+    ("End time", "Sluttid"),
+    ("Paused hours", "Pausade timmar"),
+    ("Hours", "Timmar"),
+    ("Closed", "Stängd"),
+    ("Extra info", "Extra info"),
+    ("Project", "Projekt"),
+    ("Description", "Beskrivning"),
+    ("Estimate", "Uppskattning"),
+    ("Created", "Skapad"),
+    ("Name", "Namn"),
+    ("Aliases", "Alias"),
+    ("Number of Entries", "Antal poster"),
+    ("Total Hours", "Totala timmar"),
+    ("Budget", "Budget"),
+    ("Deadline", "Slutdatum"),
+    ("Client", "Kund"),
+    ("External reference", "Extern referens"),
+    ("Year", "År"),
+    ("Number of days registered", "Antal registrerade dagar"),
+    ("Notes", "Anteckningar"),
+    ("Entries", "Poster"),
+    ("Target hours", "Måltimmar"),
+    ("Current reported hours", "Rapporterade timmar"),
+    ("Percentage done", "Procent klart"),
+    ("Target status", "Målstatus"),
+    ("Hours remaining", "Återstående timmar"),
+    ("Hours overtime", "Övertid"),
+    ("Target hours set method", "Metod för måltimmar"),
+    ("Stored target hours", "Sparade måltimmar"),
+    ("Period", "Period"),
+    ("Number", "Nummer"),
+    ("New target hours", "Nya måltimmar"),
+    ("Estimated entries", "Uppskattade poster"),
+    ("Estimated hours", "Uppskattade timmar"),
+    ("Actual hours", "Faktiska timmar"),
+    ("Difference", "Skillnad"),
+    ("Actual of estimate", "Faktiskt av uppskattning"),
+    ("Budget hours", "Budgettimmar"),
+    ("Used hours", "Använda timmar"),
+    ("Percentage used", "Procent använt"),
+    ("Hours per day", "Timmar per dag"),
+    ("Projected completion", "Beräknat klart"),
+    ("Status", "Status"),
+    ("Month", "Månad"),
+    ("Average daily hours", "Genomsnittliga timmar per dag"),
+    ("Remaining working days", "Återstående arbetsdagar"),
+    ("Forecast hours", "Prognos timmar"),
+    ("Forecast status", "Prognosstatus"),
+    ("Required hours per day", "Timmar som krävs per dag"),
+    ("Statistic", "Statistik"),
+    ("Value", "Värde"),
+    ("Change from previous month", "Förändring från föregående månad"),
+    ("Info", "Info"),
+    ("Weeks", "Veckor"),
+    ("Days", "Dagar"),
+    ("Day hours", "Dagtimmar"),
+    ("Entry hours", "Posttimmar"),
+    ("Item", "Typ"),
+    ("Added", "Tillagda"),
+    ("Merged", "Sammanslagna"),
+    ("Skipped", "Överhoppade"),
+    ("Removed", "Borttagen"),
+    ("Entry", "Post"),
+    ("Day", "Dag"),
+    ("Projects", "Projekt"),
+    ("Benchmark", "Mätning"),
+    ("Iterations", "Iterationer"),
+    ("Total (ms)", "Totalt (ms)"),
+    ("Average (ms)", "Medel (ms)"),
+    // Weekdays
+    ("Mon", "Mån"),
+    ("Tue", "Tis"),
+    ("Wed", "Ons"),
+    ("Thu", "Tor"),
+    ("Fri", "Fre"),
+    ("Sat", "Lör"),
+    ("Sun", "Sön"),
+    // Report column names
+    ("StartingTime", "Starttid"),
+    ("EndingTime", "Sluttid"),
+    ("Paused-Hours", "Pausade-timmar"),
+    ("Total", "Totalt"),
+    ("Project hours", "Projekttimmar"),
+    ("Mismatched dates", "Avvikande datum"),
+    ("Reconciled", "Avstämd"),
+    ("Metric", "Mått"),
+    ("Target attainment", "Måluppfyllnad"),
+    ("Worked hours", "Arbetade timmar"),
+    ("Remaining hours", "Återstående timmar"),
+    ("Total hours", "Totala timmar"),
+    ("Total week {}", "Totalt vecka {}"),
+    ("Generated", "Skapad"),
+    ("Report", "Rapport"),
+    ("Week target hours", "Måltimmar per vecka"),
+    ("Month target hours", "Måltimmar per månad"),
+    ("Version", "Version"),
+    // Prompts
+    ("[y/N]", "[j/N]"),
+    (
+        "Storage file version is incompatible with current application version. Do you want to proceed? This may lead \
+         to data loss or corruption if the incompatibility is due to breaking changes in the storage file format.",
+        "Lagringsfilens version är inte kompatibel med applikationens version. Vill du fortsätta? Det kan leda till \
+         förlorad eller korrupt data om inkompatibiliteten beror på ändringar i lagringsformatet.",
+    ),
+    (
+        "Are you sure you want to change the project name from {} to {}?",
+        "Är du säker på att du vill ändra projektnamnet från {} till {}?",
+    ),
+    (
+        "Are you sure you want to change the project description from {} to {}?",
+        "Är du säker på att du vill ändra projektbeskrivningen från {} till {}?",
+    ),
+    (
+        "Are you sure you want to change the Days extra info from '{}' to '{}'?",
+        "Är du säker på att du vill ändra dagens extra info från '{}' till '{}'?",
+    ),
+    (
+        "Are you sure you want to modify the entry?",
+        "Är du säker på att du vill ändra posten?",
+    ),
+    (
+        "The {} is already set to '{}'. Replace it with '{}'?",
+        "Fältet {} är redan satt till '{}'. Ersätt med '{}'?",
+    ),
+    (
+        "Are you sure you want to remove {}",
+        "Är du säker på att du vill ta bort {}",
+    ),
+    (
+        "Are you sure you want to remove entry {}",
+        "Är du säker på att du vill ta bort posten {}",
+    ),
+    (
+        "Are you sure you want to remove day {}",
+        "Är du säker på att du vill ta bort dagen {}",
+    ),
+    (
+        "Day {} was started at {} but never ended. Close it at {}?",
+        "Dagen {} startades klockan {} men avslutades aldrig. Avsluta den klockan {}?",
+    ),
+    (
+        "Update the target of {} weeks to {} hours?",
+        "Uppdatera målet för {} veckor till {} timmar?",
+    ),
+    (
+        "New target hours for week {}, empty to keep {} or \"reset\" for the configured target",
+        "Nytt mål i timmar för vecka {}, tomt för att behålla {} eller \"reset\" för det konfigurerade målet",
+    ),
+    ("Target not changed", "Målet ändrades inte"),
+    (
+        "Project {} already exists. Open it (o), add an entry to it (e), create it as {} (s) or abort (a)",
+        "Projektet {} finns redan. Öppna det (o), lägg till en post i det (e), skapa det som {} (s) eller avbryt (a)",
+    ),
+    ("Hours worked", "Arbetade timmar"),
+    ("Description of the work done", "Beskrivning av det utförda arbetet"),
+    // Report titles
+    ("Timesheet - Week {}, {}", "Tidrapport - Vecka {}, {}"),
+];
+
+/// Enum to represent the available locales
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Locale {
+    English,
+    Swedish,
+}
+
+/// Parse error for Locale
+#[derive(Debug)]
+pub struct ParseLocaleError;
+
+/// Implement Display trait for ParseLocaleError
+impl fmt::Display for ParseLocaleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid locale, valid options are: en, sv")
+    }
+}
+
+/// Implement FromStr trait for Locale
+impl FromStr for Locale {
+    type Err = ParseLocaleError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            "en" | "english" => Ok(Locale::English),
+            "sv" | "swedish" => Ok(Locale::Swedish),
+            _ => Err(ParseLocaleError),
+        }
+    }
+}
+
+impl Locale {
+    /// Answers accepted as yes to a prompt, in addition to the English ones
+    fn yes_answers(&self) -> &'static [&'static str] {
+        match self {
+            Locale::English => &[],
+            Locale::Swedish => &["ja", "j"],
+        }
+    }
+}
+
+/// Set the locale used for the output
+pub fn set_locale(locale: Locale) {
+    if let Ok(mut current) = K_LOCALE.write() {
+        *current = locale;
+    }
+}
+
+/// Get the locale used for the output
+pub fn locale() -> Locale {
+    K_LOCALE.read().map(|l| *l).unwrap_or(Locale::English)
+}
+
+/// Translate the text to the current locale, the text itself is returned if no translation exists
+pub fn tr(text: &'static str) -> &'static str {
+    let catalog: &[(&str, &str)] = match locale() {
+        Locale::English => return text,
+        Locale::Swedish => K_SWEDISH,
+    };
+
+    catalog
+        .iter()
+        .find(|(english, _)| *english == text)
+        .map_or(text, |(_, translated)| translated)
+}
+
+/// Translate the text and fill in the `{}` placeholders with the arguments, in order
+pub fn tr_args(text: &'static str, args: &[&dyn fmt::Display]) -> String {
+    let mut parts = tr(text).split("{}");
+    let mut out = parts.next().unwrap_or_default().to_string();
+    for (i, part) in parts.enumerate() {
+        if let Some(arg) = args.get(i) {
+            out.push_str(&arg.to_string());
+        }
+        out.push_str(part);
+    }
+    out
+}
+
+/// Translated short name of the weekday, e.g. "Mon"
+pub fn weekday_short(day: Weekday) -> &'static str {
+    tr(match day {
+        Weekday::Mon => "Mon",
+        Weekday::Tue => "Tue",
+        Weekday::Wed => "Wed",
+        Weekday::Thu => "Thu",
+        Weekday::Fri => "Fri",
+        Weekday::Sat => "Sat",
+        Weekday::Sun => "Sun",
+    })
+}
+
+/// Check if the answer to a prompt is yes, in English or the current locale
+pub fn is_yes(answer: &str) -> bool {
+    let answer = answer.trim().to_lowercase();
+    ["yes", "y"].contains(&answer.as_str()) || locale().yes_answers().contains(&answer.as_str())
+}
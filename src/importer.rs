@@ -0,0 +1,424 @@
+/*
+ * File: importer.rs
+ * Description: Importers reading days and project entries from other sources: the day CSV, a Toggl Track CSV export,
+ *              the commits of a git repository, or a shell command of the configuration printing the import JSON.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use crate::config::ImporterConfig;
+use crate::schema;
+
+/// Input read from stdin instead of a file
+const K_STDIN_INPUT: &str = "-";
+
+/// Error for importing data
+#[derive(Debug)]
+pub struct ImportError(String);
+
+/// Implement Display trait for ImportError
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to import: {}", self.0)
+    }
+}
+
+/// Implement Error trait for ImportError
+impl Error for ImportError {}
+
+/// Number or text in the imported data, numbers are kept as text for the same error messages as the CSV values
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NumberOrText {
+    Number(f64),
+    Text(String),
+}
+
+impl NumberOrText {
+    /// Get the value as text
+    fn into_text(self) -> String {
+        match self {
+            NumberOrText::Number(n) => n.to_string(),
+            NumberOrText::Text(t) => t,
+        }
+    }
+}
+
+/// Internal function to deserialize a number or text, e.g. the hours as 2.5 in JSON and "2.5" in CSV
+fn number_text<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    NumberOrText::deserialize(deserializer).map(NumberOrText::into_text)
+}
+
+/// Internal function to deserialize an optional number or text
+fn optional_number_text<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    Option::<NumberOrText>::deserialize(deserializer).map(|v| v.map(NumberOrText::into_text))
+}
+
+/// Imported day, a row in the day CSV
+#[derive(Debug, Deserialize)]
+pub struct DayImportRow {
+    /// Date of the day, "YYYY-MM-DD"
+    pub date: String,
+    /// Starting time, "HH:MM" or "HH:MM:SS"
+    pub start: Option<String>,
+    /// Ending time, "HH:MM" or "HH:MM:SS", on the next date if marked with "+1d", e.g. "06:00+1d"
+    pub end: Option<String>,
+    /// Date of the ending time, "YYYY-MM-DD", the date of the day if not given
+    pub end_date: Option<String>,
+    /// Paused hours, kept as string for better error messages
+    #[serde(default, deserialize_with = "optional_number_text")]
+    pub pause: Option<String>,
+    /// Extra info for the day
+    pub extra_info: Option<String>,
+}
+
+/// Imported project entry, a row in the CSV data for bulk adding entries
+#[derive(Debug, Deserialize)]
+pub struct EntryImportRow {
+    /// Project name or alias
+    pub project: String,
+    /// Hours worked, kept as string for better error messages
+    #[serde(deserialize_with = "number_text")]
+    pub hours: String,
+    /// Description of the work done
+    pub description: Option<String>,
+    /// Date the work was done, optional
+    pub date: Option<String>,
+}
+
+/// Imported data, the JSON document printed by the command importers
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ImportData {
+    /// Days to add or merge into the stored days
+    pub days: Vec<DayImportRow>,
+    /// Entries to add to the stored projects
+    pub entries: Vec<EntryImportRow>,
+    /// Rows the importer couldn't read, with the reason
+    #[serde(skip)]
+    pub rejected: Vec<String>,
+}
+
+/// Importer of days and entries from a source
+pub trait TimeDataImporter {
+    /// Name of the importer, as given to --source
+    fn name(&self) -> &str;
+
+    /// Description shown in the help of the importers
+    fn description(&self) -> String;
+
+    /// Read the data of the input, a file path or "-" for stdin
+    fn import(&self, input: &str) -> Result<ImportData, ImportError>;
+}
+
+/// Internal function to open the input, stdin for "-"
+fn open_input(input: &str) -> Result<Box<dyn Read>, ImportError> {
+    match input {
+        K_STDIN_INPUT => Ok(Box::new(io::stdin())),
+        path => File::open(path)
+            .map(|f| Box::new(f) as Box<dyn Read>)
+            .map_err(|e| ImportError(format!("could not open {}: {}", path, e))),
+    }
+}
+
+/// Importer of the day CSV, with the columns date, start, end, pause and extra_info
+pub struct CsvImporter;
+
+impl TimeDataImporter for CsvImporter {
+    fn name(&self) -> &str {
+        "csv"
+    }
+
+    fn description(&self) -> String {
+        "Days from CSV with the columns date, start, end, pause and extra_info".to_string()
+    }
+
+    fn import(&self, input: &str) -> Result<ImportData, ImportError> {
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .flexible(true)
+            .from_reader(open_input(input)?);
+
+        let mut data = ImportData::default();
+        for (i, row) in csv_reader.deserialize::<DayImportRow>().enumerate() {
+            match row {
+                Ok(row) => data.days.push(row),
+                // Header is line 1
+                Err(e) => data.rejected.push(format!("line {}: {}", i + 2, e)),
+            }
+        }
+        Ok(data)
+    }
+}
+
+/// Row of the Toggl Track detailed CSV export, only the used columns
+#[derive(Debug, Deserialize)]
+struct TogglRow {
+    #[serde(rename = "Project")]
+    project: String,
+    #[serde(rename = "Description", default)]
+    description: String,
+    #[serde(rename = "Start date")]
+    start_date: String,
+    /// Duration as "HH:MM:SS"
+    #[serde(rename = "Duration")]
+    duration: String,
+}
+
+/// Internal function to get the hours of a Toggl duration, "HH:MM:SS"
+fn toggl_hours(duration: &str) -> Option<f32> {
+    let parts: Vec<u32> = duration
+        .split(':')
+        .map(|p| p.trim().parse::<u32>())
+        .collect::<Result<_, _>>()
+        .ok()?;
+    match parts[..] {
+        [hours, minutes, seconds] if minutes < 60 && seconds < 60 => {
+            Some(hours as f32 + minutes as f32 / 60.0 + seconds as f32 / 3600.0)
+        }
+        _ => None,
+    }
+}
+
+/// Importer of the Toggl Track detailed CSV export, one entry per time entry. The Toggl project is the project name
+pub struct TogglImporter;
+
+impl TimeDataImporter for TogglImporter {
+    fn name(&self) -> &str {
+        "toggl"
+    }
+
+    fn description(&self) -> String {
+        "Entries from a Toggl Track detailed report, exported as CSV".to_string()
+    }
+
+    fn import(&self, input: &str) -> Result<ImportData, ImportError> {
+        let mut csv_reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .flexible(true)
+            .from_reader(open_input(input)?);
+
+        let mut data = ImportData::default();
+        for (i, row) in csv_reader.deserialize::<TogglRow>().enumerate() {
+            let line = i + 2;
+            let row = match row {
+                Ok(row) => row,
+                Err(e) => {
+                    data.rejected.push(format!("line {}: {}", line, e));
+                    continue;
+                }
+            };
+            let Some(hours) = toggl_hours(&row.duration) else {
+                data.rejected.push(format!(
+                    "line {}: invalid duration {}, expected HH:MM:SS",
+                    line, row.duration
+                ));
+                continue;
+            };
+
+            data.entries.push(EntryImportRow {
+                project: row.project,
+                hours: format!("{:.4}", hours),
+                description: Some(row.description).filter(|d| !d.is_empty()),
+                date: Some(row.start_date),
+            });
+        }
+        Ok(data)
+    }
+}
+
+/// Importer of the commits of a git repository, a day from the first to the last commit of each date with at least
+/// two commits. Only the commits of the configured git user are used
+pub struct GitImporter;
+
+impl TimeDataImporter for GitImporter {
+    fn name(&self) -> &str {
+        "git"
+    }
+
+    fn description(&self) -> String {
+        "Days from the first to the last commit of the git user, the input is the repository"
+            .to_string()
+    }
+
+    fn import(&self, input: &str) -> Result<ImportData, ImportError> {
+        let git = |args: &[&str]| -> Result<String, ImportError> {
+            let output = Command::new("git")
+                .arg("-C")
+                .arg(input)
+                .args(args)
+                .output()
+                .map_err(|e| ImportError(format!("could not run git: {}", e)))?;
+            if !output.status.success() {
+                return Err(ImportError(format!(
+                    "git {} failed: {}",
+                    args.join(" "),
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )));
+            }
+            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        };
+
+        let email = git(&["config", "user.email"])?.trim().to_string();
+        let author = format!("--author={}", email);
+        let log = git(&["log", "--all", "--no-merges", &author, "--format=%aI"])?;
+
+        let mut commits: BTreeMap<NaiveDate, Vec<DateTime<Local>>> = BTreeMap::new();
+        for line in log.lines().filter(|l| !l.trim().is_empty()) {
+            match DateTime::parse_from_rfc3339(line.trim()) {
+                Ok(time) => {
+                    let time = time.with_timezone(&Local);
+                    commits.entry(time.date_naive()).or_default().push(time);
+                }
+                Err(e) => tracing::warn!("Skipping commit time {}: {}", line, e),
+            }
+        }
+
+        let repository = Path::new(input)
+            .canonicalize()
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+            .unwrap_or_else(|| input.to_string());
+        let mut data = ImportData::default();
+        for (date, times) in commits {
+            if times.len() < 2 {
+                tracing::debug!("Only one commit on {}, no day imported", date);
+                continue;
+            }
+            // The times are within the date, so min and max exist
+            let start = times.iter().min().unwrap();
+            let end = times.iter().max().unwrap();
+            data.days.push(DayImportRow {
+                date: date.to_string(),
+                start: Some(start.format("%H:%M:%S").to_string()),
+                end: Some(end.format("%H:%M:%S").to_string()),
+                end_date: None,
+                pause: None,
+                extra_info: Some(format!("{} commits in {}", times.len(), repository)),
+            });
+        }
+        Ok(data)
+    }
+}
+
+/// Importer running a shell command, the command gets the input as $1 and prints the import JSON on stdout
+pub struct CommandImporter {
+    /// Name of the importer
+    name: String,
+    /// Shell command printing the import JSON
+    command: String,
+}
+
+impl CommandImporter {
+    /// Create an importer from the configuration
+    pub fn new(config: &ImporterConfig) -> Self {
+        Self {
+            name: config.name.clone(),
+            command: config.command.clone(),
+        }
+    }
+}
+
+impl TimeDataImporter for CommandImporter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> String {
+        format!("Imported by the command: {}", self.command)
+    }
+
+    fn import(&self, input: &str) -> Result<ImportData, ImportError> {
+        tracing::debug!("Importing {} with: {}", input, self.command);
+        // The command is the script of sh, the input its first argument
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .arg("sh")
+            .arg(input)
+            .stdin(Stdio::inherit())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .map_err(|e| ImportError(format!("could not run {}: {}", self.command, e)))?;
+        if !output.status.success() {
+            return Err(ImportError(format!(
+                "{} failed: {}",
+                self.command,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        let document: serde_json::Value = serde_json::from_slice(&output.stdout).map_err(|e| {
+            ImportError(format!("invalid import JSON from {}: {}", self.command, e))
+        })?;
+        // Checked against the schema first, for the location of every problem instead of the first serde error
+        schema::validate_import(&document).map_err(|e| {
+            ImportError(format!("invalid import JSON from {}: {}", self.command, e))
+        })?;
+        serde_json::from_value(document)
+            .map_err(|e| ImportError(format!("invalid import JSON from {}: {}", self.command, e)))
+    }
+}
+
+/// Registry of the importers, looked up by the source name
+pub struct ImporterRegistry {
+    /// Registered importers, the built-in importers first
+    importers: Vec<Box<dyn TimeDataImporter>>,
+}
+
+impl ImporterRegistry {
+    /// Create the registry with the built-in importers
+    pub fn new() -> Self {
+        Self {
+            importers: vec![
+                Box::new(CsvImporter),
+                Box::new(TogglImporter),
+                Box::new(GitImporter),
+            ],
+        }
+    }
+
+    /// Register an importer. An importer can't replace a built-in importer, and a later importer with the same name
+    /// replaces the earlier one
+    pub fn register(&mut self, importer: Box<dyn TimeDataImporter>) -> Result<(), ImportError> {
+        let name = importer.name().to_lowercase();
+        let builtin = ImporterRegistry::new();
+        if name.is_empty() || builtin.get(&name).is_some() {
+            return Err(ImportError(format!(
+                "invalid importer name '{}', the name must be set and not be a built-in importer",
+                importer.name()
+            )));
+        }
+
+        self.importers.retain(|i| i.name().to_lowercase() != name);
+        self.importers.push(importer);
+        Ok(())
+    }
+
+    /// Get the importer of the source, the name is case insensitive
+    pub fn get(&self, name: &str) -> Option<&dyn TimeDataImporter> {
+        self.importers
+            .iter()
+            .find(|i| i.name().eq_ignore_ascii_case(name))
+            .map(|i| i.as_ref())
+    }
+
+    /// The registered importers
+    pub fn importers(&self) -> impl Iterator<Item = &dyn TimeDataImporter> {
+        self.importers.iter().map(|i| i.as_ref())
+    }
+}
@@ -0,0 +1,191 @@
+/*
+ * File: jira.rs
+ * Description: Export of project entries as Jira/Tempo worklogs. The worklogs are written as a JSON file, or posted
+ *              to the Jira REST API with curl.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use chrono::{Local, NaiveDate, NaiveTime, TimeZone};
+use serde::Serialize;
+use std::error::Error;
+use std::fmt;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::config::JiraConfig;
+use crate::mailer::quote_config_value;
+
+/// Environment variable replacing the Jira API token in the configuration
+const K_JIRA_TOKEN_ENV: &str = "TIME_BUTLER_JIRA_TOKEN";
+
+/// Start time of the worklogs, the entries only have a date
+const K_WORKLOG_START_TIME: &str = "09:00:00";
+
+/// Error for exporting worklogs
+#[derive(Debug)]
+pub struct JiraError(String);
+
+/// Implement Display trait for JiraError
+impl fmt::Display for JiraError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to export worklogs: {}", self.0)
+    }
+}
+
+/// Implement Error trait for JiraError
+impl Error for JiraError {}
+
+/// Worklog in the Tempo format, with the issue key instead of the issue ID
+#[derive(Debug, Clone, Serialize)]
+pub struct Worklog {
+    #[serde(rename = "issueKey")]
+    issue_key: String,
+    #[serde(rename = "timeSpentSeconds")]
+    time_spent_seconds: u64,
+    #[serde(rename = "startDate")]
+    start_date: NaiveDate,
+    #[serde(rename = "startTime")]
+    start_time: String,
+    description: String,
+}
+
+impl Worklog {
+    /// Create a worklog of the hours worked on the date
+    pub fn new(issue_key: String, hours: f32, date: NaiveDate, description: &str) -> Self {
+        Self {
+            issue_key,
+            time_spent_seconds: (hours * 3600.0).round().max(0.0) as u64,
+            start_date: date,
+            start_time: K_WORKLOG_START_TIME.to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    /// Getter for `time_spent_seconds`
+    pub fn time_spent_seconds(&self) -> u64 {
+        self.time_spent_seconds
+    }
+
+    /// Internal function to get the body of the Jira worklog API
+    fn jira_body(&self) -> serde_json::Value {
+        let start_time =
+            NaiveTime::parse_from_str(&self.start_time, "%H:%M:%S").unwrap_or(NaiveTime::MIN);
+        let started = Local
+            .from_local_datetime(&self.start_date.and_time(start_time))
+            .earliest()
+            .map(|t| t.format("%Y-%m-%dT%H:%M:%S%.3f%z").to_string())
+            .unwrap_or_default();
+        serde_json::json!({
+            "timeSpentSeconds": self.time_spent_seconds,
+            "started": started,
+            "comment": self.description,
+        })
+    }
+}
+
+/// Get the first Jira issue key in the text, e.g. "ABC-123" in "ABC-123 code review"
+pub fn issue_key(text: &str) -> Option<String> {
+    text.split(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        .find(|word| {
+            let Some((project, number)) = word.split_once('-') else {
+                return false;
+            };
+            project.starts_with(|c: char| c.is_ascii_uppercase())
+                && project
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+                && !number.is_empty()
+                && number.chars().all(|c| c.is_ascii_digit())
+        })
+        .map(|word| word.to_string())
+}
+
+/// Write the worklogs to the file as a JSON array
+pub fn write_worklogs(file_path: &str, worklogs: &[Worklog]) -> Result<(), JiraError> {
+    let json = serde_json::to_string_pretty(worklogs)
+        .map_err(|e| JiraError(format!("could not serialize the worklogs: {}", e)))?;
+    std::fs::write(file_path, json)
+        .map_err(|e| JiraError(format!("could not write {}: {}", file_path, e)))
+}
+
+/// Post the worklogs to the Jira API in the configuration, one request per worklog. Stops at the first failed
+/// request, returns the number of posted worklogs
+pub fn post_worklogs(config: &JiraConfig, worklogs: &[Worklog]) -> Result<usize, JiraError> {
+    if config.url.is_empty() {
+        return Err(JiraError(
+            "Jira is not configured, set url in the jira section of the configuration".to_string(),
+        ));
+    }
+    let token = std::env::var(K_JIRA_TOKEN_ENV).unwrap_or_else(|_| config.token.clone());
+    if token.is_empty() {
+        return Err(JiraError(format!(
+            "no API token, set token in the jira section of the configuration or {}",
+            K_JIRA_TOKEN_ENV
+        )));
+    }
+
+    for (posted, worklog) in worklogs.iter().enumerate() {
+        let url = format!(
+            "{}/rest/api/2/issue/{}/worklog",
+            config.url.trim_end_matches('/'),
+            worklog.issue_key
+        );
+        tracing::debug!("Posting worklog to {}", url);
+
+        let result = Command::new("curl")
+            .arg("--silent")
+            .arg("--show-error")
+            .arg("--fail")
+            .arg("--request")
+            .arg("POST")
+            .arg("--header")
+            .arg("Content-Type: application/json")
+            .arg("--url")
+            .arg(&url)
+            .arg("--data")
+            .arg(worklog.jira_body().to_string())
+            // The credentials are given on stdin, to keep them out of the process list
+            .arg("--config")
+            .arg("-")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .and_then(|mut child| {
+                if let Some(mut stdin) = child.stdin.take() {
+                    // Jira Cloud takes the account email with the token, Jira Data Center a personal access token
+                    if config.username.is_empty() {
+                        writeln!(
+                            stdin,
+                            "header = \"Authorization: Bearer {}\"",
+                            quote_config_value(&token)
+                        )?;
+                    } else {
+                        writeln!(
+                            stdin,
+                            "user = \"{}:{}\"",
+                            quote_config_value(&config.username),
+                            quote_config_value(&token)
+                        )?;
+                    }
+                }
+                child.wait_with_output()
+            });
+
+        match result {
+            Ok(output) if output.status.success() => {}
+            Ok(output) => {
+                return Err(JiraError(format!(
+                    "posting the worklog for {} failed after {} posted: {}",
+                    worklog.issue_key,
+                    posted,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )))
+            }
+            Err(e) => return Err(JiraError(format!("could not run curl: {}", e))),
+        }
+    }
+    Ok(worklogs.len())
+}
@@ -0,0 +1,205 @@
+/*
+ * File: mailer.rs
+ * Description: Emailing of generated reports. The message is built here and sent with curl over SMTP.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use std::error::Error;
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use chrono::Local;
+use uuid::Uuid;
+
+use crate::config::EmailConfig;
+
+/// Environment variable replacing the SMTP password in the configuration
+const K_SMTP_PASSWORD_ENV: &str = "TIME_BUTLER_SMTP_PASSWORD";
+
+/// Max line length of the base64 encoded attachment
+const K_BASE64_LINE_LENGTH: usize = 76;
+
+const K_BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Error for sending a report
+#[derive(Debug)]
+pub struct MailError(String);
+
+/// Implement Display trait for MailError
+impl fmt::Display for MailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to email report: {}", self.0)
+    }
+}
+
+/// Implement Error trait for MailError
+impl Error for MailError {}
+
+/// Internal function to base64 encode the attachment
+fn base64_encode(data: &[u8]) -> String {
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+
+        encoded.push(K_BASE64_ALPHABET[(n >> 18) as usize & 63] as char);
+        encoded.push(K_BASE64_ALPHABET[(n >> 12) as usize & 63] as char);
+        if chunk.len() > 1 {
+            encoded.push(K_BASE64_ALPHABET[(n >> 6) as usize & 63] as char);
+        } else {
+            encoded.push('=');
+        }
+        if chunk.len() > 2 {
+            encoded.push(K_BASE64_ALPHABET[n as usize & 63] as char);
+        } else {
+            encoded.push('=');
+        }
+    }
+
+    encoded
+}
+
+/// Escape a value in the curl config format, also used for the Jira credentials
+pub fn quote_config_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Internal function to get the content type of the attachment from the report suffix
+fn content_type(file_name: &str) -> &'static str {
+    match file_name.rsplit('.').next() {
+        Some("json") => "application/json",
+        Some("csv") => "text/csv",
+        Some("yaml") => "application/yaml",
+        Some("html") => "text/html",
+        Some("pdf") => "application/pdf",
+        Some("txt") => "text/plain",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Internal function to build the email, a short text and the report as attachment
+fn build_message(from: &str, to: &str, file_name: &str, content: &[u8]) -> String {
+    let boundary = format!("time-butler-{}", Uuid::new_v4());
+
+    let mut message = String::new();
+    message.push_str(&format!("From: {}\r\n", from));
+    message.push_str(&format!("To: {}\r\n", to));
+    message.push_str(&format!("Subject: Time report {}\r\n", file_name));
+    message.push_str(&format!("Date: {}\r\n", Local::now().to_rfc2822()));
+    message.push_str("MIME-Version: 1.0\r\n");
+    message.push_str(&format!(
+        "Content-Type: multipart/mixed; boundary=\"{}\"\r\n\r\n",
+        boundary
+    ));
+
+    message.push_str(&format!("--{}\r\n", boundary));
+    message.push_str("Content-Type: text/plain; charset=utf-8\r\n\r\n");
+    message.push_str(&format!(
+        "Please find the time report {} attached.\r\n\r\n",
+        file_name
+    ));
+
+    message.push_str(&format!("--{}\r\n", boundary));
+    message.push_str(&format!(
+        "Content-Type: {}; name=\"{}\"\r\n",
+        content_type(file_name),
+        file_name
+    ));
+    message.push_str(&format!(
+        "Content-Disposition: attachment; filename=\"{}\"\r\n",
+        file_name
+    ));
+    message.push_str("Content-Transfer-Encoding: base64\r\n\r\n");
+
+    let encoded = base64_encode(content);
+    for line in encoded.as_bytes().chunks(K_BASE64_LINE_LENGTH) {
+        message.push_str(&String::from_utf8_lossy(line));
+        message.push_str("\r\n");
+    }
+    message.push_str(&format!("--{}--\r\n", boundary));
+
+    message
+}
+
+/// Send the report file to the given address, using the SMTP settings in the configuration
+pub fn send_report(config: &EmailConfig, to: &str, file_path: &str) -> Result<(), MailError> {
+    if config.smtp_server.is_empty() || config.from.is_empty() {
+        return Err(MailError(
+            "email is not configured, set smtp-server and from in the email section of the configuration"
+                .to_string(),
+        ));
+    }
+
+    let content = std::fs::read(file_path)
+        .map_err(|e| MailError(format!("could not read {}: {}", file_path, e)))?;
+    let file_name = Path::new(file_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| file_path.to_string());
+
+    // curl uploads the message from a file
+    let message_path =
+        std::env::temp_dir().join(format!("time-butler-mail-{}.eml", Uuid::new_v4()));
+    std::fs::write(
+        &message_path,
+        build_message(&config.from, to, &file_name, &content),
+    )
+    .map_err(|e| MailError(format!("could not write message: {}", e)))?;
+
+    let password = std::env::var(K_SMTP_PASSWORD_ENV).unwrap_or_else(|_| config.password.clone());
+
+    tracing::debug!("Sending {} to {} via {}", file_name, to, config.smtp_server);
+    let result = Command::new("curl")
+        .arg("--silent")
+        .arg("--show-error")
+        .arg("--ssl-reqd")
+        .arg("--url")
+        .arg(&config.smtp_server)
+        .arg("--mail-from")
+        .arg(&config.from)
+        .arg("--mail-rcpt")
+        .arg(to)
+        .arg("--upload-file")
+        .arg(&message_path)
+        // The credentials are given on stdin, to keep them out of the process list
+        .arg("--config")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            if let Some(mut stdin) = child.stdin.take() {
+                if !config.username.is_empty() {
+                    writeln!(
+                        stdin,
+                        "user = \"{}:{}\"",
+                        quote_config_value(&config.username),
+                        quote_config_value(&password)
+                    )?;
+                }
+            }
+            child.wait_with_output()
+        });
+
+    if let Err(e) = std::fs::remove_file(&message_path) {
+        tracing::warn!("Could not remove {}: {}", message_path.display(), e);
+    }
+
+    match result {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(output) => Err(MailError(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        )),
+        Err(e) => Err(MailError(format!("could not run curl: {}", e))),
+    }
+}
@@ -7,26 +7,62 @@
  */
 
 mod backup_organizer;
+mod badge;
+mod batch;
 mod butler;
+mod caldav;
 mod cli;
+mod clipboard;
+mod clock;
 mod config;
 mod config_reader;
+mod config_watcher;
 mod day;
+mod dev;
+mod display;
+mod duration;
 mod entry;
+mod error;
+mod hooks;
+mod i18n;
+mod importer;
+mod jira;
+mod mailer;
+mod paths;
+mod period;
+mod period_lock;
+mod porcelain;
 mod project;
+mod publisher;
 mod report;
 mod report_manager;
+mod report_renderer;
+mod report_template;
+mod schema;
+mod snapshot;
+mod stats;
 mod storage_handler;
+mod sync;
 mod tables;
 mod target;
+mod template;
+mod toml_format;
+mod trash;
+mod validation;
 mod version_info;
 mod version_manager;
 mod week;
+mod week_scheme;
+mod xlsx;
 
+use chrono::Datelike;
 use cli::{
-    AddSubcommands, Cli, Commands, ConfigurationSubcommands, ModifySubcommands, RemoveSubcommands,
-    ReportSubcommands, TargetTimesSubcommands,
+    AddSubcommands, Cli, Commands, ConfigurationSubcommands, DevSubcommands, ExportSubcommands,
+    ImportSubcommands, LockSubcommands, ModifySubcommands, NoteSubcommands, ProjectSubcommands,
+    RemoveSubcommands, ReportSubcommands, SchemaSubcommands, StatsSubcommands,
+    TargetTimesSubcommands, TemplateSubcommands, TrashSubcommands, UnlockSubcommands,
 };
+use std::io::Read;
 use std::path::Path;
 use std::process;
 use tracing::Level;
@@ -36,133 +72,311 @@ use butler::Butler;
 use clap::Parser;
 
 use crate::config::AppConfiguration;
+use crate::duration::DurationFormat;
+use crate::entry::EntrySelector;
+use crate::error::ButlerError;
+use crate::period_lock::LockedPeriod;
+use crate::report::{ReportOptions, ReportOverwrite};
 
-// Error codes
-const K_BUTLER_SAVE_FAILED: i32 = 1;
+// Exit codes, documented in the readme. Keep them stable since scripts may branch on them.
+/// Unspecified failure, e.g. unreadable configuration file
+const K_BUTLER_GENERAL_FAILURE: i32 = 1;
+/// Requested time item not found
+const K_BUTLER_NOT_FOUND: i32 = 2;
+/// Validation error, invalid input from the user (including invalid CLI usage)
+const K_BUTLER_INVALID_INPUT: i32 = 3;
+/// Reading or writing storage failed
+const K_BUTLER_STORAGE_FAILURE: i32 = 4;
+/// Time item already exists
+const K_BUTLER_DUPLICATE: i32 = 5;
+/// Operation not confirmed by the user
+const K_BUTLER_ABORTED: i32 = 6;
+/// Report generation failed
+const K_BUTLER_REPORT_FAILURE: i32 = 7;
+/// Stored data incompatible with the application version
+const K_BUTLER_VERSION_INCOMPATIBLE: i32 = 8;
 
-fn main() {
-    // Parse the CLI arguments
-    let args = Cli::parse();
-
-    // Initialize the logger based on cli arguments
-    if args.json {
-        if args.verbose {
-            tracing_subscriber::fmt()
-                .json() // Output logs in JSON format
-                .with_max_level(Level::DEBUG) // Log only INFO level and higher
-                .with_env_filter(EnvFilter::from_default_env()) // Optional: filter logs based on an environment variable
-                .init();
-        } else {
-            tracing_subscriber::fmt()
-                .json() // Output logs in JSON format
-                .with_max_level(Level::INFO) // Log only INFO level and higher
-                .with_env_filter(EnvFilter::from_default_env()) // Optional: filter logs based on an environment variable
-                .init();
-        }
-    } else if args.verbose {
-        tracing_subscriber::fmt()
-            .with_max_level(Level::DEBUG) // Log messages of `DEBUG` level and higher
-            .init();
-    } else {
-        tracing_subscriber::fmt()
-            .with_max_level(Level::INFO) // Log messages of `INFO` level and higher
-            .init();
+/// Map a butler error to the process exit code
+fn exit_code(error: &ButlerError) -> i32 {
+    match error {
+        ButlerError::NotFound(_) => K_BUTLER_NOT_FOUND,
+        ButlerError::InvalidInput(_) => K_BUTLER_INVALID_INPUT,
+        ButlerError::StorageFailure(_) => K_BUTLER_STORAGE_FAILURE,
+        ButlerError::Duplicate(_) => K_BUTLER_DUPLICATE,
+        ButlerError::Aborted(_) => K_BUTLER_ABORTED,
+        ButlerError::ReportFailure(_) => K_BUTLER_REPORT_FAILURE,
+        ButlerError::VersionIncompatible(_) => K_BUTLER_VERSION_INCOMPATIBLE,
     }
+}
 
-    tracing::debug!("Creating the Butler!");
-
-    // Interactive mode variables
-    let mut store_data = false;
+/// Parse a date argument given as "YYYY-MM-DD"
+fn parse_date_argument(date: &str) -> Result<chrono::NaiveDate, ButlerError> {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| {
+        ButlerError::InvalidInput(format!(
+            "Invalid date provided: {}, expected format YYYY-MM-DD",
+            date
+        ))
+    })
+}
 
-    // Init the butler
-    let storage_handler = storage_handler::StorageHandler::new();
+/// Internal function to parse the --as-of time, "YYYY-MM-DD HH:MM" or "YYYY-MM-DD" with the current time of day
+fn parse_as_of_argument(time: &str) -> Result<chrono::DateTime<chrono::Local>, ButlerError> {
+    let naive = chrono::NaiveDateTime::parse_from_str(time.trim(), "%Y-%m-%d %H:%M")
+        .or_else(|_| chrono::NaiveDateTime::parse_from_str(time.trim(), "%Y-%m-%dT%H:%M"))
+        .or_else(|_| {
+            parse_date_argument(time.trim())
+                .map(|date| date.and_time(chrono::Local::now().time()))
+                .map_err(|_| ())
+        })
+        .map_err(|_| {
+            ButlerError::InvalidInput(format!(
+                "Invalid time provided: {}, expected format YYYY-MM-DD or YYYY-MM-DD HH:MM",
+                time
+            ))
+        })?;
+    naive
+        .and_local_timezone(chrono::Local)
+        .earliest()
+        .ok_or_else(|| ButlerError::InvalidInput(format!("Time {} doesn't exist locally", time)))
+}
 
-    // Check if config is provided by argument, if not try to read the default path
-    let config_path = if args.config != "tb-config.json" {
-        args.config.clone()
-    } else {
-        let default_path = storage_handler.startup_storage_directory() + "/tb-config.json";
-        if Path::new(&default_path).exists() {
-            tracing::debug!(
-                "Using default configuration file at {}",
-                default_path.as_str()
-            );
-            default_path
-        } else {
-            // Just for logging, default path will be set
-            tracing::debug!("No configuration file provided and default not found. Time-butler will create a new default configuration: {}", default_path.as_str());
-            default_path
+/// Run the configuration commands working on the configuration file itself. They are run before the
+/// configuration is read by the butler, so a missing or broken file can be replaced or fixed, and the stored
+/// data is not loaded. None for the other commands
+fn run_configuration_file_command(
+    command: &ConfigurationSubcommands,
+    config_reader: &mut config_reader::ConfigReader,
+    data_directory: &String,
+) -> Option<Result<(), ButlerError>> {
+    let config_path = config_reader.get_configuration_file_path_string();
+    match command {
+        ConfigurationSubcommands::Path => {
+            outln!("{}", config_path);
+            porcelain::set("path", &config_path);
+            Some(Ok(()))
         }
-    };
-
-    let user_specific_home_directory = storage_handler.user_home_directory();
-    tracing::debug!("Using configuration file at {}", config_path.as_str());
-    let mut config_reader = config_reader::ConfigReader::new(config_path.as_str());
-
-    // Read config, if fail create the new default one
-    let default_path = storage_handler.startup_storage_directory() + "/tb-config.json";
-    let using_default_path = config_path == default_path;
-    let config_file_exists = Path::new(&config_path).exists();
-
-    match config_reader.read_config() {
-        Ok(_) => {
-            tracing::info!("Configuration file read successfully");
+        ConfigurationSubcommands::Init { force } => {
+            if Path::new(&config_path).exists() && !force {
+                return Some(Err(ButlerError::Duplicate(format!(
+                    "Configuration file {}, use --force to replace it",
+                    config_path
+                ))));
+            }
+            let res = config_reader
+                .write_config(&AppConfiguration::new_default(data_directory))
+                .map_err(|e| {
+                    ButlerError::StorageFailure(format!(
+                        "Failed to write configuration file {}: {}",
+                        config_path, e
+                    ))
+                });
+            if res.is_ok() {
+                tracing::info!("Default configuration written to {}", config_path);
+            }
+            Some(res)
         }
-        Err(e) => {
-            if using_default_path && !config_file_exists {
-                tracing::warn!(
-                    "No configuration file found. Creating a new default configuration at: {}",
-                    config_path.as_str()
-                );
-                let default_config = AppConfiguration::new_default(&user_specific_home_directory);
-                match config_reader.write_config(&default_config) {
-                    Ok(_) => {
-                        tracing::info!(
-                            "Default configuration file created successfully at: {}",
-                            config_path.as_str()
-                        );
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed to create default configuration file: {}", e);
-                    }
+        ConfigurationSubcommands::Edit => {
+            let editor = std::env::var("VISUAL")
+                .or_else(|_| std::env::var("EDITOR"))
+                .unwrap_or_else(|_| "vi".to_string());
+            tracing::debug!("Editing configuration file {} with {}", config_path, editor);
+            Some(match config_reader.edit_config(&editor) {
+                Ok(true) => {
+                    tracing::info!("Configuration file {} updated", config_path);
+                    Ok(())
                 }
-            } else {
-                tracing::error!(
-                    "Failed to read configuration file '{}': {}. Please fix or remove the file.",
-                    config_path.as_str(),
-                    e
-                );
-                std::process::exit(1);
+                Ok(false) => {
+                    tracing::info!("No changes made to configuration file {}", config_path);
+                    Ok(())
+                }
+                Err(e) => Err(ButlerError::InvalidInput(e.to_string())),
+            })
+        }
+        ConfigurationSubcommands::Get { key } => Some(
+            config_reader
+                .read_config()
+                .and_then(|_| config_reader.get_value(key))
+                .map(|value| {
+                    outln!("{}", value);
+                    porcelain::set("value", &value);
+                })
+                .map_err(|e| ButlerError::InvalidInput(e.to_string())),
+        ),
+        ConfigurationSubcommands::Convert { to } => {
+            let format = match config_reader::ConfigFormat::from_extension(to) {
+                Some(format) => format,
+                None => {
+                    return Some(Err(ButlerError::InvalidInput(format!(
+                        "Unsupported configuration format: {}, valid formats are: json, toml, yaml",
+                        to
+                    ))))
+                }
+            };
+            Some(
+                config_reader
+                    .read_config()
+                    .and_then(|_| config_reader.convert_config(format))
+                    .map(|new_path| {
+                        tracing::info!(
+                            "Configuration file {} converted to {}",
+                            config_path,
+                            new_path
+                        )
+                    })
+                    .map_err(|e| ButlerError::InvalidInput(e.to_string())),
+            )
+        }
+        ConfigurationSubcommands::Set { key, value } => {
+            let res = config_reader
+                .read_config()
+                .and_then(|_| config_reader.set_value(key, value))
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()));
+            if res.is_ok() {
+                tracing::info!("Configuration {} set to {}", key, value);
             }
+            Some(res)
         }
+        _ => None,
     }
+}
 
-    tracing::debug!("Creating the Butler!");
-    let butler_config = config_reader
-        .get_configuration()
-        .cloned()
-        .unwrap_or_else(|| AppConfiguration::new_default(&user_specific_home_directory.clone()));
-    let mut butler = Butler::new(storage_handler, butler_config);
+/// Run the commands of a batch with the loaded butler. The first failing command stops the batch, and since nothing is
+/// stored on failure none of the changes made by the batch are kept
+fn run_batch(
+    input: &str,
+    butler: &mut Butler,
+    config_reader: &config_reader::ConfigReader,
+    config_source: &str,
+    store_data: &mut bool,
+    store_config: &mut bool,
+) -> Result<(), ButlerError> {
+    let commands =
+        batch::parse_batch(input).map_err(|e| ButlerError::InvalidInput(e.to_string()))?;
+    let mut results = Vec::new();
+
+    for command in &commands {
+        tracing::debug!(
+            "Running batch command {}: {:?}",
+            command.position(),
+            command.arguments()
+        );
+        let parsed = Cli::try_parse_from(
+            std::iter::once("time-butler").chain(command.arguments().iter().map(String::as_str)),
+        )
+        .map_err(|e| {
+            // Only the first line of the message, the rest is the usage
+            let message = e.to_string();
+            ButlerError::InvalidInput(format!(
+                "Batch command {}: {}",
+                command.position(),
+                message.lines().next().unwrap_or_default()
+            ))
+        })?;
+
+        match &parsed.command {
+            Commands::Batch { .. }
+            | Commands::Configuration {
+                config:
+                    ConfigurationSubcommands::Path
+                    | ConfigurationSubcommands::Init { .. }
+                    | ConfigurationSubcommands::Edit
+                    | ConfigurationSubcommands::Get { .. }
+                    | ConfigurationSubcommands::Convert { .. }
+                    | ConfigurationSubcommands::Set { .. },
+            } => {
+                return Err(ButlerError::InvalidInput(format!(
+                    "Batch command {}: {} can't be run in a batch",
+                    command.position(),
+                    command.arguments().join(" ")
+                )))
+            }
+            _ => {}
+        }
 
-    butler.init();
+        run_command(
+            parsed.command,
+            butler,
+            config_reader,
+            config_source,
+            store_data,
+            store_config,
+        )
+        .inspect_err(|_| {
+            tracing::error!(
+                "Batch command {} failed, none of the changes are stored",
+                command.position()
+            )
+        })?;
+        if let Some(result) = porcelain::take() {
+            results.push(result);
+        }
+    }
+
+    tracing::info!("Ran {} batch commands", commands.len());
+    porcelain::set("commands", commands.len());
+    porcelain::set("results", results);
+    Ok(())
+}
 
-    match args.command {
+/// Run a command with the loaded butler. The flags are set when the data or the configuration has to be stored
+fn run_command(
+    command: Commands,
+    butler: &mut Butler,
+    config_reader: &config_reader::ConfigReader,
+    config_source: &str,
+    store_data: &mut bool,
+    store_config: &mut bool,
+) -> Result<(), ButlerError> {
+    // The periods and default years are relative to the date of the butler clock
+    let today = butler.clock().today();
+    match command {
         Commands::Add { entity } => match entity {
-            AddSubcommands::Project { name, description } => {
+            AddSubcommands::Project {
+                name,
+                description,
+                budget,
+                deadline,
+                on_duplicate,
+                entry_hours,
+                entry_description,
+            } => {
                 tracing::debug!("Adding new project");
 
-                let new_project = project::Project::new(name, description);
-                if butler.add_project(new_project) {
-                    tracing::info!("Project added successfully!");
-                    store_data = true;
-                } else {
-                    tracing::info!("Failed to add project!");
-                }
+                let mut new_project = project::Project::new(name, description);
+                let entry = match (entry_hours, entry_description) {
+                    (Some(hours), _) if hours <= 0.0 => Err(ButlerError::InvalidInput(format!(
+                        "Invalid hours provided: {}. You can't report 0 or negative hours on a project.",
+                        hours
+                    ))),
+                    (Some(hours), Some(description)) => Ok(Some(entry::Entry::new_with_clock(
+                        hours,
+                        Some(description),
+                        butler.clock(),
+                    ))),
+                    _ => Ok(None),
+                };
+                let res = on_duplicate
+                    .parse::<project::DuplicateProjectAction>()
+                    .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                    .and_then(|action| {
+                        let deadline = deadline.as_deref().map(parse_date_argument).transpose()?;
+                        new_project.set_budget(budget, deadline);
+                        butler.add_project(new_project, action, entry?)
+                    });
+                res.map(|changed| {
+                    if changed {
+                        tracing::info!("Project stored successfully!");
+                        *store_data = true;
+                    }
+                })
             }
             AddSubcommands::Entry {
                 project,
                 hours,
+                estimate,
                 description,
+                date,
+                force,
             } => {
                 tracing::debug!("Adding new entry");
                 let hours_f32 = match hours {
@@ -171,111 +385,467 @@ fn main() {
                 };
 
                 if hours_f32 <= 0.0 {
-                    tracing::error!("Invalid hours provided: {} [{} parsed value]. You can't report 0 or negative hours on a project.", hours.unwrap(), hours_f32);
-                }
-
-                let e = entry::Entry::new(hours_f32, Some(description));
-                if butler.add_entry(&project, e) {
-                    tracing::info!("Entry added successfully!");
-                    store_data = true;
+                    Err(ButlerError::InvalidInput(format!("Invalid hours provided: {} [{} parsed value]. You can't report 0 or negative hours on a project.", hours.unwrap_or_default(), hours_f32)))
                 } else {
-                    tracing::info!("Failed to add entry!");
+                    let mut e =
+                        entry::Entry::new_with_clock(hours_f32, Some(description), butler.clock());
+                    if let Some(estimate) = estimate {
+                        e.set_estimate(estimate);
+                    }
+                    let date_res = match date {
+                        Some(ref d) => parse_date_argument(d).map(|parsed| e.set_worked_on(parsed)),
+                        None => Ok(()),
+                    };
+
+                    date_res.and_then(|_| {
+                        let res = butler.add_entry(&project, e, force);
+                        if res.is_ok() {
+                            tracing::info!("Entry added successfully!");
+                            *store_data = true;
+                            if butler.configuration().week_summary_after_add() {
+                                butler.display_week_glance();
+                            }
+                        }
+                        res
+                    })
                 }
             }
+            AddSubcommands::Entries { file, force } => {
+                tracing::debug!("Adding entries in bulk");
+                let reader: Result<Box<dyn std::io::Read>, ButlerError> = match file.as_deref() {
+                    None | Some("-") => Ok(Box::new(std::io::stdin())),
+                    Some(path) => std::fs::File::open(path)
+                        .map(|f| Box::new(f) as Box<dyn std::io::Read>)
+                        .map_err(|e| {
+                            ButlerError::InvalidInput(format!("Failed to open {}: {}", path, e))
+                        }),
+                };
+
+                reader.and_then(|r| {
+                    let res = butler.add_entries(r, force);
+                    if res.is_ok() {
+                        *store_data = true;
+                    }
+                    res
+                })
+            }
+            AddSubcommands::FromTemplate {
+                name,
+                date,
+                repeat,
+                from,
+                to,
+                force,
+            } => {
+                tracing::debug!("Adding entries from template {}", name);
+                let dates = match repeat {
+                    Some(ref repeat) => repeat
+                        .parse::<template::RepeatMode>()
+                        .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                        .and_then(|mode| {
+                            let from = parse_date_argument(from.as_deref().unwrap_or_default())?;
+                            let to = parse_date_argument(to.as_deref().unwrap_or_default())?;
+                            if from > to {
+                                return Err(ButlerError::InvalidInput(format!(
+                                    "Invalid range, {} is after {}",
+                                    from, to
+                                )));
+                            }
+                            Ok(template::expand_dates(&mode, from, to))
+                        }),
+                    None => match date {
+                        Some(ref d) => parse_date_argument(d).map(|d| vec![d]),
+                        None => Ok(vec![butler.clock().today()]),
+                    },
+                };
+
+                dates.and_then(|dates| {
+                    let res = butler.add_from_template(&name, &dates, force);
+                    if res.is_ok() {
+                        *store_data = true;
+                    }
+                    res
+                })
+            }
             AddSubcommands::Day {
                 extra_info,
                 starting_time,
                 ending_time,
                 paused_hours,
+                on_conflict,
+                no_break,
             } => {
                 tracing::debug!("Adding new day");
-                let mut d = day::Day::new(extra_info);
+                let strategy = on_conflict
+                    .parse::<week::MergeStrategy>()
+                    .map_err(|e| ButlerError::InvalidInput(e.to_string()));
 
-                if starting_time {
-                    d.set_starting_time(Some(&chrono::Local::now()));
-                }
+                strategy.and_then(|strategy| {
+                    let mut d = day::Day::new_with_clock(extra_info, butler.clock());
+                    d.set_skip_default_break(no_break);
 
-                if ending_time {
-                    d.set_ending_time(Some(&chrono::Local::now()));
-                }
+                    // Starting a new day, offer to close earlier days that were never ended
+                    if starting_time && butler.close_open_days(d.date())? > 0 {
+                        *store_data = true;
+                    }
 
-                let paused_hours_f32 = match paused_hours {
-                    Some(ref s) => s.parse::<f32>().unwrap_or(0.0),
-                    None => 0.0,
-                };
+                    if starting_time {
+                        d.set_starting_time(&butler.clock().now());
+                    }
 
-                if paused_hours_f32 != 0.0 {
-                    d.set_paused_time(paused_hours_f32);
-                }
+                    if ending_time {
+                        d.set_ending_time(&butler.clock().now());
+                    }
 
-                if butler.add_day(d) {
-                    tracing::info!("Day added successfully!");
-                    store_data = true;
-                } else {
-                    tracing::info!("Failed to add day!");
-                }
+                    let paused_hours_f32 = match paused_hours {
+                        Some(ref s) => s.parse::<f32>().unwrap_or(0.0),
+                        None => 0.0,
+                    };
+
+                    if paused_hours_f32 != 0.0 {
+                        d.set_paused_time(paused_hours_f32);
+                    }
+
+                    let res = butler.add_day(d, strategy);
+                    if res.is_ok() {
+                        tracing::info!("Day added successfully!");
+                        *store_data = true;
+                        if butler.configuration().week_summary_after_add() {
+                            butler.display_week_glance();
+                        }
+                    }
+                    res
+                })
             }
         },
-        Commands::Report { entity } => match entity {
-            ReportSubcommands::Project { name, format } => {
-                tracing::debug!("Generating Project report");
-                if butler.project_report(&name, &format) {
-                    tracing::info!("Project report generated successfully!");
+        Commands::Report {
+            entity,
+            list_formats,
+            dry_run,
+            overwrite,
+            unique_name,
+            publish,
+            csv_metadata,
+            csv_totals,
+        } => {
+            let entity = match entity {
+                _ if list_formats => {
+                    tracing::debug!("Listing report formats");
+                    butler.list_report_formats();
+                    return Ok(());
                 }
-            }
-            ReportSubcommands::Week {
-                number,
-                format,
-                year,
-            } => {
-                tracing::debug!("Generating Week report");
-                if butler.week_report(number, &format, year) {
-                    tracing::info!("Report for week {} generated successfully!", number);
+                Some(entity) => entity,
+                None => {
+                    return Err(ButlerError::InvalidInput(
+                        "A report type or --list-formats is required".to_string(),
+                    ))
                 }
-            }
-            ReportSubcommands::Month {
-                number,
-                format,
-                year,
-            } => {
-                tracing::debug!("Generating Month report");
-                if butler.month_report(number, &format, year) {
-                    tracing::info!("Report for month {} generated successfully!", number);
+            };
+
+            let policy = match (overwrite, unique_name) {
+                (true, _) => ReportOverwrite::Overwrite,
+                (_, true) => ReportOverwrite::UniqueName,
+                _ => ReportOverwrite::Refuse,
+            };
+            butler.set_report_write_policy(policy, dry_run);
+            butler.set_publish_reports(publish);
+            butler.set_report_csv_metadata(csv_metadata);
+            butler.set_report_csv_totals(csv_totals);
+
+            match entity {
+                ReportSubcommands::Project {
+                    name,
+                    format,
+                    summary,
+                    columns,
+                    template,
+                    email,
+                    clipboard,
+                } => {
+                    let options = ReportOptions {
+                        summary,
+                        columns,
+                        template,
+                        email,
+                        clipboard,
+                    };
+                    tracing::debug!("Generating Project report");
+                    let res = butler.project_report(&name, &format, &options);
+                    if res.is_ok() {
+                        tracing::info!("Project report generated successfully!");
+                    }
+                    res
                 }
-            }
-            ReportSubcommands::Year { number, format } => {
-                tracing::debug!(
-                    "Generating Year report for year {} with format: {}",
+                ReportSubcommands::Week {
                     number,
-                    &format
-                );
-                unimplemented!();
+                    format,
+                    year,
+                    period,
+                    summary,
+                    columns,
+                    template,
+                    email,
+                    clipboard,
+                } => {
+                    let options = ReportOptions {
+                        summary,
+                        columns,
+                        template,
+                        email,
+                        clipboard,
+                    };
+                    tracing::debug!("Generating Week report");
+                    period::resolve_week(&number, period.as_deref(), year, today)
+                        .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                        .and_then(|(number, year)| {
+                            let res = butler.week_report(number, &format, year, &options);
+                            if res.is_ok() {
+                                tracing::info!(
+                                    "Report for week {} generated successfully!",
+                                    number
+                                );
+                            }
+                            res
+                        })
+                }
+                ReportSubcommands::Timesheet {
+                    number,
+                    period,
+                    year,
+                    format,
+                    email,
+                } => {
+                    let options = ReportOptions {
+                        email,
+                        ..Default::default()
+                    };
+                    tracing::debug!("Generating Timesheet report");
+                    period::resolve_week(&number, period.as_deref(), year, today)
+                        .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                        .and_then(|(number, year)| {
+                            let res = butler.timesheet_report(number, &format, year, &options);
+                            if res.is_ok() {
+                                tracing::info!(
+                                    "Timesheet report for week {} generated successfully!",
+                                    number
+                                );
+                            }
+                            res
+                        })
+                }
+                ReportSubcommands::Month {
+                    number,
+                    format,
+                    year,
+                    period,
+                    summary,
+                    columns,
+                    template,
+                    email,
+                    clipboard,
+                } => {
+                    let options = ReportOptions {
+                        summary,
+                        columns,
+                        template,
+                        email,
+                        clipboard,
+                    };
+                    tracing::debug!("Generating Month report");
+                    period::resolve_month(&number, period.as_deref(), year, today)
+                        .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                        .and_then(|(number, year)| {
+                            let res = butler.month_report(number, &format, year, &options);
+                            if res.is_ok() {
+                                tracing::info!(
+                                    "Report for month {} generated successfully!",
+                                    number
+                                );
+                            }
+                            res
+                        })
+                }
+                ReportSubcommands::Overview {
+                    number,
+                    year,
+                    format,
+                    summary,
+                    template,
+                    email,
+                    clipboard,
+                } => {
+                    let options = ReportOptions {
+                        summary,
+                        template,
+                        email,
+                        clipboard,
+                        ..Default::default()
+                    };
+                    tracing::debug!("Generating Overview report");
+                    period::resolve_month(&number, None, year, today)
+                        .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                        .and_then(|(number, year)| {
+                            let res = butler.overview_report(number, &format, year, &options);
+                            if res.is_ok() {
+                                tracing::info!(
+                                    "Overview report for month {} generated successfully!",
+                                    number
+                                );
+                            }
+                            res
+                        })
+                }
+                ReportSubcommands::Range {
+                    from,
+                    to,
+                    format,
+                    summary,
+                    columns,
+                    template,
+                    email,
+                    clipboard,
+                } => {
+                    let options = ReportOptions {
+                        summary,
+                        columns,
+                        template,
+                        email,
+                        clipboard,
+                    };
+                    tracing::debug!("Generating Range report");
+                    parse_date_argument(&from)
+                        .and_then(|from| parse_date_argument(&to).map(|to| (from, to)))
+                        .and_then(|(from, to)| {
+                            let res = butler.range_report(from, to, &format, &options);
+                            if res.is_ok() {
+                                tracing::info!(
+                                    "Report for {} to {} generated successfully!",
+                                    from,
+                                    to
+                                );
+                            }
+                            res
+                        })
+                }
+                ReportSubcommands::Year {
+                    number,
+                    format,
+                    summary,
+                    columns,
+                    template,
+                    email,
+                    clipboard,
+                } => {
+                    let options = ReportOptions {
+                        summary,
+                        columns,
+                        template,
+                        email,
+                        clipboard,
+                    };
+                    let number = number.unwrap_or(today.year() as u32);
+                    tracing::debug!(
+                        "Generating Year report for year {} with format: {}",
+                        number,
+                        &format
+                    );
+                    let res = butler.year_report(number as i32, &format, &options);
+                    if res.is_ok() {
+                        tracing::info!("Report for year {} generated successfully!", number);
+                    }
+                    res
+                }
             }
-        },
+        }
         Commands::List {
             project,
             week,
             month,
+            period,
             all_weeks,
+            year,
+            from_week,
+            to_week,
             all_projects,
+            include_archived,
+            sort,
+            desc,
+            columns,
+            limit,
+            offset,
+            export,
         } => {
             tracing::debug!("List selected entities");
+            let mut res = Ok(());
+
+            let list_options = match sort.as_deref().map(str::parse::<tables::ListSort>) {
+                Some(Err(e)) => {
+                    res = Err(ButlerError::InvalidInput(e.to_string()));
+                    tables::ListOptions::default()
+                }
+                parsed => tables::ListOptions {
+                    sort: parsed.and_then(Result::ok),
+                    descending: desc,
+                    columns: columns.map(|c| c.split(',').map(|s| s.to_string()).collect()),
+                    offset,
+                    limit,
+                    export,
+                },
+            };
+
+            let week_filter = period::WeekFilter {
+                year,
+                from_week,
+                to_week,
+            };
+            if let (Some(from), Some(to)) = (from_week, to_week) {
+                if from > to {
+                    res = Err(ButlerError::InvalidInput(format!(
+                        "Invalid week range, week {} is after week {}",
+                        from, to
+                    )));
+                }
+            }
 
-            if all_weeks {
+            // A filter without a specific week or period lists all the matching weeks
+            let list_filtered_weeks = !week_filter.is_empty() && week.is_none() && period.is_none();
+
+            // Each listing would overwrite the export file, so only one can be exported
+            let listings = [
+                all_weeks || list_filtered_weeks,
+                all_projects,
+                project.is_some(),
+                week.is_some(),
+                month.is_some(),
+                period.is_some(),
+            ];
+            if list_options.export.is_some() && listings.iter().filter(|l| **l).count() != 1 {
+                res = Err(ButlerError::InvalidInput(
+                    "--export requires exactly one listing, e.g. --project or --week".to_string(),
+                ));
+            }
+
+            // Nothing is listed, or exported, with invalid options
+            let (project, week, month, period) = match res {
+                Ok(_) => (project, week, month, period),
+                Err(_) => (None, None, None, None),
+            };
+            let all_projects = all_projects && res.is_ok();
+            if res.is_ok() && (all_weeks || list_filtered_weeks) {
                 tracing::info!("Listing all weeks");
-                butler.list_all_weeks();
+                res = butler.list_all_weeks(&week_filter, &list_options);
             }
 
             if all_projects {
                 tracing::info!("Listing all projects");
-                butler.list_all_projects();
+                res = res.and(butler.list_all_projects(include_archived, &list_options));
             }
 
             // Select what user want to list
             match project {
                 Some(proj_name) => {
                     tracing::debug!("Project specified: {}", proj_name);
-                    butler.list_specific_project(&proj_name);
+                    res = res.and(butler.list_specific_project(&proj_name, &list_options));
                 }
                 None => {
                     tracing::debug!("No specific project specified, no projects will be listed");
@@ -285,9 +855,13 @@ fn main() {
             match week {
                 Some(week) => {
                     tracing::debug!("Week specified: {}", week);
-                    // Convert string to int
-                    let w: u32 = week.parse().unwrap();
-                    butler.list_specific_week(w);
+                    res = res.and(
+                        period::resolve_week(&week, None, None, today)
+                            .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                            .and_then(|(w, _)| {
+                                butler.list_specific_week(w, &week_filter, &list_options)
+                            }),
+                    );
                 }
                 None => {
                     tracing::debug!("No specific week specified, no weeks will be listed");
@@ -297,42 +871,81 @@ fn main() {
             match month {
                 Some(month) => {
                     tracing::debug!("Month specified: {}", month);
-                    // Convert string to int
-                    let m: u32 = month.parse().unwrap();
-                    butler.list_specific_month(m);
+                    res = res.and(
+                        period::resolve_month(&month, None, None, today)
+                            .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                            .and_then(|(m, _)| butler.list_specific_month(m, &list_options)),
+                    );
                 }
                 None => {
                     tracing::debug!("No specific month specified, no months will be listed");
                 }
             }
+
+            if let Some(period_alias) = period {
+                tracing::debug!("Period specified: {}", period_alias);
+                res = res.and(
+                    period::parse_period(&period_alias, butler.clock().today())
+                        .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                        .and_then(|p| match p {
+                            period::Period::Week { number, .. } => {
+                                butler.list_specific_week(number, &week_filter, &list_options)
+                            }
+                            period::Period::Month { number, .. } => {
+                                butler.list_specific_month(number, &list_options)
+                            }
+                        }),
+                );
+            }
+
+            res
         }
         Commands::Remove { entity } => match entity {
             RemoveSubcommands::Project { name } => {
                 tracing::debug!("Removing project");
-                if !butler.remove_project(&name) {
-                    tracing::info!("Failed to remove project!");
-                } else {
+                let res = butler.remove_project(&name);
+                if res.is_ok() {
                     tracing::info!("Project removed successfully!");
-                    store_data = true;
+                    *store_data = true;
                 }
+                res
             }
-            RemoveSubcommands::Entry { project, id } => {
+            RemoveSubcommands::Entry {
+                project,
+                id,
+                nth,
+                last,
+            } => {
                 tracing::debug!("Removing entry");
-                if !butler.remove_entry(&project, id.unwrap_or("".to_string())) {
-                    tracing::info!("Failed to remove entry!");
-                } else {
+                let selector = match (id, nth) {
+                    (Some(id), _) => EntrySelector::Id(id),
+                    (None, Some(nth)) => EntrySelector::Nth(nth),
+                    (None, None) if last => EntrySelector::Last,
+                    // Unreachable, clap requires one of them
+                    (None, None) => EntrySelector::Id(String::new()),
+                };
+                let res = butler.remove_entry(project.as_deref(), &selector);
+                if res.is_ok() {
                     tracing::info!("Entry removed successfully!");
-                    store_data = true;
+                    *store_data = true;
                 }
+                res
             }
-            RemoveSubcommands::Day { date, week } => {
+            RemoveSubcommands::Day { date, week, id } => {
                 tracing::debug!("Removing day");
-                if !butler.remove_day(week, date) {
-                    tracing::info!("Failed to remove day!");
-                } else {
+                let res = match (id, week, date) {
+                    (Some(id), _, _) => butler.remove_day_by_id(&id),
+                    (None, Some(week), Some(date)) => butler.remove_day(week, date),
+                    // Enforced by the CLI
+                    _ => Err(ButlerError::InvalidInput(
+                        "The date and week, or the ID, of the day is required".to_string(),
+                    )),
+                };
+                if res.is_ok() {
                     tracing::info!("Day removed successfully!");
-                    store_data = true;
+                    *store_data = true;
                 }
+                res
             }
         },
         Commands::Modify { entity } => match entity {
@@ -342,12 +955,28 @@ fn main() {
                 new_description,
             } => {
                 tracing::debug!("Modifying project");
-                if !butler.modify_project(id, new_name, new_description) {
-                    tracing::info!("No project modification performed!");
-                } else {
+                let res = butler.modify_project(id, new_name, new_description);
+                if res.is_ok() {
                     tracing::info!("Project modified successfully!");
-                    store_data = true;
+                    *store_data = true;
                 }
+                res
+            }
+            ModifySubcommands::Entry {
+                project,
+                id,
+                hours,
+                description,
+                date,
+                new_project,
+            } => {
+                tracing::debug!("Modifying entry");
+                let res = butler.modify_entry(&project, id, hours, description, date, new_project);
+                if res.is_ok() {
+                    tracing::info!("Entry modified successfully!");
+                    *store_data = true;
+                }
+                res
             }
             ModifySubcommands::Day {
                 id,
@@ -357,83 +986,626 @@ fn main() {
                 new_paused_hours,
             } => {
                 tracing::debug!("Modifying day");
-                if !butler.modify_day(
+                let res = butler.modify_day(
                     id,
                     new_extra_info,
                     new_starting_time,
                     new_ending_time,
                     new_paused_hours,
-                ) {
-                    tracing::info!("Failed to modify day!");
-                } else {
+                );
+                if res.is_ok() {
                     tracing::info!("Day modified successfully!");
-                    store_data = true;
+                    *store_data = true;
                 }
+                res
+            }
+        },
+        Commands::Project { entity } => match entity {
+            ProjectSubcommands::Alias { name, alias } => {
+                tracing::debug!("Adding alias {} to project {}", alias, name);
+                let res = butler.add_project_alias(&name, &alias);
+                if res.is_ok() {
+                    *store_data = true;
+                }
+                res
+            }
+            ProjectSubcommands::Archive { name } => {
+                tracing::debug!("Archiving project {}", name);
+                let res = butler.set_project_archived(&name, true);
+                if res.is_ok() {
+                    *store_data = true;
+                }
+                res
+            }
+            ProjectSubcommands::Budget {
+                name,
+                hours,
+                deadline,
+                clear,
+            } => {
+                tracing::debug!("Setting budget of project {}", name);
+                let res = deadline
+                    .as_deref()
+                    .map(parse_date_argument)
+                    .transpose()
+                    .and_then(|deadline| {
+                        let hours = if clear { None } else { hours };
+                        butler.set_project_budget(&name, hours, deadline)
+                    });
+                if res.is_ok() {
+                    *store_data = true;
+                }
+                res
+            }
+            ProjectSubcommands::Unarchive { name } => {
+                tracing::debug!("Unarchiving project {}", name);
+                let res = butler.set_project_archived(&name, false);
+                if res.is_ok() {
+                    *store_data = true;
+                }
+                res
+            }
+            ProjectSubcommands::Set {
+                name,
+                client,
+                color,
+                reference,
+            } => {
+                tracing::debug!("Setting metadata of project {}", name);
+                let res = butler.set_project_metadata(&name, client, color, reference);
+                if res.is_ok() {
+                    *store_data = true;
+                }
+                res
+            }
+            ProjectSubcommands::Rename { name, new_name } => {
+                tracing::debug!("Renaming project {} to {}", name, new_name);
+                let res = butler.rename_project(&name, &new_name);
+                if res.is_ok() {
+                    *store_data = true;
+                }
+                res
+            }
+        },
+        Commands::Template { entity } => match entity {
+            TemplateSubcommands::Add {
+                name,
+                project,
+                hours,
+                description,
+            } => {
+                tracing::debug!("Adding template {}", name);
+                let res = butler.add_entry_template(&name, &project, hours, description);
+                if res.is_ok() {
+                    *store_config = true;
+                }
+                res
+            }
+            TemplateSubcommands::List => {
+                butler.list_entry_templates();
+                Ok(())
+            }
+            TemplateSubcommands::Remove { name } => {
+                tracing::debug!("Removing template {}", name);
+                let res = butler.remove_entry_template(&name);
+                if res.is_ok() {
+                    *store_config = true;
+                }
+                res
+            }
+        },
+        Commands::SyncRecurring => {
+            tracing::debug!("Syncing recurring entries");
+            let today = butler.clock().today();
+            let res = butler.sync_recurring(today);
+            if res.is_ok() {
+                *store_data = true;
+            }
+            res
+        }
+        Commands::Allocate {
+            date,
+            allocations,
+            clear,
+        } => {
+            tracing::debug!("Allocating day hours to projects");
+            let date = match date {
+                Some(ref d) => parse_date_argument(d),
+                None => Ok(butler.clock().today()),
+            };
+            let allocations: Result<Vec<(String, f32)>, ButlerError> = allocations
+                .iter()
+                .filter(|_| !clear)
+                .map(|a| {
+                    a.split_once('=')
+                        .and_then(|(project, hours)| {
+                            Some((
+                                project.trim().to_string(),
+                                hours.trim().parse::<f32>().ok()?,
+                            ))
+                        })
+                        .ok_or_else(|| {
+                            ButlerError::InvalidInput(format!(
+                                "Invalid allocation: {}, expected format project=hours",
+                                a
+                            ))
+                        })
+                })
+                .collect();
+
+            let res = date.and_then(|date| {
+                allocations.and_then(|allocations| butler.allocate_day(date, allocations))
+            });
+            if res.is_ok() {
+                *store_data = true;
+            }
+            res
+        }
+        Commands::Today { watch } => {
+            if watch {
+                tracing::debug!("Watching the running day");
+                return butler.watch_today();
+            }
+            tracing::debug!("Displaying status of today");
+            butler.display_today()
+        }
+        Commands::Week => {
+            tracing::debug!("Displaying status of the current week");
+            butler.display_current_week()
+        }
+        Commands::Badge { week, year, output } => {
+            tracing::debug!("Generating the badge of week {}", week);
+            period::resolve_week(&week, None, year, today)
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                .and_then(|(number, year)| butler.week_badge(number, year, output.as_deref()))
+        }
+        Commands::Standup { days } => {
+            tracing::debug!("Displaying the standup of the last {} days", days);
+            butler.display_standup(days)
+        }
+        Commands::Calendar { month, year } => {
+            tracing::debug!("Displaying calendar of month {}", month);
+            period::resolve_month(&month, None, year, today)
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                .and_then(|(month, year)| butler.display_calendar(month, year))
+        }
+        Commands::Stats { entity } => match entity {
+            None => {
+                tracing::debug!("Displaying statistics");
+                butler.stats()
+            }
+            Some(StatsSubcommands::Estimates { project }) => {
+                tracing::debug!("Displaying estimated compared to actual hours");
+                butler.stats_estimates(project.as_deref())
+            }
+            Some(StatsSubcommands::Chart { month, year }) => {
+                tracing::debug!("Displaying chart of month {}", month);
+                period::resolve_month(&month, None, year, today)
+                    .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                    .and_then(|(month, year)| butler.stats_chart(month, year))
             }
         },
         Commands::Info { short } => {
             tracing::debug!("Displaying storage info!");
-            butler.self_info(short);
+            butler.self_info(
+                short,
+                &config_reader.get_configuration_file_path_string(),
+                config_source,
+            );
+            Ok(())
         }
         Commands::Targets { entity } => match entity {
-            TargetTimesSubcommands::Week { number, year } => {
-                tracing::debug!(
-                    "Displaying target times for week {} in year {}",
-                    number,
-                    year
-                );
-                if !butler.display_week_target_status(number, year) {
-                    tracing::error!(
-                        "Failed to display target times for week {} in year {}",
-                        number,
-                        year
-                    );
-                } else {
-                    tracing::info!(
-                        "Target times for week {} displayed in year {} successfully!",
-                        number,
-                        year
-                    );
-                }
+            TargetTimesSubcommands::Project { name } => {
+                tracing::debug!("Displaying budget burn-down for project {}", name);
+                butler.display_project_target_status(&name)
             }
-            TargetTimesSubcommands::Month { number, year } => {
-                tracing::debug!(
-                    "Displaying target times for month{} in year {}",
-                    number,
-                    year
-                );
-                if !butler.display_month_target_status(number, year) {
-                    tracing::error!(
-                        "Failed to display target times for week {} in year {}",
+            TargetTimesSubcommands::Week {
+                number,
+                year,
+                period,
+            } => period::resolve_week(&number, period.as_deref(), year, today)
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                .and_then(|(number, year)| {
+                    tracing::debug!(
+                        "Displaying target times for week {} in year {}",
                         number,
                         year
                     );
-                } else {
-                    tracing::info!(
-                        "Target times for week {} displayed in year {} successfully!",
+                    let res = butler.display_week_target_status(number, year);
+                    if res.is_ok() {
+                        tracing::info!(
+                            "Target times for week {} displayed in year {} successfully!",
+                            number,
+                            year
+                        );
+                    }
+                    res
+                }),
+            TargetTimesSubcommands::Month {
+                number,
+                year,
+                period,
+            } => period::resolve_month(&number, period.as_deref(), year, today)
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                .and_then(|(number, year)| {
+                    tracing::debug!(
+                        "Displaying target times for month {} in year {}",
                         number,
                         year
                     );
-                }
+                    let res = butler.display_month_target_status(number, year);
+                    if res.is_ok() {
+                        tracing::info!(
+                            "Target times for month {} displayed in year {} successfully!",
+                            number,
+                            year
+                        );
+                    }
+                    res
+                }),
+            TargetTimesSubcommands::Edit {
+                number,
+                period,
+                year,
+                hours,
+                reset,
+                id,
+            } => match id {
+                Some(id) => butler.week_by_id(&id),
+                None => period::resolve_week(&number, period.as_deref(), year, today)
+                    .map_err(|e| ButlerError::InvalidInput(e.to_string())),
+            }
+            .and_then(|(number, year)| {
+                tracing::debug!("Editing the target of week {} in year {}", number, year);
+                let target = match (hours, reset) {
+                    (Some(hours), _) => Some(target::WeekTargetEdit::Hours(hours)),
+                    (None, true) => Some(target::WeekTargetEdit::Reset),
+                    (None, false) => None,
+                };
+                butler
+                    .edit_week_target(number, year, target)
+                    .map(|changed| {
+                        if changed {
+                            *store_data = true;
+                        }
+                    })
+            }),
+            TargetTimesSubcommands::Forecast { month, year } => {
+                period::resolve_month(&month, None, year, today)
+                    .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                    .and_then(|(month, year)| {
+                        tracing::debug!("Displaying forecast for month {} in year {}", month, year);
+                        butler.display_month_target_forecast(month, year)
+                    })
+            }
+            TargetTimesSubcommands::Reapply { from_week, year } => {
+                let from = from_week.map(|week| (week, year.unwrap_or(today.year() as u32) as i32));
+                tracing::debug!("Reapplying the week target from {:?}", from);
+                butler.reapply_week_targets(from).map(|updated| {
+                    if updated > 0 {
+                        tracing::info!("Target updated for {} weeks", updated);
+                        *store_data = true;
+                    }
+                })
             }
         },
         Commands::Backup { now } => {
             tracing::debug!("Backup ProjectData requested");
             if now {
-                if butler.force_backup() {
+                let res = butler.force_backup();
+                if res.is_ok() {
                     tracing::info!("Data backup completed successfully!");
-                } else {
-                    tracing::error!("Project data backup failed!");
                 }
+                res
             } else {
                 tracing::info!("Project data backup flag not set, skipping.");
+                Ok(())
             }
         }
+        Commands::Sync { on_conflict } => {
+            tracing::debug!("Syncing the data");
+            on_conflict
+                .parse::<week::MergeStrategy>()
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                .and_then(|strategy| butler.sync(strategy))
+                // Also when nothing changed, for the state of the sync
+                .map(|_| *store_data = true)
+        }
+        Commands::Merge { from, on_conflict } => {
+            tracing::debug!("Merging the storage in {}", from);
+            on_conflict
+                .parse::<week::MergeStrategy>()
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                .and_then(|strategy| butler.merge_storage(&from, strategy))
+                .map(|changed| {
+                    if changed {
+                        *store_data = true;
+                    }
+                })
+        }
+        Commands::Export { target } => match target {
+            ExportSubcommands::Jira {
+                project,
+                month,
+                year,
+                output,
+                post,
+            } => {
+                tracing::debug!("Exporting the worklogs of project {}", project);
+                period::resolve_month(&month, None, year, today)
+                    .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                    .and_then(|(month, year)| {
+                        butler.export_jira_worklogs(&project, month, year, output.as_deref(), post)
+                    })
+            }
+        },
+        Commands::Trash { command } => match command {
+            TrashSubcommands::List => {
+                butler.list_trash();
+                Ok(())
+            }
+            TrashSubcommands::Restore { id } => butler.restore_from_trash(&id).map(|_| {
+                tracing::info!("Restored {} from the trash", id);
+                *store_data = true;
+            }),
+        },
+        Commands::InstallHooks {
+            kind,
+            print,
+            uninstall,
+        } => {
+            tracing::debug!("Installing the {} hooks", kind);
+            kind.parse::<hooks::HookKind>()
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                .and_then(|kind| butler.install_hooks(kind, print, uninstall))
+        }
+        Commands::Doctor { open_days } => {
+            // Open days is the only check so far, run both when selected and when no check is selected
+            tracing::debug!("Running doctor checks, open days selected: {}", open_days);
+            butler.doctor_open_days().map(|closed| {
+                if closed > 0 {
+                    tracing::info!("Closed {} open days", closed);
+                    *store_data = true;
+                }
+            })
+        }
+        Commands::Import {
+            entity,
+            source,
+            file,
+            dry_run,
+            on_conflict,
+            list_sources,
+        } => {
+            if list_sources {
+                tracing::debug!("Listing import sources");
+                butler.list_import_sources();
+                return Ok(());
+            }
+
+            if let Some(ImportSubcommands::Caldav {
+                url,
+                project,
+                month,
+                year,
+                calendar,
+                keyword,
+                dry_run,
+            }) = entity
+            {
+                tracing::debug!(
+                    "Importing the meetings of a calendar to project {}",
+                    project
+                );
+                return period::resolve_month(&month, None, year, today)
+                    .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                    .and_then(|(month, year)| {
+                        let res = butler.import_caldav(
+                            url.as_deref(),
+                            &project,
+                            month,
+                            year,
+                            &calendar,
+                            &keyword,
+                            dry_run,
+                        );
+                        if res.is_ok() && !dry_run {
+                            *store_data = true;
+                        }
+                        res
+                    });
+            }
+
+            // The days subcommand is the csv source
+            let (source, file, dry_run, on_conflict) = match (entity, source, file) {
+                (
+                    Some(ImportSubcommands::Days {
+                        file,
+                        dry_run,
+                        on_conflict,
+                    }),
+                    _,
+                    _,
+                ) => ("csv".to_string(), file, dry_run, on_conflict),
+                (None, Some(source), Some(file)) => (source, file, dry_run, on_conflict),
+                _ => {
+                    return Err(ButlerError::InvalidInput(
+                        "An import source and file, or the days subcommand, is required"
+                            .to_string(),
+                    ))
+                }
+            };
+            tracing::debug!("Importing {} from {}", source, file);
+
+            on_conflict
+                .parse::<week::MergeStrategy>()
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                .and_then(|strategy| {
+                    let res = butler.import_from(&source, &file, strategy, dry_run);
+                    if res.is_ok() && !dry_run {
+                        *store_data = true;
+                    }
+                    res
+                })
+        }
+        Commands::Compare { left, right } => {
+            let today = butler.clock().today();
+            period::parse_period_spec(&left, today)
+                .and_then(|l| period::parse_period_spec(&right, today).map(|r| (l, r)))
+                .map(|(left, right)| butler.compare_periods(&left, &right))
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+        }
+        Commands::Lock { entity } => {
+            let period = match entity {
+                LockSubcommands::Week {
+                    number,
+                    year,
+                    snapshot,
+                    sign,
+                } => period::resolve_week(&number, None, year, today).map(|(number, year)| {
+                    Some((
+                        LockedPeriod::Week {
+                            number,
+                            year: year as i32,
+                        },
+                        snapshot,
+                        sign,
+                    ))
+                }),
+                LockSubcommands::Month {
+                    number,
+                    year,
+                    snapshot,
+                    sign,
+                } => period::resolve_month(&number, None, year, today).map(|(number, year)| {
+                    Some((
+                        LockedPeriod::Month {
+                            number,
+                            year: year as i32,
+                        },
+                        snapshot,
+                        sign,
+                    ))
+                }),
+                LockSubcommands::List => Ok(None),
+            };
+
+            match period.map_err(|e| ButlerError::InvalidInput(e.to_string())) {
+                Ok(Some((period, snapshot, sign))) => butler.lock_period(period).and_then(|_| {
+                    *store_data = true;
+                    if snapshot {
+                        butler.snapshot_period(period, sign)
+                    } else {
+                        Ok(())
+                    }
+                }),
+                Ok(None) => {
+                    butler.list_locked_periods();
+                    Ok(())
+                }
+                Err(e) => Err(e),
+            }
+        }
+        Commands::Unlock { entity } => {
+            let period = match entity {
+                UnlockSubcommands::Week { number, year } => {
+                    period::resolve_week(&number, None, year, today).map(|(number, year)| {
+                        LockedPeriod::Week {
+                            number,
+                            year: year as i32,
+                        }
+                    })
+                }
+                UnlockSubcommands::Month { number, year } => {
+                    period::resolve_month(&number, None, year, today).map(|(number, year)| {
+                        LockedPeriod::Month {
+                            number,
+                            year: year as i32,
+                        }
+                    })
+                }
+            };
+
+            period
+                .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                .and_then(|period| {
+                    let res = butler.unlock_period(period);
+                    if res.is_ok() {
+                        *store_data = true;
+                    }
+                    res
+                })
+        }
+        Commands::Schema { command } => match command {
+            SchemaSubcommands::Dump { document, output } => {
+                tracing::debug!("Dumping the schema of {}", document);
+                butler.dump_schema(&document, output.as_deref())
+            }
+        },
+        Commands::Note { entity } => match entity {
+            NoteSubcommands::Week { number, text, year } => {
+                period::resolve_week(&number, None, year, today)
+                    .map_err(|e| ButlerError::InvalidInput(e.to_string()))
+                    .and_then(|(number, year)| {
+                        tracing::debug!("Adding note to week {} in year {}", number, year);
+                        let res = butler.add_week_note(number, year, text);
+                        if res.is_ok() {
+                            tracing::info!("Note added to week {} successfully!", number);
+                            *store_data = true;
+                        }
+                        res
+                    })
+            }
+        },
+        Commands::Batch { file } => {
+            tracing::debug!("Running batch commands");
+            let mut input = String::new();
+            let read = match file.as_deref() {
+                None | Some("-") => std::io::stdin().read_to_string(&mut input),
+                Some(path) => {
+                    std::fs::File::open(path).and_then(|mut f| f.read_to_string(&mut input))
+                }
+            };
+            read.map_err(|e| ButlerError::InvalidInput(format!("Failed to read the batch: {}", e)))
+                .and_then(|_| {
+                    run_batch(
+                        &input,
+                        butler,
+                        config_reader,
+                        config_source,
+                        store_data,
+                        store_config,
+                    )
+                })
+        }
+        Commands::Dev { entity } => match entity {
+            DevSubcommands::Generate {
+                years,
+                entries_per_day,
+                force,
+            } => {
+                tracing::debug!(
+                    "Generating {} years of data with {} entries per day",
+                    years,
+                    entries_per_day
+                );
+                let res = butler.generate_synthetic_data(years, entries_per_day, force);
+                if res.is_ok() {
+                    *store_data = true;
+                }
+                res
+            }
+            DevSubcommands::Bench { iterations } => {
+                tracing::debug!("Running benchmarks with {} iterations", iterations);
+                butler.run_benchmarks(iterations)
+            }
+        },
         Commands::Configuration { config } => match config {
             ConfigurationSubcommands::Dump {
                 dump_terminal,
                 dump_file,
             } => {
+                let mut res = Ok(());
                 if dump_terminal {
                     tracing::info!("Dumping current configuration to terminal:");
                     butler.dump_configuration_to_terminal(
@@ -442,17 +1614,256 @@ fn main() {
                 }
                 if let Some(file_path) = dump_file {
                     tracing::info!("Dumping current configuration to file: {}", file_path);
-                    butler.dump_configuration_to_file(
+                    res = butler.dump_configuration_to_file(
                         config_reader.get_configuration_file_path_string(),
                         file_path,
                     );
                 }
+                res
             }
+            ConfigurationSubcommands::Show => {
+                butler.dump_configuration_to_terminal(
+                    config_reader.get_configuration_file_path_string(),
+                );
+                Ok(())
+            }
+            // Handled before the configuration is read
+            ConfigurationSubcommands::Path
+            | ConfigurationSubcommands::Init { .. }
+            | ConfigurationSubcommands::Edit
+            | ConfigurationSubcommands::Get { .. }
+            | ConfigurationSubcommands::Convert { .. }
+            | ConfigurationSubcommands::Set { .. } => Ok(()),
         },
     }
+}
+
+fn main() {
+    // Parse the CLI arguments, invalid usage is reported as a validation error
+    let args = match Cli::try_parse() {
+        Ok(args) => args,
+        Err(e) => {
+            let code = if e.use_stderr() {
+                K_BUTLER_INVALID_INPUT
+            } else {
+                // Help and version output
+                0
+            };
+            let _ = e.print();
+            process::exit(code);
+        }
+    };
+
+    // Initialize the logger based on cli arguments
+    let log_level = if args.verbose {
+        Level::DEBUG // Log messages of `DEBUG` level and higher
+    } else if args.quiet {
+        Level::WARN // Only warnings and errors, essential output is still printed
+    } else {
+        Level::INFO // Log messages of `INFO` level and higher
+    };
+
+    // In porcelain mode stdout only has the result document, the logs go to stderr
+    if args.porcelain {
+        porcelain::enable();
+    }
+    let log_writer = move || -> Box<dyn std::io::Write> {
+        if args.porcelain {
+            Box::new(std::io::stderr())
+        } else {
+            Box::new(std::io::stdout())
+        }
+    };
+
+    if args.json {
+        tracing_subscriber::fmt()
+            .json() // Output logs in JSON format
+            .with_max_level(log_level)
+            .with_env_filter(EnvFilter::from_default_env()) // Optional: filter logs based on an environment variable
+            .with_writer(log_writer)
+            .init();
+    } else {
+        tracing_subscriber::fmt()
+            .with_max_level(log_level)
+            .with_writer(log_writer)
+            .init();
+    }
+
+    tracing::debug!("Creating the Butler!");
+
+    // Interactive mode variables
+    let mut store_data = false;
+    let mut store_config = false;
+
+    // Init the butler
+    let storage_handler = storage_handler::StorageHandler::new();
 
-    if store_data && !butler.save() {
-        tracing::error!("Failed to save butler data, added entry will not be stored properly");
-        process::exit(K_BUTLER_SAVE_FAILED);
+    // Check if config is provided by argument or TB_CONFIG, if not try to read the default path
+    let (config_path, using_default_path) = if args.config != "tb-config.json" {
+        (args.config.clone(), false)
+    } else if let Some(env_path) = paths::config_file_override() {
+        tracing::debug!(
+            "Using configuration file from {}: {}",
+            paths::K_CONFIG_ENV,
+            env_path
+        );
+        (env_path, false)
+    } else {
+        let default_path = paths::default_config_file().unwrap_or_else(|| {
+            format!(
+                "{}/{}",
+                storage_handler.startup_storage_directory(),
+                config_reader::K_CONFIG_FILE_NAMES[0]
+            )
+        });
+        if Path::new(&default_path).exists() {
+            tracing::debug!(
+                "Using default configuration file at {}",
+                default_path.as_str()
+            );
+        } else {
+            // Just for logging, default path will be set
+            tracing::debug!("No configuration file provided and default not found. Time-butler will create a new default configuration: {}", default_path.as_str());
+        }
+        (default_path, true)
+    };
+
+    let data_directory = storage_handler.data_directory();
+    tracing::debug!("Using configuration file at {}", config_path.as_str());
+    let mut config_reader = config_reader::ConfigReader::new(config_path.as_str());
+
+    if let Commands::Configuration { config } = &args.command {
+        if let Some(res) =
+            run_configuration_file_command(config, &mut config_reader, &data_directory)
+        {
+            if let Err(e) = res {
+                tracing::error!("{}", e);
+                porcelain::finish(Some(&e), exit_code(&e));
+                process::exit(exit_code(&e));
+            }
+            porcelain::finish(None, 0);
+            return;
+        }
     }
+
+    // Read config, if fail create the new default one
+    let config_file_exists = Path::new(&config_path).exists();
+
+    match config_reader.read_config() {
+        Ok(_) => {
+            tracing::info!("Configuration file read successfully");
+        }
+        Err(e) => {
+            if using_default_path && !config_file_exists {
+                tracing::warn!(
+                    "No configuration file found. Creating a new default configuration at: {}",
+                    config_path.as_str()
+                );
+                let default_config = AppConfiguration::new_default(&data_directory);
+                match config_reader.write_config(&default_config) {
+                    Ok(_) => {
+                        tracing::info!(
+                            "Default configuration file created successfully at: {}",
+                            config_path.as_str()
+                        );
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to create default configuration file: {}", e);
+                    }
+                }
+            } else {
+                tracing::error!(
+                    "Failed to read configuration file '{}': {}. Please fix or remove the file.",
+                    config_path.as_str(),
+                    e
+                );
+                porcelain::finish(Some(&e), K_BUTLER_GENERAL_FAILURE);
+                process::exit(K_BUTLER_GENERAL_FAILURE);
+            }
+        }
+    }
+
+    tracing::debug!("Creating the Butler!");
+    let butler_config = config_reader
+        .get_configuration()
+        .cloned()
+        .unwrap_or_else(|| AppConfiguration::new_default(&data_directory));
+    let mut butler = Butler::new(storage_handler, butler_config);
+    butler.set_no_color(args.no_color);
+    butler.set_force_unlock(args.force_unlock);
+    if let Some(as_of) = &args.as_of {
+        match parse_as_of_argument(as_of) {
+            Ok(time) => butler.set_clock(std::rc::Rc::new(clock::FixedClock::new(time))),
+            Err(e) => {
+                tracing::error!("{}", e);
+                porcelain::finish(Some(&e), exit_code(&e));
+                process::exit(exit_code(&e));
+            }
+        }
+    }
+    butler.watch_configuration(config_watcher::ConfigWatcher::new(
+        &config_reader.get_configuration_file_path_string(),
+    ));
+
+    if let Err(e) = butler.init() {
+        tracing::error!("Failed to initialize the Butler: {}", e);
+        porcelain::finish(Some(&e), exit_code(&e));
+        process::exit(exit_code(&e));
+    }
+
+    if let Some(format) = &args.duration_format {
+        match format.parse::<DurationFormat>() {
+            Ok(duration) => butler.set_duration_format(duration),
+            Err(e) => {
+                let e = ButlerError::InvalidInput(e.to_string());
+                tracing::error!("{}", e);
+                porcelain::finish(Some(&e), exit_code(&e));
+                process::exit(exit_code(&e));
+            }
+        }
+    }
+
+    let config_source = if args.config != "tb-config.json" {
+        "given by --config".to_string()
+    } else if paths::config_file_override().is_some() {
+        format!("given by {}", paths::K_CONFIG_ENV)
+    } else {
+        "default location".to_string()
+    };
+
+    let result = run_command(
+        args.command,
+        &mut butler,
+        &config_reader,
+        &config_source,
+        &mut store_data,
+        &mut store_config,
+    );
+
+    if let Err(e) = result {
+        tracing::error!("{}", e);
+        porcelain::finish(Some(&e), exit_code(&e));
+        process::exit(exit_code(&e));
+    }
+
+    if store_config {
+        if let Err(e) = config_reader.write_config(butler.configuration()) {
+            tracing::error!("Failed to write configuration file: {}", e);
+            porcelain::finish(Some(&e), K_BUTLER_STORAGE_FAILURE);
+            process::exit(K_BUTLER_STORAGE_FAILURE);
+        }
+    }
+
+    if store_data {
+        if let Err(e) = butler.save() {
+            tracing::error!(
+                "Failed to save butler data, added entry will not be stored properly: {}",
+                e
+            );
+            porcelain::finish(Some(&e), exit_code(&e));
+            process::exit(exit_code(&e));
+        }
+    }
+
+    porcelain::finish(None, 0);
 }
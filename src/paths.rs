@@ -0,0 +1,93 @@
+/*
+ * File: paths.rs
+ * Description: Resolution of the default file paths, with the environment variable and XDG base directory overrides.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use std::path::Path;
+
+use crate::config_reader::K_CONFIG_FILE_NAMES;
+
+/// Environment variable with the configuration file path
+pub const K_CONFIG_ENV: &str = "TB_CONFIG";
+/// Environment variable with the base directory for all time-butler data
+pub const K_STORAGE_DIR_ENV: &str = "TB_STORAGE_DIR";
+
+/// Name of the time-butler directory in the XDG base directories
+const K_APP_DIR_NAME: &str = "time-butler";
+/// Base directory relative to the home directory, used when no override is set
+const K_HOME_BASE_PATH: &str = ".local/time-butler";
+/// Internal storage directory in the base directory
+const K_APP_STORAGE_DIR: &str = ".app_storage";
+/// Report directory in the base directory
+const K_REPORT_DIR: &str = "generated-reports";
+
+/// Internal function to get a path from an environment variable, None if not set or empty
+fn env_path(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.trim().is_empty())
+}
+
+/// Internal function to get the time-butler directory in the home directory, ~/.local/time-butler
+fn home_base_directory() -> Option<String> {
+    dirs::home_dir().map(|home| format!("{}/{}", home.display(), K_HOME_BASE_PATH))
+}
+
+/// Base directory for all time-butler data. In order: TB_STORAGE_DIR, ~/.local/time-butler if already existing,
+/// $XDG_DATA_HOME/time-butler and ~/.local/time-butler. An existing home directory is preferred over XDG_DATA_HOME,
+/// so setting the variable doesn't hide already stored data
+pub fn data_directory() -> Option<String> {
+    if let Some(dir) = env_path(K_STORAGE_DIR_ENV) {
+        return Some(dir);
+    }
+
+    let home_base = home_base_directory();
+    if home_base.as_deref().is_some_and(|d| Path::new(d).exists()) {
+        return home_base;
+    }
+
+    env_path("XDG_DATA_HOME")
+        .map(|dir| format!("{}/{}", dir, K_APP_DIR_NAME))
+        .or(home_base)
+}
+
+/// Internal storage directory, for the data files and metadata
+pub fn app_storage_directory() -> Option<String> {
+    data_directory().map(|dir| format!("{}/{}", dir, K_APP_STORAGE_DIR))
+}
+
+/// Default directory of the generated reports
+pub fn report_directory() -> Option<String> {
+    data_directory().map(|dir| format!("{}/{}", dir, K_REPORT_DIR))
+}
+
+/// Configuration file given by TB_CONFIG
+pub fn config_file_override() -> Option<String> {
+    env_path(K_CONFIG_ENV)
+}
+
+/// Default configuration file. The first existing file in $XDG_CONFIG_HOME/time-butler and the internal storage
+/// directory is used. If none exists, a new file is placed in $XDG_CONFIG_HOME/time-butler when set, otherwise in
+/// the internal storage directory
+pub fn default_config_file() -> Option<String> {
+    let xdg_dir = env_path("XDG_CONFIG_HOME").map(|dir| format!("{}/{}", dir, K_APP_DIR_NAME));
+    let directories: Vec<String> = xdg_dir
+        .iter()
+        .cloned()
+        .chain(app_storage_directory())
+        .collect();
+
+    let existing = directories.iter().find_map(|dir| {
+        K_CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| format!("{}/{}", dir, name))
+            .find(|path| Path::new(path).exists())
+    });
+
+    existing.or_else(|| {
+        directories
+            .first()
+            .map(|dir| format!("{}/{}", dir, K_CONFIG_FILE_NAMES[0]))
+    })
+}
@@ -0,0 +1,430 @@
+/*
+ * File: period.rs
+ * Description: Parsing of the week/month periods given by the user. Supports numbers, month names and relative aliases.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use chrono::{Datelike, Duration, NaiveDate};
+use std::fmt;
+
+use crate::week_scheme;
+
+const K_MONTH_NAMES: [&str; 12] = [
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// Enum to represent a resolved period
+#[derive(Debug, Clone, PartialEq)]
+pub enum Period {
+    /// Week number and year
+    Week { number: u32, year: u32 },
+    /// Month number and year
+    Month { number: u32, year: u32 },
+}
+
+impl Period {
+    /// Check if the date is in the period, weeks use the configured week scheme
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        match self {
+            Period::Week { number, year } => {
+                week_scheme::week_scheme().week_of(date) == (*number, *year as i32)
+            }
+            Period::Month { number, year } => {
+                date.month() == *number && date.year() == *year as i32
+            }
+        }
+    }
+}
+
+/// Implement Display trait for Period
+impl fmt::Display for Period {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Period::Week { number, year } => write!(f, "Week {} {}", number, year),
+            Period::Month { number, year } => write!(f, "Month {} {}", number, year),
+        }
+    }
+}
+
+/// Filter of the listed weeks, by year and by a range of week numbers. An empty filter matches all weeks
+#[derive(Debug, Clone, Default)]
+pub struct WeekFilter {
+    /// Only weeks in this year
+    pub year: Option<u32>,
+    /// First week number, inclusive
+    pub from_week: Option<u32>,
+    /// Last week number, inclusive
+    pub to_week: Option<u32>,
+}
+
+impl WeekFilter {
+    /// Check if no filter is set
+    pub fn is_empty(&self) -> bool {
+        self.year.is_none() && self.from_week.is_none() && self.to_week.is_none()
+    }
+
+    /// Check if the week matches the filter
+    pub fn matches(&self, number: u32, year: i32) -> bool {
+        self.year.is_none_or(|y| y as i32 == year)
+            && self.from_week.is_none_or(|from| number >= from)
+            && self.to_week.is_none_or(|to| number <= to)
+    }
+}
+
+/// Implement Display trait for WeekFilter
+impl fmt::Display for WeekFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut parts = Vec::new();
+        if let Some(year) = self.year {
+            parts.push(format!("year {}", year));
+        }
+        match (self.from_week, self.to_week) {
+            (Some(from), Some(to)) => parts.push(format!("weeks {}-{}", from, to)),
+            (Some(from), None) => parts.push(format!("from week {}", from)),
+            (None, Some(to)) => parts.push(format!("to week {}", to)),
+            (None, None) => {}
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// Parse error for periods
+#[derive(Debug)]
+pub struct ParsePeriodError(String);
+
+/// Implement Display trait for ParsePeriodError
+impl fmt::Display for ParsePeriodError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid period '{}'", self.0)
+    }
+}
+
+/// Enum for the relative aliases, relative to today
+enum Relative {
+    Current,
+    Last,
+    Next,
+}
+
+/// Internal function to parse a relative alias
+fn parse_relative(input: &str) -> Option<Relative> {
+    match input {
+        "current" | "this" => Some(Relative::Current),
+        "last" | "previous" => Some(Relative::Last),
+        "next" => Some(Relative::Next),
+        _ => None,
+    }
+}
+
+/// Internal function to resolve a relative week, in the configured week scheme
+fn relative_week(relative: Relative, today: NaiveDate) -> Period {
+    let date = match relative {
+        Relative::Current => today,
+        Relative::Last => today - Duration::days(7),
+        Relative::Next => today + Duration::days(7),
+    };
+
+    let (number, year) = week_scheme::week_scheme().week_of(date);
+    Period::Week {
+        number,
+        year: year as u32,
+    }
+}
+
+/// Internal function to resolve a relative month
+fn relative_month(relative: Relative, today: NaiveDate) -> Period {
+    let (number, year) = match relative {
+        Relative::Current => (today.month(), today.year()),
+        Relative::Last if today.month() == 1 => (12, today.year() - 1),
+        Relative::Last => (today.month() - 1, today.year()),
+        Relative::Next if today.month() == 12 => (1, today.year() + 1),
+        Relative::Next => (today.month() + 1, today.year()),
+    };
+
+    Period::Month {
+        number,
+        year: year as u32,
+    }
+}
+
+/// Parse a week, a number or a relative alias ("current", "last", "next")
+pub fn parse_week(input: &str, year: u32, today: NaiveDate) -> Result<Period, ParsePeriodError> {
+    let normalized = input.trim().to_lowercase();
+    if let Some(relative) = parse_relative(&normalized) {
+        return Ok(relative_week(relative, today));
+    }
+
+    match normalized.parse::<u32>() {
+        Ok(number) => Ok(Period::Week { number, year }),
+        Err(_) => Err(ParsePeriodError(input.to_string())),
+    }
+}
+
+/// Parse a month, a number, a month name (full or 3 letters) or a relative alias ("current", "last", "next")
+pub fn parse_month(input: &str, year: u32, today: NaiveDate) -> Result<Period, ParsePeriodError> {
+    let normalized = input.trim().to_lowercase();
+    if let Some(relative) = parse_relative(&normalized) {
+        return Ok(relative_month(relative, today));
+    }
+
+    if let Ok(number) = normalized.parse::<u32>() {
+        return Ok(Period::Month { number, year });
+    }
+
+    for (i, name) in K_MONTH_NAMES.iter().enumerate() {
+        if normalized == *name || (normalized.len() == 3 && name.starts_with(&normalized)) {
+            return Ok(Period::Month {
+                number: i as u32 + 1,
+                year,
+            });
+        }
+    }
+
+    Err(ParsePeriodError(input.to_string()))
+}
+
+/// Parse a period alias: "this-week", "last-week", "next-week", "this-month", "last-month" or "next-month"
+pub fn parse_period(input: &str, today: NaiveDate) -> Result<Period, ParsePeriodError> {
+    let normalized = input.trim().to_lowercase();
+    let (relative, kind) = match normalized.split_once('-') {
+        Some((relative, kind)) => (relative, kind),
+        None => return Err(ParsePeriodError(input.to_string())),
+    };
+
+    let relative = match parse_relative(relative) {
+        Some(relative) => relative,
+        None => return Err(ParsePeriodError(input.to_string())),
+    };
+
+    match kind {
+        "week" => Ok(relative_week(relative, today)),
+        "month" => Ok(relative_month(relative, today)),
+        _ => Err(ParsePeriodError(input.to_string())),
+    }
+}
+
+/// Parse a period given as "week:<week>[:<year>]" or "month:<month>[:<year>]", e.g. "month:2:2025" or "week:last", or as
+/// a period alias, e.g. "last-month". The year defaults to the current year
+pub fn parse_period_spec(input: &str, today: NaiveDate) -> Result<Period, ParsePeriodError> {
+    let parts: Vec<&str> = input.trim().split(':').collect();
+    let year = match parts.get(2) {
+        Some(year) => year
+            .parse::<u32>()
+            .map_err(|_| ParsePeriodError(input.to_string()))?,
+        None => today.year() as u32,
+    };
+
+    let period = match parts.as_slice() {
+        [alias] => parse_period(alias, today)?,
+        [kind, number] | [kind, number, _] => match kind.to_lowercase().as_str() {
+            "week" => parse_week(number, year, today)?,
+            "month" => parse_month(number, year, today)?,
+            _ => return Err(ParsePeriodError(input.to_string())),
+        },
+        _ => return Err(ParsePeriodError(input.to_string())),
+    };
+
+    let valid = match period {
+        Period::Week { number, year } => week_scheme::week_scheme()
+            .first_day(number, year as i32)
+            .is_some(),
+        Period::Month { number, .. } => (1..=12).contains(&number),
+    };
+    if !valid {
+        return Err(ParsePeriodError(input.to_string()));
+    }
+
+    Ok(period)
+}
+
+/// Resolve the week to use from the week argument or the period alias, relative to today. The year defaults to the
+/// year of today. Returns week number and year
+pub fn resolve_week(
+    week: &str,
+    period: Option<&str>,
+    year: Option<u32>,
+    today: NaiveDate,
+) -> Result<(u32, u32), ParsePeriodError> {
+    let resolved = match period {
+        Some(p) => parse_period(p, today)?,
+        None => parse_week(week, year.unwrap_or(today.year() as u32), today)?,
+    };
+
+    match resolved {
+        Period::Week { number, year } => Ok((number, year)),
+        Period::Month { .. } => Err(ParsePeriodError(period.unwrap_or(week).to_string())),
+    }
+}
+
+/// Resolve the month to use from the month argument or the period alias, relative to today. The year defaults to
+/// the year of today. Returns month number and year
+pub fn resolve_month(
+    month: &str,
+    period: Option<&str>,
+    year: Option<u32>,
+    today: NaiveDate,
+) -> Result<(u32, u32), ParsePeriodError> {
+    let resolved = match period {
+        Some(p) => parse_period(p, today)?,
+        None => parse_month(month, year.unwrap_or(today.year() as u32), today)?,
+    };
+
+    match resolved {
+        Period::Month { number, year } => Ok((number, year)),
+        Period::Week { .. } => Err(ParsePeriodError(period.unwrap_or(month).to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Internal function to get a date of the tests
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn relative_weeks_resolve_from_today() {
+        // Wednesday of ISO week 1 of 2025, the last week is in 2024
+        let today = date(2025, 1, 1);
+        assert_eq!(
+            resolve_week("current", None, None, today).unwrap(),
+            (1, 2025)
+        );
+        assert_eq!(
+            resolve_week("current", Some("last-week"), None, today).unwrap(),
+            (52, 2024)
+        );
+        assert_eq!(
+            resolve_week("current", Some("next-week"), None, today).unwrap(),
+            (2, 2025)
+        );
+        assert_eq!(resolve_week("12", None, None, today).unwrap(), (12, 2025));
+        assert_eq!(
+            resolve_week("12", None, Some(2023), today).unwrap(),
+            (12, 2023)
+        );
+    }
+
+    #[test]
+    fn relative_months_resolve_from_today() {
+        let today = date(2025, 1, 15);
+        assert_eq!(
+            resolve_month("current", None, None, today).unwrap(),
+            (1, 2025)
+        );
+        assert_eq!(
+            resolve_month("current", Some("last-month"), None, today).unwrap(),
+            (12, 2024)
+        );
+        assert_eq!(
+            resolve_month("current", Some("next-month"), None, date(2025, 12, 31)).unwrap(),
+            (1, 2026)
+        );
+        assert_eq!(
+            resolve_month("last", None, None, date(2025, 3, 31)).unwrap(),
+            (2, 2025)
+        );
+        assert!(resolve_month("current", Some("last-week"), None, today).is_err());
+    }
+
+    #[test]
+    fn period_specs_parse_relative_to_today() {
+        // Monday of ISO week 11 of 2025
+        let today = date(2025, 3, 10);
+        let month = |number, year| Period::Month { number, year };
+        let week = |number, year| Period::Week { number, year };
+        let cases = [
+            ("month:2", month(2, 2025)),
+            ("month:2:2024", month(2, 2024)),
+            ("month:february", month(2, 2025)),
+            ("month:Feb", month(2, 2025)),
+            ("MONTH:December:2023", month(12, 2023)),
+            ("month:last", month(2, 2025)),
+            ("month:next", month(4, 2025)),
+            ("last-month", month(2, 2025)),
+            ("this-month", month(3, 2025)),
+            ("next-month", month(4, 2025)),
+            ("week:12", week(12, 2025)),
+            ("week:52:2024", week(52, 2024)),
+            ("week:current", week(11, 2025)),
+            ("week:previous", week(10, 2025)),
+            ("last-week", week(10, 2025)),
+            ("this-week", week(11, 2025)),
+            ("next-week", week(12, 2025)),
+            ("  week:1  ", week(1, 2025)),
+        ];
+
+        for (spec, expected) in cases {
+            assert_eq!(
+                parse_period_spec(spec, today).unwrap(),
+                expected,
+                "{}",
+                spec
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_period_specs_are_rejected() {
+        let today = date(2025, 3, 10);
+        let cases = [
+            "",
+            "month",
+            "month:13",
+            "month:0",
+            "month:ja",
+            "month:janu",
+            "month:2:twenty",
+            "week:0",
+            "week:54",
+            "week:53:2025",
+            "week:-1",
+            "day:1",
+            "last",
+            "last-year",
+            "yesterday-week",
+            "month:2:2025:1",
+        ];
+
+        for spec in cases {
+            assert!(parse_period_spec(spec, today).is_err(), "{}", spec);
+        }
+    }
+
+    #[test]
+    fn relative_specs_cross_the_year() {
+        let month = |number, year| Period::Month { number, year };
+        assert_eq!(
+            parse_period_spec("last-month", date(2025, 1, 3)).unwrap(),
+            month(12, 2024)
+        );
+        assert_eq!(
+            parse_period_spec("next-month", date(2024, 12, 31)).unwrap(),
+            month(1, 2025)
+        );
+        assert_eq!(
+            parse_period_spec("last-week", date(2025, 1, 3)).unwrap(),
+            Period::Week {
+                number: 52,
+                year: 2024
+            }
+        );
+        // ISO week 53 exists in 2020 only
+        assert!(parse_period_spec("week:53:2020", date(2025, 1, 3)).is_ok());
+    }
+}
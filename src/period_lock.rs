@@ -0,0 +1,80 @@
+/*
+ * File: period_lock.rs
+ * Description: Locked weeks and months, e.g. after a timesheet was submitted. The days and entries in a locked period
+ *              can't be added, modified or removed unless the lock is overridden.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::week_scheme;
+
+/// Enum to represent a locked period
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(tag = "period", rename_all = "kebab-case")]
+pub enum LockedPeriod {
+    Week { number: u32, year: i32 },
+    Month { number: u32, year: i32 },
+}
+
+/// Implement Display trait for LockedPeriod
+impl fmt::Display for LockedPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LockedPeriod::Week { number, year } => write!(f, "week {} {}", number, year),
+            LockedPeriod::Month { number, year } => write!(f, "month {} {}", number, year),
+        }
+    }
+}
+
+impl LockedPeriod {
+    /// Check if the date is in the period, weeks use the configured week scheme
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        match self {
+            LockedPeriod::Week { number, year } => {
+                week_scheme::week_scheme().week_of(date) == (*number, *year)
+            }
+            LockedPeriod::Month { number, year } => date.month() == *number && date.year() == *year,
+        }
+    }
+}
+
+/// Metadata of the time periods, stored next to the project and week data
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PeriodMetadata {
+    /// Locked periods, in the order they were locked
+    locks: Vec<LockedPeriod>,
+}
+
+impl PeriodMetadata {
+    /// Getter for `locks`
+    pub fn locks(&self) -> &Vec<LockedPeriod> {
+        &self.locks
+    }
+
+    /// Lock the period, returns false if already locked
+    pub fn lock(&mut self, period: LockedPeriod) -> bool {
+        if self.locks.contains(&period) {
+            return false;
+        }
+        self.locks.push(period);
+        true
+    }
+
+    /// Unlock the period, returns false if the period wasn't locked
+    pub fn unlock(&mut self, period: &LockedPeriod) -> bool {
+        let count = self.locks.len();
+        self.locks.retain(|l| l != period);
+        count != self.locks.len()
+    }
+
+    /// Get the first locked period containing the date, if any
+    pub fn lock_for(&self, date: NaiveDate) -> Option<&LockedPeriod> {
+        self.locks.iter().find(|l| l.contains(date))
+    }
+}
@@ -0,0 +1,103 @@
+/*
+ * File: porcelain.rs
+ * Description: Porcelain output for scripting. The primary result of a command is collected and printed as a single
+ *              JSON document on stdout when the command is done, the human readable output and the logs go to stderr.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::fmt;
+use std::sync::RwLock;
+
+/// Version of the porcelain document, increased when a field is renamed, removed or changes type
+pub const K_PORCELAIN_VERSION: u32 = 1;
+
+/// Collected result of the command, None when porcelain output is disabled
+static K_PORCELAIN_RESULT: RwLock<Option<Map<String, Value>>> = RwLock::new(None);
+
+/// Print a line of human readable output, to stderr in porcelain mode so stdout only has the JSON document
+#[macro_export]
+macro_rules! outln {
+    ($($arg:tt)*) => {
+        if $crate::porcelain::enabled() {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+/// Print human readable output without a newline, e.g. a prompt, to stderr in porcelain mode
+#[macro_export]
+macro_rules! out {
+    ($($arg:tt)*) => {
+        if $crate::porcelain::enabled() {
+            eprint!($($arg)*)
+        } else {
+            print!($($arg)*)
+        }
+    };
+}
+
+/// Enable the porcelain output
+pub fn enable() {
+    if let Ok(mut result) = K_PORCELAIN_RESULT.write() {
+        *result = Some(Map::new());
+    }
+}
+
+/// Check if the porcelain output is enabled
+pub fn enabled() -> bool {
+    K_PORCELAIN_RESULT
+        .read()
+        .map(|r| r.is_some())
+        .unwrap_or(false)
+}
+
+/// Set a field of the command result, nothing is done when the porcelain output is disabled
+pub fn set(key: &str, value: impl Serialize) {
+    if let Ok(mut guard) = K_PORCELAIN_RESULT.write() {
+        if let Some(result) = guard.as_mut() {
+            match serde_json::to_value(value) {
+                Ok(value) => {
+                    result.insert(key.to_string(), value);
+                }
+                Err(e) => tracing::error!("Failed to add '{}' to the porcelain result: {}", key, e),
+            }
+        }
+    }
+}
+
+/// Take the fields set so far and start over with an empty result, e.g. to collect the result of each command in a
+/// batch. None when the porcelain output is disabled
+pub fn take() -> Option<Map<String, Value>> {
+    K_PORCELAIN_RESULT
+        .write()
+        .ok()
+        .and_then(|mut guard| guard.as_mut().map(std::mem::take))
+}
+
+/// Print the document of the command on stdout, if the porcelain output is enabled. The result is null when the
+/// command failed or had no result
+pub fn finish(error: Option<&dyn fmt::Display>, exit_code: i32) {
+    let result = match K_PORCELAIN_RESULT.write().map(|mut r| r.take()) {
+        Ok(Some(result)) => result,
+        _ => return,
+    };
+
+    let document = json!({
+        "porcelain_version": K_PORCELAIN_VERSION,
+        "ok": error.is_none(),
+        "exit_code": exit_code,
+        "error": error.map(|e| e.to_string()),
+        "result": if error.is_none() && !result.is_empty() {
+            Value::Object(result)
+        } else {
+            Value::Null
+        },
+    });
+    println!("{}", document);
+}
@@ -6,11 +6,66 @@
  * License: MIT
  */
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
 use uuid::Uuid;
 
-use crate::entry::Entry;
+use crate::entry::{Entry, EntryV1};
+
+/// Separator between the levels in a project path, e.g. "client/backend"
+pub const K_PROJECT_PATH_SEPARATOR: char = '/';
+
+/// What is done when an added project has the name of an existing project
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DuplicateProjectAction {
+    /// Nothing is added, the addition fails
+    #[default]
+    Abort,
+    /// The existing project is listed, nothing is added
+    Open,
+    /// The entry is added to the existing project instead
+    AddEntry,
+    /// The project is created with a free suffixed name, e.g. "acme-2"
+    Suffix,
+    /// The user is asked for the action
+    Interactive,
+}
+
+/// Parse error for DuplicateProjectAction
+#[derive(Debug)]
+pub struct ParseDuplicateProjectActionError(String);
+
+/// Implement Display trait for ParseDuplicateProjectActionError
+impl fmt::Display for ParseDuplicateProjectActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid duplicate project action: {}, valid actions are: abort, open, add-entry, suffix, interactive",
+            self.0
+        )
+    }
+}
+
+/// Implement Error trait for ParseDuplicateProjectActionError
+impl Error for ParseDuplicateProjectActionError {}
+
+impl FromStr for DuplicateProjectAction {
+    type Err = ParseDuplicateProjectActionError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.to_lowercase().as_str() {
+            "abort" => Ok(DuplicateProjectAction::Abort),
+            "open" => Ok(DuplicateProjectAction::Open),
+            "add-entry" => Ok(DuplicateProjectAction::AddEntry),
+            "suffix" => Ok(DuplicateProjectAction::Suffix),
+            "interactive" => Ok(DuplicateProjectAction::Interactive),
+            _ => Err(ParseDuplicateProjectActionError(input.to_string())),
+        }
+    }
+}
 
 /// Represents a project with a name and description
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -23,6 +78,48 @@ pub struct Project {
     entries: Vec<Entry>,
     /// Project ID
     id: uuid::Uuid,
+    /// Short aliases, accepted wherever the project name is
+    aliases: Vec<String>,
+    /// Archived projects are hidden by default and no new entries can be added
+    archived: bool,
+    /// Budgeted hours of the project
+    budget_hours: Option<f32>,
+    /// Date the budgeted hours should be used by
+    deadline: Option<NaiveDate>,
+    /// Name of the client the work is done for
+    client: Option<String>,
+    /// Color of the project name in the tables and HTML reports, a color name or "#rrggbb"
+    color: Option<String>,
+    /// Reference in another system, e.g. a Jira key or a PO number
+    external_reference: Option<String>,
+}
+
+/// Layout of a project in storage file version 1
+#[derive(Deserialize, Debug)]
+pub struct ProjectV1 {
+    name: String,
+    description: Option<String>,
+    entries: Vec<EntryV1>,
+    id: uuid::Uuid,
+}
+
+/// Convert a project of storage file version 1, the added values are unset
+impl From<ProjectV1> for Project {
+    fn from(project: ProjectV1) -> Self {
+        Self {
+            name: project.name,
+            description: project.description,
+            entries: project.entries.into_iter().map(Entry::from).collect(),
+            id: project.id,
+            aliases: Vec::new(),
+            archived: false,
+            budget_hours: None,
+            deadline: None,
+            client: None,
+            color: None,
+            external_reference: None,
+        }
+    }
 }
 
 /// Implementation for Project functionality
@@ -34,6 +131,13 @@ impl Project {
             description,
             entries: Vec::new(),
             id: Uuid::new_v4(),
+            aliases: Vec::new(),
+            archived: false,
+            budget_hours: None,
+            deadline: None,
+            client: None,
+            color: None,
+            external_reference: None,
         }
     }
 
@@ -52,6 +156,11 @@ impl Project {
         &self.entries
     }
 
+    /// Mutable getter for `entries`
+    pub fn entries_mut(&mut self) -> &mut Vec<Entry> {
+        &mut self.entries
+    }
+
     /// Add a new entry to the project
     pub fn add_entry(&mut self, entry: Entry) {
         self.entries.push(entry);
@@ -92,6 +201,112 @@ impl Project {
         None
     }
 
+    /// Replace an existing entry with an updated version, matched by the entry ID
+    pub fn update_entry(&mut self, entry: Entry) -> bool {
+        match self.entries.iter_mut().find(|e| e.id() == entry.id()) {
+            Some(e) => {
+                *e = entry;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Getter for `aliases`
+    pub fn aliases(&self) -> &Vec<String> {
+        &self.aliases
+    }
+
+    /// Add an alias to the project, returns false if the alias already exists
+    pub fn add_alias(&mut self, alias: String) -> bool {
+        if self.aliases.contains(&alias) {
+            false
+        } else {
+            self.aliases.push(alias);
+            true
+        }
+    }
+
+    /// Check if the project is named by the given name, either the full name or an alias
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name == name || self.aliases.iter().any(|a| a == name)
+    }
+
+    /// Getter for `archived`
+    pub fn archived(&self) -> bool {
+        self.archived
+    }
+
+    /// Set the archived state of the project
+    pub fn set_archived(&mut self, archived: bool) {
+        self.archived = archived;
+    }
+
+    /// Getter for `budget_hours`
+    pub fn budget_hours(&self) -> Option<f32> {
+        self.budget_hours
+    }
+
+    /// Getter for `deadline`
+    pub fn deadline(&self) -> Option<NaiveDate> {
+        self.deadline
+    }
+
+    /// Set the budgeted hours and the deadline, None removes them
+    pub fn set_budget(&mut self, budget_hours: Option<f32>, deadline: Option<NaiveDate>) {
+        self.budget_hours = budget_hours;
+        self.deadline = deadline;
+    }
+
+    /// Getter for `client`
+    pub fn client(&self) -> Option<&str> {
+        self.client.as_deref()
+    }
+
+    /// Getter for `color`
+    pub fn color(&self) -> Option<&str> {
+        self.color.as_deref()
+    }
+
+    /// Getter for `external_reference`
+    pub fn external_reference(&self) -> Option<&str> {
+        self.external_reference.as_deref()
+    }
+
+    /// Set the client, None removes it
+    pub fn set_client(&mut self, client: Option<String>) {
+        self.client = client;
+    }
+
+    /// Set the color, None removes it
+    pub fn set_color(&mut self, color: Option<String>) {
+        self.color = color;
+    }
+
+    /// Set the external reference, None removes it
+    pub fn set_external_reference(&mut self, external_reference: Option<String>) {
+        self.external_reference = external_reference;
+    }
+
+    /// Get the total hours of the project entries, not including sub-projects
+    pub fn total_hours(&self) -> f32 {
+        self.entries.iter().map(|e| e.hours()).sum()
+    }
+
+    /// Get the name of the parent project, None for a top level project
+    pub fn parent_name(&self) -> Option<&str> {
+        self.name
+            .rsplit_once(K_PROJECT_PATH_SEPARATOR)
+            .map(|(parent, _)| parent)
+    }
+
+    /// Check if the project is a sub-project (at any depth) of the given project path
+    pub fn is_descendant_of(&self, ancestor: &str) -> bool {
+        self.name.len() > ancestor.len()
+            && self.name.starts_with(ancestor)
+            && self.name[ancestor.len()..].starts_with(K_PROJECT_PATH_SEPARATOR)
+    }
+
     /// Update the project name
     pub fn update_name(&mut self, new_name: String) {
         self.name = new_name;
@@ -108,11 +323,12 @@ impl fmt::Display for Project {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "Name: {}\nDescription: - {:?}\n Entries: {}\n, ID: {}\n",
+            "Name: {}\nDescription: - {:?}\n Entries: {}\n, ID: {}\n, Aliases: {}\n",
             self.name,
             self.description(),
             self.entries.len(),
-            self.id
+            self.id,
+            self.aliases.join(", ")
         )
     }
 }
@@ -0,0 +1,196 @@
+/*
+ * File: publisher.rs
+ * Description: Publishing of generated reports to a WebDAV or S3 endpoint with curl, optionally encrypted with age
+ *              before the upload.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use std::error::Error;
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+
+use crate::config::PublishConfig;
+use crate::mailer::quote_config_value;
+
+/// Environment variable replacing the publish password in the configuration
+const K_PUBLISH_PASSWORD_ENV: &str = "TIME_BUTLER_PUBLISH_PASSWORD";
+
+/// Extension added to the encrypted reports
+const K_AGE_EXTENSION: &str = "age";
+
+/// Error for publishing a report
+#[derive(Debug)]
+pub struct PublishError(String);
+
+/// Implement Display trait for PublishError
+impl fmt::Display for PublishError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to publish the report: {}", self.0)
+    }
+}
+
+/// Implement Error trait for PublishError
+impl Error for PublishError {}
+
+/// Kind of endpoint the reports are published to
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum PublishTarget {
+    /// A WebDAV share, e.g. Nextcloud
+    #[default]
+    WebDav,
+    /// An S3 bucket, signed with the access key and secret
+    S3,
+}
+
+/// Error for parsing the publish target
+#[derive(Debug)]
+pub struct ParsePublishTargetError(String);
+
+impl fmt::Display for ParsePublishTargetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid publish target '{}', valid values are: webdav, s3",
+            self.0
+        )
+    }
+}
+
+impl FromStr for PublishTarget {
+    type Err = ParsePublishTargetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "webdav" => Ok(PublishTarget::WebDav),
+            "s3" => Ok(PublishTarget::S3),
+            _ => Err(ParsePublishTargetError(s.to_string())),
+        }
+    }
+}
+
+/// Internal function to encrypt the report to the age recipient, returns the path of the encrypted file
+fn encrypt(file_path: &str, recipient: &str) -> Result<String, PublishError> {
+    let encrypted = format!("{}.{}", file_path, K_AGE_EXTENSION);
+    let output = Command::new("age")
+        .arg("--encrypt")
+        .arg("--recipient")
+        .arg(recipient)
+        .arg("--output")
+        .arg(&encrypted)
+        .arg(file_path)
+        .output()
+        .map_err(|e| PublishError(format!("could not run age: {}", e)))?;
+    if !output.status.success() {
+        return Err(PublishError(format!(
+            "age failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(encrypted)
+}
+
+/// Internal function to upload the file to the url with curl, the credentials are given on stdin
+fn upload(
+    config: &PublishConfig,
+    target: PublishTarget,
+    file_path: &str,
+    url: &str,
+) -> Result<(), PublishError> {
+    let password =
+        std::env::var(K_PUBLISH_PASSWORD_ENV).unwrap_or_else(|_| config.password.clone());
+
+    let mut command = Command::new("curl");
+    command
+        .arg("--silent")
+        .arg("--show-error")
+        .arg("--fail")
+        .arg("--upload-file")
+        .arg(file_path)
+        .arg("--url")
+        .arg(url);
+    if target == PublishTarget::S3 {
+        if config.region.is_empty() {
+            return Err(PublishError(
+                "no region, set region in the publish section of the configuration".to_string(),
+            ));
+        }
+        command
+            .arg("--aws-sigv4")
+            .arg(format!("aws:amz:{}:s3", config.region));
+    }
+
+    let result = command
+        // The credentials are given on stdin, to keep them out of the process list
+        .arg("--config")
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            if let Some(mut stdin) = child.stdin.take() {
+                if !config.username.is_empty() {
+                    writeln!(
+                        stdin,
+                        "user = \"{}:{}\"",
+                        quote_config_value(&config.username),
+                        quote_config_value(&password)
+                    )?;
+                }
+            }
+            child.wait_with_output()
+        });
+
+    match result {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(output) => Err(PublishError(format!(
+            "the upload to {} failed: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))),
+        Err(e) => Err(PublishError(format!("could not run curl: {}", e))),
+    }
+}
+
+/// Publish the report file to the configured endpoint, encrypted first when an age recipient is set. The file is
+/// uploaded with its file name to the url. Returns the url of the published file
+pub fn publish(config: &PublishConfig, file_path: &str) -> Result<String, PublishError> {
+    if config.url.is_empty() {
+        return Err(PublishError(
+            "publishing is not configured, set url in the publish section of the configuration"
+                .to_string(),
+        ));
+    }
+    let target = config
+        .target
+        .parse::<PublishTarget>()
+        .map_err(|e| PublishError(e.to_string()))?;
+
+    let encrypted = if config.age_recipient.is_empty() {
+        None
+    } else {
+        Some(encrypt(file_path, &config.age_recipient)?)
+    };
+    let upload_path = encrypted.as_deref().unwrap_or(file_path);
+
+    let file_name = Path::new(upload_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| PublishError(format!("invalid report file: {}", file_path)))?;
+    let url = format!("{}/{}", config.url.trim_end_matches('/'), file_name);
+    tracing::debug!("Publishing {} to {}", upload_path, url);
+    let result = upload(config, target, upload_path, &url);
+
+    // Only the plain report is kept locally
+    if let Some(encrypted) = &encrypted {
+        if let Err(e) = std::fs::remove_file(encrypted) {
+            tracing::warn!("Failed to remove the encrypted report {}: {}", encrypted, e);
+        }
+    }
+    result.map(|_| url)
+}
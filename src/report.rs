@@ -6,10 +6,17 @@
  * License: MIT
  */
 
+use chrono::{Datelike, NaiveDate};
+use serde::Serialize;
 use std::error::Error;
 use std::fmt;
 use std::str::FromStr;
 
+use crate::duration::DurationFormat;
+use crate::i18n::{self, tr};
+use crate::target::{MonthlyTargetStatus, WeeklyTargetStatus};
+use crate::week_scheme;
+
 /// Enum to represent the format of the report
 #[derive(Clone, Debug)]
 pub enum ReportFormat {
@@ -19,6 +26,12 @@ pub enum ReportFormat {
     Html,
     Pdf,
     Text,
+    /// Spreadsheet, only supported by the timesheet report
+    Xlsx,
+    /// Rendered through the user provided template file
+    Template(String),
+    /// Rendered by the registered renderer with the name
+    Renderer(String),
 }
 
 /// Parse error for ReportFormat
@@ -44,6 +57,7 @@ impl FromStr for ReportFormat {
             "html" => Ok(ReportFormat::Html),
             "pdf" => Ok(ReportFormat::Pdf),
             "text" => Ok(ReportFormat::Text),
+            "xlsx" => Ok(ReportFormat::Xlsx),
             _ => Err(ParseReportFormatError),
         }
     }
@@ -63,43 +77,601 @@ impl fmt::Display for ReportGenerationFailure {
 /// Implement Error trait for ReportGenerationFailure
 impl Error for ReportGenerationFailure {}
 
-// Todo: Container structs can maybe be done better.
-//       Use a common container struct, and have 1 entry on format "foo:bar:42"
-//       Then split on ":" and return a vector of strings which can be used to
-//       as columns.
+/// Struct to hold the options given when generating a report
+#[derive(Debug, Default)]
+pub struct ReportOptions {
+    /// Only report the aggregates
+    pub summary: bool,
+    /// Comma separated columns to report, None for the default columns
+    pub columns: Option<String>,
+    /// Path to the template file, used by the template format
+    pub template: Option<String>,
+    /// Address the generated report is emailed to
+    pub email: Option<String>,
+    /// Copy the summary of the report to the clipboard, as Markdown
+    pub clipboard: bool,
+}
+
+/// Enum to represent what is done when the report file already exists
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum ReportOverwrite {
+    /// The report is not written
+    #[default]
+    Refuse,
+    /// The existing file is replaced
+    Overwrite,
+    /// A number is added to the file name, e.g. "report_2.csv"
+    UniqueName,
+}
+
+/// Enum to represent a column in a report, selectable by the user
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReportColumn {
+    Week,
+    Date,
+    Start,
+    End,
+    Paused,
+    Hours,
+    Description,
+    Closed,
+    Created,
+    Id,
+}
+
+/// Columns available in the day reports (week, month and range)
+pub const K_DAY_REPORT_COLUMNS: [ReportColumn; 8] = [
+    ReportColumn::Week,
+    ReportColumn::Date,
+    ReportColumn::Start,
+    ReportColumn::End,
+    ReportColumn::Paused,
+    ReportColumn::Hours,
+    ReportColumn::Description,
+    ReportColumn::Closed,
+];
+
+/// Columns available in the project reports
+pub const K_PROJECT_REPORT_COLUMNS: [ReportColumn; 5] = [
+    ReportColumn::Date,
+    ReportColumn::Hours,
+    ReportColumn::Description,
+    ReportColumn::Created,
+    ReportColumn::Id,
+];
+
+impl ReportColumn {
+    /// Get the header used for the column in the day reports, in the configured locale
+    pub fn header(&self) -> &'static str {
+        tr(match self {
+            ReportColumn::Week => "Week",
+            ReportColumn::Date => "Date",
+            ReportColumn::Start => "StartingTime",
+            ReportColumn::End => "EndingTime",
+            ReportColumn::Paused => "Paused-Hours",
+            ReportColumn::Hours => "Hours",
+            ReportColumn::Description => "Description",
+            ReportColumn::Closed => "Closed",
+            ReportColumn::Created => "Created",
+            ReportColumn::Id => "ID",
+        })
+    }
+}
+
+/// Implement Display trait for ReportColumn, same name as used on the command line
+impl fmt::Display for ReportColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ReportColumn::Week => "week",
+            ReportColumn::Date => "date",
+            ReportColumn::Start => "start",
+            ReportColumn::End => "end",
+            ReportColumn::Paused => "paused",
+            ReportColumn::Hours => "hours",
+            ReportColumn::Description => "description",
+            ReportColumn::Closed => "closed",
+            ReportColumn::Created => "created",
+            ReportColumn::Id => "id",
+        };
+        write!(f, "{}", name)
+    }
+}
 
-/// Struct to hold the column definitions in a project report
+/// Parse error for report columns
 #[derive(Debug)]
-pub struct ProjectReportColumns {
-    pub first: String,
-    pub second: String,
-    pub third: String,
-    pub fourth: String,
+pub struct ParseReportColumnError {
+    column: String,
+    valid: String,
+}
+
+/// Implement Display trait for ParseReportColumnError
+impl fmt::Display for ParseReportColumnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid column '{}', valid options are: {}",
+            self.column, self.valid
+        )
+    }
+}
+
+/// Parse a comma separated list of columns, e.g. "date,hours,description", against the columns available in the report
+pub fn parse_report_columns(
+    input: &str,
+    available: &[ReportColumn],
+) -> Result<Vec<ReportColumn>, ParseReportColumnError> {
+    let mut columns = Vec::new();
+
+    for name in input.split(',').map(|n| n.trim().to_lowercase()) {
+        match available.iter().find(|c| c.to_string() == name) {
+            Some(c) if !columns.contains(c) => columns.push(c.clone()),
+            Some(_) => tracing::warn!("Column {} given more than once, ignoring", name),
+            None => {
+                return Err(ParseReportColumnError {
+                    column: name,
+                    valid: available
+                        .iter()
+                        .map(|c| c.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                })
+            }
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Enum to represent the period covered by a day report, a month or an arbitrary date range
+#[derive(Clone, Debug)]
+pub enum ReportPeriod {
+    /// Month number
+    Month(u32),
+    /// First and last date (inclusive) of the range
+    Range(NaiveDate, NaiveDate),
+    /// Year of the dates
+    Year(i32),
+}
+
+impl ReportPeriod {
+    /// Get the period as a label, used as value in the period column
+    pub fn label(&self) -> String {
+        match self {
+            ReportPeriod::Month(number) => number.to_string(),
+            ReportPeriod::Range(from, to) => format!("{}_{}", from, to),
+            ReportPeriod::Year(year) => year.to_string(),
+        }
+    }
+
+    /// Get the prefix of the report file name
+    pub fn file_prefix(&self) -> String {
+        match self {
+            ReportPeriod::Month(number) => format!("month{}", number),
+            ReportPeriod::Range(from, to) => format!("range_{}_{}", from, to),
+            ReportPeriod::Year(year) => format!("year{}", year),
+        }
+    }
+
+    /// Get the title of the report
+    pub fn title(&self) -> String {
+        match self {
+            ReportPeriod::Month(number) => format!("Monthly Report - Month {}", number),
+            ReportPeriod::Range(from, to) => format!("Range Report - {} to {}", from, to),
+            ReportPeriod::Year(year) => format!("Yearly Report - Year {}", year),
+        }
+    }
+}
+
+/// Max difference in hours between day and project hours to still be considered reconciled
+const K_RECONCILE_TOLERANCE: f32 = 0.01;
+
+/// Struct to hold a row in the overview report, day hours and project hours for a date
+#[derive(Debug, Serialize)]
+pub struct OverviewRow {
+    #[serde(rename = "Date")]
+    pub date: NaiveDate,
+    #[serde(rename = "DayHours")]
+    pub day_hours: f32,
+    #[serde(rename = "ProjectHours")]
+    pub project_hours: f32,
+    #[serde(rename = "Difference")]
+    pub difference: f32,
+    #[serde(rename = "Reconciled")]
+    pub reconciled: bool,
+}
+
+impl OverviewRow {
+    /// Create a new row, the difference is day hours minus project hours
+    pub fn new(date: NaiveDate, day_hours: f32, project_hours: f32) -> Self {
+        let difference = day_hours - project_hours;
+        Self {
+            date,
+            day_hours,
+            project_hours,
+            difference,
+            reconciled: difference.abs() < K_RECONCILE_TOLERANCE,
+        }
+    }
 }
 
-/// Struct to hold the column definitions in a project report
+/// Struct to hold a row in the timesheet report, the hours of a project on each day of the week, Monday first
 #[derive(Debug)]
-pub struct WeekReportColumns {
-    pub first: String,
-    pub second: String,
-    pub third: String,
-    pub fourth: String,
-    pub fifth: String,
-    pub sixth: String,
-    pub seventh: String,
-    pub eighth: String,
+pub struct TimesheetRow {
+    pub project: String,
+    pub hours: [f32; 7],
 }
 
+impl TimesheetRow {
+    /// Total hours of the project in the week
+    pub fn total(&self) -> f32 {
+        self.hours.iter().sum()
+    }
+}
+
+/// Struct to hold a timesheet report, a row per project and a column per day of the week
 #[derive(Debug)]
-pub struct MonthReportColumns {
-    pub first: String,
-    pub second: String,
-    pub third: String,
-    pub fourth: String,
-    pub fifth: String,
-    pub sixth: String,
-    pub seventh: String,
-    pub eighth: String,
+pub struct Timesheet {
+    pub week: u32,
+    pub year: i32,
+    /// Dates of the week, from the first day of the week
+    pub dates: Vec<NaiveDate>,
+    /// Rows sorted by project name
+    pub rows: Vec<TimesheetRow>,
+}
+
+impl Timesheet {
+    /// Create a new empty timesheet, None if the week doesn't exist in the year
+    pub fn new(week: u32, year: i32) -> Option<Self> {
+        let first_day = week_scheme::week_scheme().first_day(week, year)?;
+        Some(Self {
+            week,
+            year,
+            dates: first_day.iter_days().take(7).collect(),
+            rows: Vec::new(),
+        })
+    }
+
+    /// Add hours of a project on a date, dates outside the week are ignored
+    pub fn add(&mut self, project: &str, date: NaiveDate, hours: f32) {
+        let Some(day) = self.dates.iter().position(|d| *d == date) else {
+            return;
+        };
+
+        let index = match self
+            .rows
+            .binary_search_by(|r| r.project.as_str().cmp(project))
+        {
+            Ok(i) => i,
+            Err(i) => {
+                self.rows.insert(
+                    i,
+                    TimesheetRow {
+                        project: project.to_string(),
+                        hours: [0.0; 7],
+                    },
+                );
+                i
+            }
+        };
+        self.rows[index].hours[day] += hours;
+    }
+
+    /// Total hours of each day of the week
+    pub fn day_totals(&self) -> [f32; 7] {
+        let mut totals = [0.0; 7];
+        for row in &self.rows {
+            for (total, hours) in totals.iter_mut().zip(row.hours) {
+                *total += hours;
+            }
+        }
+        totals
+    }
+
+    /// Total hours of the week
+    pub fn total(&self) -> f32 {
+        self.rows.iter().map(|r| r.total()).sum()
+    }
+
+    /// Column headers of the days, e.g. "Mon 2025-03-10"
+    pub fn day_headers(&self) -> Vec<String> {
+        self.dates
+            .iter()
+            .map(|d| format!("{} {}", i18n::weekday_short(d.weekday()), d))
+            .collect()
+    }
+}
+
+/// Struct to hold the totals of a week in a summary report
+#[derive(Debug, Serialize)]
+pub struct WeekTotal {
+    #[serde(rename = "Year")]
+    pub year: i32,
+    #[serde(rename = "Week")]
+    pub week: u32,
+    #[serde(rename = "Hours")]
+    pub hours: f32,
+    #[serde(rename = "Entries")]
+    pub entries: usize,
+}
+
+/// Struct to hold a summary report, only aggregates and no row-level data
+#[derive(Debug, Serialize)]
+pub struct ReportSummary {
+    #[serde(rename = "Report")]
+    pub report: String,
+    #[serde(rename = "TotalHours")]
+    pub total_hours: f32,
+    #[serde(rename = "Entries")]
+    pub entries: usize,
+    #[serde(rename = "TargetHours", skip_serializing_if = "Option::is_none")]
+    pub target_hours: Option<f32>,
+    #[serde(rename = "TargetAttainment", skip_serializing_if = "Option::is_none")]
+    pub target_attainment: Option<u32>,
+    #[serde(rename = "ProjectHours", skip_serializing_if = "Option::is_none")]
+    pub project_hours: Option<f32>,
+    #[serde(rename = "MismatchedDates", skip_serializing_if = "Option::is_none")]
+    pub mismatched_dates: Option<usize>,
+    #[serde(rename = "Weeks")]
+    pub weeks: Vec<WeekTotal>,
+}
+
+impl ReportSummary {
+    /// Create a new empty summary
+    pub fn new(report: String) -> Self {
+        Self {
+            report,
+            total_hours: 0.0,
+            entries: 0,
+            target_hours: None,
+            target_attainment: None,
+            project_hours: None,
+            mismatched_dates: None,
+            weeks: Vec::new(),
+        }
+    }
+
+    /// Add the hours of a day or entry to the totals, weeks are kept sorted
+    pub fn add(&mut self, year: i32, week: u32, hours: f32) {
+        self.total_hours += hours;
+        self.entries += 1;
+
+        match self
+            .weeks
+            .binary_search_by_key(&(year, week), |w| (w.year, w.week))
+        {
+            Ok(i) => {
+                self.weeks[i].hours += hours;
+                self.weeks[i].entries += 1;
+            }
+            Err(i) => self.weeks.insert(
+                i,
+                WeekTotal {
+                    year,
+                    week,
+                    hours,
+                    entries: 1,
+                },
+            ),
+        }
+    }
+
+    /// Set the target and the attainment in percent
+    pub fn set_target(&mut self, target_hours: f32, attainment: u32) {
+        self.target_hours = Some(target_hours);
+        self.target_attainment = Some(attainment);
+    }
+
+    /// Set the reconciliation totals, used by the overview report
+    pub fn set_reconciliation(&mut self, project_hours: f32, mismatched_dates: usize) {
+        self.project_hours = Some(project_hours);
+        self.mismatched_dates = Some(mismatched_dates);
+    }
+
+    /// Get the summary as key/value pairs with the hours in the given format, used by the flat report formats
+    pub fn as_key_values(&self, duration: DurationFormat) -> Vec<(String, String)> {
+        let mut values = vec![
+            ("Report".to_string(), self.report.clone()),
+            ("TotalHours".to_string(), duration.format(self.total_hours)),
+            ("Entries".to_string(), self.entries.to_string()),
+        ];
+
+        if let Some(target) = self.target_hours {
+            values.push(("TargetHours".to_string(), duration.format(target)));
+        }
+        if let Some(attainment) = self.target_attainment {
+            values.push(("TargetAttainment".to_string(), format!("{}%", attainment)));
+        }
+        if let Some(project_hours) = self.project_hours {
+            values.push(("ProjectHours".to_string(), duration.format(project_hours)));
+        }
+        if let Some(mismatched) = self.mismatched_dates {
+            values.push(("MismatchedDates".to_string(), mismatched.to_string()));
+        }
+
+        for w in &self.weeks {
+            values.push((
+                format!("Week {}-{}", w.year, w.week),
+                format!("{} ({} entries)", duration.format(w.hours), w.entries),
+            ));
+        }
+
+        values
+    }
+}
+
+/// Version of the JSON and YAML day report structure, increased when a field is renamed, removed or changes type
+pub const K_REPORT_FORMAT_VERSION: u32 = 2;
+
+/// Struct to hold the metadata of a generated report, so an archived report describes itself
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportMetadata {
+    pub generated_at: String,
+    pub report: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub period: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_reference: Option<String>,
+    pub week_target_hours: f32,
+    pub month_target_hours: f32,
+    pub tool_version: String,
+}
+
+impl ReportMetadata {
+    /// Get the metadata as key/value pairs with the hours in the given format, used by the flat report formats
+    pub fn as_key_values(&self, duration: DurationFormat) -> Vec<(&'static str, String)> {
+        let mut values = vec![
+            (tr("Report"), self.report.clone()),
+            (tr("Generated"), self.generated_at.clone()),
+        ];
+        if let Some(period) = &self.period {
+            values.push((tr("Period"), period.clone()));
+        }
+        if let Some(client) = &self.client {
+            values.push((tr("Client"), client.clone()));
+        }
+        if let Some(external_reference) = &self.external_reference {
+            values.push((tr("External reference"), external_reference.clone()));
+        }
+        values.push((
+            tr("Week target hours"),
+            duration.format(self.week_target_hours),
+        ));
+        values.push((
+            tr("Month target hours"),
+            duration.format(self.month_target_hours),
+        ));
+        values.push((tr("Version"), self.tool_version.clone()));
+        values
+    }
+}
+
+/// Struct to add the metadata to a report without its own document structure, the metadata is put first
+#[derive(Debug, Serialize)]
+pub struct ReportWithMetadata<'a, T: Serialize> {
+    pub metadata: &'a ReportMetadata,
+    #[serde(flatten)]
+    pub report: &'a T,
+}
+
+/// A day in a day report, only the selected columns are set. The keys and the hours as numbers are the same whatever
+/// the locale and the hours format
+#[derive(Debug, Default, Serialize)]
+pub struct DayReportRow {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<NaiveDate>,
+    /// Starting time "HH:MM:SS", "N/A" if not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub starting_time: Option<String>,
+    /// Ending time "HH:MM:SS", "N/A" if not set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ending_time: Option<String>,
+    /// Number of dates the ending time is after the date, for a day crossing midnight
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ending_days_after: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paused_hours: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hours: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub closed: Option<bool>,
+}
+
+/// Struct to hold a week of days in a day report
+#[derive(Debug, Serialize)]
+pub struct WeekReport {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub week: u32,
+    pub year: i32,
+    pub days: Vec<DayReportRow>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+    pub total_hours: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_status: Option<ReportTargetStatus>,
+}
+
+/// Struct to hold the target status of a reported week or month
+#[derive(Debug, Clone, Serialize)]
+pub struct ReportTargetStatus {
+    pub target_hours: f32,
+    pub worked_hours: f32,
+    pub remaining_hours: f32,
+    pub percentage: u32,
+    pub status: String,
+}
+
+impl ReportTargetStatus {
+    /// Create the report status from the target status of a week
+    pub fn from_week(status: &WeeklyTargetStatus) -> Self {
+        Self {
+            target_hours: *status.target_hours(),
+            worked_hours: *status.status_hours(),
+            remaining_hours: *status.remaining_hours(),
+            percentage: *status.percentage(),
+            status: status.target_status_string(),
+        }
+    }
+
+    /// Create the report status from the target status of a month
+    pub fn from_month(status: &MonthlyTargetStatus) -> Self {
+        Self {
+            target_hours: *status.target_hours(),
+            worked_hours: *status.status_hours(),
+            remaining_hours: *status.remaining_hours(),
+            percentage: *status.percentage(),
+            status: status.target_status_string(),
+        }
+    }
+
+    /// Get the status as key/value pairs with the hours in the given format, used by the flat report formats
+    pub fn as_key_values(&self, duration: DurationFormat) -> Vec<(&'static str, String)> {
+        vec![
+            (tr("Target hours"), duration.format(self.target_hours)),
+            (tr("Worked hours"), duration.format(self.worked_hours)),
+            (tr("Remaining hours"), duration.format(self.remaining_hours)),
+            (tr("Target attainment"), format!("{}%", self.percentage)),
+            (tr("Target status"), self.status.clone()),
+        ]
+    }
+}
+
+/// Struct to hold a week report document
+#[derive(Debug, Serialize)]
+pub struct WeekReportDocument {
+    pub format_version: u32,
+    pub metadata: ReportMetadata,
+    #[serde(flatten)]
+    pub week: WeekReport,
+}
+
+/// Enum to represent the period of a month, range or year report document, serialized as its key and value
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DaysReportPeriod {
+    /// Month number
+    Month(u32),
+    /// Label of the range, e.g. "2026-10-01_2026-10-14"
+    Range(String),
+    /// Year of the dates
+    Year(i32),
+}
+
+/// Struct to hold a month, range or year report document, the days grouped by week
+#[derive(Debug, Serialize)]
+pub struct DaysReportDocument {
+    pub format_version: u32,
+    pub metadata: ReportMetadata,
+    #[serde(flatten)]
+    pub period: DaysReportPeriod,
+    pub weeks: Vec<WeekReport>,
+    pub total_hours: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_status: Option<ReportTargetStatus>,
 }
 
 // Currently not used. Created for future implementation of a more detailed report summary.
@@ -6,26 +6,91 @@
  * License: MIT
  */
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::error::Error;
 use std::fs::File;
 use std::io::Write;
 
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, SecondsFormat};
 use csv::Writer;
-use maud::{html, Markup, PreEscaped};
+use maud::{html, Markup, PreEscaped, DOCTYPE};
+use serde::Serialize;
 use serde_json::json;
 use serde_json::Value;
 
-use crate::day::Day;
-use crate::project::Project;
+use crate::badge;
+use crate::config::ProjectReportOutput;
+use crate::day::{Day, RoundingPolicy};
+use crate::display;
+use crate::duration::DurationFormat;
+use crate::entry::Entry;
+use crate::i18n::{self, tr};
+use crate::paths;
+use crate::project::{Project, K_PROJECT_PATH_SEPARATOR};
+use crate::report_renderer::{RendererError, RendererRegistry, ReportRenderer};
+use crate::report_template;
+use crate::stats::K_WORKING_DAYS_PER_WEEK;
+use crate::tables::ListedRows;
+use crate::target::WeeklyTargetStatus;
 use crate::week::Week;
+use crate::week_scheme;
+use crate::xlsx::{self, XlsxCell};
 
 use crate::report::{
-    MonthReportColumns, ParseReportFormatError, ProjectReportColumns, ReportFormat,
-    ReportGenerationFailure, WeekReportColumns,
+    DayReportRow, DaysReportDocument, DaysReportPeriod, OverviewRow, ParseReportFormatError,
+    ReportColumn, ReportFormat, ReportGenerationFailure, ReportMetadata, ReportOverwrite,
+    ReportPeriod, ReportSummary, ReportTargetStatus, ReportWithMetadata, Timesheet, WeekReport,
+    WeekReportDocument, K_DAY_REPORT_COLUMNS, K_REPORT_FORMAT_VERSION,
 };
 
+/// File extensions of template files, removed when deciding the suffix of a template report
+const K_TEMPLATE_EXTENSIONS: [&str; 4] = ["tera", "j2", "jinja", "tmpl"];
+
+/// Size of the charts in the HTML reports
+const K_CHART_WIDTH: f32 = 720.0;
+const K_CHART_HEIGHT: f32 = 240.0;
+const K_CHART_MARGIN: f32 = 40.0;
+const K_CHART_MAX_BAR_WIDTH: f32 = 60.0;
+
+/// Style of the HTML reports, the tables are kept together and the colors removed when printed
+const K_HTML_STYLE: &str = "
+    body { font-family: sans-serif; margin: 2em; color: #222; background-color: #fff; }
+    header dl.metadata { display: grid; grid-template-columns: max-content auto; gap: 0.2em 1em; }
+    header dt { font-weight: bold; }
+    header dd { margin: 0; }
+    table { border-collapse: collapse; width: 100%; margin-bottom: 1em; }
+    th, td { border: 1px solid black; padding: 8px; text-align: left; }
+    th { background-color: #f2f2f2; }
+    tr.mismatch { background-color: #f8d7da; }
+    tfoot td, td.total { font-weight: bold; }
+    footer table.totals { width: auto; }
+    @media print {
+        body { margin: 0; font-size: 10pt; }
+        th, tr.mismatch { background-color: transparent; }
+        h2 { page-break-after: avoid; }
+        tr, svg { page-break-inside: avoid; }
+    }
+";
+
+/// Dark colors of the HTML reports, used when the browser prefers a dark color scheme, never when printed
+const K_HTML_DARK_STYLE: &str = "
+    @media screen and (prefers-color-scheme: dark) {
+        body { color: #ddd; background-color: #1e1e1e; }
+        th, td { border-color: #666; }
+        th { background-color: #333; }
+        tr.mismatch { background-color: #5c2b2f; }
+        svg line[stroke=\"black\"] { stroke: #ddd; }
+        svg text:not([fill]) { fill: #ddd; }
+    }
+";
+
+/// Internal support function to get the first and last date of the month, None for an invalid month
+fn month_dates(month: u32, year: i32) -> Option<(NaiveDate, NaiveDate)> {
+    let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+    let next = first.checked_add_months(chrono::Months::new(1))?;
+    Some((first, next - Duration::days(1)))
+}
+
 //TODO: Improvement - Can the report creating functions be done in smarter way, feels stupid to repeat the same code for each report type
 
 /// Report manager to handle report generation and storage
@@ -34,24 +99,79 @@ pub struct ReportManager {
     report_dir: String,
     /// Default report file name
     default_report_file_name: String,
+    /// Week target hours, drawn as target line in the HTML charts and included in the report metadata
+    week_target_hours: f32,
+    /// Month target hours of the configuration, included in the report metadata
+    month_target_hours: f32,
+    /// Time the reports are generated at, used in the file names and the report metadata
+    generated: DateTime<Local>,
+    /// Rounding of the reported hours
+    rounding: RoundingPolicy,
+    /// Format of the reported hours
+    duration: DurationFormat,
+    /// What is done when the report file already exists
+    overwrite: ReportOverwrite,
+    /// Only resolve the report paths, nothing is written
+    dry_run: bool,
+    /// Include the dark colors in the HTML reports
+    html_dark_mode: bool,
+    /// Write the metadata as `#` comment lines first in the CSV reports
+    csv_metadata: bool,
+    /// Write the total and target status rows last in the CSV reports
+    csv_totals: bool,
+    /// Renderers of the formats that aren't built in
+    renderers: RendererRegistry,
 }
 
 /// Report manager implementation
 impl ReportManager {
-    pub fn new() -> Self {
+    /// Create a new report manager, the reports are generated at the time
+    pub fn new(generated: DateTime<Local>) -> Self {
         Self {
-            report_dir: format!(
-                "{}/.local/time-butler/generated-reports",
-                std::env::var("HOME").unwrap_or_else(|_| ".".to_string())
-            ),
+            report_dir: paths::report_directory()
+                .unwrap_or_else(|| "generated-reports".to_string()),
             default_report_file_name: format!(
                 "{}_time_report.",
-                Local::now().format("%Y-%m-%d_%H-%M-%S")
+                generated.format("%Y-%m-%d_%H-%M-%S")
             )
             .to_string(),
+            week_target_hours: 40.0,
+            month_target_hours: 160.0,
+            generated,
+            rounding: RoundingPolicy::None,
+            duration: DurationFormat::Decimal,
+            overwrite: ReportOverwrite::default(),
+            dry_run: false,
+            html_dark_mode: false,
+            csv_metadata: false,
+            csv_totals: false,
+            renderers: RendererRegistry::default(),
         }
     }
 
+    /// Getter for `renderers`
+    pub fn renderers(&self) -> &RendererRegistry {
+        &self.renderers
+    }
+
+    /// Register a renderer of a report format
+    pub fn register_renderer(
+        &mut self,
+        renderer: Box<dyn ReportRenderer>,
+    ) -> Result<(), RendererError> {
+        self.renderers.register(renderer)
+    }
+
+    pub fn set_rounding_policy(&mut self, rounding: RoundingPolicy) {
+        tracing::debug!("Setting report hours rounding to: {}", rounding);
+        self.rounding = rounding;
+    }
+
+    pub fn set_duration_format(&mut self, duration: DurationFormat) {
+        tracing::debug!("Setting report hours format to: {}", duration);
+        self.duration = duration;
+    }
+
     pub fn set_report_storage_dir(&mut self, dir: String) {
         tracing::debug!(
             "Setting report storage directory to: {}, instead of default: {}",
@@ -61,72 +181,345 @@ impl ReportManager {
         self.report_dir = dir;
     }
 
-    //TODO: add extra parameter here deciding if it should be summary or regular report
-    /// Main function to generate a project report
+    /// Set what is done when a report file already exists, and if only the report paths are resolved
+    pub fn set_write_policy(&mut self, overwrite: ReportOverwrite, dry_run: bool) {
+        tracing::debug!(
+            "Setting report overwrite: {:?}, dry run: {}",
+            overwrite,
+            dry_run
+        );
+        self.overwrite = overwrite;
+        self.dry_run = dry_run;
+    }
+
+    /// Include the dark colors in the HTML reports, used when the browser prefers a dark color scheme
+    pub fn set_html_dark_mode(&mut self, dark_mode: bool) {
+        self.html_dark_mode = dark_mode;
+    }
+
+    /// Write the metadata as `#` comment lines first in the CSV reports, by default they only have the header and rows
+    pub fn set_csv_metadata(&mut self, csv_metadata: bool) {
+        self.csv_metadata = csv_metadata;
+    }
+
+    /// Write the total and target status rows in the CSV reports. By default the CSV reports only have the rows of the
+    /// reported items, so summing a column doesn't count the hours twice
+    pub fn set_csv_totals(&mut self, csv_totals: bool) {
+        self.csv_totals = csv_totals;
+    }
+
+    /// Getter for `dry_run`
+    pub fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// Internal function to get the path to write a report to, by the overwrite policy when the file already exists
+    fn report_file_path(&self, file_path: String) -> Result<String, ReportGenerationFailure> {
+        let path = std::path::Path::new(&file_path);
+        if !path.exists() {
+            return Ok(file_path);
+        }
+
+        match self.overwrite {
+            ReportOverwrite::Overwrite => {
+                tracing::info!("Replacing existing report: {}", file_path);
+                Ok(file_path)
+            }
+            ReportOverwrite::Refuse => {
+                tracing::error!(
+                    "Report {} already exists, use --overwrite or --unique-name",
+                    file_path
+                );
+                Err(ReportGenerationFailure)
+            }
+            ReportOverwrite::UniqueName => {
+                let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                let extension = path
+                    .extension()
+                    .map(|e| format!(".{}", e.to_string_lossy()))
+                    .unwrap_or_default();
+                (2..)
+                    .map(|n| path.with_file_name(format!("{}_{}{}", stem, n, extension)))
+                    .find(|p| !p.exists())
+                    .map(|p| p.display().to_string())
+                    .ok_or(ReportGenerationFailure)
+            }
+        }
+    }
+
+    /// Set the time the reports are generated at, the default file name follows
+    pub fn set_generated(&mut self, generated: DateTime<Local>) {
+        self.generated = generated;
+        self.default_report_file_name =
+            format!("{}_time_report.", generated.format("%Y-%m-%d_%H-%M-%S"));
+    }
+
+    pub fn set_week_target_hours(&mut self, hours: f32) {
+        tracing::debug!("Setting chart week target hours to: {}", hours);
+        self.week_target_hours = hours;
+    }
+
+    pub fn set_month_target_hours(&mut self, hours: f32) {
+        tracing::debug!("Setting metadata month target hours to: {}", hours);
+        self.month_target_hours = hours;
+    }
+
+    /// Internal function to get the metadata of a report, the period is the first and last date it covers
+    fn report_metadata(
+        &self,
+        report: String,
+        period: Option<(NaiveDate, NaiveDate)>,
+    ) -> ReportMetadata {
+        ReportMetadata {
+            generated_at: self.generated.to_rfc3339_opts(SecondsFormat::Secs, false),
+            report,
+            period: period.map(|(from, to)| format!("{} - {}", from, to)),
+            client: None,
+            external_reference: None,
+            week_target_hours: self.week_target_hours,
+            month_target_hours: self.month_target_hours,
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    /// Internal function to get the metadata of a project report, the period is the work dates of the entries
+    fn project_metadata(&self, project: &Project) -> ReportMetadata {
+        let dates: Vec<NaiveDate> = project.entries().iter().map(|e| e.work_date()).collect();
+        let period = dates.iter().min().copied().zip(dates.iter().max().copied());
+        ReportMetadata {
+            client: project.client().map(|c| c.to_string()),
+            external_reference: project.external_reference().map(|r| r.to_string()),
+            ..self.report_metadata(format!("Project {}", project.name()), period)
+        }
+    }
+
+    /// Internal function to get the metadata of a week report
+    fn week_metadata(&self, week: &Week) -> ReportMetadata {
+        let period = week_scheme::week_scheme()
+            .first_day(week.number(), week.year())
+            .map(|first| (first, first + Duration::days(6)));
+        self.report_metadata(format!("Week {}, {}", week.number(), week.year()), period)
+    }
+
+    /// Internal function to get the metadata of a month or range report, the year of a month is taken from its
+    /// first day
+    fn days_metadata(&self, period: &ReportPeriod, first_day: Option<&Day>) -> ReportMetadata {
+        match period {
+            ReportPeriod::Month(number) => match first_day.map(|d| d.date().year()) {
+                Some(year) => self.report_metadata(
+                    format!("Month {}, {}", number, year),
+                    month_dates(*number, year),
+                ),
+                None => self.report_metadata(format!("Month {}", number), None),
+            },
+            ReportPeriod::Range(from, to) => {
+                self.report_metadata(format!("Range {} to {}", from, to), Some((*from, *to)))
+            }
+            ReportPeriod::Year(year) => self.report_metadata(
+                format!("Year {}", year),
+                NaiveDate::from_ymd_opt(*year, 1, 1).zip(NaiveDate::from_ymd_opt(*year, 12, 31)),
+            ),
+        }
+    }
+
+    /// Internal function to get the metadata of an overview report
+    fn overview_metadata(&self, month_number: u32, year: u32) -> ReportMetadata {
+        self.report_metadata(
+            format!("Overview month {}, {}", month_number, year),
+            month_dates(month_number, year as i32),
+        )
+    }
+
+    /// Internal function to get the metadata of a timesheet report
+    fn timesheet_metadata(&self, timesheet: &Timesheet) -> ReportMetadata {
+        let period = timesheet
+            .dates
+            .first()
+            .copied()
+            .zip(timesheet.dates.last().copied());
+        self.report_metadata(
+            format!("Timesheet week {}, {}", timesheet.week, timesheet.year),
+            period,
+        )
+    }
+
+    /// Internal function to get the metadata of a summary report
+    fn summary_metadata(&self, summary: &ReportSummary) -> ReportMetadata {
+        self.report_metadata(format!("Summary {}", summary.report), None)
+    }
+
+    /// Internal function to create the writer of a CSV report. The metadata is only written, as `#` comment lines
+    /// first in the file, when enabled. The comment lines aren't part of RFC 4180 and CSV readers must be told to
+    /// skip them
+    fn csv_writer(
+        &self,
+        file_path: &str,
+        metadata: &ReportMetadata,
+    ) -> Result<Writer<File>, Box<dyn Error>> {
+        let mut file = File::create(file_path)?;
+        if self.csv_metadata {
+            for (key, value) in metadata.as_key_values(self.duration) {
+                writeln!(file, "# {}: {}", key, value)?;
+            }
+        }
+        Ok(Writer::from_writer(file))
+    }
+
+    /// Main function to generate a project report, all entry fields are reported when no columns are selected
     pub fn generate_project_report(
         &self,
         format: ReportFormat,
         project: &Project,
-    ) -> Result<(), ReportGenerationFailure> {
+        columns: Option<Vec<ReportColumn>>,
+        output: Option<&ProjectReportOutput>,
+    ) -> Result<String, ReportGenerationFailure> {
         tracing::debug!("Setting report suffix");
         let report_suffix = match self.get_report_suffix(format.clone()) {
             Ok(suffix) => suffix,
             Err(_) => return Err(ReportGenerationFailure),
         };
 
-        let file_name = format!(
-            "{}_{}{}",
-            project.name(),
-            self.default_report_file_name,
-            report_suffix
-        );
-        let file_path = format!("{}/{}", self.report_dir, file_name);
+        let file_path = match self.project_report_path(project, &report_suffix, output) {
+            Ok(path) => self.report_file_path(path)?,
+            Err(e) => {
+                tracing::error!("Invalid project report output: {}", e);
+                return Err(ReportGenerationFailure);
+            }
+        };
+        if self.dry_run {
+            return Ok(file_path);
+        }
         tracing::debug!("report file set to: {}", file_path);
 
         match format {
-            ReportFormat::Csv => match self.write_csv_project_report(project, None, &file_path) {
-                Ok(_) => {
-                    tracing::info!("Created report: {}", file_path);
-                }
-                Err(e) => {
-                    tracing::error!("Error writing report: {}", e);
-                    return Err(ReportGenerationFailure);
-                }
-            },
-            ReportFormat::Json => match self.write_json_project_report(project, None, &file_path) {
-                Ok(_) => {
-                    tracing::info!("Created report: {}", file_path);
-                }
-                Err(e) => {
-                    tracing::error!("Error writing report: {}", e);
-                    return Err(ReportGenerationFailure);
+            ReportFormat::Csv => {
+                match self.write_csv_project_report(project, columns, &file_path) {
+                    Ok(_) => {
+                        tracing::info!("Created report: {}", file_path);
+                    }
+                    Err(e) => {
+                        tracing::error!("Error writing report: {}", e);
+                        return Err(ReportGenerationFailure);
+                    }
                 }
-            },
-            ReportFormat::Yaml => match self.write_yaml_project_report(project, None, &file_path) {
-                Ok(_) => {
-                    tracing::info!("Created report: {}", file_path);
+            }
+            ReportFormat::Json => {
+                match self.write_json_project_report(project, columns, &file_path) {
+                    Ok(_) => {
+                        tracing::info!("Created report: {}", file_path);
+                    }
+                    Err(e) => {
+                        tracing::error!("Error writing report: {}", e);
+                        return Err(ReportGenerationFailure);
+                    }
                 }
-                Err(e) => {
-                    tracing::error!("Error writing report: {}", e);
-                    return Err(ReportGenerationFailure);
+            }
+            ReportFormat::Yaml => {
+                match self.write_yaml_project_report(project, columns, &file_path) {
+                    Ok(_) => {
+                        tracing::info!("Created report: {}", file_path);
+                    }
+                    Err(e) => {
+                        tracing::error!("Error writing report: {}", e);
+                        return Err(ReportGenerationFailure);
+                    }
                 }
-            },
-            ReportFormat::Html => match self.write_html_project_report(project, None, &file_path) {
-                Ok(_) => {
-                    tracing::info!("Created report: {}", file_path);
+            }
+            ReportFormat::Html => {
+                match self.write_html_project_report(project, columns, &file_path) {
+                    Ok(_) => {
+                        tracing::info!("Created report: {}", file_path);
+                    }
+                    Err(e) => {
+                        tracing::error!("Error writing report: {}", e);
+                        return Err(ReportGenerationFailure);
+                    }
                 }
-                Err(e) => {
-                    tracing::error!("Error writing report: {}", e);
-                    return Err(ReportGenerationFailure);
+            }
+            ReportFormat::Template(_) | ReportFormat::Renderer(_) => {
+                let res = self
+                    .project_report_data(project, columns)
+                    .map_err(|e| e.into())
+                    .and_then(|data| {
+                        let report = ReportWithMetadata {
+                            metadata: &self.project_metadata(project),
+                            report: &data,
+                        };
+                        self.write_data_report(&format, &report, &file_path)
+                    });
+                match res {
+                    Ok(_) => {
+                        tracing::info!("Created report: {}", file_path);
+                    }
+                    Err(e) => {
+                        tracing::error!("Error writing report: {}", e);
+                        return Err(ReportGenerationFailure);
+                    }
                 }
-            },
+            }
             _ => {
                 tracing::error!("Unsupported report format");
                 return Err(ReportGenerationFailure);
             }
         }
 
-        Ok(())
+        Ok(file_path)
+    }
+
+    /// Internal function to get the path of a project report. Without an output the default name in the report
+    /// directory is used. The directories of the path are created
+    fn project_report_path(
+        &self,
+        project: &Project,
+        suffix: &str,
+        output: Option<&ProjectReportOutput>,
+    ) -> Result<String, Box<dyn Error>> {
+        // Sub-project paths can't be used as is in the file name
+        let project_name = project.name().replace(K_PROJECT_PATH_SEPARATOR, "-");
+
+        let directory = match output.and_then(|o| o.directory.as_deref()) {
+            Some(dir) if std::path::Path::new(dir).is_absolute() => dir.to_string(),
+            Some(dir) => format!("{}/{}", self.report_dir, dir),
+            None => self.report_dir.clone(),
+        };
+
+        let file_name = match output.and_then(|o| o.file_name.as_deref()) {
+            Some(pattern) => {
+                let now = self.generated;
+                let name = pattern
+                    .replace("{project}", &project_name)
+                    .replace("{year}", &now.format("%Y").to_string())
+                    .replace("{month}", &now.format("%m").to_string())
+                    .replace("{day}", &now.format("%d").to_string())
+                    .replace("{date}", &now.format("%Y-%m-%d").to_string())
+                    .replace("{time}", &now.format("%H-%M-%S").to_string())
+                    .replace("{ext}", suffix);
+                if let Some(start) = name.find('{') {
+                    return Err(format!(
+                        "unknown placeholder {}}} in file name pattern {}",
+                        name[start..].split('}').next().unwrap_or_default(),
+                        pattern
+                    )
+                    .into());
+                }
+                if name.ends_with(&format!(".{}", suffix)) {
+                    name
+                } else {
+                    format!("{}.{}", name, suffix)
+                }
+            }
+            None => format!(
+                "{}_{}{}",
+                project_name, self.default_report_file_name, suffix
+            ),
+        };
+
+        let file_path = format!("{}/{}", directory, file_name);
+        if let Some(parent) = std::path::Path::new(&file_path).parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        Ok(file_path)
     }
 
     /// Internal function to get the suffix for the report file
@@ -139,36 +532,142 @@ impl ReportManager {
             ReportFormat::Html => Ok("html".to_string()),
             ReportFormat::Pdf => Ok("pdf".to_string()),
             ReportFormat::Text => Ok("txt".to_string()),
+            ReportFormat::Xlsx => Ok("xlsx".to_string()),
+            ReportFormat::Template(template) => Ok(self.get_template_suffix(&template)),
+            // Defaults to "txt" like the templates without an extension
+            ReportFormat::Renderer(name) => Ok(self
+                .renderers
+                .get(&name)
+                .map(|r| r.extension().trim_start_matches('.'))
+                .filter(|e| !e.is_empty())
+                .unwrap_or("txt")
+                .to_string()),
             _ => Err(ParseReportFormatError),
         }
     }
 
+    /// Internal function to get the suffix for a template report, the extension before the template extension
+    /// e.g. "timesheet.html.tera" gives "html". Defaults to "txt"
+    fn get_template_suffix(&self, template: &str) -> String {
+        let file_name = std::path::Path::new(template)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let mut parts: Vec<&str> = file_name.split('.').skip(1).collect();
+        if parts
+            .last()
+            .is_some_and(|ext| K_TEMPLATE_EXTENSIONS.contains(ext))
+        {
+            parts.pop();
+        }
+
+        parts.last().unwrap_or(&"txt").to_string()
+    }
+
+    /// Internal function to write a report file rendered from the report data, by a template or a renderer
+    fn write_data_report(
+        &self,
+        format: &ReportFormat,
+        data: &impl Serialize,
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        match format {
+            ReportFormat::Template(template) => {
+                self.write_template_report(template, data, file_path)
+            }
+            ReportFormat::Renderer(name) => self.write_renderer_report(name, data, file_path),
+            _ => Err("the format isn't rendered from the report data".into()),
+        }
+    }
+
+    /// Internal function to write a report with a registered renderer
+    fn write_renderer_report(
+        &self,
+        name: &str,
+        data: &impl Serialize,
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        let renderer = self
+            .renderers
+            .get(name)
+            .ok_or_else(|| format!("no renderer for the format {}", name))?;
+        tracing::debug!("Writing {} report", renderer.name());
+        let rendered = renderer.render(&serde_json::to_value(data)?)?;
+
+        let mut report_file = File::create(file_path)?;
+        report_file.write_all(&rendered)?;
+
+        Ok(())
+    }
+
+    /// Internal function to write a report file rendered through a template, with the same data as the JSON report
+    fn write_template_report(
+        &self,
+        template: &str,
+        data: &impl Serialize,
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("Writing template report using: {}", template);
+        let data = serde_json::to_value(data)?;
+
+        let source = std::fs::read_to_string(template)?;
+        // Same as Tera, variables are escaped in HTML templates
+        let escape = self.get_template_suffix(template) == "html";
+        let rendered = report_template::render(&source, &data, escape)?;
+
+        let mut report_file = File::create(file_path)?;
+        report_file.write_all(rendered.as_bytes())?;
+
+        Ok(())
+    }
+
     /// Internal function to write a CSV project report file
     fn write_csv_project_report(
         &self,
         project: &Project,
-        columns: Option<ProjectReportColumns>,
+        columns: Option<Vec<ReportColumn>>,
         file_path: &str,
     ) -> Result<(), Box<dyn Error>> {
         tracing::debug!("Writing CSV report");
 
         // Creating the file
-        let mut writer = Writer::from_path(file_path)?;
+        let mut writer = self.csv_writer(file_path, &self.project_metadata(project))?;
 
         // Decide how to set up the headers based on the columns provided
         match columns {
             Some(c) => {
-                writer.write_record([
-                    c.first.as_str(),
-                    c.second.as_str(),
-                    c.third.as_str(),
-                    c.fourth.as_str(),
-                ])?;
+                writer.write_record(c.iter().map(|column| column.to_string()))?;
+
+                for e in project.entries() {
+                    writer
+                        .write_record(c.iter().map(|column| self.entry_column_value(e, column)))?;
+                }
+
+                let total = self.total_record(
+                    &c,
+                    tr("Total"),
+                    self.duration.format(self.entry_hours(project.entries())),
+                );
+                if let Some(record) = total.filter(|_| self.csv_totals) {
+                    writer.write_record(&record)?;
+                }
             }
             None => {
                 for e in project.entries() {
                     writer.serialize(e)?;
                 }
+
+                // The raw entries start with the hours, the total is put in the same column
+                if let Some(first) = project.entries().first().filter(|_| self.csv_totals) {
+                    let fields = serde_json::to_value(first)?
+                        .as_object()
+                        .map_or(0, |o| o.len());
+                    let mut record = vec![String::new(); fields.max(2)];
+                    record[0] = self.duration.format(self.entry_hours(project.entries()));
+                    record[1] = tr("Total").to_string();
+                    writer.write_record(&record)?;
+                }
             }
         }
 
@@ -181,32 +680,66 @@ impl ReportManager {
     fn write_json_project_report(
         &self,
         project: &Project,
-        columns: Option<ProjectReportColumns>,
+        columns: Option<Vec<ReportColumn>>,
         file_path: &str,
     ) -> Result<(), Box<dyn Error>> {
         tracing::debug!("Writing JSON report");
 
         let report_file = File::create(file_path)?;
+        let report = ReportWithMetadata {
+            metadata: &self.project_metadata(project),
+            report: &self.project_report_data(project, columns)?,
+        };
+        serde_json::to_writer(report_file, &report)?;
+
+        Ok(())
+    }
 
+    /// Internal function to get the data of a project report, the raw project when no columns are selected
+    fn project_report_data(
+        &self,
+        project: &Project,
+        columns: Option<Vec<ReportColumn>>,
+    ) -> Result<Value, serde_json::Error> {
         match columns {
-            #[allow(unused_variables)]
             Some(c) => {
-                tracing::warn!("Customized columns not supported for JSON project reports");
-                return Err(Box::new(ReportGenerationFailure));
+                let entries: Vec<Value> = project
+                    .entries()
+                    .iter()
+                    .map(|e| {
+                        let mut entry_json = serde_json::Map::new();
+                        for column in &c {
+                            entry_json
+                                .insert(column.to_string(), self.entry_column_json(e, column));
+                        }
+                        Value::Object(entry_json)
+                    })
+                    .collect();
+
+                Ok(json!({
+                    "name": project.name(),
+                    "entries": entries,
+                    "total_hours": self.entry_hours(project.entries())
+                }))
             }
             None => {
-                serde_json::to_writer(report_file, project)?;
+                let mut project_json = serde_json::to_value(project)?;
+                if let Some(o) = project_json.as_object_mut() {
+                    o.insert(
+                        "total_hours".to_string(),
+                        json!(self.entry_hours(project.entries())),
+                    );
+                }
+                Ok(project_json)
             }
         }
-
-        Ok(())
     }
 
     /// Internal function to write a YAML project report file
     fn write_yaml_project_report(
         &self,
         project: &Project,
-        columns: Option<ProjectReportColumns>,
+        columns: Option<Vec<ReportColumn>>,
         file_path: &str,
     ) -> Result<(), Box<dyn Error>> {
         tracing::debug!("Writing YAML report");
@@ -214,13 +747,51 @@ impl ReportManager {
         let report_file = File::create(file_path)?;
 
         match columns {
-            #[allow(unused_variables)]
             Some(c) => {
-                tracing::warn!("Customized columns not supported for YAML project reports");
-                return Err(Box::new(ReportGenerationFailure));
+                let mut entries = Vec::new();
+                for e in project.entries() {
+                    let mut entry_yaml = serde_yaml::Mapping::new();
+                    for column in &c {
+                        entry_yaml.insert(
+                            serde_yaml::Value::String(column.to_string()),
+                            serde_yaml::to_value(self.entry_column_json(e, column))?,
+                        );
+                    }
+                    entries.push(serde_yaml::Value::Mapping(entry_yaml));
+                }
+
+                let mut yaml_report = serde_yaml::Mapping::new();
+                yaml_report.insert(
+                    serde_yaml::Value::String("name".to_string()),
+                    serde_yaml::Value::String(project.name().to_string()),
+                );
+                yaml_report.insert(
+                    serde_yaml::Value::String("entries".to_string()),
+                    serde_yaml::Value::Sequence(entries),
+                );
+                yaml_report.insert(
+                    serde_yaml::Value::String("total_hours".to_string()),
+                    serde_yaml::to_value(self.entry_hours(project.entries()))?,
+                );
+                let report = ReportWithMetadata {
+                    metadata: &self.project_metadata(project),
+                    report: &yaml_report,
+                };
+                serde_yaml::to_writer(report_file, &report)?;
             }
             None => {
-                serde_yaml::to_writer(report_file, project)?;
+                let mut yaml_report = serde_yaml::to_value(project)?;
+                if let Some(m) = yaml_report.as_mapping_mut() {
+                    m.insert(
+                        serde_yaml::Value::String("total_hours".to_string()),
+                        serde_yaml::to_value(self.entry_hours(project.entries()))?,
+                    );
+                }
+                let report = ReportWithMetadata {
+                    metadata: &self.project_metadata(project),
+                    report: &yaml_report,
+                };
+                serde_yaml::to_writer(report_file, &report)?;
             }
         }
 
@@ -231,7 +802,7 @@ impl ReportManager {
     fn write_html_project_report(
         &self,
         project: &Project,
-        columns: Option<ProjectReportColumns>,
+        columns: Option<Vec<ReportColumn>>,
         file_path: &str,
     ) -> Result<(), Box<dyn Error>> {
         tracing::debug!("Writing HTML report");
@@ -243,39 +814,39 @@ impl ReportManager {
             }
             None => {
                 tracing::debug!("Using default report columns");
-                ProjectReportColumns {
-                    first: "hours".to_string(),
-                    second: "description".to_string(),
-                    third: "created".to_string(),
-                    fourth: "id".to_string(),
-                }
+                vec![
+                    ReportColumn::Hours,
+                    ReportColumn::Description,
+                    ReportColumn::Created,
+                    ReportColumn::Id,
+                ]
             }
         };
 
-        let headers = vec![
-            report_columns.first,
-            report_columns.second,
-            report_columns.third,
-            report_columns.fourth,
-        ];
+        let headers: Vec<String> = report_columns.iter().map(|c| c.to_string()).collect();
 
         // Rows from the project entries
         let rows: Vec<Vec<String>> = project
             .entries()
             .iter()
             .map(|entry| {
-                vec![
-                    entry.hours().to_string(),
-                    entry.description().to_string(),
-                    entry.created().to_string(),
-                    entry.id().to_string(),
-                ]
+                report_columns
+                    .iter()
+                    .map(|c| self.entry_column_value(entry, c))
+                    .collect()
             })
             .collect();
 
+        let total_hours = self.entry_hours(project.entries());
+        // Only a valid color is put in the style, the stored value may come from an edited sync file
+        let accent = project
+            .color()
+            .filter(|c| display::parse_color(c).is_some())
+            .map(|c| format!("border-top: 4px solid {}", c.trim()));
+
         // Build the HTML markup
-        let markup: Markup = html! {
-            table border="1" {
+        let content: Markup = html! {
+            table border="1" style=[accent] {
                 thead {
                     tr {
                         @for header in &headers {
@@ -294,6 +865,22 @@ impl ReportManager {
                 }
             }
         };
+        let markup = self.html_layout(
+            &format!("Project Report - {}", project.name()),
+            &self.project_metadata(project),
+            &[
+                (tr("Project"), project.name().to_string()),
+                (
+                    tr("Description"),
+                    project.description().unwrap_or_default().to_string(),
+                ),
+            ],
+            content,
+            &[
+                (tr("Entries"), project.entries().len().to_string()),
+                (tr("Total hours"), self.duration.format(total_hours)),
+            ],
+        );
 
         let mut file = File::create(file_path)?;
         file.write_all(markup.into_string().as_bytes())?;
@@ -301,12 +888,13 @@ impl ReportManager {
         Ok(())
     }
 
-    /// Main function to generate a week report
+    /// Main function to generate a week report, all day columns are reported when no columns are selected
     pub fn generate_week_report(
         &self,
         format: ReportFormat,
         week: &Week,
-    ) -> Result<(), ReportGenerationFailure> {
+        columns: Option<Vec<ReportColumn>>,
+    ) -> Result<String, ReportGenerationFailure> {
         tracing::debug!("Setting report suffix");
         let report_suffix = match self.get_report_suffix(format.clone()) {
             Ok(suffix) => suffix,
@@ -319,56 +907,59 @@ impl ReportManager {
             self.default_report_file_name,
             report_suffix
         );
-        let file_path = format!("{}/{}", self.report_dir, file_name);
+        let file_path = self.report_file_path(format!("{}/{}", self.report_dir, file_name))?;
+        if self.dry_run {
+            return Ok(file_path);
+        }
         tracing::debug!("report file set to: {}", file_path);
 
-        let columns = WeekReportColumns {
-            first: "Week".to_string(),
-            second: "Date".to_string(),
-            third: "StartingTime".to_string(),
-            fourth: "EndingTime".to_string(),
-            fifth: "Paused-Hours".to_string(),
-            sixth: "Hours".to_string(),
-            seventh: "Description".to_string(),
-            eighth: "Closed".to_string(),
-        };
+        let columns = columns.unwrap_or_else(|| K_DAY_REPORT_COLUMNS.to_vec());
+
+        // Days are reported in date order, independent of the order they were added in
+        let mut sorted_week = week.clone();
+        sorted_week.entries_mut().sort_by_key(|d| d.date());
+        let week = &sorted_week;
 
         match format {
-            ReportFormat::Csv => {
-                match self.write_csv_week_report(week, Some(columns), &file_path) {
-                    Ok(_) => {
-                        tracing::info!("Created report: {}", file_path);
-                    }
-                    Err(e) => {
-                        tracing::error!("Error writing report: {}", e);
-                        return Err(ReportGenerationFailure);
-                    }
+            ReportFormat::Csv => match self.write_csv_week_report(week, &columns, &file_path) {
+                Ok(_) => {
+                    tracing::info!("Created report: {}", file_path);
                 }
-            }
-            ReportFormat::Json => {
-                match self.write_json_week_report(week, Some(columns), &file_path) {
-                    Ok(_) => {
-                        tracing::info!("Created report: {}", file_path);
-                    }
-                    Err(e) => {
-                        tracing::error!("Error writing report: {}", e);
-                        return Err(ReportGenerationFailure);
-                    }
+                Err(e) => {
+                    tracing::error!("Error writing report: {}", e);
+                    return Err(ReportGenerationFailure);
                 }
-            }
-            ReportFormat::Yaml => {
-                match self.write_yaml_week_report(week, Some(columns), &file_path) {
-                    Ok(_) => {
-                        tracing::info!("Created report: {}", file_path);
-                    }
-                    Err(e) => {
-                        tracing::error!("Error writing report: {}", e);
-                        return Err(ReportGenerationFailure);
-                    }
+            },
+            ReportFormat::Json => match self.write_json_week_report(week, &columns, &file_path) {
+                Ok(_) => {
+                    tracing::info!("Created report: {}", file_path);
                 }
-            }
-            ReportFormat::Html => {
-                match self.write_html_week_report(week, Some(columns), &file_path) {
+                Err(e) => {
+                    tracing::error!("Error writing report: {}", e);
+                    return Err(ReportGenerationFailure);
+                }
+            },
+            ReportFormat::Yaml => match self.write_yaml_week_report(week, &columns, &file_path) {
+                Ok(_) => {
+                    tracing::info!("Created report: {}", file_path);
+                }
+                Err(e) => {
+                    tracing::error!("Error writing report: {}", e);
+                    return Err(ReportGenerationFailure);
+                }
+            },
+            ReportFormat::Html => match self.write_html_week_report(week, &columns, &file_path) {
+                Ok(_) => {
+                    tracing::info!("Created report: {}", file_path);
+                }
+                Err(e) => {
+                    tracing::error!("Error writing report: {}", e);
+                    return Err(ReportGenerationFailure);
+                }
+            },
+            ReportFormat::Template(_) | ReportFormat::Renderer(_) => {
+                let data = self.week_report_data(week, &columns);
+                match self.write_data_report(&format, &data, &file_path) {
                     Ok(_) => {
                         tracing::info!("Created report: {}", file_path);
                     }
@@ -384,56 +975,36 @@ impl ReportManager {
             }
         }
 
-        Ok(())
+        Ok(file_path)
     }
 
     /// Internal function to write a CSV week report file
     fn write_csv_week_report(
         &self,
         week: &Week,
-        columns: Option<WeekReportColumns>,
+        columns: &[ReportColumn],
         file_path: &str,
     ) -> Result<(), Box<dyn Error>> {
         tracing::debug!("Writing CSV report");
 
         // Creating the file
-        let mut writer = Writer::from_path(file_path)?;
+        let mut writer = self.csv_writer(file_path, &self.week_metadata(week))?;
 
-        // Decide how to set up the headers based on the columns provided
-        match columns {
-            Some(c) => {
-                writer.write_record([
-                    c.first.as_str(),
-                    c.second.as_str(),
-                    c.third.as_str(),
-                    c.fourth.as_str(),
-                    c.fifth.as_str(),
-                    c.sixth.as_str(),
-                    c.seventh.as_str(),
-                    c.eighth.as_str(),
-                ])?;
-
-                for d in week.entries() {
-                    let formatted_start_time =
-                        self.format_datetime_to_report_string(d.starting_time());
-
-                    let formatted_end_time = self.format_datetime_to_report_string(d.ending_time());
-
-                    writer.write_record(&[
-                        d.week().to_string(),
-                        d.date().to_string(),
-                        formatted_start_time,
-                        formatted_end_time,
-                        d.hours_paused().to_string(),
-                        d.hours().to_string(),
-                        d.extra_info().to_string(),
-                        d.closed().to_string(),
-                    ])?;
-                }
-            }
-            None => {
-                for d in week.entries() {
-                    writer.serialize(d)?;
+        writer.write_record(columns.iter().map(|c| c.header()))?;
+
+        for d in week.entries() {
+            writer.write_record(columns.iter().map(|c| self.day_column_value(d, c)))?;
+        }
+
+        if self.csv_totals {
+            let mut totals = vec![(
+                tr("Total"),
+                self.duration.format(self.day_hours(week.entries())),
+            )];
+            totals.extend(self.week_target_status(week).as_key_values(self.duration));
+            for (label, value) in totals {
+                if let Some(record) = self.total_record(columns, label, value) {
+                    writer.write_record(&record)?;
                 }
             }
         }
@@ -447,118 +1018,49 @@ impl ReportManager {
     fn write_json_week_report(
         &self,
         week: &Week,
-        columns: Option<WeekReportColumns>,
+        columns: &[ReportColumn],
         file_path: &str,
     ) -> Result<(), Box<dyn Error>> {
         tracing::debug!("Writing JSON report");
 
-        let mut report_file = File::create(file_path)?;
-
-        match columns {
-            Some(c) => {
-                let mut days_in_json = String::from("[");
-
-                for d in week.entries() {
-                    let formatted_start_time =
-                        self.format_datetime_to_report_string(d.starting_time());
-
-                    let formatted_end_time = self.format_datetime_to_report_string(d.ending_time());
-
-                    let json_struct = format!(
-                        r#"{{"{}": "{}", "{}": "{}", "{}": "{}", "{}": "{}", "{}": "{}", "{}": "{}", "{}": "{}"}}"#,
-                        c.second,
-                        d.date(),
-                        c.third,
-                        formatted_start_time,
-                        c.fourth,
-                        formatted_end_time,
-                        c.fifth,
-                        d.hours_paused(),
-                        c.sixth,
-                        d.hours(),
-                        c.seventh,
-                        d.extra_info(),
-                        c.eighth,
-                        d.closed(),
-                    );
+        let report_file = File::create(file_path)?;
+        serde_json::to_writer_pretty(report_file, &self.week_report_data(week, columns))?;
 
-                    days_in_json.push_str(&json_struct);
-                }
+        Ok(())
+    }
 
-                days_in_json.push(']');
-                let json_report = format!(
-                    r#"{{"{}": "{}", "{}": {}}}"#,
-                    c.first,
-                    week.number(),
-                    "Days",
-                    days_in_json
-                );
-                report_file.write_all(json_report.as_bytes())?;
-            }
-            None => {
-                tracing::warn!("Using raw JSON serialization for week report");
-                serde_json::to_writer(report_file, week)?;
-            }
+    /// Internal function to get the document of a week report
+    fn week_report_data(&self, week: &Week, columns: &[ReportColumn]) -> WeekReportDocument {
+        WeekReportDocument {
+            format_version: K_REPORT_FORMAT_VERSION,
+            metadata: self.week_metadata(week),
+            week: WeekReport {
+                id: Some(week.id().to_string()),
+                week: week.number(),
+                year: week.year(),
+                days: week
+                    .entries()
+                    .iter()
+                    .map(|d| self.day_json(d, columns))
+                    .collect(),
+                notes: week.notes().to_vec(),
+                total_hours: self.day_hours(week.entries()),
+                target_status: Some(self.week_target_status(week)),
+            },
         }
-
-        Ok(())
     }
 
-    /// Internal function to write a YAML week report file
+    /// Internal function to write a YAML week report file, same structure as the JSON report
     fn write_yaml_week_report(
         &self,
         week: &Week,
-        columns: Option<WeekReportColumns>,
+        columns: &[ReportColumn],
         file_path: &str,
     ) -> Result<(), Box<dyn Error>> {
         tracing::debug!("Writing YAML report");
 
-        let mut report_file = File::create(file_path)?;
-
-        match columns {
-            Some(c) => {
-                let mut days_in_yaml = String::from("");
-
-                for d in week.entries() {
-                    let formatted_start_time =
-                        self.format_datetime_to_report_string(d.starting_time());
-
-                    let formatted_end_time = self.format_datetime_to_report_string(d.ending_time());
-
-                    let yaml_struct = format!(
-                        "\n- {}: \"{}\"\n  {}: \"{}\"\n  {}: \"{}\"\n  {}: \"{}\"\n  {}: \"{}\"\n  {}: \"{}\"\n  {}: \"{}\"\n",
-                        c.second,
-                        d.date(),
-                        c.third,
-                        formatted_start_time,
-                        c.fourth,
-                        formatted_end_time,
-                        c.fifth,
-                        d.hours_paused(),
-                        c.sixth,
-                        d.hours(),
-                        c.seventh,
-                        d.extra_info(),
-                        c.eighth,
-                        d.closed(),
-                    );
-                    days_in_yaml.push_str(&yaml_struct);
-                }
-
-                let yaml_report = format!(
-                    "{}: {}\n{}: {}\n",
-                    c.first,
-                    week.number(),
-                    "Days",
-                    days_in_yaml
-                );
-                report_file.write_all(yaml_report.as_bytes())?;
-            }
-            None => {
-                tracing::warn!("Using raw YAML serialization for week report");
-                serde_yaml::to_writer(report_file, week)?;
-            }
-        }
+        let report_file = File::create(file_path)?;
+        serde_yaml::to_writer(report_file, &self.week_report_data(week, columns))?;
 
         Ok(())
     }
@@ -567,62 +1069,36 @@ impl ReportManager {
     fn write_html_week_report(
         &self,
         week: &Week,
-        columns: Option<WeekReportColumns>,
+        columns: &[ReportColumn],
         file_path: &str,
     ) -> Result<(), Box<dyn Error>> {
         tracing::debug!("Writing HTML report");
 
-        let report_columns = match columns {
-            Some(c) => {
-                tracing::debug!("Using custom report columns provided");
-                c
-            }
-            None => {
-                tracing::warn!("Using default report columns");
-                WeekReportColumns {
-                    first: "Week".to_string(),
-                    second: "Date".to_string(),
-                    third: "StartingTime".to_string(),
-                    fourth: "EndingTime".to_string(),
-                    fifth: "Paused-Hours".to_string(),
-                    sixth: "Hours".to_string(),
-                    seventh: "Description".to_string(),
-                    eighth: "Closed".to_string(),
-                }
-            }
-        };
-
-        let headers = vec![
-            report_columns.first,
-            report_columns.second,
-            report_columns.third,
-            report_columns.fourth,
-            report_columns.fifth,
-            report_columns.sixth,
-            report_columns.seventh,
-            report_columns.eighth,
-        ];
+        let headers: Vec<&str> = columns.iter().map(|c| c.header()).collect();
 
-        // Rows from the project entries
+        // Rows from the days of the week
         let rows: Vec<Vec<String>> = week
             .entries()
             .iter()
-            .map(|entry| {
-                vec![
-                    week.number().to_string(),
-                    entry.date().to_string(),
-                    self.format_datetime_to_report_string(entry.starting_time()),
-                    self.format_datetime_to_report_string(entry.ending_time()),
-                    entry.hours_paused().to_string(),
-                    entry.hours().to_string(),
-                    entry.extra_info().to_string(),
-                    entry.closed().to_string(),
-                ]
+            .map(|d| {
+                columns
+                    .iter()
+                    .map(|c| self.day_column_value(d, c))
+                    .collect()
             })
             .collect();
 
+        let mut totals = vec![
+            (tr("Days"), week.entries().len().to_string()),
+            (
+                tr("Total hours"),
+                self.duration.format(self.day_hours(week.entries())),
+            ),
+        ];
+        totals.extend(self.week_target_status(week).as_key_values(self.duration));
+
         // Build the HTML markup
-        let markup: Markup = html! {
+        let content: Markup = html! {
             table border="1" {
                 thead {
                     tr {
@@ -641,7 +1117,24 @@ impl ReportManager {
                     }
                 }
             }
+            @if !week.notes().is_empty() {
+                ul {
+                    @for note in week.notes() {
+                        li { (note) }
+                    }
+                }
+            }
         };
+        let markup = self.html_layout(
+            &format!("Weekly Report - Week {}, {}", week.number(), week.year()),
+            &self.week_metadata(week),
+            &[
+                (tr("Week"), week.number().to_string()),
+                (tr("Year"), week.year().to_string()),
+            ],
+            content,
+            &totals,
+        );
 
         let mut file = File::create(file_path)?;
         file.write_all(markup.into_string().as_bytes())?;
@@ -655,12 +1148,179 @@ impl ReportManager {
             .unwrap_or_else(|| "N/A".to_string())
     }
 
-    pub fn generate_month_report(
+    /// Internal support function to get the value of a day column as a string, used by the flat report formats
+    fn day_column_value(&self, d: &Day, column: &ReportColumn) -> String {
+        match column {
+            ReportColumn::Week => d.week().to_string(),
+            ReportColumn::Date => d.date().to_string(),
+            ReportColumn::Start => self.format_datetime_to_report_string(d.starting_time()),
+            ReportColumn::End => {
+                let end = self.format_datetime_to_report_string(d.ending_time());
+                // Mark the ending time of a day crossing midnight, e.g. "06:00:00 +1d"
+                match d.overnight_days() {
+                    0 => end,
+                    days => format!("{} +{}d", end, days),
+                }
+            }
+            ReportColumn::Paused => self.duration.format(d.hours_paused()),
+            ReportColumn::Hours => self.duration.format(self.rounding.round(d.hours())),
+            ReportColumn::Description => d.extra_info().to_string(),
+            ReportColumn::Closed => d.closed().to_string(),
+            ReportColumn::Created | ReportColumn::Id => "N/A".to_string(),
+        }
+    }
+
+    /// Internal support function to get a day of the structured reports with the selected columns. The week is left
+    /// out, the reports group on it
+    fn day_json(&self, d: &Day, columns: &[ReportColumn]) -> DayReportRow {
+        let mut row = DayReportRow::default();
+        for c in columns {
+            match c {
+                ReportColumn::Date => row.date = Some(d.date()),
+                ReportColumn::Start => {
+                    row.starting_time =
+                        Some(self.format_datetime_to_report_string(d.starting_time()))
+                }
+                ReportColumn::End => {
+                    row.ending_time = Some(self.format_datetime_to_report_string(d.ending_time()));
+                    row.ending_days_after = Some(d.overnight_days()).filter(|days| *days > 0);
+                }
+                ReportColumn::Paused => row.paused_hours = Some(d.hours_paused()),
+                ReportColumn::Hours => row.hours = Some(self.rounding.round(d.hours())),
+                ReportColumn::Description => row.description = Some(d.extra_info().to_string()),
+                ReportColumn::Closed => row.closed = Some(d.closed()),
+                ReportColumn::Week | ReportColumn::Created | ReportColumn::Id => {}
+            }
+        }
+        row
+    }
+
+    /// Internal support function to get the value of an entry column as a string, used by the flat report formats
+    fn entry_column_value(&self, e: &Entry, column: &ReportColumn) -> String {
+        match column {
+            ReportColumn::Date => e.work_date().to_string(),
+            ReportColumn::Hours => self.duration.format(self.rounding.round(e.hours())),
+            ReportColumn::Description => e.description().to_string(),
+            ReportColumn::Created => e.created().to_string(),
+            ReportColumn::Id => e.id().to_string(),
+            _ => "N/A".to_string(),
+        }
+    }
+
+    /// Internal support function to get the value of an entry column as JSON, numbers keep their type
+    fn entry_column_json(&self, e: &Entry, column: &ReportColumn) -> Value {
+        match column {
+            ReportColumn::Hours => json!(self.rounding.round(e.hours())),
+            _ => json!(self.entry_column_value(e, column)),
+        }
+    }
+
+    /// Internal support function to get the total of the rounded hours of the days
+    fn day_hours<'a>(&self, days: impl IntoIterator<Item = &'a Day>) -> f32 {
+        days.into_iter()
+            .fold(0.0, |sum, d| sum + self.rounding.round(d.hours()))
+    }
+
+    /// Internal support function to get the total of the rounded hours of the entries
+    fn entry_hours(&self, entries: &[Entry]) -> f32 {
+        entries
+            .iter()
+            .fold(0.0, |sum, e| sum + self.rounding.round(e.hours()))
+    }
+
+    /// Internal support function to get the target status of a week, the target of the week is used as in the
+    /// target status command
+    fn week_target_status(&self, week: &Week) -> ReportTargetStatus {
+        ReportTargetStatus::from_week(&WeeklyTargetStatus::new(week, &week.target_hours()))
+    }
+
+    /// Internal support function to get a total row of the flat report formats, the value is put in the hours column
+    /// and the label in the first other column. None when the hours aren't reported
+    fn total_record(
+        &self,
+        columns: &[ReportColumn],
+        label: &str,
+        value: String,
+    ) -> Option<Vec<String>> {
+        let hours_index = columns.iter().position(|c| *c == ReportColumn::Hours)?;
+
+        let mut record = vec![String::new(); columns.len()];
+        record[hours_index] = value;
+        if let Some(label_index) = (0..columns.len()).find(|i| *i != hours_index) {
+            record[label_index] = label.to_string();
+        }
+        Some(record)
+    }
+
+    pub fn generate_month_report<'a>(
         &self,
         month_number: u32,
         format: ReportFormat,
-        days_in_month: &Vec<Day>,
-    ) -> Result<(), ReportGenerationFailure> {
+        days_in_month: impl Iterator<Item = &'a Day> + Clone,
+        week_notes: &BTreeMap<(i32, u32), Vec<String>>,
+        columns: Option<Vec<ReportColumn>>,
+        target_status: Option<&ReportTargetStatus>,
+    ) -> Result<String, ReportGenerationFailure> {
+        self.generate_days_report(
+            ReportPeriod::Month(month_number),
+            format,
+            days_in_month,
+            week_notes,
+            columns,
+            target_status,
+        )
+    }
+
+    /// Main function to generate a report for an arbitrary date range, the days can span multiple weeks and years
+    pub fn generate_range_report<'a>(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        format: ReportFormat,
+        days_in_range: impl Iterator<Item = &'a Day> + Clone,
+        week_notes: &BTreeMap<(i32, u32), Vec<String>>,
+        columns: Option<Vec<ReportColumn>>,
+    ) -> Result<String, ReportGenerationFailure> {
+        self.generate_days_report(
+            ReportPeriod::Range(from, to),
+            format,
+            days_in_range,
+            week_notes,
+            columns,
+            None,
+        )
+    }
+
+    /// Main function to generate a report of the days of a year
+    pub fn generate_year_report<'a>(
+        &self,
+        year: i32,
+        format: ReportFormat,
+        days_in_year: impl Iterator<Item = &'a Day> + Clone,
+        week_notes: &BTreeMap<(i32, u32), Vec<String>>,
+        columns: Option<Vec<ReportColumn>>,
+    ) -> Result<String, ReportGenerationFailure> {
+        self.generate_days_report(
+            ReportPeriod::Year(year),
+            format,
+            days_in_year,
+            week_notes,
+            columns,
+            None,
+        )
+    }
+
+    /// Internal function to generate a report of days grouped by week, for a month or a date range. The notes of the
+    /// reported weeks are included, except in the CSV format. The target attainment is reported when there's a target
+    fn generate_days_report<'a>(
+        &self,
+        period: ReportPeriod,
+        format: ReportFormat,
+        days: impl Iterator<Item = &'a Day> + Clone,
+        week_notes: &BTreeMap<(i32, u32), Vec<String>>,
+        columns: Option<Vec<ReportColumn>>,
+        target_status: Option<&ReportTargetStatus>,
+    ) -> Result<String, ReportGenerationFailure> {
         tracing::debug!("Setting report suffix");
         let report_suffix = match self.get_report_suffix(format.clone()) {
             Ok(suffix) => suffix,
@@ -668,31 +1328,39 @@ impl ReportManager {
         };
 
         let file_name = format!(
-            "month{}_{}{}",
-            month_number, self.default_report_file_name, report_suffix
+            "{}_{}{}",
+            period.file_prefix(),
+            self.default_report_file_name,
+            report_suffix
         );
 
-        let file_path = format!("{}/{}", self.report_dir, file_name);
+        let file_path = self.report_file_path(format!("{}/{}", self.report_dir, file_name))?;
+        if self.dry_run {
+            return Ok(file_path);
+        }
         tracing::debug!("report file set to: {}", file_path);
 
-        // Same as for week report
-        let columns = MonthReportColumns {
-            first: "Month".to_string(),
-            second: "Week".to_string(),
-            third: "Date".to_string(),
-            fourth: "StartingTime".to_string(),
-            fifth: "EndingTime".to_string(),
-            sixth: "Hours".to_string(),
-            seventh: "Description".to_string(),
-            eighth: "Closed".to_string(),
-        };
+        // Same as for week report, but the paused hours are left out by default
+        let columns = columns.unwrap_or_else(|| {
+            K_DAY_REPORT_COLUMNS
+                .iter()
+                .filter(|c| **c != ReportColumn::Paused)
+                .cloned()
+                .collect()
+        });
+
+        // Days are reported in date order, so the weeks and days are in the same order in every report
+        let mut sorted_days: Vec<&Day> = days.collect();
+        sorted_days.sort_by_key(|d| d.date());
+        let days = sorted_days.into_iter();
 
         match format {
             ReportFormat::Csv => {
                 match self.write_csv_month_report(
-                    month_number,
-                    days_in_month,
-                    Some(columns),
+                    &period,
+                    days,
+                    &columns,
+                    target_status,
                     &file_path,
                 ) {
                     Ok(_) => {
@@ -706,9 +1374,11 @@ impl ReportManager {
             }
             ReportFormat::Json => {
                 match self.write_json_month_report(
-                    month_number,
-                    days_in_month,
-                    Some(columns),
+                    &period,
+                    days,
+                    week_notes,
+                    &columns,
+                    target_status,
                     &file_path,
                 ) {
                     Ok(_) => {
@@ -722,9 +1392,11 @@ impl ReportManager {
             }
             ReportFormat::Yaml => {
                 match self.write_yaml_month_report(
-                    month_number,
-                    days_in_month,
-                    Some(columns),
+                    &period,
+                    days,
+                    week_notes,
+                    &columns,
+                    target_status,
                     &file_path,
                 ) {
                     Ok(_) => {
@@ -738,9 +1410,11 @@ impl ReportManager {
             }
             ReportFormat::Html => {
                 match self.write_html_month_report(
-                    month_number,
-                    days_in_month,
-                    Some(columns),
+                    &period,
+                    days,
+                    week_notes,
+                    &columns,
+                    target_status,
                     &file_path,
                 ) {
                     Ok(_) => {
@@ -752,63 +1426,98 @@ impl ReportManager {
                     }
                 }
             }
+            ReportFormat::Template(_) | ReportFormat::Renderer(_) => {
+                let data =
+                    self.days_report_data(&period, days, week_notes, &columns, target_status);
+                match self.write_data_report(&format, &data, &file_path) {
+                    Ok(_) => {
+                        tracing::info!("Created report: {}", file_path);
+                    }
+                    Err(e) => {
+                        tracing::error!("Error writing report: {}", e);
+                        return Err(ReportGenerationFailure);
+                    }
+                }
+            }
             _ => {
                 tracing::error!("Unsupported report format");
                 return Err(ReportGenerationFailure);
             }
         }
 
-        Ok(())
+        Ok(file_path)
+    }
+
+    /// Internal function to get the header of the period column in the day reports
+    fn period_header(&self, period: &ReportPeriod) -> &'static str {
+        match period {
+            ReportPeriod::Month(_) => "Month",
+            ReportPeriod::Range(..) => "Range",
+            ReportPeriod::Year(_) => "Year",
+        }
     }
 
-    fn write_csv_month_report(
+    fn write_csv_month_report<'a>(
         &self,
-        month_number: u32,
-        month_days: &Vec<Day>,
-        columns: Option<MonthReportColumns>,
+        period: &ReportPeriod,
+        month_days: impl Iterator<Item = &'a Day> + Clone,
+        columns: &[ReportColumn],
+        target_status: Option<&ReportTargetStatus>,
         file_path: &str,
     ) -> Result<(), Box<dyn Error>> {
         tracing::debug!("Writing CSV report");
 
         // Creating the file
-        let mut writer = Writer::from_path(file_path)?;
+        let metadata = self.days_metadata(period, month_days.clone().next());
+        let mut writer = self.csv_writer(file_path, &metadata)?;
+
+        // The period column is always first
+        let mut headers = vec![self.period_header(period)];
+        headers.extend(columns.iter().map(|c| c.header()));
+        writer.write_record(&headers)?;
+
+        // Each week is followed by its total, and the report ends with the total of the period
+        let mut week_days: BTreeMap<(i32, u32), Vec<&Day>> = BTreeMap::new();
+        for d in month_days {
+            week_days
+                .entry((d.week_year(), d.week()))
+                .or_default()
+                .push(d);
+        }
 
-        // Decide how to set up the headers based on the columns provided
-        match columns {
-            Some(c) => {
-                writer.write_record([
-                    c.first.as_str(),
-                    c.second.as_str(),
-                    c.third.as_str(),
-                    c.fourth.as_str(),
-                    c.fifth.as_str(),
-                    c.sixth.as_str(),
-                    c.seventh.as_str(),
-                    c.eighth.as_str(),
-                ])?;
-
-                for d in month_days {
-                    let formatted_start_time =
-                        self.format_datetime_to_report_string(d.starting_time());
-
-                    let formatted_end_time = self.format_datetime_to_report_string(d.ending_time());
-
-                    writer.write_record(&[
-                        month_number.to_string(),
-                        d.week().to_string(),
-                        d.date().to_string(),
-                        formatted_start_time,
-                        formatted_end_time,
-                        d.hours().to_string(),
-                        d.extra_info().to_string(),
-                        d.closed().to_string(),
-                    ])?;
-                }
+        let total_record = |label: String, hours: String| {
+            if !self.csv_totals {
+                return None;
             }
-            None => {
-                for d in month_days {
-                    writer.serialize(d)?;
-                }
+            self.total_record(columns, "", hours).map(|cells| {
+                let mut record = vec![label];
+                record.extend(cells);
+                record
+            })
+        };
+
+        for ((_, week), days) in &week_days {
+            for d in days {
+                let mut record = vec![period.label()];
+                record.extend(columns.iter().map(|c| self.day_column_value(d, c)));
+                writer.write_record(&record)?;
+            }
+
+            let label = i18n::tr_args("Total week {}", &[week]);
+            let hours = self.duration.format(self.day_hours(days.iter().copied()));
+            if let Some(record) = total_record(label, hours) {
+                writer.write_record(&record)?;
+            }
+        }
+
+        let all_days = week_days.values().flatten().copied();
+        let mut totals = vec![(tr("Total"), self.duration.format(self.day_hours(all_days)))];
+        if let Some(status) = target_status {
+            totals.extend(status.as_key_values(self.duration));
+        }
+        for (label, value) in totals {
+            if let Some(record) = total_record(label.to_string(), value) {
+                writer.write_record(&record)?;
             }
         }
 
@@ -817,240 +1526,1038 @@ impl ReportManager {
         Ok(())
     }
 
-    fn write_json_month_report(
+    fn write_json_month_report<'a>(
         &self,
-        month_number: u32,
-        month_days: &Vec<Day>,
-        columns: Option<MonthReportColumns>,
+        period: &ReportPeriod,
+        month_days: impl Iterator<Item = &'a Day> + Clone,
+        week_notes: &BTreeMap<(i32, u32), Vec<String>>,
+        columns: &[ReportColumn],
+        target_status: Option<&ReportTargetStatus>,
         file_path: &str,
     ) -> Result<(), Box<dyn Error>> {
         tracing::debug!("Writing JSON report");
 
-        let mut report_file = File::create(file_path)?;
+        let report_file = File::create(file_path)?;
+        let report = self.days_report_data(period, month_days, week_notes, columns, target_status);
+        serde_json::to_writer_pretty(report_file, &report)?;
 
-        match columns {
-            Some(c) => {
-                let mut weeks_map: HashMap<u32, Vec<Value>> = HashMap::new();
-
-                // Group days by week number
-                for d in month_days {
-                    let week_number = d.week();
-
-                    let formatted_start_time =
-                        self.format_datetime_to_report_string(d.starting_time());
-                    let formatted_end_time = self.format_datetime_to_report_string(d.ending_time());
-
-                    let day_json = json!({
-                        &c.third: d.date().to_string(),
-                        &c.fourth: formatted_start_time,
-                        &c.fifth: formatted_end_time,
-                        &c.sixth: d.hours(),
-                        &c.seventh: d.extra_info(),
-                        &c.eighth: d.closed()
-                    });
+        Ok(())
+    }
 
-                    weeks_map.entry(week_number).or_default().push(day_json);
-                }
+    /// Internal function to get the document of a month or range report, the days are grouped by week
+    fn days_report_data<'a>(
+        &self,
+        period: &ReportPeriod,
+        month_days: impl Iterator<Item = &'a Day> + Clone,
+        week_notes: &BTreeMap<(i32, u32), Vec<String>>,
+        columns: &[ReportColumn],
+        target_status: Option<&ReportTargetStatus>,
+    ) -> DaysReportDocument {
+        let metadata = self.days_metadata(period, month_days.clone().next());
+        let mut weeks_map: BTreeMap<(i32, u32), (Vec<DayReportRow>, f32)> = BTreeMap::new();
+
+        // Group days by year and week number
+        for d in month_days {
+            let week_number = (d.week_year(), d.week());
+            let (days, hours) = weeks_map.entry(week_number).or_default();
+            days.push(self.day_json(d, columns));
+            *hours += self.rounding.round(d.hours());
+        }
 
-                // Convert the grouped data into a structured JSON
-                let mut weeks_json = Vec::new();
-                for (week, days) in weeks_map {
-                    weeks_json.push(json!({
-                        &c.second: week,
-                        "Days": days
-                    }));
-                }
+        let total_hours: f32 = weeks_map.values().map(|(_, hours)| hours).sum();
+
+        let weeks = weeks_map
+            .into_iter()
+            .map(|((year, week), (days, hours))| WeekReport {
+                id: None,
+                week,
+                year,
+                days,
+                notes: week_notes.get(&(year, week)).cloned().unwrap_or_default(),
+                total_hours: hours,
+                target_status: None,
+            })
+            .collect();
 
-                // Construct the final JSON report
-                let json_report = json!({
-                    c.first: month_number,
-                    "Weeks": weeks_json
-                });
-                report_file.write_all(json_report.to_string().as_bytes())?;
-            }
-            None => {
-                tracing::warn!("Columns not provided! Using JSON serialization for week report");
-                unimplemented!(); //TODO: Implement this
-            }
+        DaysReportDocument {
+            format_version: K_REPORT_FORMAT_VERSION,
+            metadata,
+            period: match period {
+                ReportPeriod::Month(number) => DaysReportPeriod::Month(*number),
+                ReportPeriod::Range(..) => DaysReportPeriod::Range(period.label()),
+                ReportPeriod::Year(year) => DaysReportPeriod::Year(*year),
+            },
+            weeks,
+            total_hours,
+            target_status: target_status.cloned(),
         }
-        Ok(())
     }
 
-    fn write_yaml_month_report(
+    /// Internal function to write a YAML month or range report file, same structure as the JSON report
+    fn write_yaml_month_report<'a>(
         &self,
-        month_number: u32,
-        month_days: &Vec<Day>,
-        columns: Option<MonthReportColumns>,
+        period: &ReportPeriod,
+        month_days: impl Iterator<Item = &'a Day> + Clone,
+        week_notes: &BTreeMap<(i32, u32), Vec<String>>,
+        columns: &[ReportColumn],
+        target_status: Option<&ReportTargetStatus>,
         file_path: &str,
     ) -> Result<(), Box<dyn Error>> {
         tracing::debug!("Writing YAML report");
-        let mut report_file = File::create(file_path)?;
-
-        match columns {
-            Some(c) => {
-                let mut weeks_map: HashMap<u32, Vec<serde_yaml::Value>> = HashMap::new();
-
-                // Group days by week number
-                for d in month_days {
-                    let week_number = d.week();
-
-                    let formatted_start_time =
-                        self.format_datetime_to_report_string(d.starting_time());
-                    let formatted_end_time = self.format_datetime_to_report_string(d.ending_time());
-
-                    let mut day_yaml = serde_yaml::Mapping::new();
-                    day_yaml.insert(
-                        serde_yaml::Value::String(c.third.clone()),
-                        serde_yaml::Value::String(d.date().to_string()),
-                    );
-                    day_yaml.insert(
-                        serde_yaml::Value::String(c.fourth.clone()),
-                        serde_yaml::Value::String(formatted_start_time),
-                    );
-                    day_yaml.insert(
-                        serde_yaml::Value::String(c.fifth.clone()),
-                        serde_yaml::Value::String(formatted_end_time),
-                    );
-                    day_yaml.insert(
-                        serde_yaml::Value::String(c.sixth.clone()),
-                        serde_yaml::Value::Number(d.hours().into()),
-                    );
-                    day_yaml.insert(
-                        serde_yaml::Value::String(c.seventh.clone()),
-                        serde_yaml::Value::String(d.extra_info().to_string()),
-                    );
-                    day_yaml.insert(
-                        serde_yaml::Value::String(c.eighth.clone()),
-                        serde_yaml::Value::Bool(d.closed()),
-                    );
-
-                    weeks_map
-                        .entry(week_number)
-                        .or_default()
-                        .push(serde_yaml::Value::Mapping(day_yaml));
-                }
-
-                // Convert the grouped data into a structured YAML format
-                let mut weeks_yaml = Vec::new();
-                for (week, days) in weeks_map {
-                    let mut week_entry = serde_yaml::Mapping::new();
-                    week_entry.insert(
-                        serde_yaml::Value::String(c.second.clone()),
-                        serde_yaml::Value::Number(week.into()),
-                    );
-                    week_entry.insert(
-                        serde_yaml::Value::String("Days".to_string()),
-                        serde_yaml::Value::Sequence(days),
-                    );
-                    weeks_yaml.push(serde_yaml::Value::Mapping(week_entry));
-                }
 
-                // Construct the final YAML report
-                let mut yaml_report = serde_yaml::Mapping::new();
-                yaml_report.insert(
-                    serde_yaml::Value::String(c.first.clone()),
-                    serde_yaml::Value::Number(month_number.into()),
-                );
-                yaml_report.insert(
-                    serde_yaml::Value::String("Weeks".to_string()),
-                    serde_yaml::Value::Sequence(weeks_yaml),
-                );
+        let report_file = File::create(file_path)?;
+        let report = self.days_report_data(period, month_days, week_notes, columns, target_status);
+        serde_yaml::to_writer(report_file, &report)?;
 
-                let yaml_string = serde_yaml::to_string(&yaml_report)?;
-                report_file.write_all(yaml_string.as_bytes())?;
-            }
-            None => {
-                tracing::warn!("Columns not provided! Using YAML serialization for week report");
-                unimplemented!(); //TODO: Implement this
-            }
-        }
         Ok(())
     }
 
-    fn write_html_month_report(
+    fn write_html_month_report<'a>(
         &self,
-        month_number: u32,
-        month_days: &Vec<Day>,
-        columns: Option<MonthReportColumns>,
+        period: &ReportPeriod,
+        month_days: impl Iterator<Item = &'a Day> + Clone,
+        week_notes: &BTreeMap<(i32, u32), Vec<String>>,
+        columns: &[ReportColumn],
+        target_status: Option<&ReportTargetStatus>,
         file_path: &str,
     ) -> Result<(), Box<dyn Error>> {
         tracing::debug!("Writing HTML report");
 
         let mut file = File::create(file_path)?;
+        let metadata = self.days_metadata(period, month_days.clone().next());
 
-        match columns {
-            Some(c) => {
-                let mut weeks_map: HashMap<u32, Vec<Vec<String>>> = HashMap::new();
-
-                // Group days by week number
-                for d in month_days {
-                    let week_number = d.week();
-                    let formatted_start_time = d
-                        .starting_time()
-                        .map_or_else(|| "N/A".to_string(), |t| t.to_string());
-                    let formatted_end_time = d
-                        .ending_time()
-                        .map_or_else(|| "N/A".to_string(), |t| t.to_string());
-
-                    let row = vec![
-                        d.date().to_string(),
-                        formatted_start_time,
-                        formatted_end_time,
-                        d.hours().to_string(),
-                        d.extra_info().to_string(),
-                        d.closed().to_string(),
-                    ];
-
-                    weeks_map.entry(week_number).or_default().push(row);
-                }
+        // The week is left out of the tables, each week has its own heading
+        let day_columns: Vec<&ReportColumn> = columns
+            .iter()
+            .filter(|c| **c != ReportColumn::Week)
+            .collect();
 
-                // Build the HTML markup
-                let markup: Markup = html! {
-                    html {
-                        head {
-                            title { "Weekly Report" }
-                            style { (PreEscaped("
-                                table { border-collapse: collapse; width: 100%; }
-                                th, td { border: 1px solid black; padding: 8px; text-align: left; }
-                                th { background-color: #f2f2f2; }
-                            ")) }
-                        }
-                        body {
-                            h1 { (format!("Monthly Report - Month {}", month_number)) }
-                            @for (week, days) in &weeks_map {
-                                h2 { (format!("Week {}", week)) }
-                                table border="1" {
-                                    thead {
-                                        tr {
-                                            th { (c.third) }
-                                            th { (c.fourth) }
-                                            th { (c.fifth) }
-                                            th { (c.sixth) }
-                                            th { (c.seventh) }
-                                            th { (c.eighth) }
-                                        }
-                                    }
-                                    tbody {
-                                        @for row in days {
-                                            tr {
-                                                @for cell in row {
-                                                    td { (cell) }
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                };
-                file.write_all(markup.into_string().as_bytes())?;
-            }
-            None => {
-                tracing::warn!("Columns not provided! Using YAML serialization for week report");
-                unimplemented!(); //TODO: Implement this
-            }
+        let mut weeks_map: BTreeMap<(i32, u32), Vec<Vec<String>>> = BTreeMap::new();
+
+        // Group days by year and week number
+        for d in month_days.clone() {
+            let week_number = (d.week_year(), d.week());
+            let row = day_columns
+                .iter()
+                .map(|c| self.day_column_value(d, c))
+                .collect();
+
+            weeks_map.entry(week_number).or_default().push(row);
+        }
+
+        // Hours for the charts, per week and per day
+        let mut week_hours: BTreeMap<(i32, u32), f32> = BTreeMap::new();
+        let mut day_hours: BTreeMap<NaiveDate, f32> = BTreeMap::new();
+        for d in month_days {
+            let hours = self.rounding.round(d.hours());
+            *week_hours.entry((d.week_year(), d.week())).or_default() += hours;
+            *day_hours.entry(d.date()).or_default() += hours;
+        }
+
+        let week_bars: Vec<(String, f32)> = week_hours
+            .iter()
+            .map(|((_, week), hours)| (format!("W{}", week), *hours))
+            .collect();
+        let day_format = match period {
+            ReportPeriod::Month(_) => "%d",
+            ReportPeriod::Range(..) | ReportPeriod::Year(_) => "%m-%d",
+        };
+        let day_bars: Vec<(String, f32)> = day_hours
+            .iter()
+            .map(|(date, hours)| (date.format(day_format).to_string(), *hours))
+            .collect();
+
+        let week_chart = self.svg_bar_chart("Hours per week", &week_bars, self.week_target_hours);
+        let day_chart = self.svg_bar_chart(
+            "Hours per day",
+            &day_bars,
+            self.week_target_hours / K_WORKING_DAYS_PER_WEEK,
+        );
+
+        let total_hours: f32 = week_hours.values().sum();
+        let day_count: usize = weeks_map.values().map(|days| days.len()).sum();
+        let mut totals = vec![
+            (tr("Days"), day_count.to_string()),
+            (tr("Total hours"), self.duration.format(total_hours)),
+        ];
+        if let Some(status) = target_status {
+            totals.extend(status.as_key_values(self.duration));
+        }
+
+        // Build the HTML markup
+        let content: Markup = html! {
+            (week_chart)
+            (day_chart)
+            @for ((year, week), days) in &weeks_map {
+                h2 { (format!("Week {}, {}", week, year)) }
+                table border="1" {
+                    thead {
+                        tr {
+                            @for c in &day_columns {
+                                th { (c.header()) }
+                            }
+                        }
+                    }
+                    tbody {
+                        @for row in days {
+                            tr {
+                                @for cell in row {
+                                    td { (cell) }
+                                }
+                            }
+                        }
+                    }
+                    tfoot {
+                        tr {
+                            th colspan=(day_columns.len()) {
+                                (i18n::tr_args("Total week {}", &[week]))
+                                ": "
+                                (self.duration.format(week_hours.get(&(*year, *week)).copied().unwrap_or_default()))
+                            }
+                        }
+                    }
+                }
+                @if let Some(notes) = week_notes.get(&(*year, *week)) {
+                    ul {
+                        @for note in notes {
+                            li { (note) }
+                        }
+                    }
+                }
+            }
+        };
+        let markup = self.html_layout(&period.title(), &metadata, &[], content, &totals);
+        file.write_all(markup.into_string().as_bytes())?;
+
+        Ok(())
+    }
+
+    /// Internal function to wrap the content of an HTML report in the shared layout: the title, a header with the
+    /// details and the metadata of the report, and a footer with the totals. Empty metadata and total values are left out
+    fn html_layout(
+        &self,
+        title: &str,
+        report_metadata: &ReportMetadata,
+        metadata: &[(&str, String)],
+        content: Markup,
+        totals: &[(&str, String)],
+    ) -> Markup {
+        let mut metadata = metadata.to_vec();
+        metadata.extend(report_metadata.as_key_values(self.duration));
+
+        html! {
+            (DOCTYPE)
+            html {
+                head {
+                    meta charset="utf-8";
+                    title { (title) }
+                    style { (PreEscaped(K_HTML_STYLE)) }
+                    @if self.html_dark_mode {
+                        style { (PreEscaped(K_HTML_DARK_STYLE)) }
+                    }
+                }
+                body {
+                    header {
+                        h1 { (title) }
+                        dl class="metadata" {
+                            @for (key, value) in metadata.iter().filter(|(_, v)| !v.is_empty()) {
+                                dt { (key) }
+                                dd { (value) }
+                            }
+                        }
+                    }
+                    main { (content) }
+                    @if !totals.is_empty() {
+                        footer {
+                            table class="totals" {
+                                tbody {
+                                    @for (key, value) in totals.iter().filter(|(_, v)| !v.is_empty()) {
+                                        tr {
+                                            th { (key) }
+                                            td { (value) }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Internal function to draw an inline SVG bar chart, with a dashed line for the target
+    fn svg_bar_chart(&self, title: &str, bars: &[(String, f32)], target: f32) -> Markup {
+        let highest = bars.iter().map(|(_, h)| *h).fold(target, f32::max);
+        // Some space above the highest bar for the value
+        let scale_max = highest.max(1.0) * 1.1;
+
+        let plot_width = K_CHART_WIDTH - 2.0 * K_CHART_MARGIN;
+        let plot_height = K_CHART_HEIGHT - 2.0 * K_CHART_MARGIN;
+        let slot = plot_width / bars.len().max(1) as f32;
+        let bar_width = (slot * 0.7).min(K_CHART_MAX_BAR_WIDTH);
+        let y = |value: f32| K_CHART_MARGIN + plot_height - value / scale_max * plot_height;
+        let bottom = y(0.0);
+
+        html! {
+            h2 { (title) }
+            svg xmlns="http://www.w3.org/2000/svg" width=(K_CHART_WIDTH) height=(K_CHART_HEIGHT)
+                viewBox=(format!("0 0 {} {}", K_CHART_WIDTH, K_CHART_HEIGHT)) {
+                line x1=(K_CHART_MARGIN) y1=(bottom) x2=((K_CHART_WIDTH - K_CHART_MARGIN)) y2=(bottom) stroke="black" {}
+                line x1=(K_CHART_MARGIN) y1=(K_CHART_MARGIN) x2=(K_CHART_MARGIN) y2=(bottom) stroke="black" {}
+                text x=((K_CHART_MARGIN - 5.0)) y=(bottom) text-anchor="end" font-size="10" { "0" }
+                text x=((K_CHART_MARGIN - 5.0)) y=(y(highest)) text-anchor="end" font-size="10" { (highest) }
+                @for (i, (label, hours)) in bars.iter().enumerate() {
+                    @let x = K_CHART_MARGIN + i as f32 * slot + (slot - bar_width) / 2.0;
+                    rect x=(x) y=(y(*hours)) width=(bar_width) height=((bottom - y(*hours))) fill="#4a90d9" {}
+                    text x=((x + bar_width / 2.0)) y=((y(*hours) - 3.0)) text-anchor="middle" font-size="10" { (self.duration.format(*hours)) }
+                    text x=((x + bar_width / 2.0)) y=((bottom + 14.0)) text-anchor="middle" font-size="10" { (label) }
+                }
+                line x1=(K_CHART_MARGIN) y1=(y(target)) x2=((K_CHART_WIDTH - K_CHART_MARGIN)) y2=(y(target))
+                    stroke="red" stroke-dasharray="6,4" {}
+                text x=((K_CHART_WIDTH - K_CHART_MARGIN)) y=((y(target) - 4.0)) text-anchor="end" font-size="10" fill="red" {
+                    (format!("Target {}", target))
+                }
+            }
+        }
+    }
+
+    /// Main function to generate an overview report, day hours and project hours side by side for a month
+    pub fn generate_overview_report(
+        &self,
+        month_number: u32,
+        year: u32,
+        format: ReportFormat,
+        rows: &[OverviewRow],
+    ) -> Result<String, ReportGenerationFailure> {
+        tracing::debug!("Setting report suffix");
+        let report_suffix = match self.get_report_suffix(format.clone()) {
+            Ok(suffix) => suffix,
+            Err(_) => return Err(ReportGenerationFailure),
+        };
+
+        let file_name = format!(
+            "overview{}_{}{}",
+            month_number, self.default_report_file_name, report_suffix
+        );
+        let file_path = self.report_file_path(format!("{}/{}", self.report_dir, file_name))?;
+        if self.dry_run {
+            return Ok(file_path);
+        }
+        tracing::debug!("report file set to: {}", file_path);
+
+        let result = match format {
+            ReportFormat::Csv => {
+                self.write_csv_overview_report(month_number, year, rows, &file_path)
+            }
+            ReportFormat::Json => {
+                self.write_json_overview_report(month_number, year, rows, &file_path)
+            }
+            ReportFormat::Yaml => {
+                self.write_yaml_overview_report(month_number, year, rows, &file_path)
+            }
+            ReportFormat::Html => {
+                self.write_html_overview_report(month_number, year, rows, &file_path)
+            }
+            ReportFormat::Template(_) | ReportFormat::Renderer(_) => {
+                let report = ReportWithMetadata {
+                    metadata: &self.overview_metadata(month_number, year),
+                    report: &self.overview_report_data(month_number, year, rows),
+                };
+                self.write_data_report(&format, &report, &file_path)
+            }
+            _ => {
+                tracing::error!("Unsupported report format");
+                return Err(ReportGenerationFailure);
+            }
+        };
+
+        match result {
+            Ok(_) => {
+                tracing::info!("Created report: {}", file_path);
+                Ok(file_path)
+            }
+            Err(e) => {
+                tracing::error!("Error writing report: {}", e);
+                Err(ReportGenerationFailure)
+            }
+        }
+    }
+
+    fn write_csv_overview_report(
+        &self,
+        month_number: u32,
+        year: u32,
+        rows: &[OverviewRow],
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("Writing CSV report");
+
+        let mut writer = self.csv_writer(file_path, &self.overview_metadata(month_number, year))?;
+        for row in rows {
+            writer.serialize(row)?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    fn write_json_overview_report(
+        &self,
+        month_number: u32,
+        year: u32,
+        rows: &[OverviewRow],
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("Writing JSON report");
+
+        let mut report_file = File::create(file_path)?;
+        let json_report = ReportWithMetadata {
+            metadata: &self.overview_metadata(month_number, year),
+            report: &self.overview_report_data(month_number, year, rows),
+        };
+        serde_json::to_writer(&mut report_file, &json_report)?;
+
+        Ok(())
+    }
+
+    /// Internal function to get the data of an overview report
+    fn overview_report_data(&self, month_number: u32, year: u32, rows: &[OverviewRow]) -> Value {
+        json!({
+            "Month": month_number,
+            "Year": year,
+            "Days": rows
+        })
+    }
+
+    fn write_yaml_overview_report(
+        &self,
+        month_number: u32,
+        year: u32,
+        rows: &[OverviewRow],
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("Writing YAML report");
+
+        let mut report_file = File::create(file_path)?;
+        let mut yaml_report = serde_yaml::Mapping::new();
+        yaml_report.insert(
+            serde_yaml::Value::String("Month".to_string()),
+            serde_yaml::Value::Number(month_number.into()),
+        );
+        yaml_report.insert(
+            serde_yaml::Value::String("Year".to_string()),
+            serde_yaml::Value::Number(year.into()),
+        );
+        yaml_report.insert(
+            serde_yaml::Value::String("Days".to_string()),
+            serde_yaml::to_value(rows)?,
+        );
+
+        let yaml_string = serde_yaml::to_string(&ReportWithMetadata {
+            metadata: &self.overview_metadata(month_number, year),
+            report: &yaml_report,
+        })?;
+        report_file.write_all(yaml_string.as_bytes())?;
+
+        Ok(())
+    }
+
+    fn write_html_overview_report(
+        &self,
+        month_number: u32,
+        year: u32,
+        rows: &[OverviewRow],
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("Writing HTML report");
+
+        let mut file = File::create(file_path)?;
+
+        let day_hours = rows.iter().fold(0.0, |sum, r| sum + r.day_hours);
+        let project_hours = rows.iter().fold(0.0, |sum, r| sum + r.project_hours);
+
+        // Build the HTML markup, mismatching rows are highlighted
+        let content: Markup = html! {
+            table border="1" {
+                thead {
+                    tr {
+                        th { (tr("Date")) }
+                        th { (tr("Day hours")) }
+                        th { (tr("Project hours")) }
+                        th { (tr("Difference")) }
+                        th { (tr("Reconciled")) }
+                    }
+                }
+                tbody {
+                    @for row in rows {
+                        tr class=(if row.reconciled { "" } else { "mismatch" }) {
+                            td { (row.date) }
+                            td { (self.duration.format(row.day_hours)) }
+                            td { (self.duration.format(row.project_hours)) }
+                            td { (self.duration.format(row.difference)) }
+                            td { (if row.reconciled { "Yes" } else { "No" }) }
+                        }
+                    }
+                }
+            }
+        };
+        let markup = self.html_layout(
+            &format!("Overview Report - Month {}, {}", month_number, year),
+            &self.overview_metadata(month_number, year),
+            &[
+                (tr("Month"), month_number.to_string()),
+                (tr("Year"), year.to_string()),
+            ],
+            content,
+            &[
+                (tr("Day hours"), self.duration.format(day_hours)),
+                (tr("Project hours"), self.duration.format(project_hours)),
+                (
+                    tr("Difference"),
+                    self.duration.format(day_hours - project_hours),
+                ),
+            ],
+        );
+        file.write_all(markup.into_string().as_bytes())?;
+
+        Ok(())
+    }
+
+    /// Main function to generate a timesheet report, projects as rows and the days of the week as columns with
+    /// totals per row and column. Supported formats are CSV, HTML and XLSX
+    pub fn generate_timesheet_report(
+        &self,
+        timesheet: &Timesheet,
+        format: ReportFormat,
+    ) -> Result<String, ReportGenerationFailure> {
+        tracing::debug!("Setting report suffix");
+        let report_suffix = match self.get_report_suffix(format.clone()) {
+            Ok(suffix) => suffix,
+            Err(_) => return Err(ReportGenerationFailure),
+        };
+
+        let file_name = format!(
+            "timesheet{}-{}_{}{}",
+            timesheet.year, timesheet.week, self.default_report_file_name, report_suffix
+        );
+        let file_path = self.report_file_path(format!("{}/{}", self.report_dir, file_name))?;
+        if self.dry_run {
+            return Ok(file_path);
+        }
+        tracing::debug!("report file set to: {}", file_path);
+
+        let result = match format {
+            ReportFormat::Csv => self.write_csv_timesheet_report(timesheet, &file_path),
+            ReportFormat::Html => self.write_html_timesheet_report(timesheet, &file_path),
+            ReportFormat::Xlsx => self.write_xlsx_timesheet_report(timesheet, &file_path),
+            _ => {
+                tracing::error!("Unsupported report format");
+                return Err(ReportGenerationFailure);
+            }
+        };
+
+        match result {
+            Ok(_) => {
+                tracing::info!("Created report: {}", file_path);
+                Ok(file_path)
+            }
+            Err(e) => {
+                tracing::error!("Error writing report: {}", e);
+                Err(ReportGenerationFailure)
+            }
+        }
+    }
+
+    fn write_csv_timesheet_report(
+        &self,
+        timesheet: &Timesheet,
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("Writing CSV report");
+
+        let mut writer = self.csv_writer(file_path, &self.timesheet_metadata(timesheet))?;
+        let mut header = vec![tr("Project").to_string()];
+        header.extend(timesheet.day_headers());
+        header.push(tr("Total").to_string());
+        writer.write_record(&header)?;
+
+        for row in &timesheet.rows {
+            let mut record = vec![row.project.clone()];
+            record.extend(row.hours.iter().map(|h| self.duration.format(*h)));
+            record.push(self.duration.format(row.total()));
+            writer.write_record(&record)?;
+        }
+
+        let mut totals = vec![tr("Total").to_string()];
+        totals.extend(
+            timesheet
+                .day_totals()
+                .iter()
+                .map(|h| self.duration.format(*h)),
+        );
+        totals.push(self.duration.format(timesheet.total()));
+        if self.csv_totals {
+            writer.write_record(&totals)?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    fn write_html_timesheet_report(
+        &self,
+        timesheet: &Timesheet,
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("Writing HTML report");
+
+        let mut file = File::create(file_path)?;
+
+        let content: Markup = html! {
+            table border="1" {
+                thead {
+                    tr {
+                        th { (tr("Project")) }
+                        @for header in timesheet.day_headers() {
+                            th { (header) }
+                        }
+                        th { (tr("Total")) }
+                    }
+                }
+                tbody {
+                    @for row in &timesheet.rows {
+                        tr {
+                            td { (row.project) }
+                            @for hours in row.hours {
+                                td { (self.duration.format(hours)) }
+                            }
+                            td class="total" { (self.duration.format(row.total())) }
+                        }
+                    }
+                }
+                tfoot {
+                    tr {
+                        td { (tr("Total")) }
+                        @for hours in timesheet.day_totals() {
+                            td { (self.duration.format(hours)) }
+                        }
+                        td { (self.duration.format(timesheet.total())) }
+                    }
+                }
+            }
+        };
+        let markup = self.html_layout(
+            &i18n::tr_args(
+                "Timesheet - Week {}, {}",
+                &[&timesheet.week, &timesheet.year],
+            ),
+            &self.timesheet_metadata(timesheet),
+            &[
+                (tr("Week"), timesheet.week.to_string()),
+                (tr("Year"), timesheet.year.to_string()),
+            ],
+            content,
+            &[(tr("Total hours"), self.duration.format(timesheet.total()))],
+        );
+        file.write_all(markup.into_string().as_bytes())?;
+
+        Ok(())
+    }
+
+    /// Internal support function to get hours as a worksheet cell, a number for decimal hours and text for hh:mm
+    fn hours_cell(&self, hours: f32) -> XlsxCell {
+        match self.duration {
+            DurationFormat::Decimal => XlsxCell::Number(hours),
+            DurationFormat::Clock => XlsxCell::Text(self.duration.format(hours)),
+        }
+    }
+
+    fn write_xlsx_timesheet_report(
+        &self,
+        timesheet: &Timesheet,
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("Writing XLSX report");
+
+        let mut header = vec![XlsxCell::Text(tr("Project").to_string())];
+        header.extend(timesheet.day_headers().into_iter().map(XlsxCell::Text));
+        header.push(XlsxCell::Text(tr("Total").to_string()));
+
+        let mut rows = vec![header];
+        for row in &timesheet.rows {
+            let mut cells = vec![XlsxCell::Text(row.project.clone())];
+            cells.extend(row.hours.iter().map(|h| self.hours_cell(*h)));
+            cells.push(self.hours_cell(row.total()));
+            rows.push(cells);
+        }
+
+        let mut totals = vec![XlsxCell::Text(tr("Total").to_string())];
+        totals.extend(timesheet.day_totals().iter().map(|h| self.hours_cell(*h)));
+        totals.push(self.hours_cell(timesheet.total()));
+        rows.push(totals);
+
+        // The metadata is put below the timesheet, so the table starts in the first row
+        rows.push(Vec::new());
+        for (key, value) in self
+            .timesheet_metadata(timesheet)
+            .as_key_values(self.duration)
+        {
+            rows.push(vec![XlsxCell::Text(key.to_string()), XlsxCell::Text(value)]);
+        }
+
+        xlsx::write_workbook(
+            file_path,
+            &format!("Week {} {}", timesheet.week, timesheet.year),
+            &rows,
+        )
+    }
+
+    /// Main function to export the rows of a listing to the given file, the format is given by the extension.
+    /// Only CSV, JSON and YAML are supported
+    pub fn export_list(
+        &self,
+        file_path: &str,
+        listed: &ListedRows,
+    ) -> Result<(), ReportGenerationFailure> {
+        let extension = std::path::Path::new(file_path)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        let format = match extension.as_str() {
+            "yml" => ReportFormat::Yaml,
+            other => other.parse::<ReportFormat>().map_err(|_| {
+                tracing::error!("Unsupported export file extension: '{}'", extension);
+                ReportGenerationFailure
+            })?,
+        };
+
+        let result = match format {
+            ReportFormat::Csv => self.write_csv_list_export(listed, file_path),
+            ReportFormat::Json => self.write_json_list_export(listed, file_path),
+            ReportFormat::Yaml => self.write_yaml_list_export(listed, file_path),
+            _ => {
+                tracing::error!("Unsupported export format, use csv, json or yaml");
+                return Err(ReportGenerationFailure);
+            }
+        };
+
+        result.map_err(|e| {
+            tracing::error!("Error writing export: {}", e);
+            ReportGenerationFailure
+        })?;
+        tracing::info!("Exported list to: {}", file_path);
+        Ok(())
+    }
+
+    fn write_csv_list_export(
+        &self,
+        listed: &ListedRows,
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("Writing CSV export");
+
+        let mut writer = Writer::from_path(file_path)?;
+        writer.write_record(&listed.headers)?;
+        for row in &listed.rows {
+            writer.write_record(row)?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    fn write_json_list_export(
+        &self,
+        listed: &ListedRows,
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("Writing JSON export");
+
+        let rows: Vec<Value> = listed
+            .rows
+            .iter()
+            .map(|row| {
+                Value::Object(
+                    listed
+                        .headers
+                        .iter()
+                        .cloned()
+                        .zip(row.iter().map(|v| Value::String(v.clone())))
+                        .collect(),
+                )
+            })
+            .collect();
+
+        let mut export_file = File::create(file_path)?;
+        export_file.write_all(Value::Array(rows).to_string().as_bytes())?;
+
+        Ok(())
+    }
+
+    fn write_yaml_list_export(
+        &self,
+        listed: &ListedRows,
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("Writing YAML export");
+
+        // A mapping keeps the column order
+        let rows: Vec<serde_yaml::Mapping> = listed
+            .rows
+            .iter()
+            .map(|row| {
+                listed
+                    .headers
+                    .iter()
+                    .zip(row)
+                    .map(|(h, v)| (h.as_str().into(), v.as_str().into()))
+                    .collect()
+            })
+            .collect();
+
+        let export_file = File::create(file_path)?;
+        serde_yaml::to_writer(export_file, &rows)?;
+
+        Ok(())
+    }
+
+    /// Main function to generate a summary report, only the aggregates of a report
+    pub fn generate_summary_report(
+        &self,
+        file_prefix: &str,
+        format: ReportFormat,
+        summary: &ReportSummary,
+    ) -> Result<String, ReportGenerationFailure> {
+        tracing::debug!("Setting report suffix");
+        let report_suffix = match self.get_report_suffix(format.clone()) {
+            Ok(suffix) => suffix,
+            Err(_) => return Err(ReportGenerationFailure),
+        };
+
+        let file_name = format!(
+            "{}_summary_{}{}",
+            file_prefix.replace(K_PROJECT_PATH_SEPARATOR, "-"),
+            self.default_report_file_name,
+            report_suffix
+        );
+        let file_path = self.report_file_path(format!("{}/{}", self.report_dir, file_name))?;
+        if self.dry_run {
+            return Ok(file_path);
+        }
+        tracing::debug!("report file set to: {}", file_path);
+
+        let result = match format {
+            ReportFormat::Csv => self.write_csv_summary_report(summary, &file_path),
+            ReportFormat::Json => self.write_json_summary_report(summary, &file_path),
+            ReportFormat::Yaml => self.write_yaml_summary_report(summary, &file_path),
+            ReportFormat::Html => self.write_html_summary_report(summary, &file_path),
+            ReportFormat::Template(_) | ReportFormat::Renderer(_) => serde_json::to_value(summary)
+                .map_err(|e| e.into())
+                .and_then(|data| {
+                    let report = ReportWithMetadata {
+                        metadata: &self.summary_metadata(summary),
+                        report: &data,
+                    };
+                    self.write_data_report(&format, &report, &file_path)
+                }),
+            _ => {
+                tracing::error!("Unsupported report format");
+                return Err(ReportGenerationFailure);
+            }
+        };
+
+        match result {
+            Ok(_) => {
+                tracing::info!("Created report: {}", file_path);
+                Ok(file_path)
+            }
+            Err(e) => {
+                tracing::error!("Error writing report: {}", e);
+                Err(ReportGenerationFailure)
+            }
         }
+    }
+
+    /// Generate an SVG badge with the target attainment of the week, e.g. "38.5/40h • 96%", colored by the attainment.
+    /// The badge is written to the output path, by default in the report directory, and an existing file is replaced so
+    /// the badge can be regenerated
+    pub fn generate_badge(
+        &self,
+        week: &Week,
+        output: Option<&str>,
+    ) -> Result<String, ReportGenerationFailure> {
+        let hours: f32 = week
+            .entries()
+            .iter()
+            .map(|d| self.rounding.round(d.hours()))
+            .sum();
+        let status = WeeklyTargetStatus::new(week, &week.target_hours());
+        let percentage = *status.percentage();
+
+        let message = format!(
+            "{}/{}h \u{2022} {}%",
+            self.duration.format(hours),
+            self.duration.format(*status.target_hours()),
+            percentage
+        );
+        let svg = badge::render(
+            &format!("week {}", week.number()),
+            &message,
+            badge::attainment_color(percentage),
+        );
+
+        let file_path = match output {
+            Some(path) => path.to_string(),
+            None => format!(
+                "{}/week{}_{}_badge.svg",
+                self.report_dir,
+                week.number(),
+                week.year()
+            ),
+        };
+        if let Err(e) = std::fs::write(&file_path, svg) {
+            tracing::error!("Error writing badge {}: {}", file_path, e);
+            return Err(ReportGenerationFailure);
+        }
+        tracing::info!("Created badge: {}", file_path);
+        Ok(file_path)
+    }
+
+    /// Render the summary as Markdown, e.g. to paste into a chat
+    pub fn markdown_summary(&self, summary: &ReportSummary) -> String {
+        let mut out = format!("**{}**\n\n", summary.report);
+        out.push_str(&format!(
+            "- {}: {} ({} {})\n",
+            tr("Total"),
+            self.duration.format(summary.total_hours),
+            summary.entries,
+            tr("Entries").to_lowercase()
+        ));
+        if let Some(target) = summary.target_hours {
+            out.push_str(&format!(
+                "- {}: {}\n",
+                tr("Target hours"),
+                self.duration.format(target)
+            ));
+        }
+        if let Some(attainment) = summary.target_attainment {
+            out.push_str(&format!("- {}: {}%\n", tr("Target attainment"), attainment));
+        }
+        if let Some(project_hours) = summary.project_hours {
+            out.push_str(&format!(
+                "- {}: {}\n",
+                tr("Project hours"),
+                self.duration.format(project_hours)
+            ));
+        }
+        if let Some(mismatched) = summary.mismatched_dates {
+            out.push_str(&format!("- {}: {}\n", tr("Mismatched dates"), mismatched));
+        }
+
+        // The weeks only add to the totals when there is more than one
+        if summary.weeks.len() > 1 {
+            out.push_str(&format!(
+                "\n| {} | {} | {} |\n|---|---:|---:|\n",
+                tr("Week"),
+                tr("Hours"),
+                tr("Entries")
+            ));
+            for w in &summary.weeks {
+                out.push_str(&format!(
+                    "| {}-W{:02} | {} | {} |\n",
+                    w.year,
+                    w.week,
+                    self.duration.format(w.hours),
+                    w.entries
+                ));
+            }
+        }
+        out
+    }
+
+    fn write_csv_summary_report(
+        &self,
+        summary: &ReportSummary,
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("Writing CSV report");
+
+        let mut writer = self.csv_writer(file_path, &self.summary_metadata(summary))?;
+        writer.write_record([tr("Metric"), tr("Value")])?;
+        for (key, value) in summary.as_key_values(self.duration) {
+            writer.write_record([key, value])?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    fn write_json_summary_report(
+        &self,
+        summary: &ReportSummary,
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("Writing JSON report");
+
+        let report_file = File::create(file_path)?;
+        let report = ReportWithMetadata {
+            metadata: &self.summary_metadata(summary),
+            report: summary,
+        };
+        serde_json::to_writer(report_file, &report)?;
+
+        Ok(())
+    }
+
+    fn write_yaml_summary_report(
+        &self,
+        summary: &ReportSummary,
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("Writing YAML report");
+
+        let report_file = File::create(file_path)?;
+        let report = ReportWithMetadata {
+            metadata: &self.summary_metadata(summary),
+            report: summary,
+        };
+        serde_yaml::to_writer(report_file, &report)?;
+
+        Ok(())
+    }
+
+    fn write_html_summary_report(
+        &self,
+        summary: &ReportSummary,
+        file_path: &str,
+    ) -> Result<(), Box<dyn Error>> {
+        tracing::debug!("Writing HTML report");
+
+        let mut file = File::create(file_path)?;
+        let values = summary.as_key_values(self.duration);
+
+        let content: Markup = html! {
+            table border="1" {
+                tbody {
+                    @for (key, value) in &values {
+                        tr {
+                            th { (key) }
+                            td { (value) }
+                        }
+                    }
+                }
+            }
+        };
+        let markup = self.html_layout(
+            &format!("Summary - {}", summary.report),
+            &self.summary_metadata(summary),
+            &[],
+            content,
+            &[],
+        );
+        file.write_all(markup.into_string().as_bytes())?;
+
         Ok(())
     }
 }
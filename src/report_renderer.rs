@@ -0,0 +1,165 @@
+/*
+ * File: report_renderer.rs
+ * Description: Renderers adding report formats to the built-in ones. A renderer gets the report data as JSON, the same
+ *              data as the template format, and returns the content of the report file. The configured renderers run
+ *              a shell command with the JSON on stdin and the report on stdout.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use std::error::Error;
+use std::fmt;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use serde_json::Value;
+
+use crate::config::RendererConfig;
+
+/// Built-in report formats with their descriptions, listed together with the renderers
+pub const K_BUILTIN_FORMATS: [(&str, &str); 8] = [
+    ("json", "JSON document"),
+    ("csv", "Comma separated values"),
+    ("yaml", "YAML document"),
+    ("html", "HTML page with charts"),
+    ("template", "Rendered through the --template file"),
+    ("pdf", "PDF document, the overview report only"),
+    ("text", "Plain text, the overview report only"),
+    ("xlsx", "Spreadsheet, the timesheet report only"),
+];
+
+/// Error for rendering a report
+#[derive(Debug)]
+pub struct RendererError(String);
+
+/// Implement Display trait for RendererError
+impl fmt::Display for RendererError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to render the report: {}", self.0)
+    }
+}
+
+/// Implement Error trait for RendererError
+impl Error for RendererError {}
+
+/// Renderer of a report format that isn't built in
+pub trait ReportRenderer {
+    /// Name of the format, as given to --format
+    fn name(&self) -> &str;
+
+    /// Extension of the report files
+    fn extension(&self) -> &str;
+
+    /// Description shown in the list of formats
+    fn description(&self) -> String;
+
+    /// Render the report data to the content of the report file
+    fn render(&self, report: &Value) -> Result<Vec<u8>, RendererError>;
+}
+
+/// Renderer running a shell command, the report data is written as JSON to stdin and the report is read from stdout
+pub struct CommandRenderer {
+    /// Name of the format
+    name: String,
+    /// Shell command rendering the report
+    command: String,
+    /// Extension of the report files
+    extension: String,
+}
+
+impl CommandRenderer {
+    /// Create a renderer from the configuration
+    pub fn new(config: &RendererConfig) -> Self {
+        Self {
+            name: config.name.clone(),
+            command: config.command.clone(),
+            extension: config.extension.clone(),
+        }
+    }
+}
+
+impl ReportRenderer for CommandRenderer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn extension(&self) -> &str {
+        &self.extension
+    }
+
+    fn description(&self) -> String {
+        format!("Rendered by the command: {}", self.command)
+    }
+
+    fn render(&self, report: &Value) -> Result<Vec<u8>, RendererError> {
+        tracing::debug!("Rendering the {} report with: {}", self.name, self.command);
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| RendererError(format!("could not run {}: {}", self.command, e)))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            let json = serde_json::to_vec(report)
+                .map_err(|e| RendererError(format!("could not serialize the report: {}", e)))?;
+            // The command may exit without reading everything, its exit status tells if it failed
+            if let Err(e) = stdin.write_all(&json) {
+                tracing::debug!("Writing the report to {} stopped: {}", self.command, e);
+            }
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|e| RendererError(format!("could not run {}: {}", self.command, e)))?;
+        if !output.status.success() {
+            return Err(RendererError(format!(
+                "{} failed: {}",
+                self.command,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(output.stdout)
+    }
+}
+
+/// Registry of the renderers, looked up by the format name
+#[derive(Default)]
+pub struct RendererRegistry {
+    /// Registered renderers, in the order they were registered
+    renderers: Vec<Box<dyn ReportRenderer>>,
+}
+
+impl RendererRegistry {
+    /// Register a renderer. A renderer can't replace a built-in format, and a later renderer with the same name
+    /// replaces the earlier one
+    pub fn register(&mut self, renderer: Box<dyn ReportRenderer>) -> Result<(), RendererError> {
+        let name = renderer.name().to_lowercase();
+        if name.is_empty() || K_BUILTIN_FORMATS.iter().any(|(f, _)| *f == name) {
+            return Err(RendererError(format!(
+                "invalid renderer name '{}', the name must be set and not be a built-in format",
+                renderer.name()
+            )));
+        }
+
+        self.renderers.retain(|r| r.name().to_lowercase() != name);
+        self.renderers.push(renderer);
+        Ok(())
+    }
+
+    /// Get the renderer of the format, the name is case insensitive
+    pub fn get(&self, name: &str) -> Option<&dyn ReportRenderer> {
+        self.renderers
+            .iter()
+            .find(|r| r.name().eq_ignore_ascii_case(name))
+            .map(|r| r.as_ref())
+    }
+
+    /// The registered renderers
+    pub fn renderers(&self) -> impl Iterator<Item = &dyn ReportRenderer> {
+        self.renderers.iter().map(|r| r.as_ref())
+    }
+}
@@ -0,0 +1,273 @@
+/*
+ * File: report_template.rs
+ * Description: Minimal template engine for the template report format. Supports the Tera/Jinja basics:
+ *              {{ variable }}, {% for x in list %}...{% endfor %}, {% if x %}...{% else %}...{% endif %} and {# comments #}.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use serde_json::Value;
+use std::error::Error;
+use std::fmt;
+
+/// Error for template parsing and rendering
+#[derive(Debug)]
+pub struct TemplateError(String);
+
+/// Implement Display trait for TemplateError
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "template error: {}", self.0)
+    }
+}
+
+/// Implement Error trait for TemplateError
+impl Error for TemplateError {}
+
+/// Node in the parsed template
+#[derive(Debug)]
+enum Node {
+    Text(String),
+    Variable(String),
+    For {
+        name: String,
+        list: String,
+        body: Vec<Node>,
+    },
+    If {
+        condition: String,
+        negate: bool,
+        then: Vec<Node>,
+        otherwise: Vec<Node>,
+    },
+}
+
+/// Token of the template source
+#[derive(Debug)]
+enum Token {
+    Text(String),
+    Variable(String),
+    Tag(String),
+}
+
+/// Internal function to split the template source into tokens, comments are dropped
+fn tokenize(source: &str) -> Result<Vec<Token>, TemplateError> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+
+    while let Some(start) = rest.find('{') {
+        let (close, kind) = match rest[start..].chars().nth(1) {
+            Some('{') => ("}}", 'v'),
+            Some('%') => ("%}", 't'),
+            Some('#') => ("#}", 'c'),
+            _ => {
+                tokens.push(Token::Text(rest[..start + 1].to_string()));
+                rest = &rest[start + 1..];
+                continue;
+            }
+        };
+
+        if start > 0 {
+            tokens.push(Token::Text(rest[..start].to_string()));
+        }
+
+        let inner = &rest[start + 2..];
+        let end = inner
+            .find(close)
+            .ok_or_else(|| TemplateError(format!("unclosed '{}'", &rest[start..start + 2])))?;
+        let content = inner[..end].trim().to_string();
+
+        match kind {
+            'v' => tokens.push(Token::Variable(content)),
+            't' => tokens.push(Token::Tag(content)),
+            _ => {}
+        }
+        rest = &inner[end + 2..];
+    }
+
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest.to_string()));
+    }
+
+    Ok(tokens)
+}
+
+/// Internal function to parse tokens into nodes, until one of the end tags is found. Returns the nodes and the end tag
+fn parse_nodes(
+    tokens: &mut std::vec::IntoIter<Token>,
+    end_tags: &[&str],
+) -> Result<(Vec<Node>, Option<String>), TemplateError> {
+    let mut nodes = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text)),
+            Token::Variable(path) => nodes.push(Node::Variable(path)),
+            Token::Tag(tag) => {
+                if end_tags.contains(&tag.as_str()) {
+                    return Ok((nodes, Some(tag)));
+                }
+
+                let words: Vec<&str> = tag.split_whitespace().collect();
+                match words.as_slice() {
+                    ["for", name, "in", list] => {
+                        let (body, end) = parse_nodes(tokens, &["endfor"])?;
+                        if end.is_none() {
+                            return Err(TemplateError(format!("missing endfor for '{}'", tag)));
+                        }
+                        nodes.push(Node::For {
+                            name: name.to_string(),
+                            list: list.to_string(),
+                            body,
+                        });
+                    }
+                    ["if", condition] | ["if", "not", condition] => {
+                        let negate = words.len() == 3;
+                        let (then, end) = parse_nodes(tokens, &["else", "endif"])?;
+                        let otherwise = match end.as_deref() {
+                            Some("else") => {
+                                let (otherwise, end) = parse_nodes(tokens, &["endif"])?;
+                                if end.is_none() {
+                                    return Err(TemplateError(format!(
+                                        "missing endif for '{}'",
+                                        tag
+                                    )));
+                                }
+                                otherwise
+                            }
+                            Some(_) => Vec::new(),
+                            None => {
+                                return Err(TemplateError(format!("missing endif for '{}'", tag)))
+                            }
+                        };
+                        nodes.push(Node::If {
+                            condition: condition.to_string(),
+                            negate,
+                            then,
+                            otherwise,
+                        });
+                    }
+                    _ => return Err(TemplateError(format!("unsupported tag '{}'", tag))),
+                }
+            }
+        }
+    }
+
+    Ok((nodes, None))
+}
+
+/// Internal function to look up a dotted path, the innermost loop variable is searched first and then the data
+fn lookup<'a>(path: &str, scopes: &'a [(String, Value)], data: &'a Value) -> Option<&'a Value> {
+    let mut parts = path.split('.');
+    let first = parts.next()?;
+
+    let mut value = match scopes.iter().rev().find(|(name, _)| name == first) {
+        Some((_, v)) => v,
+        None => data.get(first)?,
+    };
+
+    for part in parts {
+        value = match part.parse::<usize>() {
+            Ok(index) => value.get(index)?,
+            Err(_) => value.get(part)?,
+        };
+    }
+
+    Some(value)
+}
+
+/// Internal function to check if a value is true, empty values and zero are false
+fn is_truthy(value: Option<&Value>) -> bool {
+    match value {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(b)) => *b,
+        Some(Value::Number(n)) => n.as_f64().map(|n| n != 0.0).unwrap_or(false),
+        Some(Value::String(s)) => !s.is_empty(),
+        Some(Value::Array(a)) => !a.is_empty(),
+        Some(Value::Object(o)) => !o.is_empty(),
+    }
+}
+
+/// Internal function to escape the HTML special characters
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Internal function to render the nodes into the output
+fn render_nodes(
+    nodes: &[Node],
+    scopes: &mut Vec<(String, Value)>,
+    data: &Value,
+    escape: bool,
+    output: &mut String,
+) -> Result<(), TemplateError> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Variable(path) => {
+                let value = lookup(path, scopes, data)
+                    .ok_or_else(|| TemplateError(format!("unknown variable '{}'", path)))?;
+                let text = match value {
+                    Value::String(s) => s.clone(),
+                    Value::Null => String::new(),
+                    other => other.to_string(),
+                };
+                if escape {
+                    output.push_str(&escape_html(&text));
+                } else {
+                    output.push_str(&text);
+                }
+            }
+            Node::For { name, list, body } => {
+                let items = match lookup(list, scopes, data) {
+                    Some(Value::Array(items)) => items.clone(),
+                    _ => return Err(TemplateError(format!("'{}' is not a list", list))),
+                };
+
+                let count = items.len();
+                for (i, item) in items.into_iter().enumerate() {
+                    let loop_info = serde_json::json!({
+                        "index": i + 1,
+                        "first": i == 0,
+                        "last": i + 1 == count,
+                    });
+                    scopes.push(("loop".to_string(), loop_info));
+                    scopes.push((name.clone(), item));
+                    let res = render_nodes(body, scopes, data, escape, output);
+                    scopes.truncate(scopes.len() - 2);
+                    res?;
+                }
+            }
+            Node::If {
+                condition,
+                negate,
+                then,
+                otherwise,
+            } => {
+                let truthy = is_truthy(lookup(condition, scopes, data));
+                if truthy != *negate {
+                    render_nodes(then, scopes, data, escape, output)?;
+                } else {
+                    render_nodes(otherwise, scopes, data, escape, output)?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Render the template with the report data. Variables are HTML escaped when escape is set
+pub fn render(source: &str, data: &Value, escape: bool) -> Result<String, TemplateError> {
+    let mut tokens = tokenize(source)?.into_iter();
+    let (nodes, _) = parse_nodes(&mut tokens, &[])?;
+
+    let mut output = String::new();
+    render_nodes(&nodes, &mut Vec::new(), data, escape, &mut output)?;
+
+    Ok(output)
+}
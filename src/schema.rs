@@ -0,0 +1,257 @@
+/*
+ * File: schema.rs
+ * Description: JSON Schemas of the import document and the JSON day reports, and validation of documents against them.
+ *              The validation supports the subset of JSON Schema used by the schemas: type, enum, required, properties,
+ *              additionalProperties, items, anyOf and local $ref.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use serde_json::Value;
+use std::error::Error;
+use std::fmt;
+
+/// JSON Schema of the document printed by the importer commands
+const K_IMPORT_SCHEMA: &str = include_str!("../doc/schema/import.schema.json");
+/// JSON Schema of the JSON week, month, range and year reports
+const K_REPORT_SCHEMA: &str = include_str!("../doc/schema/report.schema.json");
+/// Names of the documents with a schema, as given to "schema dump"
+pub const K_SCHEMA_DOCUMENTS: [&str; 2] = ["import", "report"];
+/// Max number of problems in the error, the rest are counted
+const K_MAX_SCHEMA_PROBLEMS: usize = 10;
+
+/// Error for a document not matching its schema, with the location of each problem
+#[derive(Debug)]
+pub struct SchemaError(String);
+
+/// Implement Display trait for SchemaError
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Implement Error trait for SchemaError
+impl Error for SchemaError {}
+
+/// Get the schema of a document, "import" or "report"
+pub fn schema(document: &str) -> Option<&'static str> {
+    match document {
+        "import" => Some(K_IMPORT_SCHEMA),
+        "report" => Some(K_REPORT_SCHEMA),
+        _ => None,
+    }
+}
+
+/// Validate an import document, the error lists the problems with their JSON pointer, e.g. "/days/2/pause"
+pub fn validate_import(document: &Value) -> Result<(), SchemaError> {
+    validate(document, K_IMPORT_SCHEMA)
+}
+
+/// Internal function to validate a document against a schema
+fn validate(document: &Value, schema: &str) -> Result<(), SchemaError> {
+    let root: Value =
+        serde_json::from_str(schema).map_err(|e| SchemaError(format!("invalid schema: {}", e)))?;
+    let mut problems = Vec::new();
+    check(document, &root, &root, "", &mut problems);
+    if problems.is_empty() {
+        return Ok(());
+    }
+
+    let count = problems.len();
+    problems.truncate(K_MAX_SCHEMA_PROBLEMS);
+    let mut message = problems.join(", ");
+    if count > K_MAX_SCHEMA_PROBLEMS {
+        message.push_str(&format!(" and {} more", count - K_MAX_SCHEMA_PROBLEMS));
+    }
+    Err(SchemaError(message))
+}
+
+/// Internal function to get the JSON Schema type name of a value
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Internal function to check if a value has a JSON Schema type, an integer is also a number
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        _ => type_name(value) == name,
+    }
+}
+
+/// Internal function to resolve a local reference, e.g. "#/$defs/day"
+fn resolve<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    reference
+        .strip_prefix('#')
+        .and_then(|pointer| root.pointer(pointer))
+}
+
+/// Internal function to check a value against a schema, adding the problems at the location
+fn check(value: &Value, schema: &Value, root: &Value, location: &str, problems: &mut Vec<String>) {
+    let at = if location.is_empty() { "/" } else { location };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        match resolve(root, reference) {
+            Some(target) => check(value, target, root, location, problems),
+            None => problems.push(format!("{}: unknown schema reference {}", at, reference)),
+        }
+        return;
+    }
+
+    if let Some(branches) = schema.get("anyOf").and_then(Value::as_array) {
+        let matches = branches.iter().any(|branch| {
+            let mut branch_problems = Vec::new();
+            check(value, branch, root, location, &mut branch_problems);
+            branch_problems.is_empty()
+        });
+        if !matches {
+            problems.push(format!(
+                "{}: doesn't match any of the allowed documents",
+                at
+            ));
+        }
+        return;
+    }
+
+    if let Some(allowed) = schema.get("type") {
+        let names: Vec<&str> = match allowed {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !names.iter().any(|name| has_type(value, name)) {
+            problems.push(format!(
+                "{}: expected {}, found {}",
+                at,
+                names.join(" or "),
+                type_name(value)
+            ));
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            let names: Vec<String> = allowed.iter().map(Value::to_string).collect();
+            problems.push(format!(
+                "{}: expected one of {}, found {}",
+                at,
+                names.join(", "),
+                value
+            ));
+        }
+    }
+
+    if let Value::Object(fields) = value {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !fields.contains_key(key) {
+                    problems.push(format!("{}: missing required key \"{}\"", at, key));
+                }
+            }
+        }
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let closed = schema.get("additionalProperties") == Some(&Value::Bool(false));
+        for (key, field) in fields {
+            let field_location = format!("{}/{}", location, key);
+            match properties.and_then(|p| p.get(key)) {
+                Some(field_schema) => check(field, field_schema, root, &field_location, problems),
+                None if closed => problems.push(format!("{}: unknown key", field_location)),
+                None => {}
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (index, item) in items.iter().enumerate() {
+            check(
+                item,
+                item_schema,
+                root,
+                &format!("{}/{}", location, index),
+                problems,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn import_document_matching_the_schema_is_valid() {
+        let document = json!({
+            "days": [{ "date": "2026-10-12", "start": "08:00", "end": "17:00", "pause": 0.5 }],
+            "entries": [{ "project": "acme", "hours": "2.5", "description": null }]
+        });
+        assert!(validate_import(&document).is_ok());
+        assert!(validate_import(&json!({})).is_ok());
+    }
+
+    #[test]
+    fn import_problems_are_reported_with_their_location() {
+        let document = json!({
+            "days": [{ "date": "2026-10-12" }, { "start": "08:00", "pause": true }],
+            "entries": [{ "project": "acme", "hours": 2, "hour": 3 }]
+        });
+        let message = validate_import(&document).unwrap_err().to_string();
+        assert!(message.contains("/days/1: missing required key \"date\""));
+        assert!(message.contains("/days/1/pause: expected number or string or null, found boolean"));
+        assert!(message.contains("/entries/0/hour: unknown key"));
+        assert!(!message.contains("/days/0"));
+
+        let message = validate_import(&json!({ "days": {} }))
+            .unwrap_err()
+            .to_string();
+        assert_eq!(message, "/days: expected array, found object");
+        let message = validate_import(&json!([])).unwrap_err().to_string();
+        assert_eq!(message, "/: expected object, found array");
+    }
+
+    #[test]
+    fn report_schema_accepts_week_and_month_documents() {
+        let metadata = json!({
+            "generated_at": "2026-10-14T09:30:00+02:00",
+            "report": "Week 42, 2026",
+            "week_target_hours": 40.0,
+            "month_target_hours": 160.0,
+            "tool_version": "2.0.0"
+        });
+        let week = json!({
+            "format_version": 2,
+            "metadata": metadata,
+            "week": 42,
+            "year": 2026,
+            "days": [{ "date": "2026-10-12", "hours": 8.0, "ending_days_after": 1, "closed": true }],
+            "total_hours": 8.0,
+            "target_status": {
+                "target_hours": 40.0, "worked_hours": 8.0, "remaining_hours": 32.0, "percentage": 20,
+                "status": "NotReached"
+            }
+        });
+        assert!(validate(&week, K_REPORT_SCHEMA).is_ok());
+
+        let month = json!({
+            "format_version": 2,
+            "metadata": metadata,
+            "month": 10,
+            "weeks": [{ "week": 42, "year": 2026, "days": [], "total_hours": 0.0 }],
+            "total_hours": 0.0
+        });
+        assert!(validate(&month, K_REPORT_SCHEMA).is_ok());
+
+        let old = json!({ "FormatVersion": 1, "Week": 42 });
+        assert!(validate(&old, K_REPORT_SCHEMA).is_err());
+    }
+}
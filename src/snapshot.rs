@@ -0,0 +1,260 @@
+/*
+ * File: snapshot.rs
+ * Description: Snapshots of locked periods. The snapshot is written as a read-only JSON file together with its SHA-256
+ *              digest, and optionally a detached signature made with gpg, to prove what was reported.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::process::Command;
+
+/// Initial hash values of SHA-256
+const K_SHA256_INIT: [u32; 8] = [
+    0x6a09_e667,
+    0xbb67_ae85,
+    0x3c6e_f372,
+    0xa54f_f53a,
+    0x510e_527f,
+    0x9b05_688c,
+    0x1f83_d9ab,
+    0x5be0_cd19,
+];
+
+/// Round constants of SHA-256
+const K_SHA256_ROUNDS: [u32; 64] = [
+    0x428a_2f98,
+    0x7137_4491,
+    0xb5c0_fbcf,
+    0xe9b5_dba5,
+    0x3956_c25b,
+    0x59f1_11f1,
+    0x923f_82a4,
+    0xab1c_5ed5,
+    0xd807_aa98,
+    0x1283_5b01,
+    0x2431_85be,
+    0x550c_7dc3,
+    0x72be_5d74,
+    0x80de_b1fe,
+    0x9bdc_06a7,
+    0xc19b_f174,
+    0xe49b_69c1,
+    0xefbe_4786,
+    0x0fc1_9dc6,
+    0x240c_a1cc,
+    0x2de9_2c6f,
+    0x4a74_84aa,
+    0x5cb0_a9dc,
+    0x76f9_88da,
+    0x983e_5152,
+    0xa831_c66d,
+    0xb003_27c8,
+    0xbf59_7fc7,
+    0xc6e0_0bf3,
+    0xd5a7_9147,
+    0x06ca_6351,
+    0x1429_2967,
+    0x27b7_0a85,
+    0x2e1b_2138,
+    0x4d2c_6dfc,
+    0x5338_0d13,
+    0x650a_7354,
+    0x766a_0abb,
+    0x81c2_c92e,
+    0x9272_2c85,
+    0xa2bf_e8a1,
+    0xa81a_664b,
+    0xc24b_8b70,
+    0xc76c_51a3,
+    0xd192_e819,
+    0xd699_0624,
+    0xf40e_3585,
+    0x106a_a070,
+    0x19a4_c116,
+    0x1e37_6c08,
+    0x2748_774c,
+    0x34b0_bcb5,
+    0x391c_0cb3,
+    0x4ed8_aa4a,
+    0x5b9c_ca4f,
+    0x682e_6ff3,
+    0x748f_82ee,
+    0x78a5_636f,
+    0x84c8_7814,
+    0x8cc7_0208,
+    0x90be_fffa,
+    0xa450_6ceb,
+    0xbef9_a3f7,
+    0xc671_78f2,
+];
+
+/// Error for writing a snapshot
+#[derive(Debug)]
+pub struct SnapshotError(String);
+
+/// Implement Display trait for SnapshotError
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to write snapshot: {}", self.0)
+    }
+}
+
+/// Implement Error trait for SnapshotError
+impl Error for SnapshotError {}
+
+/// Internal function to calculate the SHA-256 digest of the data, as a lowercase hex string
+fn sha256_hex(data: &[u8]) -> String {
+    let mut state = K_SHA256_INIT;
+
+    // Padding: a single 1 bit, zeros and the message length in bits, to a multiple of 64 bytes
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend(((data.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K_SHA256_ROUNDS[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+
+        for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *s = s.wrapping_add(v);
+        }
+    }
+
+    state.iter().map(|s| format!("{:08x}", s)).collect()
+}
+
+/// Internal function to write a new read-only file, an existing file is never replaced
+fn write_read_only(path: &Path, content: &[u8]) -> Result<(), SnapshotError> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(path)
+        .map_err(|e| SnapshotError(format!("could not create {}: {}", path.display(), e)))?;
+    file.write_all(content)
+        .map_err(|e| SnapshotError(format!("could not write {}: {}", path.display(), e)))?;
+
+    let mut permissions = file
+        .metadata()
+        .map_err(|e| SnapshotError(e.to_string()))?
+        .permissions();
+    permissions.set_readonly(true);
+    fs::set_permissions(path, permissions).map_err(|e| SnapshotError(e.to_string()))
+}
+
+/// Write the snapshot to the directory as `<name>.json`, with the digest in `<name>.json.sha256` in the format of
+/// `sha256sum`. If signing, a detached ASCII armored signature `<name>.json.asc` is made with the default gpg key.
+/// Returns the paths of the written files
+pub fn write_snapshot(
+    directory: &str,
+    name: &str,
+    content: &str,
+    sign: bool,
+) -> Result<Vec<String>, SnapshotError> {
+    fs::create_dir_all(directory)
+        .map_err(|e| SnapshotError(format!("could not create {}: {}", directory, e)))?;
+
+    let file_name = format!("{}.json", name);
+    let snapshot_path = Path::new(directory).join(&file_name);
+    write_read_only(&snapshot_path, content.as_bytes())?;
+
+    let digest_path = Path::new(directory).join(format!("{}.sha256", file_name));
+    let digest = format!("{}  {}\n", sha256_hex(content.as_bytes()), file_name);
+    write_read_only(&digest_path, digest.as_bytes())?;
+
+    let mut written = vec![
+        snapshot_path.display().to_string(),
+        digest_path.display().to_string(),
+    ];
+
+    if sign {
+        let signature_path = Path::new(directory).join(format!("{}.asc", file_name));
+        tracing::debug!("Signing {} with gpg", snapshot_path.display());
+        let output = Command::new("gpg")
+            .arg("--batch")
+            .arg("--armor")
+            .arg("--detach-sign")
+            .arg("--output")
+            .arg(&signature_path)
+            .arg(&snapshot_path)
+            .output()
+            .map_err(|e| SnapshotError(format!("could not run gpg: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(SnapshotError(format!(
+                "gpg signing failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        written.push(signature_path.display().to_string());
+    }
+
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_matches_the_known_answers() {
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        // The length doesn't fit in the first block, the padding needs a second block
+        assert_eq!(
+            sha256_hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+        assert_eq!(
+            sha256_hex(b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu"),
+            "cf5b16a778af8380036ce59e7b0492370b249b11e8f07a51afac45037afee9d1"
+        );
+    }
+}
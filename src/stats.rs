@@ -0,0 +1,233 @@
+/*
+ * File: stats.rs
+ * Description: Statistics of the stored time data, and the terminal bar charts used to visualize them.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Timelike, Weekday};
+use std::collections::BTreeMap;
+
+use crate::day::Day;
+
+/// Working days per week, used to get a daily target from the week target
+pub const K_WORKING_DAYS_PER_WEEK: f32 = 5.0;
+
+/// Number of months shown in the month-over-month trend
+pub const K_TREND_MONTHS: usize = 12;
+
+/// Width in characters of the longest bar
+const K_CHART_WIDTH: usize = 40;
+
+/// Partial blocks, one to seven eighths of a character
+const K_PARTIAL_BLOCKS: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+const K_FULL_BLOCK: char = '█';
+const K_TARGET_MARKER: char = '┊';
+
+/// Render a horizontal bar chart of hours, with the target marked in each row
+pub fn render_bar_chart(title: &str, bars: &[(String, f32)], target: f32) -> String {
+    let scale_max = bars
+        .iter()
+        .map(|(_, hours)| *hours)
+        .fold(target, f32::max)
+        .max(1.0);
+    let label_width = bars.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let target_column =
+        ((target / scale_max * K_CHART_WIDTH as f32).round() as usize).min(K_CHART_WIDTH);
+
+    let mut out = format!("{}\n", title);
+
+    for (label, hours) in bars {
+        let cells = hours.max(0.0) / scale_max * K_CHART_WIDTH as f32;
+        let full = cells.floor() as usize;
+        let eighths = ((cells - full as f32) * 8.0).round() as usize;
+
+        let mut row = [' '; K_CHART_WIDTH + 1];
+        for c in row.iter_mut().take(full) {
+            *c = K_FULL_BLOCK;
+        }
+        if eighths == 8 {
+            row[full] = K_FULL_BLOCK;
+        } else if eighths > 0 {
+            row[full] = K_PARTIAL_BLOCKS[eighths - 1];
+        }
+        // The marker is only visible where the bar doesn't reach the target
+        if row[target_column] == ' ' {
+            row[target_column] = K_TARGET_MARKER;
+        }
+
+        out.push_str(&format!(
+            "{:>width$} │{} {}h\n",
+            label,
+            row.iter().collect::<String>(),
+            hours,
+            width = label_width
+        ));
+    }
+
+    out.push_str(&format!(
+        "{:>width$}  {}▲ target {}h\n",
+        "",
+        " ".repeat(target_column),
+        target,
+        width = label_width
+    ));
+
+    out
+}
+
+/// Struct to hold the statistics of the stored days
+#[derive(Debug)]
+pub struct DayStatistics {
+    /// Number of worked days, days with hours
+    pub worked_days: usize,
+    /// Average starting time of the days
+    pub average_start: Option<NaiveTime>,
+    /// Average ending time of the days
+    pub average_end: Option<NaiveTime>,
+    /// Average hours of the worked days
+    pub average_hours: f32,
+    /// Longest streak of worked days, with first and last date. Weekends don't break a streak
+    pub longest_streak: Option<(usize, NaiveDate, NaiveDate)>,
+    /// Weekday with the most average hours
+    pub most_worked_weekday: Option<(Weekday, f32)>,
+    /// Weekday with the least average hours
+    pub least_worked_weekday: Option<(Weekday, f32)>,
+    /// Total hours per month, oldest first
+    pub months: Vec<MonthTotal>,
+}
+
+/// Struct to hold the total of a month, with the change compared to the month before
+#[derive(Debug)]
+pub struct MonthTotal {
+    pub year: i32,
+    pub month: u32,
+    pub hours: f32,
+    /// Change in hours compared to the previous month with days
+    pub change: Option<f32>,
+}
+
+/// Internal function to get the average of times, as time of day
+fn average_time(times: &[NaiveTime]) -> Option<NaiveTime> {
+    if times.is_empty() {
+        return None;
+    }
+
+    let total: u64 = times
+        .iter()
+        .map(|t| u64::from(t.num_seconds_from_midnight()))
+        .sum();
+    NaiveTime::from_num_seconds_from_midnight_opt((total / times.len() as u64) as u32, 0)
+}
+
+/// Internal function to get the next working day, Friday is followed by Monday
+fn next_working_day(date: NaiveDate) -> NaiveDate {
+    let mut next = date + Duration::days(1);
+    while matches!(next.weekday(), Weekday::Sat | Weekday::Sun) {
+        next += Duration::days(1);
+    }
+    next
+}
+
+/// Internal function to find the longest streak of worked dates, the dates must be sorted
+fn longest_streak(dates: &[NaiveDate]) -> Option<(usize, NaiveDate, NaiveDate)> {
+    let first = *dates.first()?;
+    let mut best = (1, first, first);
+    let mut current = (1, first, first);
+
+    for pair in dates.windows(2) {
+        let (previous, date) = (pair[0], pair[1]);
+        // Days worked on a weekend also continue the streak
+        if date == previous + Duration::days(1) || date == next_working_day(previous) {
+            current = (current.0 + 1, current.1, date);
+        } else {
+            current = (1, date, date);
+        }
+        if current.0 > best.0 {
+            best = current;
+        }
+    }
+
+    Some(best)
+}
+
+/// Compute the statistics of the days, only days with hours are included. None if there are no such days
+pub fn day_statistics<'a>(days: impl IntoIterator<Item = &'a Day>) -> Option<DayStatistics> {
+    let mut worked: Vec<&Day> = days.into_iter().filter(|d| d.hours() > 0.0).collect();
+    if worked.is_empty() {
+        return None;
+    }
+    worked.sort_by_key(|d| d.date());
+
+    let starts: Vec<NaiveTime> = worked
+        .iter()
+        .filter_map(|d| d.starting_time().map(|t| t.time()))
+        .collect();
+    let ends: Vec<NaiveTime> = worked
+        .iter()
+        .filter_map(|d| d.ending_time().map(|t| t.time()))
+        .collect();
+
+    let total_hours: f32 = worked.iter().map(|d| d.hours()).sum();
+
+    // Hours per date, a date can in theory be registered more than once
+    let mut date_hours: BTreeMap<NaiveDate, f32> = BTreeMap::new();
+    for d in &worked {
+        *date_hours.entry(d.date()).or_default() += d.hours();
+    }
+    let dates: Vec<NaiveDate> = date_hours.keys().copied().collect();
+
+    // Average hours per weekday, Monday first
+    let mut weekday_hours: BTreeMap<u32, (f32, usize)> = BTreeMap::new();
+    for (date, hours) in &date_hours {
+        let entry = weekday_hours
+            .entry(date.weekday().num_days_from_monday())
+            .or_default();
+        entry.0 += hours;
+        entry.1 += 1;
+    }
+    let weekday_averages: Vec<(Weekday, f32)> = weekday_hours
+        .iter()
+        .filter_map(|(day, (hours, count))| {
+            Weekday::try_from(*day as u8)
+                .ok()
+                .map(|w| (w, hours / *count as f32))
+        })
+        .collect();
+    let most_worked_weekday = weekday_averages
+        .iter()
+        .copied()
+        .max_by(|a, b| a.1.total_cmp(&b.1));
+    let least_worked_weekday = weekday_averages
+        .iter()
+        .copied()
+        .min_by(|a, b| a.1.total_cmp(&b.1));
+
+    let mut month_hours: BTreeMap<(i32, u32), f32> = BTreeMap::new();
+    for (date, hours) in &date_hours {
+        *month_hours.entry((date.year(), date.month())).or_default() += hours;
+    }
+    let mut months: Vec<MonthTotal> = Vec::new();
+    for ((year, month), hours) in month_hours {
+        let change = months.last().map(|previous| hours - previous.hours);
+        months.push(MonthTotal {
+            year,
+            month,
+            hours,
+            change,
+        });
+    }
+
+    Some(DayStatistics {
+        worked_days: date_hours.len(),
+        average_start: average_time(&starts),
+        average_end: average_time(&ends),
+        average_hours: total_hours / date_hours.len() as f32,
+        longest_streak: longest_streak(&dates),
+        most_worked_weekday,
+        least_worked_weekday,
+        months,
+    })
+}
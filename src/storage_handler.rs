@@ -6,28 +6,47 @@
  * License: MIT
  */
 
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
 use std::fs;
+use std::hash::{Hash, Hasher};
 use std::io;
 use std::io::Read;
 use std::io::Write;
 use std::path::Path;
+use std::rc::Rc;
 
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
 use crate::backup_organizer::BackupOrganizer;
+use crate::clock::Clock;
 use crate::config::AppConfiguration;
-use crate::project::Project;
-use crate::version_info::FileStorageMetadata;
-use crate::week::Week;
+use crate::paths;
+use crate::period_lock::PeriodMetadata;
+use crate::project::{Project, ProjectV1};
+use crate::sync::SyncState;
+use crate::trash::Trash;
+use crate::version_info::{FileStorageMetadata, K_LEGACY_STORAGE_FILE_VERSION};
+use crate::week::{Week, WeekV1};
 
 // Constants for base paths
-const BASE_PATH: &str = ".local/time-butler";
 const STORAGE_DIR: &str = ".app_storage";
 const REPORT_DIR: &str = "generated-reports";
 const PROJECT_DATA_FILE: &str = "prj_data.bin";
 const WEEK_DATA_FILE: &str = "week_data.bin";
 const BACKUP_DIR: &str = ".backups";
 const STORAGE_METADATA_FILE: &str = "metadata.json";
+const PERIOD_METADATA_FILE: &str = "period_metadata.json";
+const TRASH_FILE: &str = "trash.json";
+const SYNC_STATE_FILE: &str = "sync_state.json";
+/// Suffix of the journal next to a data file, with the items changed since the data file was written
+pub const K_JOURNAL_SUFFIX: &str = ".journal";
+/// Max number of records in a journal, the data file is written again with all items when exceeded
+const K_MAX_JOURNAL_RECORDS: usize = 64;
+/// Size of the checksum of the data file first in a journal and of the length before each record
+const K_JOURNAL_HEADER_SIZE: usize = 8;
+const K_JOURNAL_LENGTH_SIZE: usize = 4;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct VersionInformationWrapper {
@@ -35,6 +54,277 @@ struct VersionInformationWrapper {
     version_information: FileStorageMetadata,
 }
 
+/// State of a stored data file and its journal, used to only write the changed items
+struct StoredFile<K> {
+    /// Checksum per item, of the loaded or last stored items
+    items: HashMap<K, u64>,
+    /// Checksum of the data file, None if it isn't written or loaded, the next store then writes all items
+    file_checksum: Option<u64>,
+    /// Number of records in the journal of the data file
+    journal_records: usize,
+}
+
+impl<K> Default for StoredFile<K> {
+    fn default() -> Self {
+        Self {
+            items: HashMap::new(),
+            file_checksum: None,
+            journal_records: 0,
+        }
+    }
+}
+
+/// State of the stored projects and weeks
+#[derive(Default)]
+struct StoredChecksums {
+    /// Projects, per project ID
+    projects: StoredFile<uuid::Uuid>,
+    /// Weeks, per week number and year
+    weeks: StoredFile<(u32, i32)>,
+}
+
+/// Record in a journal, an added or changed item or the key of a removed item
+#[derive(Serialize, Deserialize)]
+enum JournalRecord<K, T> {
+    Stored(T),
+    Removed(K),
+}
+
+/// Storage files which exist but couldn't be loaded, they are never overwritten
+#[derive(Default)]
+struct LoadFailures {
+    /// The project file couldn't be read or deserialized
+    projects: bool,
+    /// The week file couldn't be read or deserialized
+    weeks: bool,
+}
+
+/// Internal function to refuse storing over an existing file which couldn't be loaded, the items in memory don't
+/// contain the items of the file
+fn check_not_failed_to_load(load_failed: bool, path: &str) -> io::Result<()> {
+    if load_failed && Path::new(path).exists() {
+        tracing::error!("File {} could not be loaded, not overwritten", path);
+        return Err(io::Error::other(format!(
+            "{} could not be loaded, it is not overwritten",
+            path
+        )));
+    }
+    Ok(())
+}
+
+/// Internal function to get the checksums of the serialized items, keyed with the given function
+fn checksums<T, K, F>(items: &[T], key: F) -> io::Result<HashMap<K, u64>>
+where
+    T: Serialize,
+    K: Hash + Eq,
+    F: Fn(&T) -> K,
+{
+    let mut sums = HashMap::new();
+    for item in items {
+        let bytes = bincode::serialize(item)
+            .map_err(|e| io::Error::other(format!("Serialization error: {}", e)))?;
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        sums.insert(key(item), hasher.finish());
+    }
+    Ok(sums)
+}
+
+/// Internal function to get the records of the changed, added and removed items compared to the stored checksums
+fn changed_records<'a, T, K, F>(
+    items: &'a [T],
+    stored: &HashMap<K, u64>,
+    current: &HashMap<K, u64>,
+    key: F,
+) -> Vec<JournalRecord<K, &'a T>>
+where
+    K: Hash + Eq + Clone,
+    F: Fn(&T) -> K,
+{
+    let mut records: Vec<JournalRecord<K, &T>> = items
+        .iter()
+        .filter(|item| {
+            let k = key(item);
+            stored.get(&k) != current.get(&k)
+        })
+        .map(JournalRecord::Stored)
+        .collect();
+    records.extend(
+        stored
+            .keys()
+            .filter(|k| !current.contains_key(*k))
+            .cloned()
+            .map(JournalRecord::Removed),
+    );
+    records
+}
+
+/// Get the FNV-1a checksum of the data, stable between builds unlike the std hasher, e.g. of a data file
+pub fn stable_checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Internal function to get the journal path of a data file
+fn journal_path(path: &str) -> String {
+    format!("{}{}", path, K_JOURNAL_SUFFIX)
+}
+
+/// Internal function to apply the journal of a data file to its items, returns the number of applied records. A
+/// journal of another version of the data file, left by an interrupted write, is ignored. An incomplete last record,
+/// left by an interrupted append, is ignored
+fn apply_journal<T, K, F>(path: &str, data: &[u8], items: &mut Vec<T>, key: F) -> io::Result<usize>
+where
+    T: DeserializeOwned,
+    K: DeserializeOwned + Eq,
+    F: Fn(&T) -> K,
+{
+    let journal = match fs::read(journal_path(path)) {
+        Ok(journal) => journal,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    let header = journal
+        .get(..K_JOURNAL_HEADER_SIZE)
+        .and_then(|h| h.try_into().ok())
+        .map(u64::from_le_bytes);
+    if header != Some(stable_checksum(data)) {
+        tracing::warn!("Journal of {} is not of the stored file, ignored", path);
+        return Ok(0);
+    }
+
+    let mut applied = 0;
+    let mut position = K_JOURNAL_HEADER_SIZE;
+    while position + K_JOURNAL_LENGTH_SIZE <= journal.len() {
+        let length_bytes = &journal[position..position + K_JOURNAL_LENGTH_SIZE];
+        let length = u32::from_le_bytes(length_bytes.try_into().unwrap_or_default()) as usize;
+        let start = position + K_JOURNAL_LENGTH_SIZE;
+        let Some(bytes) = journal.get(start..start + length) else {
+            break;
+        };
+        let record: JournalRecord<K, T> = bincode::deserialize(bytes).map_err(|e| {
+            io::Error::other(format!("Invalid record in the journal of {}: {}", path, e))
+        })?;
+        match record {
+            JournalRecord::Stored(item) => {
+                let k = key(&item);
+                match items.iter_mut().find(|i| key(i) == k) {
+                    Some(existing) => *existing = item,
+                    None => items.push(item),
+                }
+            }
+            JournalRecord::Removed(k) => items.retain(|i| key(i) != k),
+        }
+        applied += 1;
+        position = start + length;
+    }
+    if position != journal.len() {
+        tracing::warn!("Incomplete last record in the journal of {}, ignored", path);
+    }
+    Ok(applied)
+}
+
+/// Internal function to append records to the journal of a data file, a new journal starts with the checksum of the
+/// data file it belongs to
+fn append_journal<K: Serialize, T: Serialize>(
+    path: &str,
+    file_checksum: u64,
+    new_journal: bool,
+    records: &[JournalRecord<K, &T>],
+) -> io::Result<()> {
+    let mut data = Vec::new();
+    if new_journal {
+        data.extend_from_slice(&file_checksum.to_le_bytes());
+    }
+    for record in records {
+        let bytes = bincode::serialize(record)
+            .map_err(|e| io::Error::other(format!("Serialization error: {}", e)))?;
+        data.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+        data.extend_from_slice(&bytes);
+    }
+
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(!new_journal)
+        .truncate(new_journal)
+        .open(journal_path(path))?;
+    file.write_all(&data)?;
+    file.sync_all()
+}
+
+/// Internal function to store the items, only the changed items are appended to the journal of the data file. The
+/// data file is written with all items instead when it isn't loaded or written, or the journal is full. Returns the
+/// number of changed items
+fn store_items<T, K, F>(
+    path: &str,
+    items: &[T],
+    stored: &mut StoredFile<K>,
+    key: F,
+) -> io::Result<usize>
+where
+    T: Serialize,
+    K: Serialize + Hash + Eq + Clone,
+    F: Fn(&T) -> K,
+{
+    let current = checksums(items, &key)?;
+    let records = changed_records(items, &stored.items, &current, &key);
+    if records.is_empty() {
+        return Ok(0);
+    }
+
+    match stored.file_checksum {
+        Some(checksum) if stored.journal_records + records.len() <= K_MAX_JOURNAL_RECORDS => {
+            append_journal(path, checksum, stored.journal_records == 0, &records)?;
+            stored.journal_records += records.len();
+        }
+        _ => {
+            let serialized_data = bincode::serialize(items)
+                .map_err(|e| io::Error::other(format!("Serialization error: {}", e)))?;
+            write_atomically(path, &serialized_data)?;
+            // A journal left if removing it fails is ignored when loaded, it isn't of the written file
+            if let Err(e) = fs::remove_file(journal_path(path)) {
+                if e.kind() != io::ErrorKind::NotFound {
+                    tracing::warn!("Failed to remove the journal of {}: {}", path, e);
+                }
+            }
+            stored.file_checksum = Some(stable_checksum(&serialized_data));
+            stored.journal_records = 0;
+        }
+    }
+    let changed = records.len();
+    stored.items = current;
+    Ok(changed)
+}
+
+/// Internal function to deserialize the projects in the layout of the storage file version, None for the current layout
+fn deserialize_projects(buffer: &[u8], version: Option<&str>) -> bincode::Result<Vec<Project>> {
+    match version {
+        Some(K_LEGACY_STORAGE_FILE_VERSION) => bincode::deserialize::<Vec<ProjectV1>>(buffer)
+            .map(|projects| projects.into_iter().map(Project::from).collect()),
+        _ => bincode::deserialize(buffer),
+    }
+}
+
+/// Internal function to deserialize the weeks in the layout of the storage file version, None for the current layout
+fn deserialize_weeks(buffer: &[u8], version: Option<&str>) -> bincode::Result<Vec<Week>> {
+    match version {
+        Some(K_LEGACY_STORAGE_FILE_VERSION) => bincode::deserialize::<Vec<WeekV1>>(buffer)
+            .map(|weeks| weeks.into_iter().map(Week::from).collect()),
+        _ => bincode::deserialize(buffer),
+    }
+}
+
+/// Internal function to write a file through a temporary file, an interrupted write never leaves a partial file
+fn write_atomically(path: &str, data: &[u8]) -> io::Result<()> {
+    let tmp_path = format!("{}.tmp", path);
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, path)
+}
+
 /// The storage handler struct
 pub struct StorageHandler {
     /// Path to the project storage file
@@ -45,13 +335,19 @@ pub struct StorageHandler {
     storage_dir: String,
     /// Report directory
     report_dir: String,
-    /// User home directory
-    user_home_dir: String,
+    /// Base directory for all data, given by the environment or the home directory
+    data_dir: String,
     /// Flag to indicate if a successful init has been done
     init_success: bool,
     /// Flag to indicate if this is the first run
     first_run: bool,
     backup_organizer: BackupOrganizer,
+    /// Checksums of the loaded or last stored items
+    stored: StoredChecksums,
+    /// Storage file version of the files to load when older than the current, the items are converted when loaded
+    legacy_file_version: Option<String>,
+    /// Existing storage files which couldn't be loaded
+    load_failures: LoadFailures,
 }
 
 /// Implementation for StorageHandler functionality
@@ -62,10 +358,13 @@ impl StorageHandler {
             week_data_file_path: WEEK_DATA_FILE.to_string(),
             storage_dir: STORAGE_DIR.to_string(),
             report_dir: REPORT_DIR.to_string(),
-            user_home_dir: String::new(),
+            data_dir: String::new(),
             init_success: false,
             first_run: false,
             backup_organizer: BackupOrganizer::new("", "", "", ""), // dummy
+            stored: StoredChecksums::default(),
+            legacy_file_version: None,
+            load_failures: LoadFailures::default(),
         };
         instance.init();
 
@@ -80,7 +379,7 @@ impl StorageHandler {
     }
 
     /// Load projects from storage
-    pub fn load_projects(&self) -> Option<Vec<Project>> {
+    pub fn load_projects(&mut self) -> Option<Vec<Project>> {
         if !self.init_success {
             tracing::error!("Storage handler not initialized correctly, unable to load projects");
             return None;
@@ -99,26 +398,45 @@ impl StorageHandler {
             return None;
         }
 
-        let mut file = fs::File::open(prj_file)
-            .map_err(|e| io::Error::other(format!("Error opening file: {}", e)))
-            .ok()?;
-
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer).ok()?;
+        // Cleared when the file is loaded
+        self.load_failures.projects = true;
+        let buffer = match fs::read(&prj_file) {
+            Ok(buffer) => buffer,
+            Err(e) => {
+                tracing::error!("Error reading file {}: {}", prj_file, e);
+                return None;
+            }
+        };
 
-        let projects = match bincode::deserialize(&buffer) {
+        let mut projects = match deserialize_projects(&buffer, self.legacy_file_version.as_deref())
+        {
             Ok(projects) => projects,
             Err(e) => {
                 tracing::error!("Error deserializing data: {}", e);
                 return None;
             }
         };
+        let journal_records = match apply_journal(&prj_file, &buffer, &mut projects, |p| *p.id()) {
+            Ok(records) => records,
+            Err(e) => {
+                tracing::error!("Error reading the journal of {}: {}", prj_file, e);
+                return None;
+            }
+        };
 
+        self.stored.projects = StoredFile {
+            items: checksums(&projects, |p| *p.id()).ok()?,
+            file_checksum: Some(stable_checksum(&buffer)),
+            journal_records,
+        };
+        self.load_failures.projects = false;
         Some(projects)
     }
 
-    /// Store projects to storage
-    pub fn store_projects(&self, projects: Vec<Project>) -> io::Result<()> {
+    /// Store projects to storage, only the projects changed since loaded or last stored are written, appended to the
+    /// journal of the file. An existing file which couldn't be loaded is not overwritten. Returns true if anything was
+    /// written
+    pub fn store_projects(&mut self, projects: &[Project]) -> io::Result<bool> {
         if !self.init_success {
             tracing::error!("Storage handler not initialized correctly, unable to store projects");
             return Err(io::Error::other(
@@ -126,15 +444,41 @@ impl StorageHandler {
             ));
         }
 
+        check_not_failed_to_load(self.load_failures.projects, &self.project_data_file_path)?;
+
         let prj_file = self.project_data_file_path.clone();
-        tracing::debug!("Storing projects to file: {}", prj_file);
+        let changed = store_items(&prj_file, projects, &mut self.stored.projects, |p| *p.id())?;
+        if changed == 0 {
+            tracing::debug!("No projects changed, project file not written");
+            return Ok(false);
+        }
+        tracing::debug!(
+            "Stored projects to file: {}, {} projects changed",
+            prj_file,
+            changed
+        );
 
-        let serialized_data = bincode::serialize(&projects)
-            .map_err(|e| io::Error::other(format!("Serialization error: {}", e)))?;
-        let mut file = fs::File::create(prj_file)?;
-        file.write_all(&serialized_data)?;
+        Ok(true)
+    }
 
-        Ok(())
+    /// Set the source of the current time used by the backups
+    pub fn set_clock(&mut self, clock: Rc<dyn Clock>) {
+        self.backup_organizer.set_clock(clock);
+    }
+
+    /// Check if an existing project or week file couldn't be loaded
+    pub fn load_failed(&self) -> bool {
+        self.load_failures.projects || self.load_failures.weeks
+    }
+
+    /// Set the storage file version of the files to load, None when the files have the current layout
+    pub fn set_legacy_file_version(&mut self, version: Option<String>) {
+        self.legacy_file_version = version;
+    }
+
+    /// Forget the checksums of the loaded or last stored items, so the next store writes the files with all items
+    pub fn reset_stored_checksums(&mut self) {
+        self.stored = StoredChecksums::default();
     }
 
     /// Create the storage directory if not existing
@@ -165,7 +509,7 @@ impl StorageHandler {
     }
 
     /// Load weeks from storage
-    pub fn load_weeks(&self) -> Option<Vec<Week>> {
+    pub fn load_weeks(&mut self) -> Option<Vec<Week>> {
         if !self.init_success {
             tracing::error!("Storage handler not initialized correctly, unable to load weeks");
             return None;
@@ -180,26 +524,98 @@ impl StorageHandler {
             return None;
         }
 
-        let mut file = fs::File::open(week_file)
-            .map_err(|e| io::Error::other(format!("Error opening file: {}", e)))
-            .ok()?;
-
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer).ok()?;
+        // Cleared when the file is loaded
+        self.load_failures.weeks = true;
+        let buffer = match fs::read(&week_file) {
+            Ok(buffer) => buffer,
+            Err(e) => {
+                tracing::error!("Error reading file {}: {}", week_file, e);
+                return None;
+            }
+        };
 
-        let weeks = match bincode::deserialize(&buffer) {
+        let mut weeks = match deserialize_weeks(&buffer, self.legacy_file_version.as_deref()) {
             Ok(weeks) => weeks,
             Err(e) => {
                 tracing::error!("Error deserializing data: {}", e);
                 return None;
             }
         };
-
+        let journal_records =
+            match apply_journal(&week_file, &buffer, &mut weeks, |w| (w.number(), w.year())) {
+                Ok(records) => records,
+                Err(e) => {
+                    tracing::error!("Error reading the journal of {}: {}", week_file, e);
+                    return None;
+                }
+            };
+
+        self.stored.weeks = StoredFile {
+            items: checksums(&weeks, |w| (w.number(), w.year())).ok()?,
+            file_checksum: Some(stable_checksum(&buffer)),
+            journal_records,
+        };
+        self.load_failures.weeks = false;
         Some(weeks)
     }
 
-    /// Store weeks to storage
-    pub fn store_weeks(&self, weeks: Vec<Week>) -> io::Result<()> {
+    /// Read the projects and weeks of another storage, e.g. copied from another machine. The directory is the storage
+    /// directory with the data files, or the data directory it is in. A missing data file has no items. Files of an
+    /// older storage file version, given by the metadata next to them, are converted
+    pub fn read_other_storage(directory: &str) -> io::Result<(Vec<Project>, Vec<Week>)> {
+        let has_data =
+            |dir: &Path| dir.join(PROJECT_DATA_FILE).exists() || dir.join(WEEK_DATA_FILE).exists();
+        let storage_dir = if has_data(Path::new(directory)) {
+            Path::new(directory).to_path_buf()
+        } else if has_data(&Path::new(directory).join(STORAGE_DIR)) {
+            Path::new(directory).join(STORAGE_DIR)
+        } else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("no {} or {} found", PROJECT_DATA_FILE, WEEK_DATA_FILE),
+            ));
+        };
+
+        let read = |file: &str| -> io::Result<Option<Vec<u8>>> {
+            let path = storage_dir.join(file);
+            if !path.exists() {
+                return Ok(None);
+            }
+            tracing::debug!("Reading {}", path.display());
+            fs::read(&path).map(Some)
+        };
+        let version = read(STORAGE_METADATA_FILE)?
+            .and_then(|json| serde_json::from_slice::<VersionInformationWrapper>(&json).ok())
+            .map(|wrapper| wrapper.version_information.storage_file_version)
+            .filter(|version| version == K_LEGACY_STORAGE_FILE_VERSION);
+        let projects = match read(PROJECT_DATA_FILE)? {
+            Some(buffer) => {
+                let mut projects =
+                    deserialize_projects(&buffer, version.as_deref()).map_err(io::Error::other)?;
+                let path = storage_dir.join(PROJECT_DATA_FILE);
+                apply_journal(&path.to_string_lossy(), &buffer, &mut projects, |p| *p.id())?;
+                projects
+            }
+            None => Vec::new(),
+        };
+        let weeks = match read(WEEK_DATA_FILE)? {
+            Some(buffer) => {
+                let mut weeks =
+                    deserialize_weeks(&buffer, version.as_deref()).map_err(io::Error::other)?;
+                let path = storage_dir.join(WEEK_DATA_FILE);
+                apply_journal(&path.to_string_lossy(), &buffer, &mut weeks, |w| {
+                    (w.number(), w.year())
+                })?;
+                weeks
+            }
+            None => Vec::new(),
+        };
+        Ok((projects, weeks))
+    }
+
+    /// Store weeks to storage, only the weeks changed since loaded or last stored are written, appended to the journal
+    /// of the file. An existing file which couldn't be loaded is not overwritten. Returns true if anything was written
+    pub fn store_weeks(&mut self, weeks: &[Week]) -> io::Result<bool> {
         if !self.init_success {
             tracing::error!("Storage handler not initialized correctly, unable to store weeks");
             return Err(io::Error::other(
@@ -212,17 +628,23 @@ impl StorageHandler {
             self.create_storage_dir()?;
         }
 
-        let week_file = self.week_data_file_path.clone();
-        tracing::debug!("Storing projects to file: {}", week_file);
+        check_not_failed_to_load(self.load_failures.weeks, &self.week_data_file_path)?;
 
-        let serialized_data = bincode::serialize(&weeks).map_err(|e| {
-            //io::Error::new(io::ErrorKind::Other, format!("Serialization error: {}", e))
-            io::Error::other(format!("Serialization error: {}", e))
+        let week_file = self.week_data_file_path.clone();
+        let changed = store_items(&week_file, weeks, &mut self.stored.weeks, |w| {
+            (w.number(), w.year())
         })?;
-        let mut file = fs::File::create(week_file)?;
-        file.write_all(&serialized_data)?;
+        if changed == 0 {
+            tracing::debug!("No weeks changed, week file not written");
+            return Ok(false);
+        }
+        tracing::debug!(
+            "Stored weeks to file: {}, {} weeks changed",
+            week_file,
+            changed
+        );
 
-        Ok(())
+        Ok(true)
     }
 
     fn init(&mut self) {
@@ -231,24 +653,18 @@ impl StorageHandler {
             tracing::warn!("Non Linux OS detected. Time-butler is developed on and for Linux. Other OSes are not maintained at the same level. You may encounter unknown issues.");
         }
 
-        // Todo: Check if a configuration file exists, if not set default paths
-        if let Some(home_dir) = dirs::home_dir() {
+        // The default paths, replaced by the configuration later on
+        if let Some(data_dir) = paths::data_directory() {
             // Set struct default paths
             self.project_data_file_path = format!(
-                "{}/{}/{}/{}",
-                home_dir.display(),
-                BASE_PATH,
-                self.storage_dir,
-                self.project_data_file_path
+                "{}/{}/{}",
+                data_dir, self.storage_dir, self.project_data_file_path
             );
             self.week_data_file_path = format!(
-                "{}/{}/{}/{}",
-                home_dir.display(),
-                BASE_PATH,
-                self.storage_dir,
-                self.week_data_file_path
+                "{}/{}/{}",
+                data_dir, self.storage_dir, self.week_data_file_path
             );
-            self.storage_dir = format!("{}/{}/{}", home_dir.display(), BASE_PATH, self.storage_dir);
+            self.storage_dir = format!("{}/{}", data_dir, self.storage_dir);
 
             // Check if this is the first run
             if fs::metadata(&self.storage_dir).is_err() {
@@ -263,9 +679,9 @@ impl StorageHandler {
                 fs::create_dir_all(&self.storage_dir).expect("Failed to create storage directory");
             }
 
-            self.report_dir = format!("{}/{}/{}", home_dir.display(), BASE_PATH, self.report_dir);
+            self.report_dir = format!("{}/{}", data_dir, self.report_dir);
 
-            self.user_home_dir = format!("{}", home_dir.display());
+            self.data_dir = data_dir;
 
             tracing::debug!("Default paths set");
             self.init_success = true;
@@ -274,12 +690,32 @@ impl StorageHandler {
         }
     }
 
+    /// Getter for `project_data_file_path`
+    pub fn project_data_file_path(&self) -> &str {
+        &self.project_data_file_path
+    }
+
+    /// Getter for `week_data_file_path`
+    pub fn week_data_file_path(&self) -> &str {
+        &self.week_data_file_path
+    }
+
+    /// Directory of the backups
+    pub fn backup_directory(&self) -> &str {
+        self.backup_organizer.backup_dir()
+    }
+
+    /// Time of the last backup, None if no backup is done
+    pub fn last_backup(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.backup_organizer.last_backup()
+    }
+
     pub fn startup_storage_directory(&self) -> String {
         self.storage_dir.clone()
     }
 
-    pub fn user_home_directory(&self) -> String {
-        self.user_home_dir.clone()
+    pub fn data_directory(&self) -> String {
+        self.data_dir.clone()
     }
 
     pub fn set_paths_from_config(&mut self, config: &AppConfiguration) {
@@ -386,4 +822,162 @@ impl StorageHandler {
         file.write_all(json.as_bytes())?;
         Ok(())
     }
+
+    /// Load the period metadata, the default (no locked periods) is used if the file doesn't exist
+    pub fn load_period_metadata(&self) -> Result<PeriodMetadata, Box<dyn std::error::Error>> {
+        let path_str = format!(
+            "{}/{}/{}",
+            self.storage_dir, STORAGE_DIR, PERIOD_METADATA_FILE
+        );
+        if fs::metadata(&path_str).is_err() {
+            tracing::debug!("File {} does not exist, no locked periods", path_str);
+            return Ok(PeriodMetadata::default());
+        }
+
+        let json = fs::read_to_string(&path_str)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn write_period_metadata(
+        &self,
+        metadata: &PeriodMetadata,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_string_pretty(metadata)?;
+        let path_str = format!(
+            "{}/{}/{}",
+            self.storage_dir, STORAGE_DIR, PERIOD_METADATA_FILE
+        );
+        let mut file = fs::File::create(Path::new(&path_str))?;
+        file.write_all(json.as_bytes())?;
+        Ok(())
+    }
+
+    /// Load the trash, an empty trash is used if the file doesn't exist
+    pub fn load_trash(&self) -> Result<Trash, Box<dyn std::error::Error>> {
+        let path_str = format!("{}/{}/{}", self.storage_dir, STORAGE_DIR, TRASH_FILE);
+        if fs::metadata(&path_str).is_err() {
+            tracing::debug!("File {} does not exist, trash is empty", path_str);
+            return Ok(Trash::default());
+        }
+
+        let json = fs::read_to_string(&path_str)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn write_trash(&self, trash: &Trash) -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_string_pretty(trash)?;
+        let path_str = format!("{}/{}/{}", self.storage_dir, STORAGE_DIR, TRASH_FILE);
+        write_atomically(&path_str, json.as_bytes())?;
+        Ok(())
+    }
+
+    /// Load the state of the last sync, an empty state is used if the file doesn't exist
+    pub fn load_sync_state(&self) -> Result<SyncState, Box<dyn std::error::Error>> {
+        let path_str = format!("{}/{}/{}", self.storage_dir, STORAGE_DIR, SYNC_STATE_FILE);
+        if fs::metadata(&path_str).is_err() {
+            tracing::debug!("File {} does not exist, nothing synced yet", path_str);
+            return Ok(SyncState::default());
+        }
+
+        let json = fs::read_to_string(&path_str)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    pub fn write_sync_state(&self, state: &SyncState) -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_string_pretty(state)?;
+        let path_str = format!("{}/{}/{}", self.storage_dir, STORAGE_DIR, SYNC_STATE_FILE);
+        write_atomically(&path_str, json.as_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Path of a data file in a new temporary directory
+    fn data_file(name: &str) -> String {
+        let dir =
+            std::env::temp_dir().join(format!("time-butler-{}-{}", name, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.join("data.bin").to_string_lossy().to_string()
+    }
+
+    /// Load the items of a data file and its journal, as when the storage is loaded
+    fn load(path: &str) -> (Vec<(u32, String)>, usize) {
+        let data = fs::read(path).unwrap();
+        let mut items: Vec<(u32, String)> = bincode::deserialize(&data).unwrap();
+        let records = apply_journal(path, &data, &mut items, |i| i.0).unwrap();
+        (items, records)
+    }
+
+    fn item(key: u32, value: &str) -> (u32, String) {
+        (key, value.to_string())
+    }
+
+    #[test]
+    fn only_the_changed_items_are_appended_to_the_journal() {
+        let path = data_file("journal");
+        let mut stored = StoredFile::default();
+        let mut items = vec![item(1, "a"), item(2, "b"), item(3, "c")];
+        assert_eq!(store_items(&path, &items, &mut stored, |i| i.0).unwrap(), 3);
+        assert!(!Path::new(&journal_path(&path)).exists());
+        let data_file_bytes = fs::read(&path).unwrap();
+
+        items[1].1 = "changed".to_string();
+        items.remove(2);
+        items.push(item(4, "d"));
+        assert_eq!(store_items(&path, &items, &mut stored, |i| i.0).unwrap(), 3);
+        assert_eq!(store_items(&path, &items, &mut stored, |i| i.0).unwrap(), 0);
+        assert_eq!(fs::read(&path).unwrap(), data_file_bytes);
+        assert_eq!(stored.journal_records, 3);
+
+        let (loaded, records) = load(&path);
+        assert_eq!(loaded, items);
+        assert_eq!(records, 3);
+        let _ = fs::remove_dir_all(Path::new(&path).parent().unwrap());
+    }
+
+    #[test]
+    fn full_journal_is_written_to_the_data_file() {
+        let path = data_file("compaction");
+        let mut stored = StoredFile::default();
+        let mut items = vec![item(1, "0")];
+        store_items(&path, &items, &mut stored, |i| i.0).unwrap();
+        for n in 1..=K_MAX_JOURNAL_RECORDS {
+            items[0].1 = n.to_string();
+            store_items(&path, &items, &mut stored, |i| i.0).unwrap();
+        }
+        assert_eq!(stored.journal_records, K_MAX_JOURNAL_RECORDS);
+
+        items[0].1 = "last".to_string();
+        store_items(&path, &items, &mut stored, |i| i.0).unwrap();
+        assert_eq!(stored.journal_records, 0);
+        assert!(!Path::new(&journal_path(&path)).exists());
+        assert_eq!(load(&path), (items, 0));
+        let _ = fs::remove_dir_all(Path::new(&path).parent().unwrap());
+    }
+
+    #[test]
+    fn journal_of_another_data_file_and_incomplete_record_are_ignored() {
+        let path = data_file("stale");
+        let mut stored = StoredFile::default();
+        let mut items = vec![item(1, "a")];
+        store_items(&path, &items, &mut stored, |i| i.0).unwrap();
+        items.push(item(2, "b"));
+        store_items(&path, &items, &mut stored, |i| i.0).unwrap();
+
+        // Interrupted append, only a part of the length of the next record
+        let mut journal = fs::OpenOptions::new()
+            .append(true)
+            .open(journal_path(&path))
+            .unwrap();
+        journal.write_all(&[9, 0]).unwrap();
+        assert_eq!(load(&path), (items.clone(), 1));
+
+        // Interrupted write of the data file, the journal is left from the previous data file
+        fs::write(&path, bincode::serialize(&vec![item(1, "new")]).unwrap()).unwrap();
+        assert_eq!(load(&path), (vec![item(1, "new")], 0));
+        let _ = fs::remove_dir_all(Path::new(&path).parent().unwrap());
+    }
 }
@@ -0,0 +1,392 @@
+/*
+ * File: sync.rs
+ * Description: Sync directory, the projects and weeks stored as one pretty printed JSON file each so the files can be
+ *              merged by git. The directory is committed, pulled and pushed with git if it's a git repository. The
+ *              state of the last sync tells which items were changed or removed locally or by the other machines.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use chrono::NaiveDate;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::day::Day;
+use crate::entry::Entry;
+use crate::project::Project;
+use crate::storage_handler::stable_checksum;
+use crate::week::Week;
+
+/// Directory of the project files in the sync directory
+const K_PROJECTS_DIRECTORY: &str = "projects";
+/// Directory of the week files in the sync directory
+const K_WEEKS_DIRECTORY: &str = "weeks";
+
+/// Error for reading, writing or syncing the sync directory
+#[derive(Debug)]
+pub struct SyncError(String);
+
+/// Implement Display trait for SyncError
+impl fmt::Display for SyncError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "sync failed: {}", self.0)
+    }
+}
+
+/// Implement Error trait for SyncError
+impl Error for SyncError {}
+
+/// State of the last sync, the checksum of each project, week, entry and day as written to the sync directory. An item
+/// differing from it was changed since, an item missing was removed since
+#[derive(Serialize, Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct SyncState {
+    /// Sync directory of the state
+    directory: String,
+    /// Checksum per item key, e.g. "entry:<id>"
+    items: BTreeMap<String, u64>,
+}
+
+impl SyncState {
+    /// Create the state of the items of the data, as written to the directory
+    pub fn new(directory: &str, projects: &[Project], weeks: &[Week]) -> Self {
+        Self {
+            directory: directory.to_string(),
+            items: item_checksums(projects, weeks),
+        }
+    }
+
+    /// Get the state if of the directory, the state of another directory isn't used
+    pub fn of_directory(self, directory: &str) -> Self {
+        if self.directory == directory {
+            self
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Check if no items are synced
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Get the checksum of the item at the last sync, None if it wasn't synced
+    pub fn get(&self, key: &str) -> Option<u64> {
+        self.items.get(key).copied()
+    }
+}
+
+/// Side an item was changed on since the last sync, a removed item counts as changed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SyncChange {
+    /// The same locally and in the sync directory
+    Unchanged,
+    /// Only changed locally
+    Local,
+    /// Only changed by the other machines
+    Remote,
+    /// Changed locally and by the other machines
+    Both,
+}
+
+/// Get the side an item was changed on, from the checksums of the local, sync directory and last synced item. None is
+/// used for an item not stored
+pub fn sync_change(local: Option<u64>, remote: Option<u64>, synced: Option<u64>) -> SyncChange {
+    if local == remote {
+        SyncChange::Unchanged
+    } else if local == synced {
+        SyncChange::Remote
+    } else if remote == synced {
+        SyncChange::Local
+    } else {
+        SyncChange::Both
+    }
+}
+
+/// Key of a project in the sync state
+pub fn project_key(id: &impl fmt::Display) -> String {
+    format!("project:{}", id)
+}
+
+/// Key of a week in the sync state, as its file name without extension, e.g. "week:2025-W11"
+pub fn week_key(year: i32, number: u32) -> String {
+    format!("week:{}", week_file_stem(year, number))
+}
+
+/// Key of an entry in the sync state
+pub fn entry_key(id: &impl fmt::Display) -> String {
+    format!("entry:{}", id)
+}
+
+/// Key of a day in the sync state, days are synced by date
+pub fn day_key(date: &NaiveDate) -> String {
+    format!("day:{}", date)
+}
+
+/// Internal function to get the name of a week file without extension, e.g. "2025-W11"
+fn week_file_stem(year: i32, number: u32) -> String {
+    format!("{}-W{:02}", year, number)
+}
+
+/// Internal function to get the checksum of the JSON of an item, the same for an item read back from its file
+fn checksum<T: Serialize>(item: &T) -> u64 {
+    stable_checksum(&serde_json::to_vec(item).unwrap_or_default())
+}
+
+/// Get the checksums of the items of projects and weeks, keyed by the sync state key. The projects and weeks are
+/// checked without their entries and days, an entry with the ID of its project
+pub fn item_checksums(projects: &[Project], weeks: &[Week]) -> BTreeMap<String, u64> {
+    let mut items = BTreeMap::new();
+    for project in projects {
+        let mut meta = project.clone();
+        meta.entries_mut().clear();
+        items.insert(project_key(project.id()), checksum(&meta));
+        for entry in project.entries() {
+            items.insert(
+                entry_key(entry.id()),
+                checksum(&(project.id(), entry as &Entry)),
+            );
+        }
+    }
+    for week in weeks {
+        let mut meta = week.clone();
+        meta.entries_mut().clear();
+        items.insert(week_key(week.year(), week.number()), checksum(&meta));
+        for day in week.entries() {
+            items.insert(day_key(&day.date()), checksum(day as &Day));
+        }
+    }
+    items
+}
+
+/// Directory with the text copy of the stored data
+pub struct SyncDirectory {
+    /// Path of the directory
+    path: PathBuf,
+}
+
+impl SyncDirectory {
+    /// Create the sync directory handle, nothing is created until written
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: PathBuf::from(path),
+        }
+    }
+
+    /// Check if the directory is a git repository
+    pub fn is_git_repository(&self) -> bool {
+        self.path.join(".git").exists()
+    }
+
+    /// Internal function to run git in the directory, returns stdout
+    fn git(&self, args: &[&str]) -> Result<String, SyncError> {
+        tracing::debug!("Running git {} in {}", args.join(" "), self.path.display());
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(&self.path)
+            .output()
+            .map_err(|e| SyncError(format!("could not run git: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(SyncError(format!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    /// Internal function to check if the repository has a remote to pull from and push to
+    fn has_remote(&self) -> Result<bool, SyncError> {
+        Ok(!self.git(&["remote"])?.trim().is_empty())
+    }
+
+    /// Pull the changes of the other machines, nothing is done without a remote or if nothing was pushed to the remote
+    /// yet. Returns true if pulled
+    pub fn pull(&self) -> Result<bool, SyncError> {
+        if !self.has_remote()? {
+            tracing::debug!("No git remote in {}, nothing to pull", self.path.display());
+            return Ok(false);
+        }
+        if self.git(&["ls-remote", "--heads"])?.trim().is_empty() {
+            tracing::debug!("Nothing pushed to the git remote yet, nothing to pull");
+            return Ok(false);
+        }
+        self.git(&["pull", "--no-rebase", "--no-edit"])?;
+        Ok(true)
+    }
+
+    /// Commit the changed files and push them if there is a remote. Returns true if anything was committed
+    pub fn commit_and_push(&self, message: &str) -> Result<bool, SyncError> {
+        self.git(&["add", "--all", "."])?;
+        let committed = if self.git(&["status", "--porcelain"])?.trim().is_empty() {
+            tracing::debug!("No changes to commit in {}", self.path.display());
+            false
+        } else {
+            self.git(&["commit", "--quiet", "-m", message])?;
+            true
+        };
+
+        if self.has_remote()? {
+            self.git(&["push", "--quiet"])?;
+        }
+        Ok(committed)
+    }
+
+    /// Internal function to read all JSON files in a sub-directory, sorted by file name. An absent directory has no
+    /// files
+    fn read_files<T: DeserializeOwned>(&self, directory: &str) -> Result<Vec<T>, SyncError> {
+        let directory = self.path.join(directory);
+        if !directory.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut paths: Vec<PathBuf> = fs::read_dir(&directory)
+            .map_err(|e| SyncError(format!("could not read {}: {}", directory.display(), e)))?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+
+        paths
+            .iter()
+            .map(|path| {
+                let content = fs::read_to_string(path)
+                    .map_err(|e| SyncError(format!("could not read {}: {}", path.display(), e)))?;
+                serde_json::from_str(&content)
+                    .map_err(|e| SyncError(format!("invalid file {}: {}", path.display(), e)))
+            })
+            .collect()
+    }
+
+    /// Read the projects and weeks of the directory
+    pub fn read(&self) -> Result<(Vec<Project>, Vec<Week>), SyncError> {
+        Ok((
+            self.read_files(K_PROJECTS_DIRECTORY)?,
+            self.read_files(K_WEEKS_DIRECTORY)?,
+        ))
+    }
+
+    /// Internal function to write a file, only if the content changed to keep the modification time
+    fn write_file<T: Serialize>(path: &Path, item: &T) -> Result<(), SyncError> {
+        let mut content = serde_json::to_string_pretty(item)
+            .map_err(|e| SyncError(format!("could not serialize {}: {}", path.display(), e)))?;
+        content.push('\n');
+
+        if fs::read_to_string(path).is_ok_and(|existing| existing == content) {
+            return Ok(());
+        }
+        fs::write(path, content)
+            .map_err(|e| SyncError(format!("could not write {}: {}", path.display(), e)))
+    }
+
+    /// Internal function to remove the files of the sub-directory which aren't written, only of the items synced
+    /// before, i.e. removed since the last sync. Returns the number of removed files
+    fn remove_files<F: Fn(&str) -> String>(
+        directory: &Path,
+        written: &HashSet<String>,
+        synced: &SyncState,
+        key: F,
+    ) -> Result<usize, SyncError> {
+        let entries = fs::read_dir(directory)
+            .map_err(|e| SyncError(format!("could not read {}: {}", directory.display(), e)))?;
+        let mut removed = 0;
+        for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+            let Some(stem) = path
+                .extension()
+                .filter(|ext| *ext == "json")
+                .and(path.file_stem())
+                .and_then(|stem| stem.to_str())
+            else {
+                continue;
+            };
+            if written.contains(stem) || synced.get(&key(stem)).is_none() {
+                continue;
+            }
+            tracing::debug!("Removing {}, removed since the last sync", path.display());
+            fs::remove_file(&path)
+                .map_err(|e| SyncError(format!("could not remove {}: {}", path.display(), e)))?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    /// Write the projects and weeks, one file each. Projects are named by ID so a rename changes the file and not the
+    /// path, weeks by year and number, e.g. "2025-W11.json". The files of the items synced before but not written are
+    /// removed. Returns the number of written and removed files
+    pub fn write(
+        &self,
+        projects: &[Project],
+        weeks: &[Week],
+        synced: &SyncState,
+    ) -> Result<(usize, usize), SyncError> {
+        let projects_directory = self.path.join(K_PROJECTS_DIRECTORY);
+        let weeks_directory = self.path.join(K_WEEKS_DIRECTORY);
+        for directory in [&projects_directory, &weeks_directory] {
+            fs::create_dir_all(directory).map_err(|e| {
+                SyncError(format!("could not create {}: {}", directory.display(), e))
+            })?;
+        }
+
+        let mut written_projects = HashSet::new();
+        for project in projects {
+            let stem = project.id().to_string();
+            Self::write_file(&projects_directory.join(format!("{}.json", stem)), project)?;
+            written_projects.insert(stem);
+        }
+        let mut written_weeks = HashSet::new();
+        for week in weeks {
+            let stem = week_file_stem(week.year(), week.number());
+            Self::write_file(&weeks_directory.join(format!("{}.json", stem)), week)?;
+            written_weeks.insert(stem);
+        }
+
+        let removed = Self::remove_files(&projects_directory, &written_projects, synced, |s| {
+            project_key(&s)
+        })? + Self::remove_files(&weeks_directory, &written_weeks, synced, |s| {
+            format!("week:{}", s)
+        })?;
+        Ok((projects.len() + weeks.len(), removed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn change_is_given_by_the_side_differing_from_the_last_sync() {
+        assert_eq!(
+            sync_change(Some(1), Some(1), Some(0)),
+            SyncChange::Unchanged
+        );
+        assert_eq!(sync_change(None, None, Some(1)), SyncChange::Unchanged);
+        assert_eq!(sync_change(Some(1), Some(2), Some(1)), SyncChange::Remote);
+        assert_eq!(sync_change(Some(1), None, Some(1)), SyncChange::Remote);
+        assert_eq!(sync_change(None, Some(2), None), SyncChange::Remote);
+        assert_eq!(sync_change(Some(2), Some(1), Some(1)), SyncChange::Local);
+        assert_eq!(sync_change(None, Some(1), Some(1)), SyncChange::Local);
+        assert_eq!(sync_change(Some(2), Some(3), Some(1)), SyncChange::Both);
+        assert_eq!(sync_change(None, Some(3), Some(1)), SyncChange::Both);
+        assert_eq!(sync_change(Some(2), Some(3), None), SyncChange::Both);
+    }
+
+    #[test]
+    fn state_of_another_directory_is_not_used() {
+        let project = Project::new("acme".to_string(), None);
+        let state = SyncState::new("/sync", std::slice::from_ref(&project), &[]);
+        assert!(state.get(&project_key(project.id())).is_some());
+
+        let state = state.of_directory("/sync");
+        assert!(state.get(&project_key(project.id())).is_some());
+        assert!(state.of_directory("/other").is_empty());
+    }
+}
@@ -6,27 +6,305 @@
  * License: MIT
  */
 
+use chrono::{NaiveDate, Weekday};
 use comfy_table::{Cell, ContentArrangement, Table};
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
 
 use crate::day::Day;
+use crate::display::Display;
 use crate::entry::Entry;
+use crate::i18n::{self, tr};
+use crate::outln;
+
+/// Columns of the day table, the name used with --columns and the header
+pub const K_DAY_COLUMNS: [(&str, &str); 9] = [
+    ("week", "Week"),
+    ("date", "Date"),
+    ("start", "Start time"),
+    ("end", "End time"),
+    ("paused", "Paused hours"),
+    ("hours", "Hours"),
+    ("closed", "Closed"),
+    ("info", "Extra info"),
+    ("id", "ID"),
+];
+
+/// Columns of the entry table, the name used with --columns and the header
+pub const K_ENTRY_COLUMNS: [(&str, &str); 7] = [
+    ("project", "Project"),
+    ("description", "Description"),
+    ("hours", "Hours"),
+    ("estimate", "Estimate"),
+    ("date", "Date"),
+    ("created", "Created"),
+    ("id", "ID"),
+];
+
+/// Columns of the project overview table, the name used with --columns and the header
+pub const K_PROJECT_COLUMNS: [(&str, &str); 10] = [
+    ("name", "Name"),
+    ("aliases", "Aliases"),
+    ("description", "Description"),
+    ("client", "Client"),
+    ("entries", "Number of Entries"),
+    ("hours", "Total Hours"),
+    ("budget", "Budget"),
+    ("deadline", "Deadline"),
+    ("reference", "External reference"),
+    ("id", "ID"),
+];
+
+/// Columns of the week overview table, the name used with --columns and the header
+pub const K_WEEK_COLUMNS: [(&str, &str); 6] = [
+    ("year", "Year"),
+    ("week", "Week"),
+    ("days", "Number of days registered"),
+    ("hours", "Hours"),
+    ("notes", "Notes"),
+    ("id", "ID"),
+];
+
+/// Enum for the sort order of the listed rows
+#[derive(Clone, Debug, PartialEq)]
+pub enum ListSort {
+    /// By the (total) hours
+    Hours,
+    /// By the date, rows without a date last
+    Date,
+    /// By the name, e.g. the project name or the extra info of a day
+    Name,
+}
+
+/// Parse error for ListSort
+#[derive(Debug)]
+pub struct ParseListSortError;
+
+/// Implement Display trait for ParseListSortError
+impl fmt::Display for ParseListSortError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid sort order, valid options are: hours, date, name"
+        )
+    }
+}
+
+/// Implement Error trait for ParseListSortError
+impl Error for ParseListSortError {}
+
+/// Implement FromStr trait for ListSort
+impl FromStr for ListSort {
+    type Err = ParseListSortError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            "hours" => Ok(ListSort::Hours),
+            "date" => Ok(ListSort::Date),
+            "name" => Ok(ListSort::Name),
+            _ => Err(ParseListSortError),
+        }
+    }
+}
+
+/// Sorting, column selection and paging of the list output. The default keeps the stored order, all columns and
+/// all rows
+#[derive(Clone, Debug, Default)]
+pub struct ListOptions {
+    /// Sort order, None keeps the stored order
+    pub sort: Option<ListSort>,
+    /// Reverse the sort order
+    pub descending: bool,
+    /// Columns to show in the given order, None for all columns
+    pub columns: Option<Vec<String>>,
+    /// Number of rows to skip, counted after sorting
+    pub offset: usize,
+    /// Maximum number of rows to show, None for all rows
+    pub limit: Option<usize>,
+    /// File the shown rows are exported to, the format is given by the extension
+    pub export: Option<String>,
+}
+
+impl ListOptions {
+    /// Range of the rows shown out of the given number of rows
+    pub fn shown_rows(&self, total: usize) -> std::ops::Range<usize> {
+        let start = self.offset.min(total);
+        let end = self
+            .limit
+            .map_or(total, |limit| start.saturating_add(limit).min(total));
+        start..end
+    }
+}
+
+/// Error for a column not existing in the listed table
+#[derive(Debug)]
+pub struct ListColumnError {
+    /// The unknown column
+    column: String,
+    /// Valid columns of the table
+    valid: Vec<&'static str>,
+}
+
+/// Implement Display trait for ListColumnError
+impl fmt::Display for ListColumnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid column '{}', valid options are: {}",
+            self.column,
+            self.valid.join(", ")
+        )
+    }
+}
+
+/// Implement Error trait for ListColumnError
+impl Error for ListColumnError {}
+
+/// A row of a sortable table, the cells together with the values to sort by
+pub struct ListRow {
+    /// Cells matching the columns of the table
+    cells: Vec<Cell>,
+    /// Hours to sort by
+    hours: f32,
+    /// Date to sort by
+    date: Option<NaiveDate>,
+    /// Name to sort by
+    name: String,
+}
+
+impl ListRow {
+    /// Create a new row from the cells, and the values to sort by
+    pub fn new(cells: Vec<Cell>, hours: f32, date: Option<NaiveDate>, name: &str) -> Self {
+        Self {
+            cells,
+            hours,
+            date,
+            name: name.to_lowercase(),
+        }
+    }
+}
+
+/// Listed rows as text, with the columns and in the order they are shown. Used when exporting a listing
+#[derive(Debug, Default)]
+pub struct ListedRows {
+    /// Headers of the shown columns
+    pub headers: Vec<String>,
+    /// Shown rows, one value for each header
+    pub rows: Vec<Vec<String>>,
+}
+
+impl ListedRows {
+    /// Add the rows of another table with the same columns, e.g. the days of the next year
+    pub fn append(&mut self, other: ListedRows) {
+        if self.headers.is_empty() {
+            self.headers = other.headers;
+        }
+        self.rows.extend(other.rows);
+    }
+}
+
+/// Builder of a table which can be sorted and trimmed to a selection of columns
+pub struct SortableTable {
+    /// Columns of the table, the name used with --columns and the header
+    columns: &'static [(&'static str, &'static str)],
+    /// The added rows
+    rows: Vec<ListRow>,
+}
+
+impl SortableTable {
+    /// Create a new table with the columns
+    pub fn new(columns: &'static [(&'static str, &'static str)]) -> Self {
+        Self {
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    /// Add a row, the cells must match the columns
+    pub fn add_row(&mut self, row: ListRow) {
+        self.rows.push(row);
+    }
+
+    /// Number of added rows
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Build the table, sorted, paged and trimmed to the selected columns. The shown rows are also given as text
+    pub fn build(mut self, options: &ListOptions) -> Result<(Table, ListedRows), ListColumnError> {
+        let selected: Vec<usize> = match &options.columns {
+            Some(names) => names
+                .iter()
+                .map(|name| {
+                    let name = name.trim().to_lowercase();
+                    self.columns
+                        .iter()
+                        .position(|(column, _)| *column == name)
+                        .ok_or_else(|| ListColumnError {
+                            column: name,
+                            valid: self.columns.iter().map(|(column, _)| *column).collect(),
+                        })
+                })
+                .collect::<Result<_, _>>()?,
+            None => (0..self.columns.len()).collect(),
+        };
+
+        if let Some(sort) = &options.sort {
+            // Stable sort, rows with equal values keep the stored order
+            self.rows.sort_by(|a, b| {
+                let ordering = match sort {
+                    ListSort::Hours => a.hours.total_cmp(&b.hours),
+                    ListSort::Name => a.name.cmp(&b.name),
+                    ListSort::Date => match (a.date, b.date) {
+                        (Some(a), Some(b)) => a.cmp(&b),
+                        (Some(_), None) => Ordering::Less,
+                        (None, Some(_)) => Ordering::Greater,
+                        (None, None) => Ordering::Equal,
+                    },
+                };
+                if options.descending {
+                    ordering.reverse()
+                } else {
+                    ordering
+                }
+            });
+        }
+
+        let mut table = Table::new();
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(selected.iter().map(|i| Cell::new(tr(self.columns[*i].1))));
+
+        let mut listed = ListedRows {
+            headers: selected
+                .iter()
+                .map(|i| tr(self.columns[*i].1).to_string())
+                .collect(),
+            rows: Vec::new(),
+        };
+        for row in &self.rows[options.shown_rows(self.rows.len())] {
+            let cells: Vec<Cell> = selected.iter().map(|i| row.cells[*i].clone()).collect();
+            listed
+                .rows
+                .push(cells.iter().map(|c| c.content()).collect());
+            table.add_row(cells);
+        }
+
+        Ok((table, listed))
+    }
+}
 
 /// Internal function to get the table for printing a day
 pub fn get_table_day() -> Table {
     let mut table = Table::new();
     table.set_content_arrangement(ContentArrangement::Dynamic);
 
-    table.set_header(vec![
-        Cell::new("Week"),
-        Cell::new("Date"),
-        Cell::new("Start time"),
-        Cell::new("End time"),
-        Cell::new("Paused hours"),
-        Cell::new("Hours"),
-        Cell::new("Closed"),
-        Cell::new("Extra info"),
-        Cell::new("ID"),
-    ]);
+    table.set_header(
+        K_DAY_COLUMNS
+            .iter()
+            .map(|(_, header)| Cell::new(tr(header))),
+    );
 
     table
 }
@@ -36,12 +314,24 @@ pub fn get_table_entry() -> Table {
     let mut table = Table::new();
     table.set_content_arrangement(ContentArrangement::Dynamic);
 
+    table.set_header(
+        K_ENTRY_COLUMNS
+            .iter()
+            .map(|(_, header)| Cell::new(tr(header))),
+    );
+
+    table
+}
+
+/// Internal function to get a table for printing the project hours in a period
+pub fn get_table_project_hours() -> Table {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
     table.set_header(vec![
-        Cell::new("Project"),
-        Cell::new("Description"),
-        Cell::new("Hours"),
-        Cell::new("Created"),
-        Cell::new("ID"),
+        Cell::new(tr("Project")),
+        Cell::new(tr("Entries")),
+        Cell::new(tr("Hours")),
     ]);
 
     table
@@ -53,14 +343,125 @@ pub fn get_table_target_week() -> Table {
     table.set_content_arrangement(ContentArrangement::Dynamic);
 
     table.set_header(vec![
-        Cell::new("Week"),
-        Cell::new("Target hours"),
-        Cell::new("Current reported hours"),
-        Cell::new("Percentage done"),
-        Cell::new("Target status"),
-        Cell::new("Hours remaining"),
-        Cell::new("Hours overtime"),
-        Cell::new("Target hours set method"),
+        Cell::new(tr("Week")),
+        Cell::new(tr("Target hours")),
+        Cell::new(tr("Current reported hours")),
+        Cell::new(tr("Percentage done")),
+        Cell::new(tr("Target status")),
+        Cell::new(tr("Hours remaining")),
+        Cell::new(tr("Hours overtime")),
+        Cell::new(tr("Target hours set method")),
+    ]);
+
+    table
+}
+
+/// Internal function to get a table for printing the locked periods
+pub fn get_table_locked_periods() -> Table {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    table.set_header(vec![
+        Cell::new(tr("Period")),
+        Cell::new(tr("Number")),
+        Cell::new(tr("Year")),
+    ]);
+
+    table
+}
+
+/// Internal function to get a table for printing the items in the trash
+pub fn get_table_trash() -> Table {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    table.set_header(vec![
+        Cell::new(tr("ID")),
+        Cell::new(tr("Item")),
+        Cell::new(tr("Removed")),
+        Cell::new(tr("Description")),
+    ]);
+
+    table
+}
+
+/// Internal function to get a table for comparing two periods side by side
+pub fn get_table_compare(left: &str, right: &str) -> Table {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    table.set_header(vec![
+        Cell::new(tr("Metric")),
+        Cell::new(left),
+        Cell::new(right),
+        Cell::new(tr("Difference")),
+    ]);
+
+    table
+}
+
+/// Internal function to get a table for printing the week targets changed by a reapply
+pub fn get_table_target_reapply() -> Table {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    table.set_header(vec![
+        Cell::new(tr("Week")),
+        Cell::new(tr("Year")),
+        Cell::new(tr("Stored target hours")),
+        Cell::new(tr("New target hours")),
+    ]);
+
+    table
+}
+
+/// Internal function to get a table for printing the summary of a merge
+pub fn get_table_merge_summary() -> Table {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    table.set_header(vec![
+        Cell::new(tr("Item")),
+        Cell::new(tr("Added")),
+        Cell::new(tr("Merged")),
+        Cell::new(tr("Skipped")),
+    ]);
+
+    table
+}
+
+/// Internal function to get a table for printing the estimated and actual hours per project
+pub fn get_table_estimates() -> Table {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    table.set_header(vec![
+        Cell::new(tr("Project")),
+        Cell::new(tr("Estimated entries")),
+        Cell::new(tr("Estimated hours")),
+        Cell::new(tr("Actual hours")),
+        Cell::new(tr("Difference")),
+        Cell::new(tr("Actual of estimate")),
+    ]);
+
+    table
+}
+
+/// Internal function to get a table for printing the project burn-down
+pub fn get_table_target_project() -> Table {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    table.set_header(vec![
+        Cell::new(tr("Project")),
+        Cell::new(tr("Budget hours")),
+        Cell::new(tr("Used hours")),
+        Cell::new(tr("Percentage used")),
+        Cell::new(tr("Hours remaining")),
+        Cell::new(tr("Hours per day")),
+        Cell::new(tr("Projected completion")),
+        Cell::new(tr("Deadline")),
+        Cell::new(tr("Status")),
     ]);
 
     table
@@ -72,59 +473,132 @@ pub fn get_table_target_month() -> Table {
     table.set_content_arrangement(ContentArrangement::Dynamic);
 
     table.set_header(vec![
-        Cell::new("Month"),
-        Cell::new("Target hours"),
-        Cell::new("Current reported hours"),
-        Cell::new("Percentage done"),
-        Cell::new("Target status"),
-        Cell::new("Hours remaining"),
-        Cell::new("Hours overtime"),
-        Cell::new("Target hours set method"),
+        Cell::new(tr("Month")),
+        Cell::new(tr("Target hours")),
+        Cell::new(tr("Current reported hours")),
+        Cell::new(tr("Percentage done")),
+        Cell::new(tr("Target status")),
+        Cell::new(tr("Hours remaining")),
+        Cell::new(tr("Hours overtime")),
+        Cell::new(tr("Target hours set method")),
     ]);
 
     table
 }
 
-// Internal function to print a single day, in report table format
-pub fn print_day_in_report_table(day: &Day) {
-    let mut table = get_table_day();
+/// Internal function to get a table for printing the month target forecast
+pub fn get_table_target_forecast() -> Table {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
 
-    let start_time = match day.starting_time() {
-        Some(st) => st.to_string(),
-        None => "N/A".to_string(),
-    };
-
-    let end_time = match day.ending_time() {
-        Some(et) => et.to_string(),
-        None => "N/A".to_string(),
-    };
-
-    table.add_row(vec![
-        Cell::new(day.week().to_string()),
-        Cell::new(day.date().to_string()),
-        Cell::new(start_time),
-        Cell::new(end_time),
-        Cell::new(day.hours_paused().to_string()),
-        Cell::new(day.hours().to_string()),
-        Cell::new(day.closed().to_string()),
-        Cell::new(day.extra_info()),
-        Cell::new(day.id().to_string()),
+    table.set_header(vec![
+        Cell::new(tr("Month")),
+        Cell::new(tr("Target hours")),
+        Cell::new(tr("Current reported hours")),
+        Cell::new(tr("Average daily hours")),
+        Cell::new(tr("Remaining working days")),
+        Cell::new(tr("Forecast hours")),
+        Cell::new(tr("Forecast status")),
+        Cell::new(tr("Required hours per day")),
     ]);
 
-    println!("{}", table);
+    table
 }
 
-// Internal function to print a single entry, in report table format
-pub fn print_entry_in_report_table(entry: &Entry, project_name: &str) {
-    let mut table = get_table_entry();
+/// Internal function to get a table for printing statistics
+pub fn get_table_statistics() -> Table {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
 
-    table.add_row(vec![
-        Cell::new(project_name),
-        Cell::new(entry.description()),
-        Cell::new(entry.hours().to_string()),
-        Cell::new(entry.created().to_string()),
-        Cell::new(entry.id().to_string()),
+    table.set_header(vec![Cell::new(tr("Statistic")), Cell::new(tr("Value"))]);
+
+    table
+}
+
+/// Internal function to get a table for printing the month-over-month trend
+pub fn get_table_month_trend() -> Table {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    table.set_header(vec![
+        Cell::new(tr("Month")),
+        Cell::new(tr("Hours")),
+        Cell::new(tr("Change from previous month")),
     ]);
 
-    println!("{}", table);
+    table
+}
+
+/// Internal function to get a table for printing the storage info
+pub fn get_table_info() -> Table {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    table.set_header(vec![Cell::new(tr("Info")), Cell::new(tr("Value"))]);
+
+    table
+}
+
+/// Internal function to get a table for printing the stored time items per year
+pub fn get_table_year_counts() -> Table {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    table.set_header(vec![
+        Cell::new(tr("Year")),
+        Cell::new(tr("Weeks")),
+        Cell::new(tr("Days")),
+        Cell::new(tr("Entries")),
+        Cell::new(tr("Day hours")),
+        Cell::new(tr("Entry hours")),
+    ]);
+
+    table
+}
+
+/// Internal function to get the table for printing a calendar month, a row per week starting on the given day
+pub fn get_table_calendar(week_start: Weekday) -> Table {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    let mut header = vec![Cell::new(tr("Week"))];
+    let mut day = week_start;
+    for _ in 0..7 {
+        header.push(Cell::new(i18n::weekday_short(day)));
+        day = day.succ();
+    }
+    table.set_header(header);
+
+    table
+}
+
+/// Internal function to get a table for printing the benchmark timings
+pub fn get_table_benchmarks() -> Table {
+    let mut table = Table::new();
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    table.set_header(vec![
+        Cell::new(tr("Benchmark")),
+        Cell::new(tr("Iterations")),
+        Cell::new(tr("Total (ms)")),
+        Cell::new(tr("Average (ms)")),
+    ]);
+
+    table
+}
+
+// Internal function to print a single day, in report table format
+pub fn print_day_in_report_table(day: &Day, display: &Display) {
+    let mut table = get_table_day();
+    table.add_row(display.day_row(day));
+
+    outln!("{}", table);
+}
+
+// Internal function to print a single entry, in report table format
+pub fn print_entry_in_report_table(entry: &Entry, project_name: &str, display: &Display) {
+    let mut table = get_table_entry();
+    table.add_row(display.entry_row(entry, project_name));
+
+    outln!("{}", table);
 }
@@ -6,6 +6,8 @@
  * License: MIT
  */
 
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
 use crate::day::Day;
 use crate::week::Week;
 
@@ -14,6 +16,15 @@ const K_TARGET_HOURS_NOT_SET: f32 = 0.0;
 const K_DEFAULT_WEEK_TARGET_HOURS: f32 = 40.0;
 const K_DEFAULT_MONTH_TARGET_HOURS: f32 = 160.0;
 
+/// Change of the target of a single week
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WeekTargetEdit {
+    /// Target of the week in hours
+    Hours(f32),
+    /// Back to the configured week target
+    Reset,
+}
+
 /// TargetStatus enum to represent the progress of the target
 #[derive(Debug, Clone)]
 enum TargetStatus {
@@ -32,6 +43,8 @@ enum TargetSetMethod {
     SetFromConfig,
     /// Target is set from default
     SetFromDefault,
+    /// Target is set for the week only
+    WeekOverride,
 }
 
 /// Weekly-Target struct to store the target and status of the week
@@ -72,7 +85,118 @@ pub struct MonthlyTargetStatus {
     target_set_method: TargetSetMethod,
 }
 
-//Todo: project target
+/// Project burn-down, the budgeted hours used so far and the projected completion at the current pace
+#[derive(Debug, Clone)]
+pub struct ProjectBurnDown {
+    /// Budgeted hours
+    budget_hours: f32,
+    /// Hours used so far
+    used_hours: f32,
+    /// Budgeted hours left, negative when over budget
+    remaining_hours: f32,
+    /// Percentage of the budget used
+    percentage: u32,
+    /// Date the budget should be used by
+    deadline: Option<NaiveDate>,
+    /// Average hours per calendar day since the first entry
+    daily_pace: f32,
+    /// Date the budget is used at the current pace, None without any progress or when already used
+    projected_completion: Option<NaiveDate>,
+}
+
+impl ProjectBurnDown {
+    /// Create a new ProjectBurnDown from the date and hours of the project entries
+    pub fn new<I>(
+        budget_hours: f32,
+        entries: I,
+        deadline: Option<NaiveDate>,
+        today: NaiveDate,
+    ) -> Self
+    where
+        I: IntoIterator<Item = (NaiveDate, f32)>,
+    {
+        let mut used_hours = 0.0;
+        let mut first_date: Option<NaiveDate> = None;
+        for (date, hours) in entries {
+            used_hours += hours;
+            first_date = Some(first_date.map_or(date, |first| first.min(date)));
+        }
+
+        // The first day counts as a full day, also when the first entry is today
+        let elapsed_days = first_date.map_or(0, |first| (today - first).num_days().max(0) + 1);
+        let daily_pace = if elapsed_days > 0 {
+            used_hours / elapsed_days as f32
+        } else {
+            0.0
+        };
+
+        let remaining_hours = budget_hours - used_hours;
+        let projected_completion = if remaining_hours > 0.0 && daily_pace > 0.0 {
+            Some(today + Duration::days((remaining_hours / daily_pace).ceil() as i64))
+        } else {
+            None
+        };
+
+        let percentage = if budget_hours > 0.0 {
+            (used_hours / budget_hours * K_100_PERCENT as f32).round() as u32
+        } else {
+            0
+        };
+
+        Self {
+            budget_hours,
+            used_hours,
+            remaining_hours,
+            percentage,
+            deadline,
+            daily_pace,
+            projected_completion,
+        }
+    }
+
+    /// Getter for `budget_hours`
+    pub fn budget_hours(&self) -> &f32 {
+        &self.budget_hours
+    }
+    /// Getter for `used_hours`
+    pub fn used_hours(&self) -> &f32 {
+        &self.used_hours
+    }
+    /// Getter for `remaining_hours`
+    pub fn remaining_hours(&self) -> &f32 {
+        &self.remaining_hours
+    }
+    /// Getter for `percentage`
+    pub fn percentage(&self) -> &u32 {
+        &self.percentage
+    }
+    /// Getter for `deadline`
+    pub fn deadline(&self) -> Option<&NaiveDate> {
+        self.deadline.as_ref()
+    }
+    /// Getter for `daily_pace`
+    pub fn daily_pace(&self) -> &f32 {
+        &self.daily_pace
+    }
+    /// Getter for `projected_completion`
+    pub fn projected_completion(&self) -> Option<&NaiveDate> {
+        self.projected_completion.as_ref()
+    }
+
+    /// Get the burn-down status as a string: budget used, on track or late for the deadline
+    pub fn status_string(&self) -> String {
+        if self.remaining_hours <= 0.0 {
+            return "BudgetUsed".to_string();
+        }
+
+        match (self.projected_completion, self.deadline) {
+            (Some(completion), Some(deadline)) if completion > deadline => "Late".to_string(),
+            (Some(_), Some(_)) => "OnTrack".to_string(),
+            (Some(_), None) => "NoDeadline".to_string(),
+            (None, _) => "NotStarted".to_string(),
+        }
+    }
+}
 
 impl WeeklyTargetStatus {
     /// Create a new WeeklyTarget
@@ -83,7 +207,9 @@ impl WeeklyTargetStatus {
             status_hours += day.hours();
         }
 
-        let target_set_method = if target_hours_conf != &K_TARGET_HOURS_NOT_SET {
+        let target_set_method = if week.target_override() {
+            TargetSetMethod::WeekOverride
+        } else if target_hours_conf != &K_TARGET_HOURS_NOT_SET {
             TargetSetMethod::SetFromConfig
         } else {
             TargetSetMethod::SetFromDefault
@@ -152,6 +278,7 @@ impl WeeklyTargetStatus {
         match self.target_set_method {
             TargetSetMethod::SetFromConfig => "Set from config".to_string(),
             TargetSetMethod::SetFromDefault => "Set from default".to_string(),
+            TargetSetMethod::WeekOverride => "Set for the week".to_string(),
         }
     }
 }
@@ -160,7 +287,10 @@ impl WeeklyTargetStatus {
 
 impl MonthlyTargetStatus {
     /// Create a new MonthlyTarget
-    pub fn new(days_in_month: &Vec<Day>, target_hours_conf: &f32) -> Self {
+    pub fn new<'a>(
+        days_in_month: impl IntoIterator<Item = &'a Day>,
+        target_hours_conf: &f32,
+    ) -> Self {
         let mut status_hours = 0.0;
         // Calculate the total hours for the week
         for day in days_in_month {
@@ -236,6 +366,117 @@ impl MonthlyTargetStatus {
         match self.target_set_method {
             TargetSetMethod::SetFromConfig => "Set from config".to_string(),
             TargetSetMethod::SetFromDefault => "Set from default".to_string(),
+            TargetSetMethod::WeekOverride => "Set for the week".to_string(),
+        }
+    }
+}
+
+/// Monthly-Forecast struct, predicts the end of month total from the average daily hours so far
+#[derive(Debug, Clone)]
+pub struct MonthlyForecast {
+    /// Target for the month
+    target_hours: f32,
+    /// Hours reported so far
+    status_hours: f32,
+    /// Average hours of the worked days so far
+    average_daily_hours: f32,
+    /// Working days (Monday to Friday) left of the month
+    remaining_working_days: u32,
+    /// Predicted total at the end of the month
+    forecast_hours: f32,
+    /// Hours per remaining working day needed to reach the target, None when no working days are left
+    required_daily_hours: Option<f32>,
+}
+
+impl MonthlyForecast {
+    /// Create a new MonthlyForecast. Today is counted as remaining unless it's already closed
+    pub fn new<'a, I>(
+        days_in_month: I,
+        target_hours_conf: &f32,
+        month: u32,
+        year: i32,
+        today: NaiveDate,
+    ) -> Self
+    where
+        I: Iterator<Item = &'a Day> + Clone,
+    {
+        let status = MonthlyTargetStatus::new(days_in_month.clone(), target_hours_conf);
+        let status_hours = *status.status_hours();
+        let target_hours = *status.target_hours();
+
+        let worked_days = days_in_month.clone().filter(|d| d.hours() > 0.0).count();
+        let average_daily_hours = if worked_days > 0 {
+            status_hours / worked_days as f32
+        } else {
+            0.0
+        };
+
+        let today_closed = days_in_month
+            .clone()
+            .any(|d| d.date() == today && d.closed());
+        let first_remaining = if today_closed {
+            today + Duration::days(1)
+        } else {
+            today
+        };
+
+        let mut remaining_working_days = 0;
+        let mut date = NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(today);
+        while date.month() == month {
+            if date >= first_remaining && !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+                remaining_working_days += 1;
+            }
+            date += Duration::days(1);
+        }
+
+        let forecast_hours = status_hours + average_daily_hours * remaining_working_days as f32;
+        let required_daily_hours = if remaining_working_days > 0 {
+            Some((target_hours - status_hours).max(0.0) / remaining_working_days as f32)
+        } else {
+            None
+        };
+
+        Self {
+            target_hours,
+            status_hours,
+            average_daily_hours,
+            remaining_working_days,
+            forecast_hours,
+            required_daily_hours,
+        }
+    }
+
+    /// Getter for `target_hours`
+    pub fn target_hours(&self) -> &f32 {
+        &self.target_hours
+    }
+    /// Getter for `status_hours`
+    pub fn status_hours(&self) -> &f32 {
+        &self.status_hours
+    }
+    /// Getter for `average_daily_hours`
+    pub fn average_daily_hours(&self) -> &f32 {
+        &self.average_daily_hours
+    }
+    /// Getter for `remaining_working_days`
+    pub fn remaining_working_days(&self) -> &u32 {
+        &self.remaining_working_days
+    }
+    /// Getter for `forecast_hours`
+    pub fn forecast_hours(&self) -> &f32 {
+        &self.forecast_hours
+    }
+    /// Getter for `required_daily_hours`
+    pub fn required_daily_hours(&self) -> Option<f32> {
+        self.required_daily_hours
+    }
+
+    /// Get the forecasted target status as string, same values as for the target status
+    pub fn forecast_status_string(&self) -> String {
+        if self.forecast_hours >= self.target_hours {
+            "Reached".to_string()
+        } else {
+            "NotReached".to_string()
         }
     }
 }
@@ -0,0 +1,71 @@
+/*
+ * File: template.rs
+ * Description: Repeat modes for entry templates. Expands a date range into the dates to add entries for.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::fmt;
+use std::str::FromStr;
+
+/// Enum to represent how a template is repeated over a date range
+#[derive(Clone, Debug, PartialEq)]
+pub enum RepeatMode {
+    /// Every day in the range
+    Daily,
+    /// Monday to Friday in the range
+    Weekdays,
+    /// Same weekday as the first date, every week in the range
+    Weekly,
+}
+
+/// Parse error for RepeatMode
+#[derive(Debug)]
+pub struct ParseRepeatModeError;
+
+/// Implement Display trait for ParseRepeatModeError
+impl fmt::Display for ParseRepeatModeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid repeat mode, valid options are: daily, weekdays, weekly"
+        )
+    }
+}
+
+/// Implement FromStr trait for RepeatMode
+impl FromStr for RepeatMode {
+    type Err = ParseRepeatModeError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.to_lowercase().as_str() {
+            "daily" => Ok(RepeatMode::Daily),
+            "weekdays" => Ok(RepeatMode::Weekdays),
+            "weekly" => Ok(RepeatMode::Weekly),
+            _ => Err(ParseRepeatModeError),
+        }
+    }
+}
+
+/// Expand a date range (inclusive) into the dates matching the repeat mode
+pub fn expand_dates(mode: &RepeatMode, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+    let mut dates = Vec::new();
+    let mut current = from;
+
+    while current <= to {
+        let include = match mode {
+            RepeatMode::Daily => true,
+            RepeatMode::Weekdays => !matches!(current.weekday(), Weekday::Sat | Weekday::Sun),
+            RepeatMode::Weekly => current.weekday() == from.weekday(),
+        };
+
+        if include {
+            dates.push(current);
+        }
+        current += Duration::days(1);
+    }
+
+    dates
+}
@@ -0,0 +1,487 @@
+/*
+ * File: toml_format.rs
+ * Description: Conversion between TOML and JSON values, covering the TOML used by the configuration file:
+ *              tables, arrays of tables, strings, numbers, booleans and single line arrays.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use serde_json::{Map, Number, Value};
+use std::error::Error;
+use std::fmt;
+
+/// Error for TOML parsing and writing
+#[derive(Debug)]
+pub struct TomlError(String);
+
+/// Implement Display trait for TomlError
+impl fmt::Display for TomlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TOML error: {}", self.0)
+    }
+}
+
+/// Implement Error trait for TomlError
+impl Error for TomlError {}
+
+/// Internal function to check if a value is written inline, as "key = value", rather than as a table
+fn is_inline(value: &Value) -> bool {
+    match value {
+        Value::Object(_) => false,
+        Value::Array(items) => items.is_empty() || !items.iter().all(Value::is_object),
+        _ => true,
+    }
+}
+
+/// Internal function to write a key, quoted if it's not a bare key
+fn format_key(key: &str) -> String {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        key.to_string()
+    } else {
+        format_string(key)
+    }
+}
+
+/// Internal function to write a basic string, with the special characters escaped
+fn format_string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04X}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Internal function to write a number, floats always get a decimal point to keep their type
+fn format_number(number: &Number) -> String {
+    if number.is_f64() {
+        let value = number.as_f64().unwrap_or_default();
+        // Values from f32 fields are written with the f32 precision, 0.1 rather than 0.10000000149011612
+        let text = if f64::from(value as f32) == value {
+            format!("{:?}", value as f32)
+        } else {
+            format!("{:?}", value)
+        };
+        if text.contains(['.', 'e', 'E']) {
+            text
+        } else {
+            format!("{}.0", text)
+        }
+    } else {
+        number.to_string()
+    }
+}
+
+/// Internal function to write an inline value
+fn format_value(value: &Value, key: &str) -> Result<String, TomlError> {
+    match value {
+        Value::String(s) => Ok(format_string(s)),
+        Value::Number(n) => Ok(format_number(n)),
+        Value::Bool(b) => Ok(b.to_string()),
+        Value::Array(items) => {
+            let items = items
+                .iter()
+                .filter(|v| !v.is_null())
+                .map(|v| format_value(v, key))
+                .collect::<Result<Vec<String>, TomlError>>()?;
+            Ok(format!("[{}]", items.join(", ")))
+        }
+        Value::Null | Value::Object(_) => Err(TomlError(format!(
+            "'{}' can not be written as an inline value",
+            key
+        ))),
+    }
+}
+
+/// Internal function to write the values of a table, inline values first since they belong to the table header above
+fn write_table(
+    path: &[String],
+    table: &Map<String, Value>,
+    out: &mut String,
+) -> Result<(), TomlError> {
+    // TOML has no null, unset optional values are left out
+    for (key, value) in table {
+        if !value.is_null() && is_inline(value) {
+            out.push_str(&format!(
+                "{} = {}\n",
+                format_key(key),
+                format_value(value, key)?
+            ));
+        }
+    }
+
+    for (key, value) in table {
+        let mut sub_path = path.to_vec();
+        sub_path.push(format_key(key));
+        match value {
+            Value::Object(sub_table) => {
+                out.push_str(&format!("\n[{}]\n", sub_path.join(".")));
+                write_table(&sub_path, sub_table, out)?;
+            }
+            Value::Array(items) if !is_inline(value) => {
+                for item in items {
+                    out.push_str(&format!("\n[[{}]]\n", sub_path.join(".")));
+                    if let Value::Object(item_table) = item {
+                        write_table(&sub_path, item_table, out)?;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Write a JSON object as TOML
+pub fn to_string(value: &Value) -> Result<String, TomlError> {
+    let table = value
+        .as_object()
+        .ok_or_else(|| TomlError("only objects can be written as TOML".to_string()))?;
+
+    let mut out = String::new();
+    write_table(&[], table, &mut out)?;
+
+    Ok(out.trim_start().to_string())
+}
+
+/// Parser of a single line, the position is kept as byte offset
+struct LineParser<'a> {
+    line: &'a str,
+    pos: usize,
+}
+
+impl<'a> LineParser<'a> {
+    fn new(line: &'a str) -> Self {
+        Self { line, pos: 0 }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.line[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t']).len();
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.pos += c.len_utf8();
+                Ok(())
+            }
+            Some(c) => Err(format!("expected '{}', found '{}'", expected, c)),
+            None => Err(format!("expected '{}'", expected)),
+        }
+    }
+
+    /// Only whitespace and a comment may follow
+    fn expect_end(&mut self) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.peek() {
+            None | Some('#') => Ok(()),
+            Some(c) => Err(format!("unexpected '{}'", c)),
+        }
+    }
+
+    fn parse_key(&mut self) -> Result<String, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"') => self.parse_basic_string(),
+            Some('\'') => self.parse_literal_string(),
+            _ => {
+                let rest = self.rest();
+                let len = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+                    .unwrap_or(rest.len());
+                if len == 0 {
+                    return Err("expected a key".to_string());
+                }
+                self.pos += len;
+                Ok(rest[..len].to_string())
+            }
+        }
+    }
+
+    /// Keys separated by dots, used in table headers
+    fn parse_key_path(&mut self) -> Result<Vec<String>, String> {
+        let mut path = vec![self.parse_key()?];
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('.') {
+                return Ok(path);
+            }
+            self.pos += 1;
+            path.push(self.parse_key()?);
+        }
+    }
+
+    fn parse_basic_string(&mut self) -> Result<String, String> {
+        self.expect('"')?;
+        let mut out = String::new();
+        let mut chars = self.rest().char_indices();
+
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(out);
+                }
+                '\\' => {
+                    let escaped = match chars.next() {
+                        Some((_, 'n')) => '\n',
+                        Some((_, 'r')) => '\r',
+                        Some((_, 't')) => '\t',
+                        Some((_, '"')) => '"',
+                        Some((_, '\\')) => '\\',
+                        Some((_, 'u')) => {
+                            let hex: String = (0..4)
+                                .filter_map(|_| chars.next())
+                                .map(|(_, c)| c)
+                                .collect();
+                            u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| format!("invalid unicode escape '\\u{}'", hex))?
+                        }
+                        Some((_, other)) => return Err(format!("invalid escape '\\{}'", other)),
+                        None => return Err("unterminated string".to_string()),
+                    };
+                    out.push(escaped);
+                }
+                c => out.push(c),
+            }
+        }
+
+        Err("unterminated string".to_string())
+    }
+
+    fn parse_literal_string(&mut self) -> Result<String, String> {
+        self.expect('\'')?;
+        let rest = self.rest();
+        let end = rest
+            .find('\'')
+            .ok_or_else(|| "unterminated string".to_string())?;
+        self.pos += end + 1;
+        Ok(rest[..end].to_string())
+    }
+
+    fn parse_array(&mut self) -> Result<Value, String> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(Value::Array(items));
+                }
+                None => return Err("arrays must be written on a single line".to_string()),
+                _ => {}
+            }
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {}
+                _ => return Err("expected ',' or ']' in array".to_string()),
+            }
+        }
+    }
+
+    fn parse_scalar(&mut self) -> Result<Value, String> {
+        let rest = self.rest();
+        let len = rest.find([' ', '\t', ',', ']', '#']).unwrap_or(rest.len());
+        let token = &rest[..len];
+        self.pos += len;
+
+        match token {
+            "true" => return Ok(Value::Bool(true)),
+            "false" => return Ok(Value::Bool(false)),
+            "" => return Err("expected a value".to_string()),
+            _ => {}
+        }
+
+        let digits = token.replace('_', "");
+        if let Ok(integer) = digits.parse::<i64>() {
+            return Ok(Value::Number(integer.into()));
+        }
+        digits
+            .parse::<f64>()
+            .ok()
+            .filter(|f| f.is_finite())
+            .and_then(Number::from_f64)
+            .map(Value::Number)
+            .ok_or_else(|| format!("unsupported value '{}'", token))
+    }
+
+    fn parse_value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('"') => self.parse_basic_string().map(Value::String),
+            Some('\'') => self.parse_literal_string().map(Value::String),
+            Some('[') => self.parse_array(),
+            Some('{') => Err("inline tables are not supported".to_string()),
+            _ => self.parse_scalar(),
+        }
+    }
+}
+
+/// Internal function to get the table at the path, tables are created when missing. The last table of an array
+/// of tables is used
+fn table_at<'a>(
+    root: &'a mut Map<String, Value>,
+    path: &[String],
+) -> Result<&'a mut Map<String, Value>, String> {
+    let mut table = root;
+    for key in path {
+        let entry = table
+            .entry(key.clone())
+            .or_insert_with(|| Value::Object(Map::new()));
+        table = match entry {
+            Value::Object(t) => t,
+            Value::Array(items) => match items.last_mut() {
+                Some(Value::Object(t)) => t,
+                _ => return Err(format!("'{}' is not a table", key)),
+            },
+            _ => return Err(format!("'{}' is not a table", key)),
+        };
+    }
+    Ok(table)
+}
+
+/// Parse TOML into a JSON object
+pub fn from_str(source: &str) -> Result<Value, TomlError> {
+    let mut root = Map::new();
+    let mut current: Vec<String> = Vec::new();
+
+    for (number, line) in source.lines().enumerate() {
+        let line_error = |e: String| TomlError(format!("line {}: {}", number + 1, e));
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let mut parser = LineParser::new(trimmed);
+        if let Some(header) = trimmed.strip_prefix("[[") {
+            parser.pos = trimmed.len() - header.len();
+            let path = parser.parse_key_path().map_err(line_error)?;
+            parser
+                .expect(']')
+                .and_then(|_| parser.expect(']'))
+                .and_then(|_| parser.expect_end())
+                .map_err(line_error)?;
+
+            let (name, parent) = path
+                .split_last()
+                .ok_or_else(|| line_error("empty header".to_string()))?;
+            let parent_table = table_at(&mut root, parent).map_err(line_error)?;
+            match parent_table
+                .entry(name.clone())
+                .or_insert_with(|| Value::Array(Vec::new()))
+            {
+                Value::Array(items) => items.push(Value::Object(Map::new())),
+                _ => return Err(line_error(format!("'{}' is not an array of tables", name))),
+            }
+            current = path;
+        } else if trimmed.starts_with('[') {
+            parser.pos = 1;
+            let path = parser.parse_key_path().map_err(line_error)?;
+            parser
+                .expect(']')
+                .and_then(|_| parser.expect_end())
+                .map_err(line_error)?;
+            table_at(&mut root, &path).map_err(line_error)?;
+            current = path;
+        } else {
+            let key = parser.parse_key().map_err(line_error)?;
+            parser.expect('=').map_err(line_error)?;
+            let value = parser.parse_value().map_err(line_error)?;
+            parser.expect_end().map_err(line_error)?;
+
+            let table = table_at(&mut root, &current).map_err(line_error)?;
+            if table.contains_key(&key) {
+                return Err(line_error(format!("duplicate key '{}'", key)));
+            }
+            table.insert(key, value);
+        }
+    }
+
+    Ok(Value::Object(root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfiguration;
+    use serde_json::json;
+
+    #[test]
+    fn generated_config_round_trips() {
+        let config = AppConfiguration::new_default(&"/home/user".to_string());
+        let value = serde_json::to_value(&config).unwrap();
+
+        let text = to_string(&value).unwrap();
+        let parsed: AppConfiguration = serde_json::from_value(from_str(&text).unwrap()).unwrap();
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), value);
+    }
+
+    #[test]
+    fn duplicate_keys_are_rejected() {
+        let error = from_str("[a]\nx = 1\nx = 2\n").unwrap_err();
+        assert!(error.to_string().contains("line 3: duplicate key 'x'"));
+    }
+
+    #[test]
+    fn table_below_array_of_tables_is_in_the_last_table() {
+        let value = from_str("[[a]]\nx = 1\n[[a]]\nx = 2\n[a.b]\ny = 3\n").unwrap();
+        assert_eq!(value, json!({"a": [{"x": 1}, {"x": 2, "b": {"y": 3}}]}));
+    }
+
+    #[test]
+    fn literal_strings_are_not_escaped() {
+        let value = from_str("path = 'C:\\dir\\new \"file\"' # comment\n").unwrap();
+        assert_eq!(value["path"], "C:\\dir\\new \"file\"");
+    }
+
+    #[test]
+    fn unicode_escapes_are_parsed_and_written() {
+        let value = from_str("s = \"caf\\u00E9 \\u0001\"\n").unwrap();
+        assert_eq!(value["s"], "caf\u{e9} \u{1}");
+        assert_eq!(format_string("caf\u{e9} \u{1}"), "\"caf\u{e9} \\u0001\"");
+        assert!(from_str("s = \"\\u00G9\"\n").is_err());
+    }
+
+    #[test]
+    fn numbers_keep_their_type_and_the_f32_precision() {
+        let number = |value: Value| match value {
+            Value::Number(n) => format_number(&n),
+            _ => unreachable!(),
+        };
+        assert_eq!(number(json!(f64::from(0.1_f32))), "0.1");
+        assert_eq!(number(json!(f64::from(40.0_f32))), "40.0");
+        assert_eq!(number(json!(0.1_f64)), "0.1");
+        assert_eq!(number(json!(0.123456789012_f64)), "0.123456789012");
+        assert_eq!(number(json!(1e300_f64)), "1e300");
+        assert_eq!(number(json!(7)), "7");
+    }
+}
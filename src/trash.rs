@@ -0,0 +1,122 @@
+/*
+ * File: trash.rs
+ * Description: Trash of the removed projects, entries and days. The removed items are kept with the time of removal
+ *              and can be restored until purged after the configured retention.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::day::Day;
+use crate::entry::Entry;
+use crate::project::Project;
+
+/// Removed item, with what is needed to restore it
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum TrashedItem {
+    /// Removed project, with its entries
+    Project { project: Project },
+    /// Removed entry, with the ID and name of its project
+    Entry {
+        project_id: Uuid,
+        project: String,
+        entry: Entry,
+    },
+    /// Removed day
+    Day { day: Day },
+}
+
+impl TrashedItem {
+    /// ID of the removed item, the same ID as before it was removed
+    pub fn id(&self) -> Uuid {
+        match self {
+            TrashedItem::Project { project } => *project.id(),
+            TrashedItem::Entry { entry, .. } => *entry.id(),
+            TrashedItem::Day { day } => *day.id(),
+        }
+    }
+
+    /// Name of the item type, as shown in the listing
+    pub fn kind(&self) -> &'static str {
+        match self {
+            TrashedItem::Project { .. } => "Project",
+            TrashedItem::Entry { .. } => "Entry",
+            TrashedItem::Day { .. } => "Day",
+        }
+    }
+}
+
+/// Item in the trash
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TrashRecord {
+    /// Time of the removal
+    removed: DateTime<Local>,
+    /// The removed item
+    item: TrashedItem,
+}
+
+impl TrashRecord {
+    /// Getter for `removed`
+    pub fn removed(&self) -> &DateTime<Local> {
+        &self.removed
+    }
+
+    /// Getter for `item`
+    pub fn item(&self) -> &TrashedItem {
+        &self.item
+    }
+}
+
+/// The trash, stored next to the project and week data
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Trash {
+    /// Removed items, in the order they were removed
+    records: Vec<TrashRecord>,
+}
+
+impl Trash {
+    /// Getter for `records`
+    pub fn records(&self) -> &Vec<TrashRecord> {
+        &self.records
+    }
+
+    /// Add a removed item
+    pub fn add(&mut self, item: TrashedItem, removed: DateTime<Local>) {
+        self.records.push(TrashRecord { removed, item });
+    }
+
+    /// IDs of the items matching the ID or ID prefix
+    pub fn matching(&self, prefix: &str) -> Vec<Uuid> {
+        let prefix = prefix.trim().to_lowercase();
+        self.records
+            .iter()
+            .map(|r| r.item.id())
+            .filter(|id| id.to_string().starts_with(&prefix))
+            .collect()
+    }
+
+    /// Take the item with the ID out of the trash
+    pub fn take(&mut self, id: &Uuid) -> Option<TrashRecord> {
+        let index = self.records.iter().position(|r| r.item.id() == *id)?;
+        Some(self.records.remove(index))
+    }
+
+    /// Put a taken item back, e.g. when it couldn't be restored
+    pub fn put_back(&mut self, record: TrashRecord) {
+        self.records.push(record);
+        self.records.sort_by_key(|r| r.removed);
+    }
+
+    /// Remove the items removed before the time, returns the number of purged items
+    pub fn purge_before(&mut self, before: DateTime<Local>) -> usize {
+        let count = self.records.len();
+        self.records.retain(|r| r.removed >= before);
+        count - self.records.len()
+    }
+}
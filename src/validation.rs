@@ -0,0 +1,381 @@
+/*
+ * File: validation.rs
+ * Description: Cross-field validation of the days and entries before they are stored.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use chrono::{DateTime, Duration, Local};
+use std::error::Error;
+use std::fmt;
+
+use crate::config::ValidationConfig;
+use crate::day::Day;
+use crate::entry::Entry;
+use crate::week_scheme;
+
+/// Highest ISO week number, only some years have it
+const K_MAX_WEEK_NUMBER: u32 = 53;
+/// Max hours the allocations can exceed the day with, for the float artifacts of the summed hours
+const K_ALLOCATION_TOLERANCE: f32 = 0.01;
+
+/// Error for data failing the validation, the message describes the invalid value
+#[derive(Debug)]
+pub struct ValidationError(String);
+
+/// Implement Display trait for ValidationError
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Implement Error trait for ValidationError
+impl Error for ValidationError {}
+
+/// Validate a week number, the week must exist in the year
+pub fn validate_week(week_number: u32, year: i32) -> Result<(), ValidationError> {
+    if !(1..=K_MAX_WEEK_NUMBER).contains(&week_number) {
+        return Err(ValidationError(format!(
+            "Week number {} is invalid, valid week numbers are 1-{}",
+            week_number, K_MAX_WEEK_NUMBER
+        )));
+    }
+
+    if week_scheme::week_scheme()
+        .first_day(week_number, year)
+        .is_none()
+    {
+        return Err(ValidationError(format!(
+            "Week number {} doesn't exist in year {}",
+            week_number, year
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate a day: the week, the ending time after the starting time, the paused hours within the day and the
+/// hours within the limit
+pub fn validate_day(day: &Day, limits: &ValidationConfig) -> Result<(), ValidationError> {
+    validate_week(day.week(), day.week_year())?;
+
+    if day.hours_paused() < 0.0 {
+        return Err(ValidationError(format!(
+            "Paused hours can't be negative: {}",
+            day.hours_paused()
+        )));
+    }
+
+    if let (Some(start), Some(end)) = (day.starting_time(), day.ending_time()) {
+        if end < start {
+            return Err(ValidationError(format!(
+                "Ending time {} is before starting time {} for day {}",
+                end.format("%Y-%m-%d %H:%M:%S"),
+                start.format("%Y-%m-%d %H:%M:%S"),
+                day.date()
+            )));
+        }
+
+        let span_hours = (*end - *start).num_minutes() as f32 / 60.0;
+        if day.hours_paused() > span_hours {
+            return Err(ValidationError(format!(
+                "Paused hours {} are more than the {:.2} hours between starting and ending time for day {}",
+                day.hours_paused(),
+                span_hours,
+                day.date()
+            )));
+        }
+    }
+
+    if day.hours() > limits.max_day_hours {
+        return Err(ValidationError(format!(
+            "Day {} has {} hours, the limit is {} hours (validation.max-day-hours)",
+            day.date(),
+            day.hours(),
+            limits.max_day_hours
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate the times of an imported day as given, before an ending time earlier than the starting time on the same
+/// date is moved to the next date. Such a time is only taken as an overnight shift of at most the max hours, and the
+/// paused hours must be within the shift
+pub fn validate_import_times(
+    start: &DateTime<Local>,
+    end: &DateTime<Local>,
+    pause: f32,
+    max_overnight_hours: f32,
+) -> Result<(), ValidationError> {
+    if pause < 0.0 {
+        return Err(ValidationError(format!(
+            "Paused hours can't be negative: {}",
+            pause
+        )));
+    }
+
+    let mut span = *end - *start;
+    if end < start && end.date_naive() == start.date_naive() {
+        span += Duration::days(1);
+        let shift_hours = span.num_minutes() as f32 / 60.0;
+        if shift_hours > max_overnight_hours {
+            return Err(ValidationError(format!(
+                "Ending time {} is before starting time {} for day {}. Mark the ending time of a shift crossing \
+                 midnight with +1d or give its end date, unmarked shifts can be at most {} hours \
+                 (days.max-overnight-hours)",
+                end.format("%H:%M:%S"),
+                start.format("%H:%M:%S"),
+                start.date_naive(),
+                max_overnight_hours
+            )));
+        }
+    } else if end < start {
+        return Err(ValidationError(format!(
+            "Ending time {} is before starting time {}",
+            end.format("%Y-%m-%d %H:%M:%S"),
+            start.format("%Y-%m-%d %H:%M:%S")
+        )));
+    }
+
+    let span_hours = span.num_minutes() as f32 / 60.0;
+    if pause > span_hours {
+        return Err(ValidationError(format!(
+            "Paused hours {} are more than the {:.2} hours between starting and ending time for day {}",
+            pause,
+            span_hours,
+            start.date_naive()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate the allocations of a day: the day must be closed and the allocated hours within the hours of the day
+pub fn validate_allocations(day: &Day, day_hours: f32) -> Result<(), ValidationError> {
+    if !day.closed() {
+        return Err(ValidationError(format!(
+            "Day {} is not closed, only the hours of a closed day can be allocated",
+            day.date()
+        )));
+    }
+
+    let allocated: f32 = day.allocations().iter().map(|a| a.hours()).sum();
+    if allocated > day_hours + K_ALLOCATION_TOLERANCE {
+        return Err(ValidationError(format!(
+            "Allocated hours {} are more than the {} hours of day {}",
+            allocated,
+            day_hours,
+            day.date()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate the budgeted hours of a project, the budget must be positive
+pub fn validate_budget_hours(budget_hours: f32) -> Result<(), ValidationError> {
+    if !budget_hours.is_finite() || budget_hours <= 0.0 {
+        return Err(ValidationError(format!(
+            "Invalid budget: {}, the budgeted hours must be positive",
+            budget_hours
+        )));
+    }
+
+    Ok(())
+}
+
+/// Validate an entry: the hours must be positive and within the limit, and the estimate positive if given
+pub fn validate_entry(entry: &Entry, limits: &ValidationConfig) -> Result<(), ValidationError> {
+    validate_entry_hours(entry.hours(), limits)?;
+
+    match entry.estimate() {
+        Some(estimate) if !estimate.is_finite() || estimate <= 0.0 => {
+            Err(ValidationError(format!(
+                "Invalid estimate provided: {}, the estimated hours must be positive",
+                estimate
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Validate the hours of an entry: the hours must be positive and within the limit
+pub fn validate_entry_hours(hours: f32, limits: &ValidationConfig) -> Result<(), ValidationError> {
+    if !hours.is_finite() || hours <= 0.0 {
+        return Err(ValidationError(format!(
+            "Invalid hours provided: {}. You can't report 0 or negative hours on a project",
+            hours
+        )));
+    }
+
+    if hours > limits.max_entry_hours {
+        return Err(ValidationError(format!(
+            "Invalid hours provided: {}, the limit is {} hours (validation.max-entry-hours)",
+            hours, limits.max_entry_hours
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::FixedClock;
+    use crate::day::Allocation;
+    use chrono::TimeZone;
+
+    /// Internal function to get a local time of the tests
+    fn time(day: u32, hour: u32, min: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2025, 3, day, hour, min, 0).unwrap()
+    }
+
+    /// Internal function to get a day of the tests, started and ended at the times
+    fn day(start: DateTime<Local>, end: DateTime<Local>) -> Day {
+        let mut day = Day::new_with_clock(None, &FixedClock::new(start));
+        day.set_ending_time(&end);
+        day.set_starting_time(&start);
+        day
+    }
+
+    /// Limits of the tests
+    fn limits() -> ValidationConfig {
+        ValidationConfig {
+            max_entry_hours: 10.0,
+            max_day_hours: 12.0,
+        }
+    }
+
+    #[test]
+    fn week_must_exist_in_the_year() {
+        assert!(validate_week(1, 2025).is_ok());
+        assert!(validate_week(53, 2020).is_ok());
+        assert!(validate_week(0, 2025).is_err());
+        assert!(validate_week(54, 2020).is_err());
+        assert!(validate_week(53, 2021).is_err());
+    }
+
+    #[test]
+    fn day_ending_before_starting_is_rejected() {
+        assert!(validate_day(&day(time(10, 8, 0), time(10, 16, 0)), &limits()).is_ok());
+        assert!(validate_day(&day(time(10, 22, 0), time(11, 6, 0)), &limits()).is_ok());
+
+        let error = validate_day(&day(time(10, 9, 0), time(10, 8, 0)), &limits()).unwrap_err();
+        assert!(error.to_string().contains("before starting time"));
+    }
+
+    #[test]
+    fn day_pause_must_be_within_the_day() {
+        let mut within = day(time(10, 8, 0), time(10, 16, 0));
+        within.set_paused_time(8.0);
+        assert!(validate_day(&within, &limits()).is_ok());
+
+        let mut negative = day(time(10, 8, 0), time(10, 16, 0));
+        negative.set_paused_time(-0.5);
+        assert!(validate_day(&negative, &limits()).is_err());
+
+        let mut longer = day(time(10, 8, 0), time(10, 16, 0));
+        longer.set_paused_time(8.5);
+        assert!(validate_day(&longer, &limits()).is_err());
+    }
+
+    #[test]
+    fn day_hours_must_be_within_the_limit() {
+        assert!(validate_day(&day(time(10, 6, 0), time(10, 18, 0)), &limits()).is_ok());
+
+        let error = validate_day(&day(time(10, 6, 0), time(10, 18, 1)), &limits()).unwrap_err();
+        assert!(error.to_string().contains("validation.max-day-hours"));
+
+        // The paused hours are not worked
+        let mut paused = day(time(10, 6, 0), time(10, 19, 0));
+        paused.set_paused_time(1.0);
+        assert!(validate_day(&paused, &limits()).is_ok());
+    }
+
+    #[test]
+    fn import_times_are_validated_as_given() {
+        // Ending time on the next date
+        assert!(validate_import_times(&time(10, 22, 0), &time(11, 6, 0), 0.5, 0.0).is_ok());
+        // Unmarked overnight shift, within the max hours
+        assert!(validate_import_times(&time(10, 22, 0), &time(10, 6, 0), 0.5, 8.0).is_ok());
+        assert!(validate_import_times(&time(10, 22, 0), &time(10, 6, 0), 0.5, 7.9).is_err());
+        // Not an overnight shift, 23 hours
+        assert!(validate_import_times(&time(10, 9, 0), &time(10, 8, 0), 0.0, 12.0).is_err());
+        // Ending date before the starting date
+        assert!(validate_import_times(&time(10, 22, 0), &time(9, 23, 0), 0.0, 12.0).is_err());
+    }
+
+    #[test]
+    fn import_pause_must_be_within_the_shift() {
+        assert!(validate_import_times(&time(10, 8, 0), &time(10, 16, 0), 8.0, 12.0).is_ok());
+        assert!(validate_import_times(&time(10, 8, 0), &time(10, 16, 0), 8.5, 12.0).is_err());
+        assert!(validate_import_times(&time(10, 8, 0), &time(10, 16, 0), -1.0, 12.0).is_err());
+        // The pause is checked against the overnight shift, not the times on the same date
+        assert!(validate_import_times(&time(10, 22, 0), &time(10, 6, 0), 8.0, 12.0).is_ok());
+        assert!(validate_import_times(&time(10, 22, 0), &time(10, 6, 0), 8.5, 12.0).is_err());
+    }
+
+    #[test]
+    fn entry_hours_must_be_positive_and_within_the_limit() {
+        assert!(validate_entry_hours(0.25, &limits()).is_ok());
+        assert!(validate_entry_hours(10.0, &limits()).is_ok());
+        assert!(validate_entry_hours(10.5, &limits()).is_err());
+        assert!(validate_entry_hours(0.0, &limits()).is_err());
+        assert!(validate_entry_hours(-1.0, &limits()).is_err());
+        assert!(validate_entry_hours(f32::NAN, &limits()).is_err());
+        assert!(validate_entry_hours(f32::INFINITY, &limits()).is_err());
+    }
+
+    #[test]
+    fn entry_estimate_must_be_positive() {
+        let clock = FixedClock::new(time(10, 8, 0));
+        let mut entry = Entry::new_with_clock(2.0, None, &clock);
+        assert!(validate_entry(&entry, &limits()).is_ok());
+
+        entry.set_estimate(4.0);
+        assert!(validate_entry(&entry, &limits()).is_ok());
+        entry.set_estimate(0.0);
+        assert!(validate_entry(&entry, &limits()).is_err());
+        entry.set_estimate(f32::NAN);
+        assert!(validate_entry(&entry, &limits()).is_err());
+
+        let entry = Entry::new_with_clock(11.0, None, &clock);
+        assert!(validate_entry(&entry, &limits()).is_err());
+    }
+
+    #[test]
+    fn allocations_must_be_within_a_closed_day() {
+        let allocations = |hours: &[f32]| {
+            hours
+                .iter()
+                .map(|h| Allocation::new("acme".to_string(), *h))
+                .collect::<Vec<_>>()
+        };
+
+        let mut closed = day(time(10, 8, 0), time(10, 16, 0));
+        closed.set_allocations(allocations(&[5.0, 3.0]));
+        assert!(validate_allocations(&closed, 8.0).is_ok());
+        // Float artifacts of the summed hours are tolerated
+        closed.set_allocations(allocations(&[8.005]));
+        assert!(validate_allocations(&closed, 8.0).is_ok());
+        closed.set_allocations(allocations(&[5.0, 3.5]));
+        assert!(validate_allocations(&closed, 8.0).is_err());
+
+        let mut open = Day::new_with_clock(None, &FixedClock::new(time(10, 8, 0)));
+        open.set_starting_time(&time(10, 8, 0));
+        open.set_allocations(allocations(&[1.0]));
+        assert!(validate_allocations(&open, 8.0).is_err());
+    }
+
+    #[test]
+    fn budget_must_be_positive() {
+        assert!(validate_budget_hours(0.5).is_ok());
+        assert!(validate_budget_hours(0.0).is_err());
+        assert!(validate_budget_hours(-10.0).is_err());
+        assert!(validate_budget_hours(f32::NAN).is_err());
+        assert!(validate_budget_hours(f32::INFINITY).is_err());
+    }
+}
@@ -8,6 +8,9 @@
 
 use serde::{Deserialize, Serialize};
 
+/// Storage file version of the 1.x releases, the files are converted to the current layout when loaded
+pub const K_LEGACY_STORAGE_FILE_VERSION: &str = "1";
+
 /*
    Note: Time-butler has two types of versioning:
    1. Application Version: This is the version of the time-butler application itself, which is defined in the Cargo.toml file. It follows semantic versioning
@@ -8,12 +8,14 @@
 
 use crate::version_info::FileStorageMetadata;
 use crate::version_info::VersionInfo;
+use crate::version_info::K_LEGACY_STORAGE_FILE_VERSION;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum VersionCompatibility {
     Compatible,
     Incompatible(String), // Contains a message about the incompatibility
-    Unknown,              // When no metadata is loaded
+    Migratable(String), // Contains the older storage file version, converted to the current when loaded
+    Unknown,            // When no metadata is loaded
 }
 
 pub struct VersionManager {
@@ -59,6 +61,10 @@ impl VersionManager {
                 {
                     self.storage_compatibility = VersionCompatibility::Compatible;
                     VersionCompatibility::Compatible
+                } else if metadata.storage_file_version == K_LEGACY_STORAGE_FILE_VERSION {
+                    self.storage_compatibility =
+                        VersionCompatibility::Migratable(metadata.storage_file_version.clone());
+                    self.storage_compatibility.clone()
                 } else {
                     self.storage_compatibility = VersionCompatibility::Incompatible(format!(
                         "Incompatible storage file version: {}. Expected: {}",
@@ -79,6 +85,12 @@ impl VersionManager {
         }
     }
 
+    /// Mark the storage files as migrated to the current storage file version
+    pub fn set_migrated(&mut self) {
+        self.loaded_storage_metadata = Some(self.metadata());
+        self.storage_compatibility = VersionCompatibility::Compatible;
+    }
+
     pub fn metadata(&self) -> FileStorageMetadata {
         self.current_version.as_metadata()
     }
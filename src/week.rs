@@ -6,9 +6,86 @@
  * License: MIT
  */
 
-use crate::day::Day;
-use chrono::NaiveDate;
+use crate::day::{Day, DayV1};
+use chrono::{DateTime, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// How conflicting values are handled when a day is merged into an existing day
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MergeStrategy {
+    /// Existing values are kept, closed days can't be merged into
+    #[default]
+    KeepExisting,
+    /// New values replace the existing
+    Overwrite,
+    /// Existing times are kept, the new extra info is appended to the existing
+    AppendInfo,
+    /// The user is asked for each conflict
+    Interactive,
+}
+
+/// Parse error for MergeStrategy
+#[derive(Debug)]
+pub struct ParseMergeStrategyError(String);
+
+/// Implement Display trait for ParseMergeStrategyError
+impl fmt::Display for ParseMergeStrategyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Invalid conflict strategy: {}, valid strategies are: keep-existing, overwrite, append-info, interactive",
+            self.0
+        )
+    }
+}
+
+/// Implement Error trait for ParseMergeStrategyError
+impl Error for ParseMergeStrategyError {}
+
+impl FromStr for MergeStrategy {
+    type Err = ParseMergeStrategyError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.to_lowercase().as_str() {
+            "keep-existing" => Ok(MergeStrategy::KeepExisting),
+            "overwrite" => Ok(MergeStrategy::Overwrite),
+            "append-info" => Ok(MergeStrategy::AppendInfo),
+            "interactive" => Ok(MergeStrategy::Interactive),
+            _ => Err(ParseMergeStrategyError(input.to_string())),
+        }
+    }
+}
+
+/// A value set in both the existing and the merged day
+#[derive(Debug)]
+pub struct MergeConflict {
+    /// Name of the value, e.g. "starting time"
+    pub field: &'static str,
+    /// Existing value
+    pub existing: String,
+    /// Value of the merged day
+    pub new: String,
+}
+
+/// Internal function to format a time of a day for a conflict
+fn time_string(t: Option<&DateTime<Local>>) -> String {
+    t.map(|t| t.format("%H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+/// Result of a merge of days
+#[derive(Debug, PartialEq)]
+pub enum MergeOutcome {
+    /// The day was merged
+    Merged,
+    /// No day with the date exists in the week
+    NotFound,
+    /// The existing day is closed, and the strategy keeps existing values
+    Closed,
+}
 
 /// Represents a week with a number, ISO-style
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -21,6 +98,36 @@ pub struct Week {
     year: i32,
     /// Target hours
     target_hours: f32,
+    /// Free-form notes, e.g. "short week, conference"
+    notes: Vec<String>,
+    /// The target was set for this week only, it's kept when the week target in the configuration is reapplied
+    target_override: bool,
+    /// Unique ID for the week
+    id: uuid::Uuid,
+}
+
+/// Layout of a week in storage file version 1
+#[derive(Deserialize, Debug)]
+pub struct WeekV1 {
+    number: u32,
+    entries: Vec<DayV1>,
+    year: i32,
+    target_hours: f32,
+}
+
+/// Convert a week of storage file version 1, the week is given an ID
+impl From<WeekV1> for Week {
+    fn from(week: WeekV1) -> Self {
+        Self {
+            number: week.number,
+            entries: week.entries.into_iter().map(Day::from).collect(),
+            year: week.year,
+            target_hours: week.target_hours,
+            notes: Vec::new(),
+            target_override: false,
+            id: uuid::Uuid::new_v4(),
+        }
+    }
 }
 
 impl Week {
@@ -31,6 +138,9 @@ impl Week {
             entries: Vec::new(),
             year,
             target_hours,
+            notes: Vec::new(),
+            target_override: false,
+            id: uuid::Uuid::new_v4(),
         }
     }
 
@@ -39,11 +149,21 @@ impl Week {
         self.number
     }
 
+    /// Getter for `id`
+    pub fn id(&self) -> &uuid::Uuid {
+        &self.id
+    }
+
     /// Getter for `entries`
     pub fn entries(&self) -> &Vec<Day> {
         &self.entries
     }
 
+    /// Iterate the days of a month in the week, a week can span two months
+    pub fn days_in_month(&self, month: u32) -> impl Iterator<Item = &Day> + Clone + '_ {
+        self.entries.iter().filter(move |d| d.month() == month)
+    }
+
     /// Mutable getter for `entries`
     pub fn entries_mut(&mut self) -> &mut Vec<Day> {
         &mut self.entries
@@ -57,6 +177,32 @@ impl Week {
         self.target_hours
     }
 
+    /// Setter for `target_hours`
+    pub fn set_target_hours(&mut self, target_hours: f32) {
+        self.target_hours = target_hours;
+    }
+
+    /// Getter for `target_override`
+    pub fn target_override(&self) -> bool {
+        self.target_override
+    }
+
+    /// Set the target for this week only, or with None go back to the given configured target
+    pub fn set_target_override(&mut self, target_hours: Option<f32>, configured_hours: f32) {
+        self.target_override = target_hours.is_some();
+        self.target_hours = target_hours.unwrap_or(configured_hours);
+    }
+
+    /// Getter for `notes`
+    pub fn notes(&self) -> &Vec<String> {
+        &self.notes
+    }
+
+    /// Add a note to the week
+    pub fn add_note(&mut self, note: String) {
+        self.notes.push(note);
+    }
+
     // Uncomment if needed
     //  pub fn target_hours_reached(&self) -> bool {
     //      let total_hours: f32 = self.entries.iter().map(|d| d.hours()).sum();
@@ -88,36 +234,113 @@ impl Week {
         false
     }
 
-    /// Modify data for a specific day in the vector
-    pub fn merge_day(&mut self, entry: &Day) -> bool {
+    /// Modify data for a specific day in the vector, values only set in the merged day are always taken. Values set
+    /// in both are handled by the strategy, for the interactive strategy `resolve` decides if the new value is taken
+    pub fn merge_day<F>(
+        &mut self,
+        entry: &Day,
+        strategy: MergeStrategy,
+        mut resolve: F,
+    ) -> MergeOutcome
+    where
+        F: FnMut(&MergeConflict) -> bool,
+    {
         // Find the day by matching the date
-        if let Some(day) = self.entries.iter_mut().find(|d| d.date() == entry.date()) {
-            // Modify the data
-            if day.closed() {
-                tracing::warn!("Day already closed, unable to modify it");
-                return false;
+        let day = match self.entries.iter_mut().find(|d| d.date() == entry.date()) {
+            Some(day) => day,
+            None => return MergeOutcome::NotFound,
+        };
+
+        if day.closed() && strategy == MergeStrategy::KeepExisting {
+            tracing::warn!("Day already closed, unable to modify it");
+            return MergeOutcome::Closed;
+        }
+
+        // Decide for a value set in both days if the new value is taken
+        let mut take_new = |conflict: MergeConflict| {
+            let take = match strategy {
+                MergeStrategy::Overwrite => true,
+                MergeStrategy::KeepExisting | MergeStrategy::AppendInfo => false,
+                MergeStrategy::Interactive => resolve(&conflict),
+            };
+            if take {
+                tracing::info!(
+                    "Conflicting {}: '{}' replaced by '{}'",
+                    conflict.field,
+                    conflict.existing,
+                    conflict.new
+                );
+            } else {
+                tracing::warn!(
+                    "Conflicting {}: '{}' kept, '{}' ignored",
+                    conflict.field,
+                    conflict.existing,
+                    conflict.new
+                );
             }
+            take
+        };
 
-            if !day.start_time_set() && entry.start_time_set() {
+        if entry.start_time_set()
+            && (!day.start_time_set()
+                || (day.starting_time() != entry.starting_time()
+                    && take_new(MergeConflict {
+                        field: "starting time",
+                        existing: time_string(day.starting_time()),
+                        new: time_string(entry.starting_time()),
+                    })))
+        {
+            if let Some(t) = entry.starting_time() {
                 tracing::debug!("Setting start time for the day");
-                day.set_starting_time(entry.starting_time());
+                day.set_starting_time(t);
             }
+        }
 
-            if !day.ending_time_set() && entry.ending_time_set() {
+        if entry.ending_time_set()
+            && (!day.ending_time_set()
+                || (day.ending_time() != entry.ending_time()
+                    && take_new(MergeConflict {
+                        field: "ending time",
+                        existing: time_string(day.ending_time()),
+                        new: time_string(entry.ending_time()),
+                    })))
+        {
+            if let Some(t) = entry.ending_time() {
                 tracing::debug!("Setting ending time for the day");
-                day.set_ending_time(entry.ending_time());
+                day.set_ending_time(t);
             }
+        }
 
-            if day.extra_info().is_empty() && !entry.extra_info().is_empty() {
+        if entry.hours_paused() != 0.0
+            && (day.hours_paused() == 0.0
+                || (day.hours_paused() != entry.hours_paused()
+                    && take_new(MergeConflict {
+                        field: "paused hours",
+                        existing: day.hours_paused().to_string(),
+                        new: entry.hours_paused().to_string(),
+                    })))
+        {
+            tracing::debug!("Setting paused hours for the day");
+            day.set_paused_time(entry.hours_paused());
+        }
+
+        if !entry.extra_info().is_empty() && day.extra_info() != entry.extra_info() {
+            if day.extra_info().is_empty() {
                 tracing::debug!("Setting extra info for the day");
                 day.set_extra_info(entry.extra_info().to_string());
+            } else if strategy == MergeStrategy::AppendInfo {
+                tracing::debug!("Appending extra info for the day");
+                day.set_extra_info(format!("{}; {}", day.extra_info(), entry.extra_info()));
+            } else if take_new(MergeConflict {
+                field: "extra info",
+                existing: day.extra_info().to_string(),
+                new: entry.extra_info().to_string(),
+            }) {
+                day.set_extra_info(entry.extra_info().to_string());
             }
-
-            true
-        } else {
-            // Day not found
-            false
         }
+
+        MergeOutcome::Merged
     }
 
     /// Return a copy of an Day
@@ -140,3 +363,122 @@ impl Week {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::{Clock, FixedClock};
+    use chrono::TimeZone;
+
+    /// Internal function to get a local time of the tests, on Monday 2025-03-10
+    fn time(hour: u32, min: u32) -> DateTime<Local> {
+        Local.with_ymd_and_hms(2025, 3, 10, hour, min, 0).unwrap()
+    }
+
+    /// Internal function to get a day of the tests, closed if the ending time is given
+    fn day(start: (u32, u32), end: Option<(u32, u32)>, info: &str) -> Day {
+        let clock = FixedClock::new(time(start.0, start.1));
+        let mut day = Day::new_with_clock(Some(info.to_string()), &clock);
+        day.set_starting_time(&clock.now());
+        if let Some((hour, min)) = end {
+            day.set_ending_time(&time(hour, min));
+        }
+        day
+    }
+
+    /// Internal function to get a week of the tests with an open day, started 08:00
+    fn week_with_open_day() -> Week {
+        let mut week = Week::new(11, 2025, 40.0);
+        week.add_entry(day((8, 0), None, "existing"));
+        week
+    }
+
+    /// Internal function to get the merged day of the week
+    fn merged(week: &Week) -> &Day {
+        &week.entries()[0]
+    }
+
+    #[test]
+    fn keep_existing_keeps_conflicting_values() {
+        let mut week = week_with_open_day();
+        let new = day((9, 0), Some((17, 0)), "new");
+
+        let outcome = week.merge_day(&new, MergeStrategy::KeepExisting, |_| unreachable!());
+        assert_eq!(outcome, MergeOutcome::Merged);
+        let day = merged(&week);
+        assert_eq!(day.starting_time(), Some(&time(8, 0)));
+        // Only set in the merged day, always taken
+        assert_eq!(day.ending_time(), Some(&time(17, 0)));
+        assert_eq!(day.extra_info(), "existing");
+        assert!(day.closed());
+    }
+
+    #[test]
+    fn keep_existing_does_not_merge_into_a_closed_day() {
+        let mut week = Week::new(11, 2025, 40.0);
+        week.add_entry(day((8, 0), Some((16, 0)), "existing"));
+        let new = day((9, 0), Some((17, 0)), "new");
+
+        let outcome = week.merge_day(&new, MergeStrategy::KeepExisting, |_| unreachable!());
+        assert_eq!(outcome, MergeOutcome::Closed);
+        let day = merged(&week);
+        assert_eq!(day.starting_time(), Some(&time(8, 0)));
+        assert_eq!(day.ending_time(), Some(&time(16, 0)));
+        assert_eq!(day.extra_info(), "existing");
+    }
+
+    #[test]
+    fn overwrite_takes_conflicting_values() {
+        let mut week = Week::new(11, 2025, 40.0);
+        week.add_entry(day((8, 0), Some((16, 0)), "existing"));
+        let new = day((9, 0), Some((17, 0)), "new");
+
+        let outcome = week.merge_day(&new, MergeStrategy::Overwrite, |_| unreachable!());
+        assert_eq!(outcome, MergeOutcome::Merged);
+        let day = merged(&week);
+        assert_eq!(day.starting_time(), Some(&time(9, 0)));
+        assert_eq!(day.ending_time(), Some(&time(17, 0)));
+        assert_eq!(day.extra_info(), "new");
+        assert_eq!(day.hours(), 8.0);
+    }
+
+    #[test]
+    fn append_info_keeps_times_and_appends_the_info() {
+        let mut week = week_with_open_day();
+        let new = day((9, 0), Some((17, 0)), "new");
+
+        let outcome = week.merge_day(&new, MergeStrategy::AppendInfo, |_| unreachable!());
+        assert_eq!(outcome, MergeOutcome::Merged);
+        let day = merged(&week);
+        assert_eq!(day.starting_time(), Some(&time(8, 0)));
+        assert_eq!(day.ending_time(), Some(&time(17, 0)));
+        assert_eq!(day.extra_info(), "existing; new");
+    }
+
+    #[test]
+    fn interactive_asks_for_each_conflict() {
+        let mut week = week_with_open_day();
+        let new = day((9, 0), Some((17, 0)), "new");
+
+        let mut asked = Vec::new();
+        let outcome = week.merge_day(&new, MergeStrategy::Interactive, |conflict| {
+            asked.push(conflict.field);
+            conflict.field == "extra info"
+        });
+        assert_eq!(outcome, MergeOutcome::Merged);
+        assert_eq!(asked, vec!["starting time", "extra info"]);
+        let day = merged(&week);
+        assert_eq!(day.starting_time(), Some(&time(8, 0)));
+        assert_eq!(day.extra_info(), "new");
+    }
+
+    #[test]
+    fn merge_of_a_missing_date_is_not_found() {
+        let mut week = Week::new(11, 2025, 40.0);
+        let new = day((9, 0), Some((17, 0)), "new");
+
+        let outcome = week.merge_day(&new, MergeStrategy::Overwrite, |_| unreachable!());
+        assert_eq!(outcome, MergeOutcome::NotFound);
+        assert!(week.entries().is_empty());
+    }
+}
@@ -0,0 +1,231 @@
+/*
+ * File: week_scheme.rs
+ * Description: Week numbering of the dates. The first day of the week and the numbering scheme (ISO or US) are set
+ *              once from the configuration, and used for all days, weeks and reports.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+/// Highest possible week number in a year
+const K_MAX_WEEKS_IN_YEAR: u32 = 53;
+
+/// Week scheme used for the week numbers, set once from the configuration
+static K_WEEK_SCHEME: RwLock<WeekScheme> = RwLock::new(WeekScheme::iso());
+
+/// Enum to represent the week numbering schemes
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WeekNumbering {
+    /// Week 1 is the first week with at least four days in the year, a week belongs to the year most of its days are in
+    Iso,
+    /// Week 1 is the week with January 1st, a week belongs to the year of its last day
+    Us,
+}
+
+/// Parse error for WeekNumbering
+#[derive(Debug)]
+pub struct ParseWeekNumberingError;
+
+/// Implement Display trait for ParseWeekNumberingError
+impl fmt::Display for ParseWeekNumberingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid week numbering, valid options are: iso, us")
+    }
+}
+
+/// Implement Error trait for ParseWeekNumberingError
+impl Error for ParseWeekNumberingError {}
+
+/// Implement FromStr trait for WeekNumbering
+impl FromStr for WeekNumbering {
+    type Err = ParseWeekNumberingError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_lowercase().as_str() {
+            "iso" => Ok(WeekNumbering::Iso),
+            "us" => Ok(WeekNumbering::Us),
+            _ => Err(ParseWeekNumberingError),
+        }
+    }
+}
+
+/// Parse error for the first day of the week
+#[derive(Debug)]
+pub struct ParseWeekStartError;
+
+/// Implement Display trait for ParseWeekStartError
+impl fmt::Display for ParseWeekStartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid first day of week, valid options are: monday, sunday"
+        )
+    }
+}
+
+/// Implement Error trait for ParseWeekStartError
+impl Error for ParseWeekStartError {}
+
+/// Parse the first day of the week, only Monday and Sunday are used
+pub fn parse_week_start(input: &str) -> Result<Weekday, ParseWeekStartError> {
+    match input.trim().to_lowercase().as_str() {
+        "monday" | "mon" => Ok(Weekday::Mon),
+        "sunday" | "sun" => Ok(Weekday::Sun),
+        _ => Err(ParseWeekStartError),
+    }
+}
+
+/// First day of the week and the numbering of the weeks
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WeekScheme {
+    /// First day of the week
+    start: Weekday,
+    /// Numbering scheme
+    numbering: WeekNumbering,
+}
+
+impl WeekScheme {
+    /// Create a new week scheme
+    pub const fn new(start: Weekday, numbering: WeekNumbering) -> Self {
+        Self { start, numbering }
+    }
+
+    /// ISO 8601 weeks, starting on Monday
+    pub const fn iso() -> Self {
+        Self::new(Weekday::Mon, WeekNumbering::Iso)
+    }
+
+    /// Getter for `start`
+    pub fn start(&self) -> Weekday {
+        self.start
+    }
+
+    /// First date of the week the date is in
+    pub fn week_start(&self, date: NaiveDate) -> NaiveDate {
+        date - Duration::days(date.weekday().days_since(self.start) as i64)
+    }
+
+    /// The day of the week deciding the year and number of the week
+    fn anchor(&self, week_start: NaiveDate) -> NaiveDate {
+        match self.numbering {
+            WeekNumbering::Iso => week_start + Duration::days(3),
+            WeekNumbering::Us => week_start + Duration::days(6),
+        }
+    }
+
+    /// Week number and the year the week belongs to, for the date
+    pub fn week_of(&self, date: NaiveDate) -> (u32, i32) {
+        let anchor = self.anchor(self.week_start(date));
+        (anchor.ordinal0() / 7 + 1, anchor.year())
+    }
+
+    /// First date of the week with the number in the year, None if the week doesn't exist in the year
+    pub fn first_day(&self, week_number: u32, year: i32) -> Option<NaiveDate> {
+        if !(1..=K_MAX_WEEKS_IN_YEAR).contains(&week_number) {
+            return None;
+        }
+
+        let january_first = NaiveDate::from_ymd_opt(year, 1, 1)?;
+        let mut first_week = self.week_start(january_first);
+        if self.anchor(first_week).year() < year {
+            first_week += Duration::days(7);
+        }
+
+        let start = first_week + Duration::days(7 * (week_number as i64 - 1));
+        (self.week_of(start) == (week_number, year)).then_some(start)
+    }
+}
+
+/// Set the week scheme used for the week numbers
+pub fn set_week_scheme(scheme: WeekScheme) {
+    if let Ok(mut current) = K_WEEK_SCHEME.write() {
+        *current = scheme;
+    }
+}
+
+/// Get the week scheme used for the week numbers
+pub fn week_scheme() -> WeekScheme {
+    K_WEEK_SCHEME
+        .read()
+        .map(|s| *s)
+        .unwrap_or(WeekScheme::iso())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Internal function to get a date of the tests
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    /// US weeks, starting on Sunday
+    fn us() -> WeekScheme {
+        WeekScheme::new(Weekday::Sun, WeekNumbering::Us)
+    }
+
+    #[test]
+    fn iso_weeks_around_new_year() {
+        let iso = WeekScheme::iso();
+        // Wednesday and Thursday, the week has most of its days in 2026
+        assert_eq!(iso.week_of(date(2025, 12, 31)), (1, 2026));
+        assert_eq!(iso.week_of(date(2026, 1, 1)), (1, 2026));
+        // Thursday and Friday, the week has most of its days in 2020
+        assert_eq!(iso.week_of(date(2020, 12, 31)), (53, 2020));
+        assert_eq!(iso.week_of(date(2021, 1, 1)), (53, 2020));
+        // Sunday is the last day of the week
+        assert_eq!(iso.week_of(date(2023, 1, 1)), (52, 2022));
+        assert_eq!(iso.week_of(date(2023, 1, 2)), (1, 2023));
+    }
+
+    #[test]
+    fn us_weeks_around_new_year() {
+        let us = us();
+        // The week with January 1st is week 1, also for the days in December
+        assert_eq!(us.week_of(date(2025, 12, 31)), (1, 2026));
+        assert_eq!(us.week_of(date(2026, 1, 1)), (1, 2026));
+        assert_eq!(us.week_of(date(2021, 1, 1)), (1, 2021));
+        assert_eq!(us.week_of(date(2020, 12, 31)), (1, 2021));
+        // Saturday ending week 53, Sunday starting week 1
+        assert_eq!(us.week_of(date(2022, 12, 31)), (53, 2022));
+        assert_eq!(us.week_of(date(2023, 1, 1)), (1, 2023));
+    }
+
+    #[test]
+    fn first_days_of_the_weeks() {
+        let iso = WeekScheme::iso();
+        assert_eq!(iso.first_day(1, 2026), Some(date(2025, 12, 29)));
+        assert_eq!(iso.first_day(53, 2020), Some(date(2020, 12, 28)));
+        assert_eq!(iso.first_day(53, 2021), None);
+        assert_eq!(iso.first_day(0, 2021), None);
+        assert_eq!(iso.first_day(54, 2020), None);
+
+        let us = us();
+        assert_eq!(us.first_day(1, 2026), Some(date(2025, 12, 28)));
+        assert_eq!(us.first_day(1, 2023), Some(date(2023, 1, 1)));
+        assert_eq!(us.first_day(53, 2022), Some(date(2022, 12, 25)));
+        assert_eq!(us.first_day(53, 2023), None);
+    }
+
+    #[test]
+    fn first_day_and_week_of_agree() {
+        for scheme in [WeekScheme::iso(), us()] {
+            for year in 2019..=2028 {
+                for number in 1..=K_MAX_WEEKS_IN_YEAR {
+                    if let Some(start) = scheme.first_day(number, year) {
+                        assert_eq!(start.weekday(), scheme.start());
+                        assert_eq!(scheme.week_of(start), (number, year));
+                        assert_eq!(scheme.week_of(start + Duration::days(6)), (number, year));
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,247 @@
+/*
+ * File: xlsx.rs
+ * Description: Minimal XLSX writer, a single worksheet of text and number cells. The workbook is written as an
+ *              uncompressed zip archive, so no compression library is needed.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use std::error::Error;
+use std::fs::File;
+use std::io::Write;
+
+/// Signatures of the zip records
+const K_ZIP_LOCAL_HEADER: u32 = 0x0403_4b50;
+const K_ZIP_CENTRAL_HEADER: u32 = 0x0201_4b50;
+const K_ZIP_END_OF_DIRECTORY: u32 = 0x0605_4b50;
+/// Zip version needed to extract, 2.0
+const K_ZIP_VERSION: u16 = 20;
+/// Zip modification date of the files, 1980-01-01 in MS-DOS format
+const K_ZIP_DATE: u16 = (1 << 5) | 1;
+/// Reversed polynomial of the zip CRC-32
+const K_CRC32_POLYNOMIAL: u32 = 0xedb8_8320;
+
+/// Cell in the worksheet
+pub enum XlsxCell {
+    Text(String),
+    Number(f32),
+}
+
+/// Write a workbook with a single worksheet, one row of cells for each row
+pub fn write_workbook(
+    file_path: &str,
+    sheet_name: &str,
+    rows: &[Vec<XlsxCell>],
+) -> Result<(), Box<dyn Error>> {
+    let files = [
+        ("[Content_Types].xml", content_types()),
+        ("_rels/.rels", root_relationships()),
+        ("xl/workbook.xml", workbook(sheet_name)),
+        ("xl/_rels/workbook.xml.rels", workbook_relationships()),
+        ("xl/worksheets/sheet1.xml", worksheet(rows)),
+    ];
+
+    let mut file = File::create(file_path)?;
+    file.write_all(&zip_stored(&files))?;
+
+    Ok(())
+}
+
+/// Internal function to get the column letters of a zero based column index, e.g. 0 is "A" and 26 is "AA"
+fn column_name(mut index: usize) -> String {
+    let mut name = Vec::new();
+    loop {
+        name.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    name.reverse();
+    String::from_utf8_lossy(&name).to_string()
+}
+
+/// Internal function to escape the text of a cell
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn content_types() -> String {
+    concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#,
+        r#"<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>"#,
+        r#"<Default Extension="xml" ContentType="application/xml"/>"#,
+        r#"<Override PartName="/xl/workbook.xml" "#,
+        r#"ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml"/>"#,
+        r#"<Override PartName="/xl/worksheets/sheet1.xml" "#,
+        r#"ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml"/>"#,
+        r#"</Types>"#
+    )
+    .to_string()
+}
+
+fn root_relationships() -> String {
+    concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+        r#"<Relationship Id="rId1" "#,
+        r#"Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" "#,
+        r#"Target="xl/workbook.xml"/>"#,
+        r#"</Relationships>"#
+    )
+    .to_string()
+}
+
+fn workbook(sheet_name: &str) -> String {
+    format!(
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            r#"<workbook xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" "#,
+            r#"xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">"#,
+            r#"<sheets><sheet name="{}" sheetId="1" r:id="rId1"/></sheets>"#,
+            r#"</workbook>"#
+        ),
+        escape(sheet_name)
+    )
+}
+
+fn workbook_relationships() -> String {
+    concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+        r#"<Relationship Id="rId1" "#,
+        r#"Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet" "#,
+        r#"Target="worksheets/sheet1.xml"/>"#,
+        r#"</Relationships>"#
+    )
+    .to_string()
+}
+
+/// Internal function to get the worksheet XML, text is stored as inline strings
+fn worksheet(rows: &[Vec<XlsxCell>]) -> String {
+    let mut sheet = String::from(concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><sheetData>"#
+    ));
+
+    for (r, row) in rows.iter().enumerate() {
+        sheet.push_str(&format!(r#"<row r="{}">"#, r + 1));
+        for (c, cell) in row.iter().enumerate() {
+            let reference = format!("{}{}", column_name(c), r + 1);
+            match cell {
+                XlsxCell::Text(text) => sheet.push_str(&format!(
+                    r#"<c r="{}" t="inlineStr"><is><t>{}</t></is></c>"#,
+                    reference,
+                    escape(text)
+                )),
+                XlsxCell::Number(value) => {
+                    sheet.push_str(&format!(r#"<c r="{}"><v>{}</v></c>"#, reference, value))
+                }
+            }
+        }
+        sheet.push_str("</row>");
+    }
+
+    sheet.push_str("</sheetData></worksheet>");
+    sheet
+}
+
+/// Internal function to calculate the CRC-32 of the data, as used by zip
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffff_u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ K_CRC32_POLYNOMIAL
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Internal function to create a zip archive of the files, stored without compression
+fn zip_stored(files: &[(&str, String)]) -> Vec<u8> {
+    let mut archive: Vec<u8> = Vec::new();
+    let mut directory: Vec<u8> = Vec::new();
+
+    for (name, content) in files {
+        let data = content.as_bytes();
+        let crc = crc32(data);
+        let offset = archive.len() as u32;
+
+        archive.extend(K_ZIP_LOCAL_HEADER.to_le_bytes());
+        archive.extend(K_ZIP_VERSION.to_le_bytes());
+        archive.extend(0u16.to_le_bytes()); // flags
+        archive.extend(0u16.to_le_bytes()); // stored, no compression
+        archive.extend(0u16.to_le_bytes()); // time
+        archive.extend(K_ZIP_DATE.to_le_bytes());
+        archive.extend(crc.to_le_bytes());
+        archive.extend((data.len() as u32).to_le_bytes()); // compressed size
+        archive.extend((data.len() as u32).to_le_bytes()); // uncompressed size
+        archive.extend((name.len() as u16).to_le_bytes());
+        archive.extend(0u16.to_le_bytes()); // extra field length
+        archive.extend(name.as_bytes());
+        archive.extend(data);
+
+        directory.extend(K_ZIP_CENTRAL_HEADER.to_le_bytes());
+        directory.extend(K_ZIP_VERSION.to_le_bytes()); // made by
+        directory.extend(K_ZIP_VERSION.to_le_bytes()); // needed to extract
+        directory.extend(0u16.to_le_bytes()); // flags
+        directory.extend(0u16.to_le_bytes()); // stored, no compression
+        directory.extend(0u16.to_le_bytes()); // time
+        directory.extend(K_ZIP_DATE.to_le_bytes());
+        directory.extend(crc.to_le_bytes());
+        directory.extend((data.len() as u32).to_le_bytes());
+        directory.extend((data.len() as u32).to_le_bytes());
+        directory.extend((name.len() as u16).to_le_bytes());
+        directory.extend(0u16.to_le_bytes()); // extra field length
+        directory.extend(0u16.to_le_bytes()); // comment length
+        directory.extend(0u16.to_le_bytes()); // disk number
+        directory.extend(0u16.to_le_bytes()); // internal attributes
+        directory.extend(0u32.to_le_bytes()); // external attributes
+        directory.extend(offset.to_le_bytes());
+        directory.extend(name.as_bytes());
+    }
+
+    let directory_offset = archive.len() as u32;
+    archive.extend(&directory);
+
+    archive.extend(K_ZIP_END_OF_DIRECTORY.to_le_bytes());
+    archive.extend(0u16.to_le_bytes()); // disk number
+    archive.extend(0u16.to_le_bytes()); // disk with the directory
+    archive.extend((files.len() as u16).to_le_bytes()); // entries on this disk
+    archive.extend((files.len() as u16).to_le_bytes()); // total entries
+    archive.extend((directory.len() as u32).to_le_bytes());
+    archive.extend(directory_offset.to_le_bytes());
+    archive.extend(0u16.to_le_bytes()); // comment length
+
+    archive
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_names_continue_with_two_and_three_letters() {
+        assert_eq!(column_name(0), "A");
+        assert_eq!(column_name(25), "Z");
+        assert_eq!(column_name(26), "AA");
+        assert_eq!(column_name(701), "ZZ");
+        assert_eq!(column_name(702), "AAA");
+    }
+
+    #[test]
+    fn crc32_matches_the_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+        assert_eq!(crc32(b""), 0);
+    }
+}
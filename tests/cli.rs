@@ -0,0 +1,1056 @@
+/*
+ * File: cli.rs
+ * Description: End-to-end tests of the time-butler binary. Each test runs against its own temporary home and
+ *              storage directory, so the user data is never touched.
+ * Author: dherslof
+ * Created: 14-10-2026
+ * License: MIT
+ */
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Exit code for a time item not found
+const K_EXIT_NOT_FOUND: i32 = 2;
+/// Exit code for invalid input
+const K_EXIT_INVALID_INPUT: i32 = 3;
+/// Exit code for an already existing time item
+const K_EXIT_DUPLICATE: i32 = 5;
+
+/// Counter to give every test environment its own directory
+static K_ENV_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Isolated environment for running the binary, the directory is removed when dropped
+struct TestEnv {
+    /// Temporary directory, used as the home directory
+    root: PathBuf,
+}
+
+impl TestEnv {
+    /// Create a new environment with an empty temporary directory
+    fn new() -> Self {
+        let root = std::env::temp_dir().join(format!(
+            "time-butler-test-{}-{}",
+            std::process::id(),
+            K_ENV_COUNTER.fetch_add(1, Ordering::SeqCst)
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).expect("Failed to create test directory");
+        Self { root }
+    }
+
+    /// Base directory for all time-butler data
+    fn data_dir(&self) -> PathBuf {
+        self.root.join("data")
+    }
+
+    /// Directory of the generated reports
+    fn report_dir(&self) -> PathBuf {
+        self.data_dir().join("generated-reports")
+    }
+
+    /// Run the binary with the arguments, the input is given on stdin for the confirmation prompts
+    fn run_with_input(&self, args: &[&str], input: &str) -> Output {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_time-butler"))
+            .args(["-q"])
+            .args(args)
+            .env_clear()
+            .env("PATH", std::env::var("PATH").unwrap_or_default())
+            .env("HOME", &self.root)
+            .env("TB_STORAGE_DIR", self.data_dir())
+            .env("NO_COLOR", "1")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("Failed to run time-butler");
+
+        child
+            .stdin
+            .take()
+            .expect("Failed to open stdin")
+            .write_all(input.as_bytes())
+            .expect("Failed to write stdin");
+        child
+            .wait_with_output()
+            .expect("Failed to wait for time-butler")
+    }
+
+    /// Run the binary with the arguments and no input
+    fn run(&self, args: &[&str]) -> Output {
+        self.run_with_input(args, "")
+    }
+
+    /// Run the binary and check that it succeeded, returns stdout
+    fn run_ok(&self, args: &[&str]) -> String {
+        let output = self.run(args);
+        assert_success(&output, args);
+        String::from_utf8_lossy(&output.stdout).to_string()
+    }
+}
+
+impl Drop for TestEnv {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+/// Check that the command succeeded, with the output in the failure message
+fn assert_success(output: &Output, args: &[&str]) {
+    assert!(
+        output.status.success(),
+        "time-butler {:?} failed with {:?}\nstdout:\n{}\nstderr:\n{}",
+        args,
+        output.status.code(),
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+/// Files in a directory, empty if the directory doesn't exist
+fn files_in(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).collect())
+        .unwrap_or_default()
+}
+
+/// Use the sync directory without git, shared by the test environments
+fn use_sync_directory(env: &TestEnv, directory: &Path) {
+    env.run_ok(&["info"]);
+    let config_path = env.data_dir().join(".app_storage").join("tb-config.json");
+    let config = fs::read_to_string(&config_path).expect("Failed to read configuration");
+    let mut config: serde_json::Value =
+        serde_json::from_str(&config).expect("Invalid configuration");
+    config["sync"]["directory"] = directory.to_string_lossy().to_string().into();
+    config["sync"]["git"] = false.into();
+    fs::write(&config_path, config.to_string()).expect("Failed to write configuration");
+}
+
+/// ID on the listed line containing the text
+fn id_of(listing: &str, text: &str) -> Option<String> {
+    listing
+        .lines()
+        .find(|line| line.contains(text))
+        .and_then(first_id)
+}
+
+/// First ID in a listing, the IDs are the only words with the UUID format
+fn first_id(listing: &str) -> Option<String> {
+    listing
+        .split(|c: char| c.is_whitespace() || c == '|')
+        .find(|word| word.len() == 36 && word.matches('-').count() == 4)
+        .map(|word| word.to_string())
+}
+
+#[test]
+fn storage_is_created_in_the_storage_directory() {
+    let env = TestEnv::new();
+    env.run_ok(&["add", "project", "-n", "acme"]);
+
+    let storage_dir = env.data_dir().join(".app_storage");
+    assert!(storage_dir.join("prj_data.bin").exists());
+    assert!(storage_dir.join("tb-config.json").exists());
+    assert!(!env.root.join(".local").exists());
+}
+
+#[test]
+fn changes_are_appended_to_the_journal_of_the_storage_file() {
+    let env = TestEnv::new();
+    env.run_ok(&["add", "project", "-n", "acme"]);
+    let storage_dir = env.data_dir().join(".app_storage");
+    let project_file = fs::read(storage_dir.join("prj_data.bin")).expect("No project file");
+    assert!(!storage_dir.join("prj_data.bin.journal").exists());
+
+    for description in ["planning", "review"] {
+        env.run_ok(&[
+            "add",
+            "entry",
+            "--project",
+            "acme",
+            "--hours",
+            "1",
+            "--description",
+            description,
+        ]);
+    }
+    assert!(storage_dir.join("prj_data.bin.journal").exists());
+    assert_eq!(
+        fs::read(storage_dir.join("prj_data.bin")).expect("No project file"),
+        project_file
+    );
+
+    let listing = env.run_ok(&["list", "-p", "acme"]);
+    assert!(listing.contains("planning"));
+    assert!(listing.contains("review"));
+}
+
+#[test]
+fn sync_propagates_removals_and_changes() {
+    let laptop = TestEnv::new();
+    let desktop = TestEnv::new();
+    let sync_dir = laptop.root.join("shared-sync");
+    use_sync_directory(&laptop, &sync_dir);
+    use_sync_directory(&desktop, &sync_dir);
+
+    laptop.run_ok(&["add", "project", "-n", "acme"]);
+    for description in ["keeper", "mistake"] {
+        laptop.run_ok(&[
+            "add",
+            "entry",
+            "--project",
+            "acme",
+            "--hours",
+            "2",
+            "--description",
+            description,
+        ]);
+    }
+    let days = laptop.root.join("days.csv");
+    fs::write(
+        &days,
+        "date,start,end,pause\n2025-03-10,08:00,16:00,0\n2025-03-11,08:00,17:00,0\n",
+    )
+    .expect("Failed to write days");
+    laptop.run_ok(&["import", "days", "--file", days.to_str().unwrap()]);
+    laptop.run_ok(&["sync"]);
+    desktop.run_ok(&["sync"]);
+    let listing = desktop.run_ok(&["list", "-p", "acme"]);
+    assert!(listing.contains("keeper") && listing.contains("mistake"));
+    let week = desktop.run_ok(&["--as-of", "2025-03-14", "list", "-w"]);
+    assert!(week.contains("2025-03-11"));
+
+    // Removed and changed on the laptop
+    let listing = laptop.run_ok(&["list", "-p", "acme"]);
+    let mistake = id_of(&listing, "mistake").expect("No entry ID listed");
+    let keeper = id_of(&listing, "keeper").expect("No entry ID listed");
+    let args = [
+        "remove",
+        "entry",
+        "--project",
+        "acme",
+        "--id",
+        mistake.as_str(),
+    ];
+    assert_success(&laptop.run_with_input(&args, "y\n"), &args);
+    let args = ["remove", "day", "--date", "2025-03-11", "--week", "11"];
+    assert_success(&laptop.run_with_input(&args, "y\n"), &args);
+    let args = [
+        "modify",
+        "entry",
+        "--project",
+        "acme",
+        "--id",
+        &keeper,
+        "--hours",
+        "3",
+    ];
+    assert_success(&laptop.run_with_input(&args, "y\n"), &args);
+    laptop.run_ok(&["sync"]);
+
+    // Synced twice, the removed items don't come back from the desktop
+    desktop.run_ok(&["sync"]);
+    desktop.run_ok(&["sync"]);
+    laptop.run_ok(&["sync"]);
+    for env in [&laptop, &desktop] {
+        let listing = env.run_ok(&["list", "-p", "acme"]);
+        assert!(!listing.contains("mistake"), "{}", listing);
+        let keeper_line = listing.lines().find(|l| l.contains("keeper"));
+        assert!(
+            keeper_line.is_some_and(|l| l.contains("| 3 ")),
+            "{}",
+            listing
+        );
+        let week = env.run_ok(&["--as-of", "2025-03-14", "list", "-w"]);
+        assert!(week.contains("2025-03-10"));
+        assert!(!week.contains("2025-03-11"), "{}", week);
+    }
+    assert!(desktop.run_ok(&["trash", "list"]).contains(&mistake));
+    let week_file =
+        fs::read_to_string(sync_dir.join("weeks").join("2025-W11.json")).expect("No week file");
+    assert!(!week_file.contains("2025-03-11"));
+
+    // A removed project's file is removed from the sync directory
+    let args = ["remove", "project", "-n", "acme"];
+    assert_success(&desktop.run_with_input(&args, "y\n"), &args);
+    desktop.run_ok(&["sync"]);
+    assert!(files_in(&sync_dir.join("projects")).is_empty());
+    laptop.run_ok(&["sync"]);
+    assert_eq!(
+        laptop.run(&["list", "-p", "acme"]).status.code(),
+        Some(K_EXIT_NOT_FOUND)
+    );
+}
+
+#[test]
+fn add_and_list_project_entries() {
+    let env = TestEnv::new();
+    env.run_ok(&["add", "project", "-n", "acme", "-d", "The customer"]);
+    env.run_ok(&[
+        "add",
+        "entry",
+        "--project",
+        "acme",
+        "--hours",
+        "2.5",
+        "--description",
+        "planning",
+    ]);
+
+    let listing = env.run_ok(&["list", "-p", "acme"]);
+    assert!(listing.contains("planning"));
+    assert!(listing.contains("2.5"));
+
+    let all = env.run_ok(&["list", "--all-projects"]);
+    assert!(all.contains("acme"));
+}
+
+#[test]
+fn listed_rows_are_exported() {
+    let env = TestEnv::new();
+    env.run_ok(&["add", "project", "-n", "acme"]);
+    for (hours, description) in [("1", "small"), ("4", "big")] {
+        env.run_ok(&[
+            "add",
+            "entry",
+            "--project",
+            "acme",
+            "--hours",
+            hours,
+            "--description",
+            description,
+        ]);
+    }
+
+    let export = env.root.join("export.csv");
+    env.run_ok(&[
+        "list",
+        "-p",
+        "acme",
+        "--sort",
+        "hours",
+        "--desc",
+        "--columns",
+        "description,hours",
+        "--export",
+        export.to_str().expect("Invalid export path"),
+    ]);
+
+    let content = fs::read_to_string(&export).expect("Failed to read export");
+    assert_eq!(content, "Description,Hours\nbig,4\nsmall,1\n");
+}
+
+#[test]
+fn duplicate_project_is_rejected() {
+    let env = TestEnv::new();
+    env.run_ok(&["add", "project", "-n", "acme"]);
+
+    let output = env.run(&["add", "project", "-n", "acme"]);
+    assert_eq!(output.status.code(), Some(K_EXIT_DUPLICATE));
+}
+
+#[test]
+fn entry_for_unknown_project_is_not_found() {
+    let env = TestEnv::new();
+    let output = env.run(&[
+        "add",
+        "entry",
+        "--project",
+        "missing",
+        "--hours",
+        "1",
+        "--description",
+        "work",
+    ]);
+    assert_eq!(output.status.code(), Some(K_EXIT_NOT_FOUND));
+}
+
+#[test]
+fn invalid_usage_is_invalid_input() {
+    let env = TestEnv::new();
+    let output = env.run(&["add", "entry", "--project", "acme"]);
+    assert_eq!(output.status.code(), Some(K_EXIT_INVALID_INPUT));
+}
+
+#[test]
+fn add_day_and_list_week() {
+    let env = TestEnv::new();
+    env.run_ok(&["add", "day", "--starting-time", "-e", "office"]);
+    env.run_ok(&["add", "day", "--ending-time"]);
+
+    let listing = env.run_ok(&["list", "-w"]);
+    assert!(listing.contains("office"));
+    assert!(listing.contains("true"));
+}
+
+#[test]
+fn week_and_project_reports_are_generated() {
+    let env = TestEnv::new();
+    env.run_ok(&["add", "project", "-n", "acme"]);
+    env.run_ok(&[
+        "add",
+        "entry",
+        "--project",
+        "acme",
+        "--hours",
+        "3",
+        "--description",
+        "review",
+    ]);
+    env.run_ok(&["add", "day", "--starting-time"]);
+
+    env.run_ok(&["report", "project", "-n", "acme", "-f", "json"]);
+    env.run_ok(&["report", "week", "-f", "csv"]);
+
+    let reports = files_in(&env.report_dir());
+    assert_eq!(reports.len(), 2, "reports: {:?}", reports);
+
+    let project_report = reports
+        .iter()
+        .find(|p| p.extension().is_some_and(|e| e == "json"))
+        .expect("No JSON report generated");
+    let content = fs::read_to_string(project_report).expect("Failed to read report");
+    assert!(content.contains("review"));
+}
+
+#[test]
+fn csv_report_metadata_is_opt_in() {
+    let env = TestEnv::new();
+    env.run_ok(&["add", "project", "-n", "acme"]);
+    env.run_ok(&[
+        "add",
+        "entry",
+        "--project",
+        "acme",
+        "--hours",
+        "3",
+        "--description",
+        "review",
+    ]);
+
+    env.run_ok(&["report", "project", "-n", "acme", "-f", "csv"]);
+    let reports = files_in(&env.report_dir());
+    let content = fs::read_to_string(&reports[0]).expect("Failed to read report");
+    assert!(!content.contains('#'), "report: {}", content);
+    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    assert!(reader.headers().expect("No CSV header").len() > 1);
+    assert!(reader.records().all(|r| r.is_ok()));
+
+    env.run_ok(&[
+        "report",
+        "project",
+        "-n",
+        "acme",
+        "-f",
+        "csv",
+        "--overwrite",
+        "--csv-metadata",
+    ]);
+    let reports = files_in(&env.report_dir());
+    assert_eq!(reports.len(), 1, "reports: {:?}", reports);
+    let content = fs::read_to_string(&reports[0]).expect("Failed to read report");
+    assert!(content.starts_with("# "), "report: {}", content);
+}
+
+#[test]
+fn csv_totals_are_opt_in() {
+    let env = TestEnv::new();
+    let days = env.root.join("days.csv");
+    fs::write(
+        &days,
+        "date,start,end\n2025-03-10,08:00,16:00\n2025-03-11,08:00,12:00\n",
+    )
+    .expect("Failed to write days");
+    env.run_ok(&["import", "days", "--file", days.to_str().unwrap()]);
+
+    let week = ["report", "week", "-n", "11", "-y", "2025", "-f", "csv"];
+    env.run_ok(&week);
+    let reports = files_in(&env.report_dir());
+    let content = fs::read_to_string(&reports[0]).expect("Failed to read report");
+    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let hours = reader
+        .headers()
+        .expect("No CSV header")
+        .iter()
+        .position(|h| h == "Hours")
+        .expect("No hours column");
+    let rows: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+    assert_eq!(rows.len(), 2, "report: {}", content);
+    let sum: f32 = rows.iter().map(|r| r[hours].parse::<f32>().unwrap()).sum();
+    assert_eq!(sum, 12.0);
+
+    let mut with_totals = week.to_vec();
+    with_totals.extend(["--overwrite", "--csv-totals"]);
+    env.run_ok(&with_totals);
+    let content = fs::read_to_string(&reports[0]).expect("Failed to read report");
+    assert!(content.contains("Total"), "report: {}", content);
+    assert!(
+        csv::Reader::from_reader(content.as_bytes())
+            .records()
+            .count()
+            > 2
+    );
+}
+
+#[test]
+fn year_report_has_the_days_of_the_year() {
+    let env = TestEnv::new();
+    let days = env.root.join("days.csv");
+    fs::write(
+        &days,
+        "date,start,end\n2024-12-31,08:00,16:00\n2025-01-02,08:00,12:00\n2025-06-02,08:00,10:00\n",
+    )
+    .expect("Failed to write days");
+    env.run_ok(&["import", "days", "--file", days.to_str().unwrap()]);
+
+    env.run_ok(&["report", "year", "-n", "2025", "-f", "json"]);
+    let reports = files_in(&env.report_dir());
+    assert_eq!(reports.len(), 1, "reports: {:?}", reports);
+    let report = fs::read_to_string(&reports[0]).expect("Failed to read report");
+    let json: serde_json::Value = serde_json::from_str(&report).expect("Invalid JSON report");
+    assert_eq!(json["year"], 2025);
+    assert_eq!(json["weeks"].as_array().map(|w| w.len()), Some(2));
+    assert!(!report.contains("2024-12-31"));
+    assert!(report.contains("2025-06-02"));
+
+    let output = env.run(&["report", "year", "-n", "2023", "-f", "json"]);
+    assert_eq!(output.status.code(), Some(K_EXIT_NOT_FOUND));
+}
+
+#[test]
+fn removed_entry_and_project_are_gone() {
+    let env = TestEnv::new();
+    env.run_ok(&["add", "project", "-n", "acme"]);
+    env.run_ok(&[
+        "add",
+        "entry",
+        "--project",
+        "acme",
+        "--hours",
+        "1",
+        "--description",
+        "mistake",
+    ]);
+
+    let listing = env.run_ok(&["list", "-p", "acme"]);
+    let id = first_id(&listing).expect("No entry ID listed");
+
+    let args = ["remove", "entry", "--project", "acme", "--id", id.as_str()];
+    let output = env.run_with_input(&args, "y\n");
+    assert_success(&output, &args);
+    assert!(!env.run_ok(&["list", "-p", "acme"]).contains("mistake"));
+
+    let args = ["remove", "project", "-n", "acme"];
+    let output = env.run_with_input(&args, "y\n");
+    assert_success(&output, &args);
+    assert_eq!(
+        env.run(&["list", "-p", "acme"]).status.code(),
+        Some(K_EXIT_NOT_FOUND)
+    );
+}
+
+#[test]
+fn removed_entry_is_restored_from_the_trash() {
+    let env = TestEnv::new();
+    env.run_ok(&["add", "project", "-n", "acme"]);
+    env.run_ok(&[
+        "add",
+        "entry",
+        "--project",
+        "acme",
+        "--hours",
+        "1",
+        "--description",
+        "keeper",
+    ]);
+
+    let id = first_id(&env.run_ok(&["list", "-p", "acme"])).expect("No entry ID listed");
+    let args = ["remove", "entry", "--id", &id[..8]];
+    let output = env.run_with_input(&args, "y\n");
+    assert_success(&output, &args);
+    assert!(!env.run_ok(&["list", "-p", "acme"]).contains("keeper"));
+    assert!(env.run_ok(&["trash", "list"]).contains(&id));
+
+    env.run_ok(&["trash", "restore", &id]);
+    assert!(env.run_ok(&["list", "-p", "acme"]).contains("keeper"));
+    assert!(!env.run_ok(&["trash", "list"]).contains(&id));
+}
+
+#[test]
+fn not_confirmed_removal_keeps_the_project() {
+    let env = TestEnv::new();
+    env.run_ok(&["add", "project", "-n", "acme"]);
+
+    env.run_with_input(&["remove", "project", "-n", "acme"], "n\n");
+    assert!(env.run_ok(&["list", "--all-projects"]).contains("acme"));
+}
+
+#[test]
+fn backup_copies_the_storage_files() {
+    let env = TestEnv::new();
+    env.run_ok(&["add", "project", "-n", "acme"]);
+    env.run_ok(&["add", "day", "--starting-time"]);
+    env.run_ok(&["backup", "--now"]);
+
+    let backup_dir = env.data_dir().join("backups");
+    let backups = files_in(&backup_dir);
+    assert!(!backups.is_empty(), "No backup in {}", backup_dir.display());
+}
+
+#[test]
+fn entries_in_locked_month_are_rejected() {
+    let env = TestEnv::new();
+    env.run_ok(&["add", "project", "-n", "acme"]);
+    env.run_ok(&["lock", "month", "3", "--year", "2025"]);
+
+    let args = [
+        "add",
+        "entry",
+        "--project",
+        "acme",
+        "--hours",
+        "2",
+        "--description",
+        "late",
+        "--date",
+        "2025-03-14",
+    ];
+    assert_eq!(env.run(&args).status.code(), Some(K_EXIT_INVALID_INPUT));
+    assert!(!env.run_ok(&["list", "-p", "acme"]).contains("late"));
+
+    let mut forced = vec!["--force-unlock"];
+    forced.extend(args);
+    env.run_ok(&forced);
+    assert!(env.run_ok(&["list", "-p", "acme"]).contains("late"));
+}
+
+#[test]
+fn json_and_yaml_reports_parse_to_the_same_document() {
+    let env = TestEnv::new();
+    let days = env.root.join("days.csv");
+    fs::write(
+        &days,
+        "date,start,end,pause,extra_info\n2025-03-10,08:00,17:00,1,office\n",
+    )
+    .expect("Failed to write days");
+    env.run_ok(&["import", "days", "--file", days.to_str().unwrap()]);
+    env.run_ok(&["note", "week", "11", "conference", "--year", "2025"]);
+
+    for report in [["week", "11"], ["month", "3"]] {
+        let _ = fs::remove_dir_all(env.report_dir());
+        for format in ["json", "yaml"] {
+            let args = [
+                "report", report[0], "-n", report[1], "-y", "2025", "-f", format,
+            ];
+            env.run_ok(&args);
+        }
+
+        let reports = files_in(&env.report_dir());
+        let read = |extension: &str| {
+            let path = reports
+                .iter()
+                .find(|p| p.extension().is_some_and(|e| e == extension))
+                .unwrap_or_else(|| panic!("No {} report generated", extension));
+            fs::read_to_string(path).expect("Failed to read report")
+        };
+        let mut json: serde_json::Value =
+            serde_json::from_str(&read("json")).expect("Invalid JSON report");
+        let mut yaml: serde_json::Value =
+            serde_yaml::from_str(&read("yaml")).expect("Invalid YAML report");
+
+        // The reports are generated by separate runs, only the generation time differs
+        for document in [&mut json, &mut yaml] {
+            let generated = document["metadata"]
+                .as_object_mut()
+                .and_then(|m| m.remove("generated_at"));
+            assert!(generated.is_some(), "{} report without metadata", report[0]);
+        }
+        assert_eq!(json, yaml, "{} report", report[0]);
+
+        assert_eq!(json["format_version"], 2);
+        let week = match report[0] {
+            "week" => &json,
+            _ => &json["weeks"][0],
+        };
+        assert_eq!(week["week"], 11);
+        assert_eq!(week["year"], 2025);
+        assert_eq!(week["notes"][0], "conference");
+        assert_eq!(week["days"][0]["date"], "2025-03-10");
+        assert_eq!(week["days"][0]["description"], "office");
+        assert_eq!(week["days"][0]["hours"], 8.0);
+        assert_eq!(week["total_hours"], 8.0);
+        assert_eq!(json["total_hours"], 8.0);
+    }
+}
+
+#[test]
+fn report_keys_and_hours_do_not_follow_the_locale_and_format() {
+    let env = TestEnv::new();
+    let days = env.root.join("days.csv");
+    fs::write(&days, "date,start,end,pause\n2025-03-10,08:00,16:45,0.5\n")
+        .expect("Failed to write days");
+    env.run_ok(&["import", "days", "--file", days.to_str().unwrap()]);
+
+    let config_path = env.data_dir().join(".app_storage").join("tb-config.json");
+    let config = fs::read_to_string(&config_path).expect("Failed to read configuration");
+    let mut config: serde_json::Value =
+        serde_json::from_str(&config).expect("Invalid configuration");
+    config["display"]["locale"] = "sv".into();
+    fs::write(&config_path, config.to_string()).expect("Failed to write configuration");
+
+    env.run_ok(&[
+        "--duration-format",
+        "hh:mm",
+        "report",
+        "week",
+        "-n",
+        "11",
+        "-y",
+        "2025",
+        "-f",
+        "json",
+    ]);
+    let reports = files_in(&env.report_dir());
+    let report = fs::read_to_string(&reports[0]).expect("Failed to read report");
+    let json: serde_json::Value = serde_json::from_str(&report).expect("Invalid JSON report");
+    let day = &json["days"][0];
+    assert_eq!(day["date"], "2025-03-10");
+    assert_eq!(day["starting_time"], "08:00:00");
+    assert_eq!(day["paused_hours"], 0.5);
+    assert_eq!(day["hours"], 8.25);
+    assert_eq!(day["closed"], true);
+    assert_eq!(json["total_hours"], 8.25);
+    assert_eq!(json["target_status"]["worked_hours"], 8.25);
+    assert_eq!(json["target_status"]["status"], "NotReached");
+}
+
+#[test]
+fn command_import_is_validated_against_the_import_schema() {
+    let env = TestEnv::new();
+    env.run_ok(&["info"]);
+    let config_path = env.data_dir().join(".app_storage").join("tb-config.json");
+    let config = fs::read_to_string(&config_path).expect("Failed to read configuration");
+    let mut config: serde_json::Value =
+        serde_json::from_str(&config).expect("Invalid configuration");
+    config["importers"] = serde_json::json!([{ "name": "script", "command": "cat \"$1\"" }]);
+    fs::write(&config_path, config.to_string()).expect("Failed to write configuration");
+
+    let document = env.root.join("import.json");
+    fs::write(
+        &document,
+        r#"{"days": [{"date": "2025-03-10", "start": "08:00", "end": "16:00"}, {"start": "09:00", "pasue": 1}]}"#,
+    )
+    .expect("Failed to write import document");
+    let output = env.run(&[
+        "--porcelain",
+        "import",
+        "--source",
+        "script",
+        "--file",
+        document.to_str().unwrap(),
+    ]);
+    assert!(!output.status.success());
+    let result: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Invalid porcelain output");
+    let error = result["error"].as_str().expect("No error message");
+    assert!(
+        error.contains("/days/1: missing required key \"date\""),
+        "{}",
+        error
+    );
+    assert!(error.contains("/days/1/pasue: unknown key"), "{}", error);
+    let listing = env.run(&["--as-of", "2025-03-14", "list", "-w"]);
+    assert!(!String::from_utf8_lossy(&listing.stdout).contains("2025-03-10"));
+
+    let schema_path = env.root.join("import.schema.json");
+    env.run_ok(&[
+        "schema",
+        "dump",
+        "import",
+        "-o",
+        schema_path.to_str().unwrap(),
+    ]);
+    let schema = fs::read_to_string(&schema_path).expect("Failed to read schema");
+    let schema: serde_json::Value = serde_json::from_str(&schema).expect("Invalid schema");
+    assert_eq!(schema["$defs"]["day"]["required"][0], "date");
+    assert!(!env.run(&["schema", "dump", "config"]).status.success());
+}
+
+#[test]
+fn porcelain_output_is_a_single_json_document() {
+    let env = TestEnv::new();
+    env.run_ok(&["add", "project", "-n", "acme"]);
+    let added = env.run_ok(&[
+        "--porcelain",
+        "add",
+        "entry",
+        "--project",
+        "acme",
+        "--hours",
+        "2",
+        "--description",
+        "planning",
+    ]);
+    let added: serde_json::Value = serde_json::from_str(&added).expect("Invalid porcelain output");
+    assert_eq!(added["ok"], true);
+    assert_eq!(added["result"]["project"], "acme");
+    assert!(added["result"]["entry"]["id"].is_string());
+
+    let output = env.run(&[
+        "--porcelain",
+        "add",
+        "entry",
+        "--project",
+        "missing",
+        "--hours",
+        "1",
+        "--description",
+        "work",
+    ]);
+    assert_eq!(output.status.code(), Some(K_EXIT_NOT_FOUND));
+    let failed: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("Invalid porcelain output");
+    assert_eq!(failed["ok"], false);
+    assert_eq!(failed["exit_code"], K_EXIT_NOT_FOUND);
+    assert!(failed["result"].is_null());
+}
+
+#[test]
+fn failed_batch_stores_nothing() {
+    let env = TestEnv::new();
+    env.run_ok(&["add", "project", "-n", "acme"]);
+    let batch = "add entry --project acme --hours 2 --description \"first batch\"\n\
+                 add entry --project acme --hours 1 --description second\n";
+    let output = env.run_with_input(&["batch"], batch);
+    assert_success(&output, &["batch"]);
+    let listing = env.run_ok(&["list", "-p", "acme"]);
+    assert!(listing.contains("first batch"));
+    assert!(listing.contains("second"));
+
+    let failing = "add entry --project acme --hours 4 --description kept\n\
+                   add entry --project missing --hours 1 --description work\n";
+    let output = env.run_with_input(&["batch"], failing);
+    assert_eq!(output.status.code(), Some(K_EXIT_NOT_FOUND));
+    let listing = env.run_ok(&["list", "-p", "acme"]);
+    assert!(!listing.contains("kept"));
+}
+
+#[test]
+fn overnight_day_is_imported_on_the_start_date() {
+    let env = TestEnv::new();
+    let days = env.root.join("days.csv");
+    fs::write(
+        &days,
+        "date,start,end,pause,extra_info\n2025-03-10,22:00,06:00,0.5,night shift\n",
+    )
+    .expect("Failed to write days");
+    env.run_ok(&["import", "days", "--file", days.to_str().unwrap()]);
+
+    env.run_ok(&["report", "week", "-n", "11", "-y", "2025", "-f", "json"]);
+    let reports = files_in(&env.report_dir());
+    let report = fs::read_to_string(&reports[0]).expect("Failed to read report");
+    let json: serde_json::Value = serde_json::from_str(&report).expect("Invalid JSON report");
+    assert_eq!(json["days"][0]["date"], "2025-03-10");
+    assert_eq!(json["days"][0]["hours"], 7.5);
+    assert_eq!(json["days"][0]["ending_time"], "06:00:00");
+    assert_eq!(json["days"][0]["ending_days_after"], 1);
+}
+
+#[test]
+fn ending_time_before_starting_time_is_rejected() {
+    let env = TestEnv::new();
+    let days = env.root.join("days.csv");
+    fs::write(
+        &days,
+        "date,start,end,end_date,pause,extra_info\n\
+         2025-03-10,09:00,08:00,,,typo\n\
+         2025-03-11,09:00,08:00+1d,,,marked\n\
+         2025-03-12,20:00,07:00,2025-03-13,,end date\n",
+    )
+    .expect("Failed to write days");
+    let output = env.run(&["import", "days", "--file", days.to_str().unwrap()]);
+    assert_success(&output, &["import", "days"]);
+    assert!(String::from_utf8_lossy(&output.stdout)
+        .contains("Days added: 2, merged: 0, entries added: 0, skipped: 1"));
+
+    let listing = env.run_ok(&["--as-of", "2025-03-14", "list", "-w"]);
+    assert!(!listing.contains("2025-03-10"));
+    assert!(listing.contains("2025-03-12 08:00:00"));
+    assert!(listing.contains("2025-03-13 07:00:00"));
+}
+
+#[test]
+fn week_badge_shows_the_target_attainment() {
+    let env = TestEnv::new();
+    let days = env.root.join("days.csv");
+    fs::write(
+        &days,
+        "date,start,end,pause,extra_info\n2025-03-10,08:00,16:00,0,\n2025-03-11,08:00,12:00,0,\n",
+    )
+    .expect("Failed to write days");
+    env.run_ok(&["import", "days", "--file", days.to_str().unwrap()]);
+
+    let badge = env.root.join("badge.svg");
+    env.run_ok(&[
+        "badge",
+        "--week",
+        "11",
+        "--year",
+        "2025",
+        "--output",
+        badge.to_str().unwrap(),
+    ]);
+    let svg = fs::read_to_string(&badge).expect("Failed to read badge");
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.contains("<title>week 11: 12/40h \u{2022} 30%</title>"));
+}
+
+/// Layout of a project in storage file version 1
+#[derive(serde::Serialize)]
+struct ProjectV1 {
+    name: String,
+    description: Option<String>,
+    entries: Vec<EntryV1>,
+    id: uuid::Uuid,
+}
+
+/// Layout of an entry in storage file version 1
+#[derive(serde::Serialize)]
+struct EntryV1 {
+    hours: f32,
+    description: Option<String>,
+    created: chrono::DateTime<chrono::Local>,
+    id: uuid::Uuid,
+}
+
+/// Layout of a week in storage file version 1
+#[derive(serde::Serialize)]
+struct WeekV1 {
+    number: u32,
+    entries: Vec<DayV1>,
+    year: i32,
+    target_hours: f32,
+}
+
+/// Layout of a day in storage file version 1
+#[derive(serde::Serialize)]
+struct DayV1 {
+    starting_time: Option<chrono::DateTime<chrono::Local>>,
+    ending_time: Option<chrono::DateTime<chrono::Local>>,
+    hours: f32,
+    extra_info: String,
+    created: chrono::DateTime<chrono::Local>,
+    week: u32,
+    date: chrono::NaiveDate,
+    start_time_set: bool,
+    ending_time_set: bool,
+    closed: bool,
+    hours_paused: f32,
+    id: uuid::Uuid,
+}
+
+#[test]
+fn storage_of_version_1_is_converted() {
+    use chrono::TimeZone;
+
+    let env = TestEnv::new();
+    let storage_dir = env.data_dir().join(".app_storage");
+    fs::create_dir_all(&storage_dir).expect("Failed to create storage directory");
+    let time = |hour: u32, min: u32| {
+        chrono::Local
+            .with_ymd_and_hms(2025, 3, 10, hour, min, 0)
+            .unwrap()
+    };
+
+    let projects = vec![ProjectV1 {
+        name: "acme".to_string(),
+        description: None,
+        entries: vec![EntryV1 {
+            hours: 2.5,
+            description: Some("planning".to_string()),
+            created: time(9, 0),
+            id: uuid::Uuid::new_v4(),
+        }],
+        id: uuid::Uuid::new_v4(),
+    }];
+    let weeks = vec![WeekV1 {
+        number: 11,
+        entries: vec![DayV1 {
+            starting_time: Some(time(8, 0)),
+            ending_time: Some(time(15, 30)),
+            hours: 7.5,
+            extra_info: "office".to_string(),
+            created: time(8, 0),
+            week: 11,
+            date: chrono::NaiveDate::from_ymd_opt(2025, 3, 10).unwrap(),
+            start_time_set: true,
+            ending_time_set: true,
+            closed: true,
+            hours_paused: 0.0,
+            id: uuid::Uuid::new_v4(),
+        }],
+        year: 2025,
+        target_hours: 40.0,
+    }];
+    fs::write(
+        storage_dir.join("prj_data.bin"),
+        bincode::serialize(&projects).unwrap(),
+    )
+    .expect("Failed to write projects");
+    fs::write(
+        storage_dir.join("week_data.bin"),
+        bincode::serialize(&weeks).unwrap(),
+    )
+    .expect("Failed to write weeks");
+    fs::write(
+        storage_dir.join("metadata.json"),
+        r#"{"VersionInformation":{"storage_file_version":"1"}}"#,
+    )
+    .expect("Failed to write metadata");
+
+    let listing = env.run_ok(&["list", "-p", "acme"]);
+    assert!(listing.contains("planning"));
+    assert!(listing.contains("2.5"));
+
+    let metadata =
+        fs::read_to_string(storage_dir.join("metadata.json")).expect("Failed to read metadata");
+    assert!(metadata.contains(r#""storage_file_version": "2""#));
+
+    // The converted files are read in the current layout
+    let badge = env.root.join("badge.svg");
+    env.run_ok(&[
+        "badge",
+        "--week",
+        "11",
+        "--year",
+        "2025",
+        "--output",
+        badge.to_str().unwrap(),
+    ]);
+    let svg = fs::read_to_string(&badge).expect("Failed to read badge");
+    assert!(svg.contains("<title>week 11: 7.5/40h"));
+}
+
+#[test]
+fn as_of_freezes_the_current_time() {
+    let env = TestEnv::new();
+    env.run_ok(&[
+        "--as-of",
+        "2025-03-10 08:00",
+        "add",
+        "day",
+        "--starting-time",
+    ]);
+    env.run_ok(&["--as-of", "2025-03-10 16:30", "add", "day", "--ending-time"]);
+
+    // The current week is the week of the frozen time
+    let listing = env.run_ok(&["--as-of", "2025-03-12", "list", "-w"]);
+    assert!(listing.contains("2025-03-10"));
+    assert!(listing.contains("08:00:00"));
+    assert!(listing.contains("8.5"));
+
+    let last_week = env.run_ok(&["--as-of", "2025-03-17", "list", "-w", "last"]);
+    assert!(last_week.contains("2025-03-10"));
+}